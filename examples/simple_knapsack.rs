@@ -18,6 +18,7 @@ define
     println!("{}", solution)
 }
 
+#[allow(dead_code)]
 fn main_with_data() {
     let source = "
 max sum((value, i) in enumerate(values)) { value * x_i }
@@ -15,7 +15,7 @@ export const RoocLanguage = {
     defaultToken: 'invalid',
     ignoreCase: true,
     tokenPostfix: '.rooc',
-    keywords: ["where", "for", "min", "max", "in", "as", "define", "let", 'solve'],
+    keywords: ["where", "for", "min", "max", "in", "as", "define", "let", 'solve', 'if', 'else'],
     literals: ["true", "false"],
     operators: ["+", "-", "/", "*", "!", "&", "|", "=", "<=", "=>", "<", ">"],
     symbols: /[=><!~?:&|+\-*\/\^%]+/,
@@ -139,7 +139,9 @@ const keywords = {
     'as': 'Assert that the domain of variable is of a certain type',
     'define': 'Define the domain of the variables in your model, all variables must be defined',
     'let': 'Define a variable in the model',
-    'solve': 'Define the model as a satisfiability problem and not an optimization problem'
+    'solve': 'Define the model as a satisfiability problem and not an optimization problem',
+    'if': 'Pick between two compile time values depending on a boolean condition',
+    'else': 'The value used when the condition of the if is false'
 }
 const domainTypes = {
     'Boolean': 'A boolean value {0,1}',
@@ -330,7 +332,7 @@ export function makeRoocCompletionToken(entry: PossibleCompletionToken) {
         return {
             label: entry.name,
             kind: languages.CompletionItemKind.Function,
-            insertText: `${entry.name}() { }`,
+            insertText: `${entry.name}[] { }`,
             insertTextRules: languages.CompletionItemInsertTextRule.InsertAsSnippet,
             detail: `[BlockScopedFunction] ${entry.name}: ${entry.description}`,
         }
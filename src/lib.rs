@@ -50,9 +50,11 @@ use crate::prelude::*;
 use indexmap::IndexMap;
 use std::fmt::{Display, Formatter};
 
+use parser::diagnostics::{Diagnostic, Diagnostics};
+use parser::lint::Warning;
 use parser::pre_model::{parse_problem_source, PreModel};
 
-use crate::parser::model_transformer::{transform_parsed_problem, Model};
+use crate::parser::model_transformer::{transform_parsed_problem_with_builtins, Model};
 
 #[macro_use]
 mod macros;
@@ -115,6 +117,7 @@ mod prelude {
 #[derive(Debug, Clone)]
 pub struct RoocParser {
     source: String,
+    functions: FunctionRegistry,
 }
 
 impl RoocParser {
@@ -123,7 +126,39 @@ impl RoocParser {
     /// # Arguments
     /// * `source` - The Rooc source code as a String
     pub fn new(source: String) -> Self {
-        Self { source }
+        Self {
+            source,
+            functions: FunctionRegistry::new(),
+        }
+    }
+
+    /// Registers a host defined function that can be called inside the source code,
+    /// together with the functions of the standard library.
+    ///
+    /// # Arguments
+    /// * `name` - The name the function is called with
+    /// * `function` - The implementation of the function
+    ///
+    /// # Returns
+    /// * `Ok(RoocParser)` - The parser with the function registered
+    /// * `Err(TransformError)` - If the name is already used by another function
+    pub fn with_function(
+        mut self,
+        name: &str,
+        function: impl RoocFunction + Send + Sync + 'static,
+    ) -> Result<Self, TransformError> {
+        self.functions.register(name, function)?;
+        Ok(self)
+    }
+
+    /// Uses the functions of the registry, replacing the ones registered so far.
+    pub fn with_functions(mut self, functions: FunctionRegistry) -> Self {
+        self.functions = functions;
+        self
+    }
+
+    pub fn functions(&self) -> &FunctionRegistry {
+        &self.functions
     }
 
     /// Parses the source code into a PreModel representation.
@@ -135,6 +170,18 @@ impl RoocParser {
         parse_problem_source(&self.source)
     }
 
+    /// Finds the problems of the source code that don't prevent it from compiling,
+    /// like constants that are never used.
+    ///
+    /// # Returns
+    /// The warnings with the span they refer to, a source that fails to parse has no warnings
+    pub fn lint(&self) -> Vec<Warning> {
+        match self.parse() {
+            Ok(parsed) => parsed.lint(),
+            Err(_) => vec![],
+        }
+    }
+
     /// Formats the source code according to Rooc's formatting rules.
     ///
     /// # Returns
@@ -149,7 +196,7 @@ impl RoocParser {
     ///
     /// # Arguments
     /// * `constants` - Vector of constant definitions to be used during transformation
-    /// * `fns` - Map of function names to their implementations
+    /// * `fns` - Map of function names to their implementations, used after the registered ones
     ///
     /// # Returns
     /// * `Ok(Model)` - The transformed model
@@ -162,7 +209,8 @@ impl RoocParser {
         let parsed = self
             .parse()
             .map_err(|e| e.to_string_from_source(&self.source))?;
-        let transformed = transform_parsed_problem(parsed, constants, fns);
+        let builtins = self.functions.make_std_with();
+        let transformed = transform_parsed_problem_with_builtins(parsed, constants, fns, &builtins);
         match transformed {
             Ok(transformed) => Ok(transformed),
             Err(e) => Err(e
@@ -182,19 +230,53 @@ impl RoocParser {
     /// * `Err(String)` - Error message if type checking fails
     pub fn type_check(
         &self,
-        constants: &Vec<Constant>,
+        constants: &[Constant],
         fns: &FunctionContextMap,
     ) -> Result<(), String> {
         let parsed = self
             .parse()
             .map_err(|e| e.to_string_from_source(&self.source))?;
-        match parsed.create_type_checker(constants, fns) {
+        let builtins = self.functions.make_std_with();
+        match parsed.create_type_checker_with_builtins(constants, fns, &builtins) {
             Ok(_) => Ok(()),
             Err(e) => Err(e
                 .trace_from_source(&self.source)
                 .unwrap_or(e.traced_error())),
         }
     }
+
+    /// Parses and type checks the source code, collecting the problems found as structured
+    /// diagnostics with their span and severity, instead of a single error message.
+    ///
+    /// # Arguments
+    /// * `constants` - Vector of constants to check against
+    /// * `fns` - Map of function names to their implementations
+    ///
+    /// # Returns
+    /// The parse or type error, if any, followed by the lint warnings.
+    /// A source that fails to parse is not type checked nor linted
+    pub fn parse_and_type_check(
+        &self,
+        constants: &[Constant],
+        fns: &FunctionContextMap,
+    ) -> Diagnostics {
+        let mut diagnostics = Diagnostics::default();
+        let parsed = match self.parse() {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                diagnostics.push(Diagnostic::from(&e));
+                return diagnostics;
+            }
+        };
+        let builtins = self.functions.make_std_with();
+        if let Err(e) = parsed.create_type_checker_with_builtins(constants, fns, &builtins) {
+            diagnostics.push(Diagnostic::from(&e));
+        }
+        for warning in parsed.lint() {
+            diagnostics.push(Diagnostic::from(&warning));
+        }
+        diagnostics
+    }
 }
 pub type FunctionContextMap = IndexMap<String, Box<dyn RoocFunction>>;
 pub struct RoocSolver {
@@ -279,6 +361,24 @@ impl RoocParser {
         let fns = js_value_to_fns_map(fns);
         self.parse_and_transform(constants, &fns)
     }
+    pub fn parse_and_type_check_wasm(
+        &self,
+        constants: JsValue,
+        fns: Vec<JsFunction>,
+    ) -> Result<JsValue, String> {
+        let constants: Vec<(String, Primitive)> =
+            serde_wasm_bindgen::from_value(constants).map_err(|e| e.to_string())?;
+        let constants = constants
+            .into_iter()
+            .map(|v| Constant::from_primitive(&v.0, v.1))
+            .collect::<Vec<_>>();
+        let fns = js_value_to_fns_map(fns);
+        let diagnostics = self.parse_and_type_check(&constants, &fns);
+        Ok(serde_wasm_bindgen::to_value(&diagnostics).unwrap())
+    }
+    pub fn lint_wasm(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.lint()).unwrap()
+    }
     pub fn wasm_get_source(&self) -> String {
         self.source.clone()
     }
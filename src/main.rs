@@ -1,9 +1,6 @@
 use indexmap::IndexMap;
-#[allow(unused)]
-use rooc::pipe::{
-    CompilerPipe, LinearModelPipe, ModelPipe, PipeContext, PipeRunner, PipeableData, PreModelPipe,
-    RealSolver, StandardLinearModelPipe,
-};
+use rooc::pipe::{PipeContext, PipeableData};
+use rooc::rooc_pipe;
 
 #[allow(unused)]
 fn main() {
@@ -18,13 +15,7 @@ define
     a, b, ca, s, m, ch as NonNegativeReal(0, 100)
     "#
     .to_string();
-    let pipe_runner = PipeRunner::new(vec![
-        Box::new(CompilerPipe::new()),
-        Box::new(PreModelPipe::new()),
-        Box::new(ModelPipe::new()),
-        Box::new(LinearModelPipe::new()),
-        Box::new(RealSolver::new()),
-    ]);
+    let pipe_runner = rooc_pipe!(solve_real());
 
     let (result) = pipe_runner.run(
         PipeableData::String(source),
@@ -1,5 +1,6 @@
 //TODO find a better name for this file
 
+use crate::math::{float_eq, float_ge, float_gt, float_le, float_lt};
 use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
@@ -14,11 +15,11 @@ use crate::{enum_with_variants_to_string, InputSpan, Spanned};
 use core::f64;
 use core::fmt;
 use num_traits::ToPrimitive;
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::str::FromStr;
 
 enum_with_variants_to_string! {
-    pub enum Comparison derives[Debug, PartialEq, Clone, Copy] with_wasm {
+    pub enum Comparison derives[Debug, PartialEq, Clone, Copy, Deserialize] with_wasm {
         LessOrEqual,
         GreaterOrEqual,
         Equal,
@@ -53,6 +54,19 @@ impl fmt::Display for Comparison {
     }
 }
 
+impl Comparison {
+    /// Checks if the comparison holds between two numbers, within 5 decimal digits.
+    pub fn evaluate(&self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Comparison::LessOrEqual => float_le(lhs, rhs),
+            Comparison::GreaterOrEqual => float_ge(lhs, rhs),
+            Comparison::Equal => float_eq(lhs, rhs),
+            Comparison::Less => float_lt(lhs, rhs),
+            Comparison::Greater => float_gt(lhs, rhs),
+        }
+    }
+}
+
 impl FromStr for Comparison {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -69,7 +83,7 @@ impl FromStr for Comparison {
 
 enum_with_variants_to_string! {
 
-    pub enum OptimizationType derives[Debug, PartialEq, Clone] with_wasm {
+    pub enum OptimizationType derives[Debug, PartialEq, Clone, Deserialize] with_wasm {
         Min,
         Max,
         Satisfy
@@ -121,6 +135,8 @@ pub enum PreVariableType {
     Real(Option<PreExp>, Option<PreExp>),
     /// Integer within a specified range [min, max]
     IntegerRange(PreExp, PreExp),
+    /// Any integer within a [min, max] range (optional)
+    Integer(Option<PreExp>, Option<PreExp>),
 }
 
 fn default_bound(negative: bool, zero: bool) -> PreExp {
@@ -157,7 +173,8 @@ impl PartialEq<Self> for PreVariableType {
                 };
                 min_eq && max_eq
             }
-            (PreVariableType::Real(min1, max1), PreVariableType::Real(min2, max2)) => {
+            (PreVariableType::Real(min1, max1), PreVariableType::Real(min2, max2))
+            | (PreVariableType::Integer(min1, max1), PreVariableType::Integer(min2, max2)) => {
                 let min1 = min1.clone().unwrap_or_else(|| default_bound(true, false));
                 let max1 = max1.clone().unwrap_or_else(|| default_bound(false, false));
                 let min2 = min2.clone().unwrap_or_else(|| default_bound(true, false));
@@ -198,6 +215,7 @@ impl FromStr for PreVariableType {
             "Boolean" => Ok(PreVariableType::Boolean),
             "NonNegativeReal" => Ok(PreVariableType::NonNegativeReal(None, None)),
             "Real" => Ok(PreVariableType::Real(None, None)),
+            "Integer" => Ok(PreVariableType::Integer(None, None)),
             _ => Err(()),
         }
     }
@@ -210,6 +228,7 @@ impl PreVariableType {
             "Boolean".to_string(),
             "NonNegativeReal".to_string(),
             "Real".to_string(),
+            "Integer".to_string(),
             "IntegerRange(min, max)".to_string(),
         ]
     }
@@ -240,7 +259,7 @@ impl PreVariableType {
                 };
                 VariableType::NonNegativeReal(min, max)
             }
-            PreVariableType::Real(min, max) => {
+            PreVariableType::Real(min, max) | PreVariableType::Integer(min, max) => {
                 let min = match min {
                     Some(PreExp::Primitive(p)) => match **p {
                         Primitive::Integer(v) => v.to_f64().unwrap_or(f64::NEG_INFINITY),
@@ -259,7 +278,10 @@ impl PreVariableType {
                     },
                     _ => f64::INFINITY,
                 };
-                VariableType::Real(min, max)
+                match self {
+                    PreVariableType::Integer(_, _) => VariableType::Integer(min, max),
+                    _ => VariableType::Real(min, max),
+                }
             }
             PreVariableType::IntegerRange(min, max) => {
                 let min = match min {
@@ -321,6 +343,27 @@ impl PreVariableType {
                 }
                 Ok(VariableType::Real(min_f64, max_f64))
             }
+            PreVariableType::Integer(min, max) => {
+                let min = min.clone().unwrap_or_else(|| default_bound(true, false));
+                let max = max.clone().unwrap_or_else(|| default_bound(false, false));
+                let min_f64 = min.as_number_cast(context, fn_context)?;
+                let max_f64 = max.as_number_cast(context, fn_context)?;
+                for (bound, exp) in [(min_f64, &min), (max_f64, &max)] {
+                    if bound.is_finite() && bound.fract() != 0.0 {
+                        return Err(TransformError::Other(format!(
+                            "Bounds of an Integer must be integers or infinite. Got {}",
+                            bound
+                        ))
+                        .add_span(exp.span()));
+                    }
+                }
+                if min_f64 > max_f64 {
+                    return Err(TransformError::Other(
+                        format!("Minimum value must be less than or equal to the maximum value. Got {} > {}", min_f64, max_f64)
+                    ).add_span(min.span()));
+                }
+                Ok(VariableType::Integer(min_f64, max_f64))
+            }
             PreVariableType::IntegerRange(min, max) => {
                 let min_i64 = min.as_integer_cast(context, fn_context)?;
                 let max_i64 = max.as_integer_cast(context, fn_context)?;
@@ -394,10 +437,41 @@ impl ToLatex for PreVariableType {
                 min.to_latex(),
                 max.to_latex()
             ),
+            PreVariableType::Integer(min, max) => match (min, max) {
+                (None, None) => "\\mathbb{Z}".to_string(),
+                (min, max) => format!(
+                    "\\{{x \\in \\mathbb{{Z}} | {} \\leq x \\leq {}\\}}",
+                    min.clone()
+                        .map(|m| m.to_latex())
+                        .unwrap_or_else(|| "-\\infty".to_string()),
+                    max.clone()
+                        .map(|m| m.to_latex())
+                        .unwrap_or_else(|| "\\infty".to_string())
+                ),
+            },
         }
     }
 }
 
+/// Checks that a bound of a domain does not reference a variable of the model, as bounds
+/// must be computed from constants only.
+fn assert_constant_bound(
+    bound: &PreExp,
+    context: &TypeCheckerContext,
+) -> Result<(), TransformError> {
+    let variable = bound.referenced_names().into_iter().find(|name| {
+        context.value_of(name).is_none() && context.static_domain_variable_of(name).is_some()
+    });
+    match variable {
+        Some(name) => Err(TransformError::Other(format!(
+            "The bounds of a domain must be constant, but they use the model variable \"{}\"",
+            name
+        ))
+        .add_span(bound.span())),
+        None => Ok(()),
+    }
+}
+
 impl TypeCheckable for PreVariableType {
     fn type_check(
         &self,
@@ -408,6 +482,7 @@ impl TypeCheckable for PreVariableType {
             PreVariableType::Boolean => Ok(()),
             PreVariableType::NonNegativeReal(min, max) => {
                 if let Some(min) = min {
+                    assert_constant_bound(min, context)?;
                     min.type_check(context, fn_context)?;
                     let min_type = min.get_type(context, fn_context);
                     if !min_type.is_numeric() {
@@ -419,6 +494,7 @@ impl TypeCheckable for PreVariableType {
                     }
                 }
                 if let Some(max) = max {
+                    assert_constant_bound(max, context)?;
                     max.type_check(context, fn_context)?;
                     let max_type = max.get_type(context, fn_context);
                     if !max_type.is_numeric() {
@@ -431,8 +507,9 @@ impl TypeCheckable for PreVariableType {
                 }
                 Ok(())
             }
-            PreVariableType::Real(min, max) => {
+            PreVariableType::Real(min, max) | PreVariableType::Integer(min, max) => {
                 if let Some(min) = min {
+                    assert_constant_bound(min, context)?;
                     min.type_check(context, fn_context)?;
                     let min_type = min.get_type(context, fn_context);
                     if !min_type.is_numeric() {
@@ -444,6 +521,7 @@ impl TypeCheckable for PreVariableType {
                     }
                 }
                 if let Some(max) = max {
+                    assert_constant_bound(max, context)?;
                     max.type_check(context, fn_context)?;
                     let max_type = max.get_type(context, fn_context);
                     if !max_type.is_numeric() {
@@ -457,7 +535,9 @@ impl TypeCheckable for PreVariableType {
                 Ok(())
             }
             PreVariableType::IntegerRange(min, max) => {
+                assert_constant_bound(min, context)?;
                 min.type_check(context, fn_context)?;
+                assert_constant_bound(max, context)?;
                 max.type_check(context, fn_context)?;
                 let lhs = min.get_type(context, fn_context);
                 let rhs = max.get_type(context, fn_context);
@@ -487,7 +567,9 @@ impl TypeCheckable for PreVariableType {
     ) {
         match self {
             PreVariableType::Boolean => {}
-            PreVariableType::NonNegativeReal(min, max) | PreVariableType::Real(min, max) => {
+            PreVariableType::NonNegativeReal(min, max)
+            | PreVariableType::Real(min, max)
+            | PreVariableType::Integer(min, max) => {
                 if let Some(min) = min {
                     min.populate_token_type_map(context, fn_context);
                 }
@@ -527,6 +609,16 @@ impl fmt::Display for PreVariableType {
                 ),
             },
             PreVariableType::IntegerRange(min, max) => format!("IntegerRange({}, {})", min, max),
+            PreVariableType::Integer(min, max) => match (min, max) {
+                (None, None) => "Integer".to_string(),
+                (min, max) => format!(
+                    "Integer({}, {})",
+                    min.clone()
+                        .map_or("MinusInfinity".to_string(), |m| m.to_string()),
+                    max.clone()
+                        .map_or("Infinity".to_string(), |m| m.to_string())
+                ),
+            },
         };
 
         f.write_str(&s)
@@ -534,17 +626,38 @@ impl fmt::Display for PreVariableType {
 }
 
 /// Represents the final, resolved type of a variable after being compiled
-#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")]
 pub enum VariableType {
     /// Boolean variable (0 or 1)
     Boolean,
     /// Real number greater than or equal to zero
-    NonNegativeReal(f64, f64),
+    NonNegativeReal(
+        #[serde(deserialize_with = "deserialize_lower_bound")] f64,
+        #[serde(deserialize_with = "deserialize_upper_bound")] f64,
+    ),
     /// Any real number
-    Real(f64, f64),
+    Real(
+        #[serde(deserialize_with = "deserialize_lower_bound")] f64,
+        #[serde(deserialize_with = "deserialize_upper_bound")] f64,
+    ),
     /// Integer within a specified range [min, max]
     IntegerRange(i32, i32),
+    /// Any integer within a range [min, max], which can be infinite
+    Integer(
+        #[serde(deserialize_with = "deserialize_lower_bound")] f64,
+        #[serde(deserialize_with = "deserialize_upper_bound")] f64,
+    ),
+}
+
+/// Infinite bounds are serialized as null in JSON, a missing lower bound is minus infinity
+fn deserialize_lower_bound<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NEG_INFINITY))
+}
+
+/// Infinite bounds are serialized as null in JSON, a missing upper bound is infinity
+fn deserialize_upper_bound<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
 }
 
 //TODO change this
@@ -557,6 +670,9 @@ export type VariableType = {
 } | {
     type: "IntegerRange"
     value: [number, number]
+} | {
+    type: "Integer"
+    value: [number, number]
 }
 "#;
 
@@ -573,12 +689,43 @@ impl VariableType {
     pub fn integer_range(min: i32, max: i32) -> VariableType {
         VariableType::IntegerRange(min, max)
     }
+    pub fn integer() -> VariableType {
+        VariableType::Integer(f64::NEG_INFINITY, f64::INFINITY)
+    }
+    /// Returns true if the variable can only take integer values
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            VariableType::Boolean | VariableType::IntegerRange(_, _) | VariableType::Integer(_, _)
+        )
+    }
+    /// Returns the name of the kind of the variable, as written in the domain declaration
+    fn kind_name(&self) -> &'static str {
+        match self {
+            VariableType::Boolean => "Boolean",
+            VariableType::NonNegativeReal(_, _) => "NonNegativeReal",
+            VariableType::Real(_, _) => "Real",
+            VariableType::IntegerRange(_, _) => "IntegerRange",
+            VariableType::Integer(_, _) => "Integer",
+        }
+    }
+    /// Returns the (min, max) values the variable can take, which can be infinite
+    pub fn bounds(&self) -> (f64, f64) {
+        match self {
+            VariableType::Boolean => (0.0, 1.0),
+            VariableType::NonNegativeReal(min, max)
+            | VariableType::Real(min, max)
+            | VariableType::Integer(min, max) => (*min, *max),
+            VariableType::IntegerRange(min, max) => (*min as f64, *max as f64),
+        }
+    }
     /// Returns a list of all available variable type names as strings
     pub fn kinds_to_string() -> Vec<String> {
         vec![
             "Boolean".to_string(),
             "NonNegativeReal".to_string(),
             "Real".to_string(),
+            "Integer".to_string(),
             "IntegerRange(min, max)".to_string(),
         ]
     }
@@ -600,10 +747,11 @@ impl fmt::Display for VariableType {
                     }
                 ),
             },
-            VariableType::Real(min, max) => match (*min, *max) {
-                (f64::NEG_INFINITY, f64::INFINITY) => "Real".to_string(),
+            VariableType::Real(min, max) | VariableType::Integer(min, max) => match (*min, *max) {
+                (f64::NEG_INFINITY, f64::INFINITY) => self.kind_name().to_string(),
                 _ => format!(
-                    "Real({}, {})",
+                    "{}({}, {})",
+                    self.kind_name(),
                     if *min == f64::NEG_INFINITY {
                         "MinusInfinity".to_string()
                     } else {
@@ -645,24 +793,31 @@ impl ToLatex for VariableType {
                     }
                 ),
             },
-            VariableType::Real(min, max) => match (*min, *max) {
-                (f64::NEG_INFINITY, f64::INFINITY) => "\\mathbb{R}".to_string(),
-                _ => format!(
-                    "\\{{{} \\in \\mathbb{{R}} | {} \\leq {} \\leq {}\\}}",
-                    "x",
-                    if *min == f64::NEG_INFINITY {
-                        "-\\infty".to_string()
-                    } else {
-                        min.to_string()
-                    },
-                    "x",
-                    if *max == f64::INFINITY {
-                        "\\infty".to_string()
-                    } else {
-                        max.to_string()
-                    }
-                ),
-            },
+            VariableType::Real(min, max) | VariableType::Integer(min, max) => {
+                let set = match self {
+                    VariableType::Integer(_, _) => "\\mathbb{Z}",
+                    _ => "\\mathbb{R}",
+                };
+                match (*min, *max) {
+                    (f64::NEG_INFINITY, f64::INFINITY) => set.to_string(),
+                    _ => format!(
+                        "\\{{{} \\in {} | {} \\leq {} \\leq {}\\}}",
+                        "x",
+                        set,
+                        if *min == f64::NEG_INFINITY {
+                            "-\\infty".to_string()
+                        } else {
+                            min.to_string()
+                        },
+                        "x",
+                        if *max == f64::INFINITY {
+                            "\\infty".to_string()
+                        } else {
+                            max.to_string()
+                        }
+                    ),
+                }
+            }
             VariableType::IntegerRange(min, max) => format!(
                 "\\{{{} \\in \\mathbb{{Z}} | {} \\leq {} \\leq {}\\}}",
                 min, min, "x", max
@@ -15,7 +15,17 @@ enum_with_variants_to_string! {
         Sub,
         Mul,
         Div,
+        Mod,
         Neg,
+        And,
+        Or,
+        Not,
+        Equal,
+        NotEqual,
+        Less,
+        LessOrEqual,
+        Greater,
+        GreaterOrEqual,
     }
 }
 impl Operator {
@@ -24,9 +34,17 @@ impl Operator {
     /// Higher precedence values indicate that the operator should be evaluated first.
     pub fn precedence(&self) -> u8 {
         match self {
-            Operator::Add | Operator::Sub => 1,
-            Operator::Mul | Operator::Div => 2,
-            Operator::Neg => 3,
+            Operator::Or => 1,
+            Operator::And => 2,
+            Operator::Equal
+            | Operator::NotEqual
+            | Operator::Less
+            | Operator::LessOrEqual
+            | Operator::Greater
+            | Operator::GreaterOrEqual => 3,
+            Operator::Add | Operator::Sub => 4,
+            Operator::Mul | Operator::Div | Operator::Mod => 5,
+            Operator::Neg | Operator::Not => 6,
         }
     }
 
@@ -36,8 +54,20 @@ impl Operator {
     /// For example, a - b - c is evaluated as (a - b) - c.
     pub fn is_left_associative(&self) -> bool {
         match self {
-            Operator::Add | Operator::Sub | Operator::Mul | Operator::Div => true,
-            Operator::Neg => false,
+            Operator::Add
+            | Operator::Sub
+            | Operator::Mul
+            | Operator::Div
+            | Operator::Mod
+            | Operator::And
+            | Operator::Or
+            | Operator::Equal
+            | Operator::NotEqual
+            | Operator::Less
+            | Operator::LessOrEqual
+            | Operator::Greater
+            | Operator::GreaterOrEqual => true,
+            Operator::Neg | Operator::Not => false,
         }
     }
 }
@@ -49,7 +79,17 @@ impl fmt::Display for Operator {
             Operator::Sub => "-".to_string(),
             Operator::Mul => "*".to_string(),
             Operator::Div => "/".to_string(),
+            Operator::Mod => "%".to_string(),
             Operator::Neg => "-".to_string(),
+            Operator::And => "&&".to_string(),
+            Operator::Or => "||".to_string(),
+            Operator::Not => "!".to_string(),
+            Operator::Equal => "==".to_string(),
+            Operator::NotEqual => "!=".to_string(),
+            Operator::Less => "<".to_string(),
+            Operator::LessOrEqual => "<=".to_string(),
+            Operator::Greater => ">".to_string(),
+            Operator::GreaterOrEqual => ">=".to_string(),
         };
 
         f.write_str(&s)
@@ -57,14 +97,20 @@ impl fmt::Display for Operator {
 }
 
 enum_with_variants_to_string! {
-    pub enum BinOp derives[Debug, PartialEq, Clone, Copy] with_wasm {
+    pub enum BinOp derives[Debug, PartialEq, Clone, Copy, Deserialize] with_wasm {
         Add,
         Sub,
         Mul,
         Div,
-        //And
-        //Or
-        //Not
+        Mod,
+        And,
+        Or,
+        Equal,
+        NotEqual,
+        Less,
+        LessOrEqual,
+        Greater,
+        GreaterOrEqual,
         //Xor
     }
 }
@@ -73,18 +119,61 @@ impl BinOp {
     /// Returns the precedence level of the binary operator.
     pub fn precedence(&self) -> u8 {
         match self {
-            BinOp::Add | BinOp::Sub => 1,
-            BinOp::Mul | BinOp::Div => 2,
+            BinOp::Or => 1,
+            BinOp::And => 2,
+            BinOp::Equal
+            | BinOp::NotEqual
+            | BinOp::Less
+            | BinOp::LessOrEqual
+            | BinOp::Greater
+            | BinOp::GreaterOrEqual => 3,
+            BinOp::Add | BinOp::Sub => 4,
+            BinOp::Mul | BinOp::Div | BinOp::Mod => 5,
         }
     }
 
     /// Determines if the binary operator is left associative.
     pub fn is_left_associative(&self) -> bool {
         match self {
-            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div => true,
+            BinOp::Add
+            | BinOp::Sub
+            | BinOp::Mul
+            | BinOp::Div
+            | BinOp::Mod
+            | BinOp::And
+            | BinOp::Or
+            | BinOp::Equal
+            | BinOp::NotEqual
+            | BinOp::Less
+            | BinOp::LessOrEqual
+            | BinOp::Greater
+            | BinOp::GreaterOrEqual => true,
         }
     }
 
+    /// Determines if the binary operator works on booleans rather than numbers.
+    pub fn is_logical(&self) -> bool {
+        matches!(self, BinOp::And | BinOp::Or)
+    }
+
+    /// Determines if the binary operator compares two values, producing a boolean.
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinOp::Equal
+                | BinOp::NotEqual
+                | BinOp::Less
+                | BinOp::LessOrEqual
+                | BinOp::Greater
+                | BinOp::GreaterOrEqual
+        )
+    }
+
+    /// Determines if the binary operator produces a boolean rather than a number.
+    pub fn is_boolean(&self) -> bool {
+        self.is_logical() || self.is_comparison()
+    }
+
     /// Converts a binary operator to the corresponding general Operator enum.
     pub fn to_operator(&self) -> Operator {
         match self {
@@ -92,6 +181,15 @@ impl BinOp {
             BinOp::Sub => Operator::Sub,
             BinOp::Mul => Operator::Mul,
             BinOp::Div => Operator::Div,
+            BinOp::Mod => Operator::Mod,
+            BinOp::And => Operator::And,
+            BinOp::Or => Operator::Or,
+            BinOp::Equal => Operator::Equal,
+            BinOp::NotEqual => Operator::NotEqual,
+            BinOp::Less => Operator::Less,
+            BinOp::LessOrEqual => Operator::LessOrEqual,
+            BinOp::Greater => Operator::Greater,
+            BinOp::GreaterOrEqual => Operator::GreaterOrEqual,
         }
     }
 }
@@ -103,6 +201,15 @@ impl ToLatex for BinOp {
             BinOp::Sub => "-".to_string(),
             BinOp::Mul => "\\cdot".to_string(),
             BinOp::Div => "\\div".to_string(),
+            BinOp::Mod => "\\bmod".to_string(),
+            BinOp::And => "\\land".to_string(),
+            BinOp::Or => "\\lor".to_string(),
+            BinOp::Equal => "=".to_string(),
+            BinOp::NotEqual => "\\neq".to_string(),
+            BinOp::Less => "<".to_string(),
+            BinOp::LessOrEqual => "\\leq".to_string(),
+            BinOp::Greater => ">".to_string(),
+            BinOp::GreaterOrEqual => "\\geq".to_string(),
         }
     }
 }
@@ -114,6 +221,15 @@ impl fmt::Display for BinOp {
             BinOp::Sub => "-".to_string(),
             BinOp::Mul => "*".to_string(),
             BinOp::Div => "/".to_string(),
+            BinOp::Mod => "%".to_string(),
+            BinOp::And => "&&".to_string(),
+            BinOp::Or => "||".to_string(),
+            BinOp::Equal => "==".to_string(),
+            BinOp::NotEqual => "!=".to_string(),
+            BinOp::Less => "<".to_string(),
+            BinOp::LessOrEqual => "<=".to_string(),
+            BinOp::Greater => ">".to_string(),
+            BinOp::GreaterOrEqual => ">=".to_string(),
         };
 
         f.write_str(&s)
@@ -128,14 +244,24 @@ impl FromStr for BinOp {
             "-" => Ok(BinOp::Sub),
             "*" => Ok(BinOp::Mul),
             "/" => Ok(BinOp::Div),
+            "%" => Ok(BinOp::Mod),
+            "&&" => Ok(BinOp::And),
+            "||" => Ok(BinOp::Or),
+            "==" => Ok(BinOp::Equal),
+            "!=" => Ok(BinOp::NotEqual),
+            "<" => Ok(BinOp::Less),
+            "<=" => Ok(BinOp::LessOrEqual),
+            ">" => Ok(BinOp::Greater),
+            ">=" => Ok(BinOp::GreaterOrEqual),
             _ => Err(()),
         }
     }
 }
 
 enum_with_variants_to_string! {
-    pub enum UnOp derives[Debug, PartialEq, Clone, Copy] with_wasm {
+    pub enum UnOp derives[Debug, PartialEq, Clone, Copy, Deserialize] with_wasm {
         Neg,
+        Not,
     }
 }
 
@@ -143,14 +269,14 @@ impl UnOp {
     /// Returns the precedence level of the unary operator.
     pub fn precedence(&self) -> u8 {
         match self {
-            UnOp::Neg => 3,
+            UnOp::Neg | UnOp::Not => 6,
         }
     }
 
     /// Determines if the unary operator is left associative.
     pub fn is_left_associative(&self) -> bool {
         match self {
-            UnOp::Neg => false,
+            UnOp::Neg | UnOp::Not => false,
         }
     }
 
@@ -158,6 +284,7 @@ impl UnOp {
     pub fn to_operator(&self) -> Operator {
         match self {
             UnOp::Neg => Operator::Neg,
+            UnOp::Not => Operator::Not,
         }
     }
 }
@@ -166,6 +293,7 @@ impl ToLatex for UnOp {
     fn to_latex(&self) -> String {
         match self {
             UnOp::Neg => "-".to_string(),
+            UnOp::Not => "\\neg ".to_string(),
         }
     }
 }
@@ -174,6 +302,7 @@ impl fmt::Display for UnOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             UnOp::Neg => "-".to_string(),
+            UnOp::Not => "!".to_string(),
         };
 
         f.write_str(&s)
@@ -185,6 +314,7 @@ impl FromStr for UnOp {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "-" => Ok(UnOp::Neg),
+            "!" => Ok(UnOp::Not),
             _ => Err(()),
         }
     }
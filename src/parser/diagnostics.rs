@@ -0,0 +1,154 @@
+use core::fmt;
+
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::Serialize;
+
+use crate::parser::lint::Warning;
+use crate::parser::model_transformer::TransformError;
+use crate::utils::{CompilationError, InputSpan};
+
+/// How serious a [`Diagnostic`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Severity {
+    /// The source can't be compiled
+    Error,
+    /// The source compiles but is likely a mistake
+    Warning,
+}
+
+/// A problem found in the source, with the span it refers to
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    span: Option<InputSpan>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IDiagnostics: &'static str = r#"
+export type SerializedSeverity = "Error" | "Warning"
+export type SerializedDiagnostic = {
+    severity: SerializedSeverity,
+    message: string,
+    span: InputSpan | null
+}
+export type SerializedDiagnostics = {
+    diagnostics: SerializedDiagnostic[]
+}
+"#;
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: String, span: Option<InputSpan>) -> Self {
+        Self {
+            severity,
+            message,
+            span,
+        }
+    }
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// The span of the source the diagnostic refers to, if known
+    pub fn span(&self) -> Option<&InputSpan> {
+        self.span.as_ref()
+    }
+}
+
+impl From<&CompilationError> for Diagnostic {
+    fn from(error: &CompilationError) -> Self {
+        Diagnostic::new(
+            Severity::Error,
+            error.to_error_string().trim_end().to_string(),
+            Some(error.span().clone()),
+        )
+    }
+}
+
+impl From<&TransformError> for Diagnostic {
+    fn from(error: &TransformError) -> Self {
+        Diagnostic::new(
+            Severity::Error,
+            error.base_error().to_string(),
+            error.origin_span(),
+        )
+    }
+}
+
+impl From<&Warning> for Diagnostic {
+    fn from(warning: &Warning) -> Self {
+        Diagnostic::new(
+            Severity::Warning,
+            warning.to_string(),
+            Some(warning.span().clone()),
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match &self.span {
+            Some(span) => write!(
+                f,
+                "{} at {}:{}: {}",
+                severity, span.start_line, span.start_column, self.message
+            ),
+            None => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+/// The errors and warnings found while checking a source, in the order they were found
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new(diagnostics: Vec<Diagnostic>) -> Self {
+        Self { diagnostics }
+    }
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic)
+    }
+    pub fn diagnostics(&self) -> &Vec<Diagnostic> {
+        &self.diagnostics
+    }
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Error)
+    }
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.with_severity(Severity::Warning)
+    }
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+    fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |d| d.severity == severity)
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
@@ -9,9 +9,12 @@ use crate::math::PreVariableType;
 use crate::model_transformer::DomainVariable;
 use crate::parser::il::CompoundVariable;
 use crate::parser::il::IterableSet;
+use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
+use crate::primitives::PrimitiveKind;
 use crate::type_checker::type_checker_context::FunctionContext;
+use crate::type_checker::type_checker_context::WithType;
 use crate::{
     math::VariableType,
     traits::{escape_latex, ToLatex},
@@ -86,6 +89,8 @@ pub struct VariablesDomainDeclaration {
     as_type: PreVariableType,
     /// Optional iteration scopes to iterate compound variables
     iteration: Vec<IterableSet>,
+    /// Optional initial value of the variables, used as a warm start by the solvers
+    initial_value: Option<PreExp>,
     /// Source code span for error reporting
     span: InputSpan,
 }
@@ -98,6 +103,7 @@ export type SerializedVariablesDomainDeclaration = {
     variables: SerializedSpanned<SerializedVariableToAssert>[],
     as_type: VariableType,
     iteration: SerializedIterableSet[],
+    initial_value?: SerializedPreExp,
     span: InputSpan,
 }
 "#;
@@ -114,10 +120,22 @@ impl VariablesDomainDeclaration {
             variables,
             as_type,
             iteration: iters,
+            initial_value: None,
             span,
         }
     }
 
+    /// Sets the initial value of the variables, it must evaluate to a number in their domain
+    pub fn with_initial_value(mut self, initial_value: PreExp) -> Self {
+        self.initial_value = Some(initial_value);
+        self
+    }
+
+    /// Returns the initial value of the variables, if one was given
+    pub fn initial_value(&self) -> Option<&PreExp> {
+        self.initial_value.as_ref()
+    }
+
     /// Returns reference to variables in this domain
     pub fn variables(&self) -> &Vec<Spanned<VariableToAssert>> {
         &self.variables
@@ -151,25 +169,36 @@ impl VariablesDomainDeclaration {
         &self,
         context: &mut TransformerContext,
         fn_context: &FunctionContext,
-    ) -> Result<Vec<(String, Spanned<VariableType>)>, TransformError> {
+    ) -> Result<Vec<(String, DomainVariable)>, TransformError> {
         self.variables
             .iter()
             .map(|v| {
-                match v.value() {
+                let (name, var_type) = match v.value() {
                     VariableToAssert::Variable(name) => {
                         let var_type = self.as_type.to_variable_type(context, fn_context)?;
-                        Ok((name.clone(), var_type))
+                        (name.clone(), var_type)
                     }
                     VariableToAssert::CompoundVariable(c) => {
                         let indexes = &c.compute_indexes(context, fn_context)?;
                         let name = context.flatten_compound_variable(&c.name, indexes)?;
                         let var_type = self.as_type.to_variable_type(context, fn_context)?;
-                        Ok((name, var_type))
+                        (name, var_type)
+                    }
+                };
+                let variable = DomainVariable::new(var_type, v.span().clone());
+                match &self.initial_value {
+                    Some(initial_value) => {
+                        let value = initial_value
+                            .as_number_cast(context, fn_context)
+                            .map_err(|e| e.add_span(initial_value.span()))?;
+                        assert_in_domain(&name, value, variable.get_type())
+                            .map_err(|e| e.add_span(initial_value.span()))?;
+                        Ok((name, variable.with_initial_value(value)))
                     }
+                    None => Ok((name, variable)),
                 }
-                .map(|(name, t)| (name, Spanned::new(t, v.span().clone())))
             })
-            .collect::<Result<Vec<(String, Spanned<VariableType>)>, TransformError>>()
+            .collect::<Result<Vec<(String, DomainVariable)>, TransformError>>()
             .map_err(|e| e.add_span(&self.span))
     }
 
@@ -178,11 +207,11 @@ impl VariablesDomainDeclaration {
         &self,
         context: &mut TransformerContext,
         fn_context: &FunctionContext,
-    ) -> Result<Vec<(String, Spanned<VariableType>)>, TransformError> {
+    ) -> Result<Vec<(String, DomainVariable)>, TransformError> {
         if self.iteration.is_empty() {
             return self.compute_domain_values(context, fn_context);
         }
-        let mut results: Vec<Vec<(String, Spanned<VariableType>)>> = Vec::new();
+        let mut results: Vec<Vec<(String, DomainVariable)>> = Vec::new();
         recursive_set_resolver(
             &self.iteration,
             context,
@@ -196,6 +225,24 @@ impl VariablesDomainDeclaration {
     }
 }
 
+/// Checks that the initial value of a variable is inside its bounds, and integral when required
+fn assert_in_domain(name: &str, value: f64, as_type: &VariableType) -> Result<(), TransformError> {
+    let (min, max) = as_type.bounds();
+    if value < min || value > max {
+        return Err(TransformError::Other(format!(
+            "The initial value {} of \"{}\" is outside of its domain {}",
+            value, name, as_type
+        )));
+    }
+    if as_type.is_integer() && value.fract() != 0.0 {
+        return Err(TransformError::Other(format!(
+            "The initial value {} of \"{}\" must be an integer",
+            value, name
+        )));
+    }
+    Ok(())
+}
+
 impl TypeCheckable for VariablesDomainDeclaration {
     fn type_check(
         &self,
@@ -233,6 +280,36 @@ impl TypeCheckable for VariablesDomainDeclaration {
         self.as_type
             .type_check(context, fn_context)
             .map_err(|e| e.add_span(&self.span))?;
+        if let Some(initial_value) = &self.initial_value {
+            initial_value
+                .type_check(context, fn_context)
+                .map_err(|e| e.add_span(&self.span))?;
+            let value_type = initial_value.get_type(context, fn_context);
+            let integer = matches!(
+                value_type,
+                PrimitiveKind::Integer | PrimitiveKind::PositiveInteger | PrimitiveKind::Any
+            );
+            let (expected, matches) = match self.as_type {
+                PreVariableType::Boolean => (
+                    PrimitiveKind::Boolean,
+                    integer || matches!(value_type, PrimitiveKind::Boolean),
+                ),
+                PreVariableType::IntegerRange(..) | PreVariableType::Integer(..) => {
+                    (PrimitiveKind::Integer, integer)
+                }
+                PreVariableType::Real(..) | PreVariableType::NonNegativeReal(..) => (
+                    PrimitiveKind::Number,
+                    value_type.is_numeric() || value_type.is_any(),
+                ),
+            };
+            if !matches {
+                return Err(TransformError::from_wrong_type(
+                    expected,
+                    value_type,
+                    initial_value.span().clone(),
+                ));
+            }
+        }
         for _ in &self.iteration {
             context.pop_scope()?;
         }
@@ -249,6 +326,9 @@ impl TypeCheckable for VariablesDomainDeclaration {
         }
 
         self.as_type.populate_token_type_map(context, fn_context);
+        if let Some(initial_value) = &self.initial_value {
+            initial_value.populate_token_type_map(context, fn_context);
+        }
         for variable in &self.variables {
             if let VariableToAssert::CompoundVariable(c) = variable.value() {
                 for index in &c.indexes {
@@ -273,6 +353,9 @@ impl ToLatex for VariablesDomainDeclaration {
             .collect::<Vec<String>>()
             .join(", ");
         s.push_str(format!("{} &\\in {}", vars, self.as_type.to_latex()).as_str());
+        if let Some(initial_value) = &self.initial_value {
+            s.push_str(format!(" = {}", initial_value.to_latex()).as_str());
+        }
         if !self.iteration.is_empty() {
             let iters = self
                 .iteration
@@ -294,6 +377,10 @@ impl Display for VariablesDomainDeclaration {
             .map(|v| v.to_string())
             .collect::<Vec<String>>()
             .join(", ");
+        write!(f, "{} as {}", vars, self.as_type)?;
+        if let Some(initial_value) = &self.initial_value {
+            write!(f, " = {}", initial_value)?;
+        }
         if !self.iteration.is_empty() {
             let iters = self
                 .iteration
@@ -301,10 +388,9 @@ impl Display for VariablesDomainDeclaration {
                 .map(|iter| iter.to_string())
                 .collect::<Vec<String>>()
                 .join(", ");
-            write!(f, "{} as {} for {}", vars, self.as_type, iters)
-        } else {
-            write!(f, "{} as {}", vars, self.as_type)
+            write!(f, " for {}", iters)?;
         }
+        Ok(())
     }
 }
 
@@ -0,0 +1,112 @@
+use core::fmt;
+
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::Serialize;
+
+use crate::parser::il::il_exp::PreExp;
+use crate::parser::il::iterable_set::IterableSet;
+use crate::parser::iterable_utils::flatten_primitive_array_values;
+use crate::parser::model_transformer::{TransformError, TransformerContext};
+use crate::parser::recursive_set_resolver::recursive_set_resolver;
+use crate::primitives::{IterableKind, Primitive};
+use crate::traits::ToLatex;
+use crate::type_checker::type_checker_context::FunctionContext;
+
+/// An array built by evaluating an expression for each binding of the iteration variables,
+/// like `[i * 2 for i in 0..5]`, optionally keeping only the bindings that satisfy a guard,
+/// like `[i for i in A where i > 0]`.
+#[derive(Debug, Serialize, Clone)]
+pub struct ArrayComprehension {
+    /// The expression evaluated for each element
+    pub exp: Box<PreExp>,
+    /// The iteration variables and their domains
+    pub iters: Vec<IterableSet>,
+    /// The condition an element must satisfy to be kept
+    pub guard: Option<Box<PreExp>>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IArrayComprehension: &'static str = r#"
+export type SerializedArrayComprehension = {
+    exp: SerializedPreExp,
+    iters: SerializedIterableSet[],
+    guard: SerializedPreExp | null,
+}
+"#;
+
+impl ArrayComprehension {
+    /// Creates a new ArrayComprehension.
+    ///
+    /// # Arguments
+    /// * `exp` - The expression evaluated for each element
+    /// * `iters` - Vector of iteration variables and their domains
+    /// * `guard` - The optional condition an element must satisfy to be kept
+    pub fn new(exp: Box<PreExp>, iters: Vec<IterableSet>, guard: Option<Box<PreExp>>) -> Self {
+        Self { exp, iters, guard }
+    }
+
+    /// Evaluates the elements of the array, the iteration variables are declared
+    /// in a copy of the context as the array can only be a compile time value.
+    pub fn evaluate(
+        &self,
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<IterableKind, TransformError> {
+        let mut context = context.clone();
+        let mut results: Vec<Option<Primitive>> = Vec::new();
+        recursive_set_resolver(
+            &self.iters,
+            &mut context,
+            fn_context,
+            &mut results,
+            0,
+            &|context| {
+                if let Some(guard) = &self.guard {
+                    if !guard.as_boolean(context, fn_context)? {
+                        return Ok(None);
+                    }
+                }
+                self.exp.as_primitive(context, fn_context).map(Some)
+            },
+        )?;
+        Ok(flatten_primitive_array_values(
+            results.into_iter().flatten().collect(),
+        ))
+    }
+}
+
+impl ToLatex for ArrayComprehension {
+    fn to_latex(&self) -> String {
+        let mut conditions = self
+            .iters
+            .iter()
+            .map(|i| i.to_latex())
+            .collect::<Vec<String>>();
+        if let Some(guard) = &self.guard {
+            conditions.push(guard.to_latex());
+        }
+        format!(
+            "\\left[ {} \\mid {} \\right]",
+            self.exp.to_latex(),
+            conditions.join(",\\ ")
+        )
+    }
+}
+
+impl fmt::Display for ArrayComprehension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let iters = self
+            .iters
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        match &self.guard {
+            Some(guard) => write!(f, "[{} for {} where {}]", self.exp, iters, guard),
+            None => write!(f, "[{} for {}]", self.exp, iters),
+        }
+    }
+}
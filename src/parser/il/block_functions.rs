@@ -18,6 +18,9 @@ enum_with_variants_to_string! {
         Min,
         Max,
         Avg,
+        ArgMin,
+        ArgMax,
+        Count,
     }
 }
 impl fmt::Display for BlockScopedFunctionKind {
@@ -28,6 +31,9 @@ impl fmt::Display for BlockScopedFunctionKind {
             Self::Min => "min".to_string(),
             Self::Max => "max".to_string(),
             Self::Avg => "avg".to_string(),
+            Self::ArgMin => "argmin".to_string(),
+            Self::ArgMax => "argmax".to_string(),
+            Self::Count => "count".to_string(),
         };
         f.write_str(&s)
     }
@@ -41,6 +47,9 @@ impl ToLatex for BlockScopedFunctionKind {
             Self::Min => "\\min".to_string(),
             Self::Max => "\\max".to_string(),
             Self::Avg => "avg".to_string(),
+            Self::ArgMin => "\\arg\\min".to_string(),
+            Self::ArgMax => "\\arg\\max".to_string(),
+            Self::Count => "count".to_string(),
         }
     }
 }
@@ -54,6 +63,9 @@ impl FromStr for BlockScopedFunctionKind {
             "min" => Ok(Self::Min),
             "max" => Ok(Self::Max),
             "avg" => Ok(Self::Avg),
+            "argmin" => Ok(Self::ArgMin),
+            "argmax" => Ok(Self::ArgMax),
+            "count" => Ok(Self::Count),
             _ => Err(()),
         }
     }
@@ -176,7 +188,7 @@ impl fmt::Display for BlockScopedFunction {
         let name = self.kind.to_string();
         write!(
             f,
-            "{}({}) {{ {} }}",
+            "{}[{}] {{ {} }}",
             name,
             self.iters
                 .iter()
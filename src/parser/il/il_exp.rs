@@ -5,6 +5,7 @@ use crate::prelude::*;
 use serde::Serialize;
 
 use crate::math::{BinOp, UnOp};
+use crate::parser::il::array_comprehension::ArrayComprehension;
 use crate::parser::il::block_functions::{
     BlockFunction, BlockFunctionKind, BlockScopedFunction, BlockScopedFunctionKind,
 };
@@ -12,11 +13,12 @@ use crate::parser::il::il_problem::{AddressableAccess, CompoundVariable};
 use crate::parser::model_transformer::Exp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
+use crate::parser::model_transformer::VariableKind;
 use crate::parser::recursive_set_resolver::recursive_set_resolver;
-use crate::primitives::ApplyOp;
 use crate::primitives::IterableKind;
+use crate::primitives::{ApplyOp, OperatorError};
 use crate::primitives::{Graph, GraphEdge, GraphNode};
-use crate::primitives::{Primitive, PrimitiveKind};
+use crate::primitives::{Primitive, PrimitiveKind, Tuple};
 use crate::runtime_builtin::FunctionCall;
 use crate::traits::{escape_latex, ToLatex};
 use crate::type_checker::type_checker_context::{
@@ -50,6 +52,20 @@ pub enum PreExp {
     BinaryOperation(Spanned<BinOp>, Box<PreExp>, Box<PreExp>),
     /// A unary operation like negation
     UnaryOperation(Spanned<UnOp>, Box<PreExp>),
+    /// A conditional expression like 'if cond { a } else { b }',
+    /// holding the condition, the then branch and the else branch
+    IfElse(InputSpan, Box<PreExp>, Box<PreExp>, Box<PreExp>),
+    /// A piecewise linear function like 'piecewise(x, [0, 1, 2], [0, 1, 4])',
+    /// holding the argument, the breakpoints and the values at each breakpoint
+    Piecewise(InputSpan, Box<PreExp>, Box<PreExp>, Box<PreExp>),
+    /// The inner product of two iterables of numbers or variable names like 'dot(A[i], ["x", "y"])',
+    /// expanded into a sum of products
+    Dot(InputSpan, Box<PreExp>, Box<PreExp>),
+    /// An explicit type annotation like '[] as Number[]', holding the expression
+    /// and the kind it is cast to
+    Cast(InputSpan, Box<PreExp>, PrimitiveKind),
+    /// An array built from an iteration like '[i * 2 for i in 0..5 where i != 2]'
+    ArrayComprehension(Spanned<ArrayComprehension>),
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -78,7 +94,26 @@ export type SerializedPreExp = {span: InputSpan} & (
     {type: "UnaryOperation", value: {
         op: UnOp,
         exp: SerializedPreExp,
-    }}
+    }} |
+    {type: "IfElse", value: {
+        condition: SerializedPreExp,
+        then_branch: SerializedPreExp,
+        else_branch: SerializedPreExp,
+    }} |
+    {type: "Piecewise", value: {
+        argument: SerializedPreExp,
+        breakpoints: SerializedPreExp,
+        values: SerializedPreExp,
+    }} |
+    {type: "Dot", value: {
+        lhs: SerializedPreExp,
+        rhs: SerializedPreExp,
+    }} |
+    {type: "Cast", value: {
+        exp: SerializedPreExp,
+        kind: SerializedPrimitiveKind,
+    }} |
+    {type: "ArrayComprehension", value: SerializedArrayComprehension}
 )
 "#;
 
@@ -96,6 +131,10 @@ impl TypeCheckable for PreExp {
             Self::BinaryOperation(op, lhs, rhs) => {
                 lhs.type_check(context, fn_context)?;
                 rhs.type_check(context, fn_context)?;
+                if !op.is_boolean() {
+                    lhs.expect_numeric_operation()?;
+                    rhs.expect_numeric_operation()?;
+                }
                 let lhs_type = lhs.get_type(context, fn_context);
                 let rhs_type = rhs.get_type(context, fn_context);
                 if !lhs_type.can_apply_binary_op(**op, rhs_type.clone()) {
@@ -127,6 +166,7 @@ impl TypeCheckable for PreExp {
             Self::Abs(_, exp) => {
                 exp.type_check(context, fn_context)
                     .map_err(|e| e.add_span(exp.span()))?;
+                exp.expect_numeric_operation()?;
                 let exp_type = exp.get_type(context, fn_context);
                 if !exp_type.is_numeric() {
                     return Err(TransformError::from_wrong_type(
@@ -157,7 +197,11 @@ impl TypeCheckable for PreExp {
                     exp.type_check(context, fn_context)
                         .map_err(|e| e.add_span(f.span()))?;
                     let exp_type = exp.get_type(context, fn_context);
-                    if !exp_type.is_numeric() {
+                    let is_numeric = match &exp_type {
+                        PrimitiveKind::Iterable(inner) => inner.is_numeric() || inner.is_any(),
+                        kind => kind.is_numeric(),
+                    };
+                    if !is_numeric {
                         return Err(TransformError::from_wrong_type(
                             PrimitiveKind::Number,
                             exp_type,
@@ -193,6 +237,18 @@ impl TypeCheckable for PreExp {
                 if let Err(e) = res {
                     return Err(e.add_span(f.span()));
                 }
+                if let BlockScopedFunctionKind::Count = f.kind {
+                    //the body of a count is a predicate
+                    if exp_type != PrimitiveKind::Boolean && !exp_type.is_any() {
+                        return Err(TransformError::from_wrong_type(
+                            PrimitiveKind::Boolean,
+                            exp_type,
+                            f.exp.span().clone(),
+                        )
+                        .add_span(f.span()));
+                    }
+                    return Ok(());
+                }
                 if !exp_type.is_numeric() {
                     let err = TransformError::from_wrong_type(
                         PrimitiveKind::Number,
@@ -204,10 +260,135 @@ impl TypeCheckable for PreExp {
                 }
                 Ok(())
             }
+            Self::ArrayComprehension(c) => {
+                for iter in &c.iters {
+                    iter.iterator
+                        .type_check(context, fn_context)
+                        .map_err(|e| e.add_span(c.span()))?;
+                    context.add_scope();
+                    let types = iter
+                        .variable_types(context, fn_context)
+                        .map_err(|e| e.add_span(c.span()))?;
+                    for (name, t) in types {
+                        context.add_token_type(
+                            t,
+                            name.span().clone(),
+                            Some(name.value().clone()),
+                        )?;
+                    }
+                }
+                let res = type_check_comprehension_body(c, context, fn_context);
+                for _ in &c.iters {
+                    context.pop_scope().map_err(|e| e.add_span(c.span()))?;
+                }
+                res.map_err(|e| e.add_span(c.span()))
+            }
             Self::ArrayAccess(array_access) => context
                 .get_addressable_value(array_access, fn_context)
                 .map(|_| ())
                 .map_err(|e| e.add_span(array_access.span())),
+            Self::IfElse(span, condition, then_branch, else_branch) => {
+                condition
+                    .type_check(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                then_branch
+                    .type_check(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                else_branch
+                    .type_check(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                let condition_type = condition.get_type(context, fn_context);
+                if condition_type != PrimitiveKind::Boolean && !condition_type.is_any() {
+                    return Err(TransformError::from_wrong_type(
+                        PrimitiveKind::Boolean,
+                        condition_type,
+                        condition.span().clone(),
+                    )
+                    .add_span(span));
+                }
+                let then_type = then_branch.get_type(context, fn_context);
+                let else_type = else_branch.get_type(context, fn_context);
+                let compatible = then_type == else_type
+                    || then_type.is_any()
+                    || else_type.is_any()
+                    || (then_type.is_numeric() && else_type.is_numeric());
+                if !compatible {
+                    return Err(TransformError::from_wrong_type(
+                        then_type,
+                        else_type,
+                        else_branch.span().clone(),
+                    )
+                    .add_span(span));
+                }
+                Ok(())
+            }
+            Self::Piecewise(span, argument, breakpoints, values) => {
+                for exp in [argument, breakpoints, values] {
+                    exp.type_check(context, fn_context)
+                        .map_err(|e| e.add_span(span))?;
+                }
+                let argument_type = argument.get_type(context, fn_context);
+                if !argument_type.is_numeric() && !argument_type.is_any() {
+                    return Err(TransformError::from_wrong_type(
+                        PrimitiveKind::Number,
+                        argument_type,
+                        argument.span().clone(),
+                    )
+                    .add_span(span));
+                }
+                for points in [breakpoints, values] {
+                    let points_type = points.get_type(context, fn_context);
+                    match &points_type {
+                        PrimitiveKind::Iterable(kind) if kind.is_numeric() || kind.is_any() => {}
+                        PrimitiveKind::Any => {}
+                        _ => {
+                            return Err(TransformError::from_wrong_type(
+                                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+                                points_type,
+                                points.span().clone(),
+                            )
+                            .add_span(span))
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Self::Dot(span, lhs, rhs) => {
+                for exp in [lhs, rhs] {
+                    exp.type_check(context, fn_context)
+                        .map_err(|e| e.add_span(span))?;
+                    let exp_type = exp.get_type(context, fn_context);
+                    match &exp_type {
+                        PrimitiveKind::Iterable(kind)
+                            if kind.is_numeric()
+                                || kind.is_any()
+                                || **kind == PrimitiveKind::String => {}
+                        PrimitiveKind::Any => {}
+                        _ => {
+                            return Err(TransformError::from_wrong_type(
+                                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+                                exp_type,
+                                exp.span().clone(),
+                            )
+                            .add_span(span))
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Self::Cast(span, exp, kind) => {
+                exp.type_check(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                let exp_type = exp.get_type(context, fn_context);
+                if !exp_type.can_cast_to(kind) {
+                    return Err(TransformError::Other(format!(
+                        "cannot cast \"{}\" to \"{}\"",
+                        exp_type, kind
+                    ))
+                    .add_span(span));
+                }
+                Ok(())
+            }
         }
     }
     fn populate_token_type_map(
@@ -289,6 +470,32 @@ impl TypeCheckable for PreExp {
                 }
                 f.exp.populate_token_type_map(context, fn_context);
             }
+            Self::ArrayComprehension(c) => {
+                for iter in &c.iters {
+                    iter.populate_token_type_map(context, fn_context);
+                }
+                c.exp.populate_token_type_map(context, fn_context);
+                if let Some(guard) = &c.guard {
+                    guard.populate_token_type_map(context, fn_context);
+                }
+            }
+            Self::IfElse(_, condition, then_branch, else_branch) => {
+                condition.populate_token_type_map(context, fn_context);
+                then_branch.populate_token_type_map(context, fn_context);
+                else_branch.populate_token_type_map(context, fn_context);
+            }
+            Self::Piecewise(_, argument, breakpoints, values) => {
+                argument.populate_token_type_map(context, fn_context);
+                breakpoints.populate_token_type_map(context, fn_context);
+                values.populate_token_type_map(context, fn_context);
+            }
+            Self::Dot(_, lhs, rhs) => {
+                lhs.populate_token_type_map(context, fn_context);
+                rhs.populate_token_type_map(context, fn_context);
+            }
+            Self::Cast(_, exp, _) => {
+                exp.populate_token_type_map(context, fn_context);
+            }
         }
     }
 }
@@ -320,15 +527,40 @@ impl WithType for PreExp {
                     }
                 }
             }
-            Self::BinaryOperation(_, lhs, _) => lhs.get_type(context, fn_context),
+            //divisions of integer literals were already folded into rationals by the parser,
+            //the operations left follow the promotion rules
+            Self::BinaryOperation(op, lhs, rhs) => lhs
+                .get_type(context, fn_context)
+                .binary_op_result(**op, &rhs.get_type(context, fn_context)),
             Self::UnaryOperation(_, exp) => exp.get_type(context, fn_context),
             Self::Abs(_, exp) => exp.get_type(context, fn_context),
             Self::ArrayAccess(a) => context
                 .get_addressable_value(a, fn_context)
                 .unwrap_or(PrimitiveKind::Undefined),
             Self::BlockFunction(_) => PrimitiveKind::Number, //TODO check if this is true always
-            Self::BlockScopedFunction(_) => PrimitiveKind::Number, //TODO check if this is true always
+            Self::BlockScopedFunction(f) => match f.kind {
+                BlockScopedFunctionKind::ArgMin | BlockScopedFunctionKind::ArgMax => {
+                    binding_type(f, context, fn_context)
+                }
+                BlockScopedFunctionKind::Count => PrimitiveKind::PositiveInteger,
+                _ => PrimitiveKind::Number, //TODO check if this is true always
+            },
             Self::CompoundVariable(_) => PrimitiveKind::Number, //TODO check if this is true always
+            Self::Piecewise(_, _, _, _) => PrimitiveKind::Number,
+            Self::Dot(_, _, _) => PrimitiveKind::Number,
+            Self::Cast(_, _, kind) => kind.clone(),
+            Self::ArrayComprehension(c) => PrimitiveKind::Iterable(Box::new(
+                comprehension_element_type(c, context, fn_context),
+            )),
+            Self::IfElse(_, _, then_branch, else_branch) => {
+                let then_type = then_branch.get_type(context, fn_context);
+                let else_type = else_branch.get_type(context, fn_context);
+                if then_type != else_type && then_type.is_numeric() && else_type.is_numeric() {
+                    PrimitiveKind::Number
+                } else {
+                    then_type
+                }
+            }
         }
     }
 }
@@ -349,6 +581,91 @@ impl PreExp {
             Self::ArrayAccess(array_access) => array_access.span(),
             Self::BlockScopedFunction(function) => function.span(),
             Self::FunctionCall(span, _) => span,
+            Self::IfElse(span, _, _, _) => span,
+            Self::Piecewise(span, _, _, _) => span,
+            Self::Dot(span, _, _) => span,
+            Self::Cast(span, _, _) => span,
+            Self::ArrayComprehension(c) => c.span(),
+        }
+    }
+    /// Returns the names of the variables and arrays this expression refers to, in order of appearance.
+    pub fn referenced_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.collect_referenced_names(&mut names);
+        names
+    }
+    fn collect_referenced_names(&self, names: &mut Vec<String>) {
+        match self {
+            Self::Primitive(_) => {}
+            Self::Variable(name) => names.push(name.value().clone()),
+            //the name of a compound variable depends on the value of its indexes
+            Self::CompoundVariable(c) => c
+                .indexes
+                .iter()
+                .for_each(|i| i.collect_referenced_names(names)),
+            Self::ArrayAccess(array_access) => {
+                names.push(array_access.name.clone());
+                array_access
+                    .accesses
+                    .iter()
+                    .for_each(|a| a.collect_referenced_names(names));
+            }
+            Self::BlockFunction(f) => f
+                .exps
+                .iter()
+                .for_each(|e| e.collect_referenced_names(names)),
+            Self::BlockScopedFunction(f) => {
+                let mut inner = Vec::new();
+                f.iters
+                    .iter()
+                    .for_each(|i| i.iterator.collect_referenced_names(&mut inner));
+                f.exp.collect_referenced_names(&mut inner);
+                //the iteration variables shadow the names outside of the block
+                let bound = f
+                    .iters
+                    .iter()
+                    .flat_map(|i| match &i.var {
+                        VariableKind::Single(name) => vec![name.value()],
+                        VariableKind::Tuple(names) => names.iter().map(|n| n.value()).collect(),
+                    })
+                    .collect::<Vec<_>>();
+                names.extend(inner.into_iter().filter(|n| !bound.contains(&n)));
+            }
+            Self::ArrayComprehension(c) => {
+                let mut inner = Vec::new();
+                c.iters
+                    .iter()
+                    .for_each(|i| i.iterator.collect_referenced_names(&mut inner));
+                c.exp.collect_referenced_names(&mut inner);
+                if let Some(guard) = &c.guard {
+                    guard.collect_referenced_names(&mut inner);
+                }
+                let bound = c
+                    .iters
+                    .iter()
+                    .flat_map(|i| match &i.var {
+                        VariableKind::Single(name) => vec![name.value()],
+                        VariableKind::Tuple(names) => names.iter().map(|n| n.value()).collect(),
+                    })
+                    .collect::<Vec<_>>();
+                names.extend(inner.into_iter().filter(|n| !bound.contains(&n)));
+            }
+            Self::FunctionCall(_, call) => call
+                .args
+                .iter()
+                .for_each(|a| a.collect_referenced_names(names)),
+            Self::Abs(_, exp) | Self::UnaryOperation(_, exp) | Self::Cast(_, exp, _) => {
+                exp.collect_referenced_names(names)
+            }
+            Self::BinaryOperation(_, lhs, rhs) | Self::Dot(_, lhs, rhs) => {
+                lhs.collect_referenced_names(names);
+                rhs.collect_referenced_names(names);
+            }
+            Self::IfElse(_, a, b, c) | Self::Piecewise(_, a, b, c) => {
+                a.collect_referenced_names(names);
+                b.collect_referenced_names(names);
+                c.collect_referenced_names(names);
+            }
         }
     }
     pub fn into_exp(
@@ -357,6 +674,18 @@ impl PreExp {
         fn_context: &FunctionContext,
     ) -> Result<Exp, TransformError> {
         match self {
+            Self::BinaryOperation(_, _, _) if self.is_exact_rational_constant() == Some(true) => {
+                match self
+                    .as_primitive(context, fn_context)
+                    .and_then(|p| p.as_number_cast())
+                {
+                    Ok(n) => Ok(Exp::Number(n)),
+                    Err(e) => Err(e.add_span(self.span())),
+                }
+            }
+            Self::BinaryOperation(op, _, _) if op.is_boolean() => {
+                Err(boolean_operator_in_model(op.to_string()).add_span(op.span()))
+            }
             Self::BinaryOperation(op, lhs, rhs) => {
                 let lhs = lhs
                     .into_exp(context, fn_context)
@@ -364,6 +693,13 @@ impl PreExp {
                 let rhs = rhs
                     .into_exp(context, fn_context)
                     .map_err(|e| e.add_span(self.span()))?;
+                //the divisor can be a constant expression even if the dividend has variables
+                if matches!(**op, BinOp::Div | BinOp::Mod)
+                    && matches!(rhs.simplify(), Exp::Number(n) if n == 0.0)
+                {
+                    let error = OperatorError::division_by_zero(**op);
+                    return Err(TransformError::Other(error.to_string()).add_span(op.span()));
+                }
                 Ok(Exp::BinOp(**op, lhs.to_box(), rhs.to_box()))
             }
             Self::Primitive(n) => match n.as_number_cast() {
@@ -377,12 +713,35 @@ impl PreExp {
                 Ok(Exp::Abs(inner.to_box()))
             }
             Self::BlockFunction(f) => {
-                let mut parsed_exp = f
-                    .exps
-                    .iter()
-                    .map(|exp| exp.into_exp(context, fn_context))
-                    .collect::<Result<Vec<Exp>, TransformError>>()
-                    .map_err(|e| e.add_span(self.span()))?;
+                let mut parsed_exp = Vec::with_capacity(f.exps.len());
+                for exp in &f.exps {
+                    match exp.compile_time_value(context, fn_context) {
+                        Some(value) => {
+                            let value = value.map_err(|e| e.add_span(exp.span()))?;
+                            //iterables are spread into their elements, like in "min { x, A }"
+                            let values = match value {
+                                Primitive::Iterable(iterable) => iterable.to_primitives(),
+                                value => vec![value],
+                            };
+                            for value in values {
+                                let value =
+                                    value.as_number_cast().map_err(|e| e.add_span(exp.span()))?;
+                                parsed_exp.push(Exp::Number(value));
+                            }
+                        }
+                        None => parsed_exp.push(
+                            exp.into_exp(context, fn_context)
+                                .map_err(|e| e.add_span(self.span()))?,
+                        ),
+                    }
+                }
+                if parsed_exp.is_empty() {
+                    return Err(TransformError::Other(format!(
+                        "Cannot compute the {} of an empty list of values",
+                        f.kind
+                    ))
+                    .add_span(self.span()));
+                }
                 match f.kind {
                     BlockFunctionKind::Min => Ok(Exp::Min(parsed_exp)),
                     BlockFunctionKind::Max => Ok(Exp::Max(parsed_exp)),
@@ -401,6 +760,9 @@ impl PreExp {
                 }
             }
 
+            Self::UnaryOperation(op, _) if **op == UnOp::Not => {
+                Err(boolean_operator_in_model(op.to_string()).add_span(op.span()))
+            }
             Self::UnaryOperation(op, exp) => {
                 let inner = exp
                     .into_exp(context, fn_context)
@@ -446,6 +808,23 @@ impl PreExp {
                     Err(e) => Err(e.add_span(self.span())),
                 }
             }
+            Self::BlockScopedFunction(f)
+                if matches!(
+                    f.kind,
+                    BlockScopedFunctionKind::ArgMin
+                        | BlockScopedFunctionKind::ArgMax
+                        | BlockScopedFunctionKind::Count
+                ) =>
+            {
+                //the index or count can only be a compile time value, so the whole expression is folded
+                let value = self
+                    .as_primitive(context, fn_context)
+                    .map_err(|e| e.add_span(self.span()))?;
+                match value.as_number_cast() {
+                    Ok(n) => Ok(Exp::Number(n)),
+                    Err(e) => Err(e.add_span(self.span())),
+                }
+            }
             Self::BlockScopedFunction(f) => {
                 let mut results = Vec::new();
                 recursive_set_resolver(
@@ -480,6 +859,9 @@ impl PreExp {
                     }
                     BlockScopedFunctionKind::Min => Ok(Exp::Min(results)),
                     BlockScopedFunctionKind::Max => Ok(Exp::Max(results)),
+                    BlockScopedFunctionKind::ArgMin
+                    | BlockScopedFunctionKind::ArgMax
+                    | BlockScopedFunctionKind::Count => unreachable!(),
                     BlockScopedFunctionKind::Avg => {
                         let len = results.len();
                         let mut sum = results.pop().unwrap_or(Exp::Number(0.0));
@@ -510,6 +892,104 @@ impl PreExp {
                     Err(e) => Err(e.add_span(self.span())),
                 }
             }
+            Self::Piecewise(span, argument, breakpoints, values) => {
+                let argument = argument
+                    .into_exp(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                let breakpoints = breakpoints
+                    .as_number_array(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                let values = values
+                    .as_number_array(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                if breakpoints.len() != values.len() {
+                    return Err(TransformError::Other(format!(
+                        "piecewise expects as many values as breakpoints, got {} breakpoints and {} values",
+                        breakpoints.len(),
+                        values.len()
+                    ))
+                    .add_span(span));
+                }
+                if breakpoints.len() < 2 {
+                    return Err(TransformError::Other(format!(
+                        "piecewise expects at least 2 breakpoints, got {}",
+                        breakpoints.len()
+                    ))
+                    .add_span(span));
+                }
+                if breakpoints.windows(2).any(|w| w[0] >= w[1]) {
+                    return Err(TransformError::Other(format!(
+                        "the breakpoints of piecewise must be strictly increasing, got {:?}",
+                        breakpoints
+                    ))
+                    .add_span(span));
+                }
+                Ok(Exp::Piecewise(argument.to_box(), breakpoints, values))
+            }
+            Self::Dot(span, lhs, rhs) => {
+                let lhs = lhs
+                    .as_iterator(context, fn_context)
+                    .map_err(|e| e.add_span(span))?
+                    .to_primitives();
+                let rhs = rhs
+                    .as_iterator(context, fn_context)
+                    .map_err(|e| e.add_span(span))?
+                    .to_primitives();
+                if lhs.len() != rhs.len() {
+                    return Err(TransformError::Other(format!(
+                        "dot expects two iterables of the same length, got {} and {} elements",
+                        lhs.len(),
+                        rhs.len()
+                    ))
+                    .add_span(span));
+                }
+                let mut products = lhs
+                    .iter()
+                    .zip(rhs.iter())
+                    .map(|(lhs, rhs)| {
+                        let lhs = dot_operand_to_exp(lhs, context)?;
+                        let rhs = dot_operand_to_exp(rhs, context)?;
+                        Ok(Exp::BinOp(BinOp::Mul, lhs.to_box(), rhs.to_box()))
+                    })
+                    .collect::<Result<Vec<Exp>, TransformError>>()
+                    .map_err(|e| e.add_span(span))?;
+                let mut sum = products.pop().unwrap_or(Exp::Number(0.0));
+                for product in products.into_iter().rev() {
+                    sum = Exp::BinOp(BinOp::Add, product.to_box(), sum.to_box());
+                }
+                Ok(sum)
+            }
+            Self::IfElse(_, _, _, _) | Self::Cast(_, _, _) | Self::ArrayComprehension(_) => {
+                //the branches, casts and arrays can only be compile time values, so the whole expression is folded
+                let span = self.span();
+                let value = self
+                    .as_primitive(context, fn_context)
+                    .map_err(|e| e.add_span(span))?;
+                match value.as_number_cast() {
+                    Ok(n) => Ok(Exp::Number(n)),
+                    Err(e) => Err(e.add_span(span)),
+                }
+            }
+        }
+    }
+
+    /// Evaluates the expressions that are always folded at compile time, the only ones whose
+    /// value can be an iterable, returns None for the ones that become an expression of the model
+    fn compile_time_value(
+        &self,
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Option<Result<Primitive, TransformError>> {
+        match self {
+            Self::Variable(name) if context.value(name).is_none() => None,
+            Self::Primitive(_)
+            | Self::Variable(_)
+            | Self::ArrayAccess(_)
+            | Self::FunctionCall(_, _)
+            | Self::IfElse(_, _, _, _)
+            | Self::Cast(_, _, _)
+            | Self::ArrayComprehension(_) => Some(self.as_primitive(context, fn_context)),
+            _ => None,
         }
     }
 
@@ -529,15 +1009,10 @@ impl PreExp {
             PreExp::Variable(s) => match context.value(s) {
                 Some(value) => Ok(value.clone()),
                 None => match context.variable_domain(s) {
-                    None => Err(TransformError::UndeclaredVariable(
+                    None => Err(TransformError::UndeclaredVariable(s.value().clone())),
+                    Some(_) => Err(TransformError::DomainVariableInConstantContext(
                         s.value().clone(),
                     )),
-                    Some(_) => Err(
-                        //TODO create a specific error for this
-                        TransformError::Other(
-                            format!("Variable \"{}\" is a domain variable and cannot be used inside expression valuation", s.value())
-                        )
-                    )
                 },
             },
             PreExp::CompoundVariable(c) => {
@@ -546,22 +1021,15 @@ impl PreExp {
                 match context.value(&name) {
                     Some(value) => Ok(value.clone()),
                     None => match context.variable_domain(&name) {
-                        None => Err(TransformError::UndeclaredVariable(
-                            name.clone(),
-                        )),
-                        Some(_) => Err(
-                            //TODO create a specific error for this
-                            TransformError::Other(
-                                format!("Variable \"{}\" is a domain variable and cannot be used inside expression valuation", name)
-                            )
-                        )
+                        None => Err(TransformError::UndeclaredVariable(name.clone())),
+                        Some(_) => Err(TransformError::DomainVariableInConstantContext(name)),
                     },
                 }
             }
             PreExp::FunctionCall(_, fun) => {
-                let f = fn_context.function(&fun.name).ok_or_else(|| {
-                    TransformError::NonExistentFunction(fun.name.clone())
-                })?;
+                let f = fn_context
+                    .function(&fun.name)
+                    .ok_or_else(|| TransformError::NonExistentFunction(fun.name.clone()))?;
                 let value = f.call(&fun.args, context, fn_context)?;
                 Ok(value)
             }
@@ -573,6 +1041,9 @@ impl PreExp {
                 let value = v.as_primitive(context, fn_context)?;
                 match value.apply_unary_op(**op) {
                     Ok(value) => Ok(value),
+                    Err(e @ OperatorError::IntegerOverflow { .. }) => {
+                        Err(TransformError::Other(e.to_string()).add_span(op.span()))
+                    }
                     Err(_) => Err(TransformError::from_wrong_unop(
                         **op,
                         value.get_type(),
@@ -585,6 +1056,10 @@ impl PreExp {
                 let rhs = rhs.as_primitive(context, fn_context)?;
                 match lhs.apply_binary_op(**op, &rhs) {
                     Ok(value) => Ok(value),
+                    Err(
+                        e @ (OperatorError::DivisionByZero { .. }
+                        | OperatorError::IntegerOverflow { .. }),
+                    ) => Err(TransformError::Other(e.to_string()).add_span(op.span())),
                     Err(_) => Err(TransformError::from_wrong_binop(
                         **op,
                         lhs.get_type(),
@@ -593,7 +1068,42 @@ impl PreExp {
                     )),
                 }
             }
-            PreExp::Abs(_, _) | PreExp::BlockFunction(_) | PreExp::BlockScopedFunction(_) => {
+            PreExp::IfElse(_, condition, then_branch, else_branch) => {
+                if condition.as_boolean(context, fn_context)? {
+                    then_branch.as_primitive(context, fn_context)
+                } else {
+                    else_branch.as_primitive(context, fn_context)
+                }
+            }
+            PreExp::Cast(span, exp, kind) => exp
+                .as_primitive(context, fn_context)?
+                .cast_to(kind)
+                .map_err(|e| e.add_span(span)),
+            PreExp::BlockScopedFunction(f)
+                if matches!(
+                    f.kind,
+                    BlockScopedFunctionKind::ArgMin | BlockScopedFunctionKind::ArgMax
+                ) =>
+            {
+                extremal_binding(f, context, fn_context).map_err(|e| e.add_span(f.span()))
+            }
+            PreExp::BlockScopedFunction(f) if matches!(f.kind, BlockScopedFunctionKind::Count) => {
+                let results = resolve_as_constant(f, context, fn_context, &|context| {
+                    f.exp.as_boolean(context, fn_context)
+                })
+                .map_err(|e| e.add_span(f.span()))?;
+                let count = results.into_iter().filter(|satisfied| *satisfied).count();
+                Ok(Primitive::PositiveInteger(count as u64))
+            }
+            PreExp::ArrayComprehension(c) => c
+                .evaluate(context, fn_context)
+                .map(Primitive::Iterable)
+                .map_err(|e| e.add_span(c.span())),
+            PreExp::Abs(_, _)
+            | PreExp::BlockFunction(_)
+            | PreExp::BlockScopedFunction(_)
+            | PreExp::Piecewise(_, _, _, _)
+            | PreExp::Dot(_, _, _) => {
                 //TODO is this correct?
                 Err(TransformError::WrongArgument {
                     got: PrimitiveKind::Undefined,
@@ -722,6 +1232,61 @@ impl PreExp {
             .map_err(|e| e.add_span(self.span()))?
     }
 
+    pub fn as_number_array(
+        &self,
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Vec<f64>, TransformError> {
+        self.as_iterator(context, fn_context)?
+            .to_primitives()
+            .iter()
+            .map(|p| p.as_number_cast())
+            .collect::<Result<Vec<f64>, TransformError>>()
+            .map_err(|e| e.add_span(self.span()))
+    }
+
+    /// Checks if the expression is made only of integer and rational literals, with at
+    /// least one rational, so that it can be folded exactly before being turned into a float
+    fn is_exact_rational_constant(&self) -> Option<bool> {
+        match self {
+            Self::Primitive(p) => match p.value() {
+                Primitive::Rational(_, _) => Some(true),
+                Primitive::Integer(_) | Primitive::PositiveInteger(_) => Some(false),
+                _ => None,
+            },
+            Self::UnaryOperation(_, exp) => exp.is_exact_rational_constant(),
+            Self::BinaryOperation(_, lhs, rhs) => {
+                let lhs = lhs.is_exact_rational_constant()?;
+                let rhs = rhs.is_exact_rational_constant()?;
+                Some(lhs || rhs)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the expression is an operation that produces a boolean, like a comparison
+    pub(crate) fn is_boolean_operation(&self) -> bool {
+        match self {
+            Self::BinaryOperation(op, _, _) => op.is_boolean(),
+            Self::UnaryOperation(op, _) => **op == UnOp::Not,
+            _ => false,
+        }
+    }
+
+    /// Errors if the expression is an operation that produces a boolean, as it can't be used
+    /// where a number is required, like in arithmetic, constraints or objectives
+    pub(crate) fn expect_numeric_operation(&self) -> Result<(), TransformError> {
+        if self.is_boolean_operation() {
+            Err(TransformError::from_wrong_type(
+                PrimitiveKind::Number,
+                PrimitiveKind::Boolean,
+                self.span().clone(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) fn is_leaf(&self) -> bool {
         !matches!(
             self,
@@ -740,7 +1305,8 @@ impl PreExp {
                 */
                 let lhs_str = lhs.to_string_with_precedence(op.precedence());
                 let rhs_str = rhs.to_string_with_precedence(op.precedence());
-                if op.precedence() < previous_precedence {
+                //comparisons are only allowed inside of parenthesis or conditions
+                if op.precedence() < previous_precedence || op.is_comparison() {
                     format!("({} {} {})", lhs_str, **op, rhs_str)
                 } else {
                     format!("{} {} {}", lhs_str, **op, rhs_str)
@@ -779,6 +1345,7 @@ impl ToLatex for PreExp {
             Self::ArrayAccess(a) => a.to_latex(),
             Self::BlockFunction(f) => f.to_latex(),
             Self::BlockScopedFunction(f) => f.to_latex(),
+            Self::ArrayComprehension(c) => c.to_latex(),
             Self::BinaryOperation(op, lhs, rhs) => {
                 let rhs = rhs.to_latex_with_precedence(op.precedence());
                 let lhs = lhs.to_latex_with_precedence(op.precedence());
@@ -799,20 +1366,211 @@ impl ToLatex for PreExp {
             Self::Abs(_, exp) => format!("|{}|", exp.to_latex()),
             Self::CompoundVariable(c) => c.to_latex(),
             Self::FunctionCall(_, f) => f.to_latex(),
+            Self::IfElse(_, condition, then_branch, else_branch) => format!(
+                "\\begin{{cases}} {} & \\text{{if }} {} \\\\ {} & \\text{{otherwise}} \\end{{cases}}",
+                then_branch.to_latex(),
+                condition.to_latex(),
+                else_branch.to_latex()
+            ),
+            Self::Piecewise(_, argument, breakpoints, values) => format!(
+                "\\text{{piecewise}}({}, {}, {})",
+                argument.to_latex(),
+                breakpoints.to_latex(),
+                values.to_latex()
+            ),
+            Self::Dot(_, lhs, rhs) => format!("{} \\cdot {}", lhs.to_latex(), rhs.to_latex()),
+            Self::Cast(_, exp, _) => exp.to_latex(),
+        }
+    }
+}
+
+/// Returns the type of the bindings of an argmin/argmax, a tuple if there is more than one
+/// iteration variable
+fn binding_type(
+    f: &BlockScopedFunction,
+    context: &TypeCheckerContext,
+    fn_context: &FunctionContext,
+) -> PrimitiveKind {
+    let types = f
+        .iters
+        .iter()
+        .map(|iter| iter.variable_types(context, fn_context))
+        .collect::<Result<Vec<_>, TransformError>>();
+    match types {
+        Ok(types) => {
+            let mut types = types
+                .into_iter()
+                .flatten()
+                .map(|(_, t)| t)
+                .collect::<Vec<_>>();
+            if types.len() == 1 {
+                types.remove(0)
+            } else {
+                PrimitiveKind::Tuple(types)
+            }
+        }
+        //the iterators can depend on the previous iteration variables, which are not in scope here
+        Err(_) => PrimitiveKind::Any,
+    }
+}
+
+/// Checks the element and the guard of an array comprehension, with the iteration variables in scope
+fn type_check_comprehension_body(
+    c: &ArrayComprehension,
+    context: &mut TypeCheckerContext,
+    fn_context: &FunctionContext,
+) -> Result<(), TransformError> {
+    c.exp.type_check(context, fn_context)?;
+    let exp_type = c.exp.get_type(context, fn_context);
+    if exp_type == PrimitiveKind::Undefined {
+        return Err(TransformError::from_wrong_type(
+            PrimitiveKind::Any,
+            exp_type,
+            c.exp.span().clone(),
+        ));
+    }
+    if let Some(guard) = &c.guard {
+        guard.type_check(context, fn_context)?;
+        let guard_type = guard.get_type(context, fn_context);
+        if guard_type != PrimitiveKind::Boolean && !guard_type.is_any() {
+            return Err(TransformError::from_wrong_type(
+                PrimitiveKind::Boolean,
+                guard_type,
+                guard.span().clone(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Finds the type of the elements of an array comprehension, declaring the iteration
+/// variables in a copy of the context
+fn comprehension_element_type(
+    c: &ArrayComprehension,
+    context: &TypeCheckerContext,
+    fn_context: &FunctionContext,
+) -> PrimitiveKind {
+    let mut context = context.scoped_copy();
+    for iter in &c.iters {
+        context.add_scope();
+        let types = match iter.variable_types(&context, fn_context) {
+            Ok(types) => types,
+            Err(_) => return PrimitiveKind::Any,
+        };
+        for (name, t) in types {
+            if context.declare_variable(name.value(), t, false).is_err() {
+                return PrimitiveKind::Any;
+            }
+        }
+    }
+    match c.exp.get_type(&context, fn_context) {
+        PrimitiveKind::Undefined => PrimitiveKind::Any,
+        kind => kind,
+    }
+}
+
+/// Evaluates the body of a scoped function for every binding of its iteration variables,
+/// the bindings are declared in a copy of the context as the function is evaluated as a constant.
+fn resolve_as_constant<T>(
+    f: &BlockScopedFunction,
+    context: &TransformerContext,
+    fn_context: &FunctionContext,
+    on_leaf: &dyn Fn(&mut TransformerContext) -> Result<T, TransformError>,
+) -> Result<Vec<T>, TransformError> {
+    let mut context = context.clone();
+    let mut results = Vec::new();
+    recursive_set_resolver(&f.iters, &mut context, fn_context, &mut results, 0, on_leaf)?;
+    Ok(results)
+}
+
+/// Finds the binding of the iteration variables of an argmin/argmax whose body is the minimum
+/// or maximum, keeping the first one on ties.
+fn extremal_binding(
+    f: &BlockScopedFunction,
+    context: &TransformerContext,
+    fn_context: &FunctionContext,
+) -> Result<Primitive, TransformError> {
+    let names = f
+        .iters
+        .iter()
+        .flat_map(|iter| match &iter.var {
+            VariableKind::Single(name) => vec![name.value().clone()],
+            VariableKind::Tuple(names) => names.iter().map(|n| n.value().clone()).collect(),
+        })
+        .collect::<Vec<String>>();
+    let results = resolve_as_constant(f, context, fn_context, &|context| {
+        let value = f.exp.as_number_cast(context, fn_context)?;
+        let binding = names
+            .iter()
+            .map(|name| {
+                context
+                    .value(name)
+                    .cloned()
+                    .ok_or_else(|| TransformError::UndeclaredVariable(name.clone()))
+            })
+            .collect::<Result<Vec<Primitive>, TransformError>>()?;
+        Ok((value, binding))
+    })?;
+    let best = results.into_iter().reduce(|best, current| {
+        let is_better = match f.kind {
+            BlockScopedFunctionKind::ArgMax => current.0 > best.0,
+            _ => current.0 < best.0,
+        };
+        if is_better {
+            current
+        } else {
+            best
+        }
+    });
+    match best {
+        Some((_, mut binding)) if binding.len() == 1 => Ok(binding.remove(0)),
+        Some((_, binding)) => Ok(Primitive::Tuple(Tuple::new(binding))),
+        None => Err(TransformError::Other(format!(
+            "{} expects at least one element to iterate over",
+            f.kind
+        ))),
+    }
+}
+
+/// Converts an element of the iterables of a dot product into an expression,
+/// strings are the names of the variables of the model, the rest must be numbers
+fn dot_operand_to_exp(
+    operand: &Primitive,
+    context: &mut TransformerContext,
+) -> Result<Exp, TransformError> {
+    match operand {
+        Primitive::String(name) => {
+            context.increment_domain_variable_usage(name)?;
+            Ok(Exp::Variable(name.clone()))
         }
+        _ => operand.as_number_cast().map(Exp::Number),
     }
 }
 
+/// Logical and comparison operators only work on constants, like in the condition of an if,
+/// so they can't become part of the expressions of the model
+fn boolean_operator_in_model(operator: String) -> TransformError {
+    TransformError::Other(format!(
+        "the operator \"{}\" produces a boolean and can only be used with constants, it can't be part of the model",
+        operator
+    ))
+}
+
 impl fmt::Display for PreExp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             Self::ArrayAccess(a) => a.to_string(),
             Self::BlockFunction(f) => f.to_string(),
             Self::BlockScopedFunction(f) => f.to_string(),
+            Self::ArrayComprehension(c) => c.to_string(),
             Self::BinaryOperation(op, lhs, rhs) => {
                 let rhs = rhs.to_string_with_precedence(op.precedence());
                 let lhs = lhs.to_string_with_precedence(op.precedence());
-                format!("{} {} {}", lhs, **op, rhs)
+                if op.is_comparison() {
+                    format!("({} {} {})", lhs, **op, rhs)
+                } else {
+                    format!("{} {} {}", lhs, **op, rhs)
+                }
             }
             Self::CompoundVariable(c) => c.to_string(),
             Self::FunctionCall(_, f) => f.to_string(),
@@ -825,6 +1583,15 @@ impl fmt::Display for PreExp {
                     format!("{}({})", **op, **exp)
                 }
             }
+            Self::IfElse(_, condition, then_branch, else_branch) => format!(
+                "if {} {{ {} }} else {{ {} }}",
+                **condition, **then_branch, **else_branch
+            ),
+            Self::Piecewise(_, argument, breakpoints, values) => {
+                format!("piecewise({}, {}, {})", **argument, **breakpoints, **values)
+            }
+            Self::Dot(_, lhs, rhs) => format!("dot({}, {})", **lhs, **rhs),
+            Self::Cast(_, exp, kind) => format!("{} as {}", **exp, kind),
             Self::Variable(name) => {
                 if name.contains('_') {
                     //in case this is a escaped variable
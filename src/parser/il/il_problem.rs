@@ -11,9 +11,9 @@ use crate::traits::ToLatex;
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{
     math::{Comparison, OptimizationType},
-    primitives::Primitive,
+    primitives::{Primitive, PrimitiveKind},
     type_checker::type_checker_context::{TypeCheckable, TypeCheckerContext, WithType},
-    utils::InputSpan,
+    utils::{InputSpan, Spanned},
 };
 
 /// Represents array-like access to a variable, such as `x[1][2]`.
@@ -139,6 +139,9 @@ impl ToLatex for CompoundVariable {
                 _ => format!("({})", i.to_latex()),
             })
             .collect::<Vec<String>>();
+        if indexes.is_empty() {
+            return self.name.clone();
+        }
         format!("{}_{{{}}}", self.name, indexes.join(""))
     }
 }
@@ -160,6 +163,9 @@ impl fmt::Display for CompoundVariable {
                 _ => format!("{{{}}}", i),
             })
             .collect::<Vec<String>>();
+        if indexes.is_empty() {
+            return f.write_str(&self.name);
+        }
         write!(f, "{}_{}", self.name, indexes.join("_"))
     }
 }
@@ -197,7 +203,8 @@ impl TypeCheckable for PreObjective {
         context: &mut TypeCheckerContext,
         fn_context: &FunctionContext,
     ) -> Result<(), TransformError> {
-        self.rhs.type_check(context, fn_context)
+        self.rhs.type_check(context, fn_context)?;
+        self.rhs.expect_numeric_operation()
     }
     fn populate_token_type_map(
         &self,
@@ -228,6 +235,33 @@ impl fmt::Display for PreObjective {
     }
 }
 
+/// Marks a constraint as a goal, like `x + y ~ 10`, whose deviation from the target is
+/// minimized in the objective instead of being enforced.
+#[derive(Debug, Serialize, Clone)]
+pub struct PreGoal {
+    /// Weight of the deviation in the objective, 1 if it is not given
+    pub weight: Option<PreExp>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IPreGoal: &'static str = r#"
+export type SerializedPreGoal = {
+    weight?: SerializedPreExp,
+}
+"#;
+
+impl PreGoal {
+    /// Creates a new goal.
+    ///
+    /// # Arguments
+    /// * `weight` - Weight of the deviation in the objective, 1 if None
+    pub fn new(weight: Option<PreExp>) -> Self {
+        Self { weight }
+    }
+}
+
 /// Represents a constraint in a mathematical programming problem.
 ///
 /// # Examp
@@ -241,6 +275,12 @@ pub struct PreConstraint {
     pub rhs: PreExp,
     /// Optional iteration sets for quantified constraints
     pub iteration: Vec<IterableSet>,
+    /// Other constraints joined with `or`, at least one of them must hold
+    pub alternatives: Vec<PreConstraint>,
+    /// Optional name of the constraint, its indexes are evaluated for every iteration
+    pub name: Option<Spanned<CompoundVariable>>,
+    /// Set if the constraint is a goal, which is not enforced but minimizes its deviation
+    pub goal: Option<PreGoal>,
     /// Source location information
     pub span: InputSpan,
 }
@@ -254,6 +294,9 @@ export type SerializedPreConstraint = {
     constraint_type: Comparison,
     rhs: SerializedPreExp,
     iteration: SerializedVariableKind[],
+    alternatives: SerializedPreConstraint[],
+    name?: SerializedSpanned<SerializedCompoundVariable>,
+    goal?: SerializedPreGoal,
     span: InputSpan,
 }
 "#;
@@ -279,9 +322,87 @@ impl PreConstraint {
             constraint_type,
             rhs,
             iteration,
+            alternatives: vec![],
+            name: None,
+            goal: None,
             span,
         }
     }
+
+    /// Names the constraint, like `capacity` in `capacity: x <= 2`.
+    ///
+    /// # Arguments
+    /// * `name` - The name, which can have indexes like `capacity_i`
+    pub fn with_name(mut self, name: Spanned<CompoundVariable>) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the constraints that can hold in place of this one, like `x >= 8` in `x <= 2 or x >= 8`.
+    ///
+    /// # Arguments
+    /// * `alternatives` - The other sides of the disjunction
+    pub fn with_alternatives(mut self, alternatives: Vec<PreConstraint>) -> Self {
+        self.alternatives = alternatives;
+        self
+    }
+
+    /// Turns the constraint into a goal, like `x + y ~ 10`.
+    ///
+    /// # Arguments
+    /// * `goal` - The goal, with its optional weight
+    pub fn with_goal(mut self, goal: PreGoal) -> Self {
+        self.goal = Some(goal);
+        self
+    }
+
+    /// Type checks the weight of the goal, which must be a number
+    fn type_check_goal(
+        &self,
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        let weight = match self.goal.as_ref().and_then(|g| g.weight.as_ref()) {
+            Some(weight) => weight,
+            None => return Ok(()),
+        };
+        weight.type_check(context, fn_context)?;
+        weight.expect_numeric_operation()?;
+        let weight_type = weight.get_type(context, fn_context);
+        if !weight_type.is_numeric() && !weight_type.is_any() {
+            return Err(TransformError::from_wrong_type(
+                PrimitiveKind::Number,
+                weight_type,
+                weight.span().clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Type checks the two sides of the comparison, the iteration scopes must already be populated
+    fn type_check_comparison(
+        &self,
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        self.lhs.type_check(context, fn_context)?;
+        self.rhs.type_check(context, fn_context)?;
+        self.lhs.expect_numeric_operation()?;
+        self.rhs.expect_numeric_operation()?;
+        let lhs_type = self.lhs.get_type(context, fn_context);
+        let rhs_type = self.rhs.get_type(context, fn_context);
+        if (!lhs_type.is_numeric() && !lhs_type.is_any())
+            || (!rhs_type.is_numeric() && !rhs_type.is_any())
+        {
+            let err = TransformError::Other(format!(
+                "Expected comparison of \"Number\", got \"{}\" {} \"{}\"",
+                lhs_type, self.constraint_type, rhs_type
+            ))
+            .add_span(&self.span);
+            return Err(err);
+        }
+        Ok(())
+    }
 }
 
 impl TypeCheckable for PreConstraint {
@@ -300,34 +421,22 @@ impl TypeCheckable for PreConstraint {
                 context.add_token_type(t, name.span().clone(), Some(name.value().clone()))?;
             }
         }
-        match (
-            self.lhs.type_check(context, fn_context),
-            self.rhs.type_check(context, fn_context),
-        ) {
-            (Ok(()), Ok(())) => (),
-            (Err(e), _) | (_, Err(e)) => {
-                for _ in &self.iteration {
-                    context.pop_scope()?;
-                }
-                return Err(e);
-            }
+        let result = match &self.name {
+            Some(name) => context
+                .check_compound_variable(&name.indexes, fn_context)
+                .map_err(|e| e.add_span(name.span())),
+            None => Ok(()),
         }
-        let lhs_type = self.lhs.get_type(context, fn_context);
-        let rhs_type = self.rhs.get_type(context, fn_context);
+        .and_then(|_| self.type_check_goal(context, fn_context))
+        .and_then(|_| {
+            std::iter::once(self)
+                .chain(self.alternatives.iter())
+                .try_for_each(|c| c.type_check_comparison(context, fn_context))
+        });
         for _ in &self.iteration {
             context.pop_scope()?;
         }
-        if (!lhs_type.is_numeric() && !lhs_type.is_any())
-            || (!rhs_type.is_numeric() && !rhs_type.is_any())
-        {
-            let err = TransformError::Other(format!(
-                "Expected comparison of \"Number\", got \"{}\" {} \"{}\"",
-                lhs_type, self.constraint_type, rhs_type
-            ))
-            .add_span(&self.span);
-            return Err(err);
-        }
-        Ok(())
+        result
     }
     fn populate_token_type_map(
         &self,
@@ -338,8 +447,18 @@ impl TypeCheckable for PreConstraint {
             context.add_scope();
             iter.populate_token_type_map(context, fn_context);
         }
-        self.lhs.populate_token_type_map(context, fn_context);
-        self.rhs.populate_token_type_map(context, fn_context);
+        if let Some(name) = &self.name {
+            for index in &name.indexes {
+                index.populate_token_type_map(context, fn_context);
+            }
+        }
+        if let Some(weight) = self.goal.as_ref().and_then(|g| g.weight.as_ref()) {
+            weight.populate_token_type_map(context, fn_context);
+        }
+        for c in std::iter::once(self).chain(self.alternatives.iter()) {
+            c.lhs.populate_token_type_map(context, fn_context);
+            c.rhs.populate_token_type_map(context, fn_context);
+        }
         for _ in &self.iteration {
             let _ = context.pop_scope();
         }
@@ -350,7 +469,27 @@ impl ToLatex for PreConstraint {
     fn to_latex(&self) -> String {
         let lhs = self.lhs.to_latex();
         let rhs = self.rhs.to_latex();
-        let constraint = self.constraint_type.to_latex();
+        let constraint = match &self.goal {
+            Some(_) => "\\approx".to_string(),
+            None => self.constraint_type.to_latex(),
+        };
+        let lhs = match self.goal.as_ref().and_then(|g| g.weight.as_ref()) {
+            Some(weight) => format!("[{}]\\ {}", weight.to_latex(), lhs),
+            None => lhs,
+        };
+        let rhs = self.alternatives.iter().fold(rhs, |acc, c| {
+            format!(
+                "{} \\lor {} {} {}",
+                acc,
+                c.lhs.to_latex(),
+                c.constraint_type.to_latex(),
+                c.rhs.to_latex()
+            )
+        });
+        let lhs = match &self.name {
+            Some(name) => format!("{}:\\ {}", name.to_latex(), lhs),
+            None => lhs,
+        };
         let iterations = self
             .iteration
             .iter()
@@ -373,10 +512,24 @@ impl ToLatex for PreConstraint {
 impl fmt::Display for PreConstraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut s = String::new();
-        s.push_str(&format!(
-            "{} {} {}",
-            self.lhs, self.constraint_type, self.rhs
-        ));
+        if let Some(name) = &self.name {
+            s.push_str(&format!("{}: ", **name));
+        }
+        match &self.goal {
+            Some(goal) => {
+                if let Some(weight) = &goal.weight {
+                    s.push_str(&format!("@weight({}) ", weight));
+                }
+                s.push_str(&format!("{} ~ {}", self.lhs, self.rhs));
+            }
+            None => s.push_str(&format!(
+                "{} {} {}",
+                self.lhs, self.constraint_type, self.rhs
+            )),
+        }
+        for alternative in &self.alternatives {
+            s.push_str(&format!(" or {}", alternative));
+        }
         if !self.iteration.is_empty() {
             s.push_str(" for ");
             s.push_str(
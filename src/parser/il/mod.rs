@@ -1,8 +1,10 @@
+pub mod array_comprehension;
 pub mod block_functions;
 pub mod il_exp;
 pub mod il_problem;
 pub mod iterable_set;
 
+pub use array_comprehension::*;
 pub use block_functions::*;
 pub use il_exp::*;
 pub use il_problem::*;
@@ -13,6 +13,17 @@ pub fn flatten_primitive_array_values(values: Vec<Primitive>) -> IterableKind {
     if first.is_none() {
         return IterableKind::Anys(vec![]);
     }
+    //nested arrays are kept as iterables even if their inner types differ
+    if values.iter().all(|v| matches!(v, Primitive::Iterable(_))) {
+        let values = values
+            .into_iter()
+            .map(|v| match v {
+                Primitive::Iterable(b) => b,
+                _ => unreachable!(),
+            })
+            .collect();
+        return IterableKind::Iterables(values);
+    }
     let first_kind = first.unwrap().get_type();
     let all_equal_type = values.iter().all(|v| v.get_type() == first_kind);
     if !all_equal_type {
@@ -22,6 +33,7 @@ pub fn flatten_primitive_array_values(values: Vec<Primitive>) -> IterableKind {
     //TODO try to make this return a Mixed Primitive if the types are different, instead of failing
     match first_kind {
         PrimitiveKind::Any => IterableKind::Numbers(vec![]), //can never happen
+        PrimitiveKind::Rational | PrimitiveKind::Map(_) => IterableKind::Anys(values),
         PrimitiveKind::Boolean => {
             let values = values
                 .into_iter()
@@ -125,3 +137,38 @@ pub fn flatten_primitive_array_values(values: Vec<Primitive>) -> IterableKind {
         }
     }
 }
+
+/// Checks that the elements of a nested array all have the same shape, so that
+/// the array is rectangular at every level of nesting.
+///
+/// Arrays whose elements are not all arrays are not checked.
+///
+/// # Arguments
+/// * `values` - The elements of the array
+///
+/// # Returns
+/// * `Ok(())` if the array is rectangular
+/// * `Err(String)` describing the first element with a different shape
+pub fn check_array_rectangularity(values: &[Primitive]) -> Result<(), String> {
+    let shapes = values
+        .iter()
+        .map(|v| match v {
+            Primitive::Iterable(i) => Some(i.shape()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    let shapes = match shapes {
+        Some(shapes) => shapes,
+        None => return Ok(()),
+    };
+    match shapes.first() {
+        Some(expected) => match shapes.iter().position(|s| s != expected) {
+            Some(i) => Err(format!(
+                "Array is not rectangular, expected every element to have shape {:?} but element {} has shape {:?}",
+                expected, i, shapes[i]
+            )),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,140 @@
+use core::fmt;
+
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::Serialize;
+
+use crate::math::PreVariableType;
+use crate::parser::domain_declaration::{VariableToAssert, VariablesDomainDeclaration};
+use crate::parser::il::{IterableSet, PreConstraint, PreExp};
+use crate::parser::pre_model::PreModel;
+use crate::utils::{InputSpan, Spanned};
+
+/// A problem in the source that doesn't prevent the model from compiling,
+/// but is likely a mistake.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Warning {
+    /// A constant of the `where` section that is never used
+    UnusedConstant(Spanned<String>),
+    /// A constraint whose sides are both constants and that always holds, it is removed from the model
+    AlwaysSatisfiedConstraint(Spanned<String>),
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IWarning: &'static str = r#"
+export type SerializedWarning = {
+    type: "UnusedConstant",
+    value: SerializedSpanned<string>
+} | {
+    type: "AlwaysSatisfiedConstraint",
+    value: SerializedSpanned<string>
+}
+"#;
+
+impl Warning {
+    /// Returns the span of the source the warning refers to.
+    pub fn span(&self) -> &InputSpan {
+        match self {
+            Warning::UnusedConstant(name) => name.span(),
+            Warning::AlwaysSatisfiedConstraint(constraint) => constraint.span(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::UnusedConstant(name) => write!(
+                f,
+                "[UnusedConstant] Constant \"{}\" is never used",
+                name.value()
+            ),
+            Warning::AlwaysSatisfiedConstraint(constraint) => write!(
+                f,
+                "[AlwaysSatisfiedConstraint] Constraint \"{}\" is always satisfied and was removed",
+                constraint.value()
+            ),
+        }
+    }
+}
+
+/// Finds the problems of a model that don't prevent it from compiling, like the constants
+/// that are never used in the objective, the constraints, the domains or other constants.
+///
+/// # Arguments
+/// * `model` - The model to check
+///
+/// # Returns
+/// The warnings, in the order of the source
+pub fn lint_model(model: &PreModel) -> Vec<Warning> {
+    let mut names = model.objective().rhs.referenced_names();
+    for constraint in model.constraints() {
+        collect_constraint_names(constraint, &mut names);
+    }
+    for domain in model.domains() {
+        collect_domain_names(domain, &mut names);
+    }
+    let constants = model.constants();
+    constants
+        .iter()
+        .filter(|constant| {
+            let name = constant.name.value();
+            //a constant that only uses itself is still unused
+            let used_by_constants = constants.iter().any(|other| {
+                other.name.value() != name && other.value.referenced_names().contains(name)
+            });
+            !used_by_constants && !names.contains(name)
+        })
+        .map(|constant| Warning::UnusedConstant(constant.name.clone()))
+        .collect()
+}
+
+fn collect_iteration_names(iteration: &[IterableSet], names: &mut Vec<String>) {
+    for set in iteration {
+        names.extend(set.iterator.referenced_names());
+    }
+}
+
+fn collect_constraint_names(constraint: &PreConstraint, names: &mut Vec<String>) {
+    names.extend(constraint.lhs.referenced_names());
+    names.extend(constraint.rhs.referenced_names());
+    collect_iteration_names(&constraint.iteration, names);
+    if let Some(name) = &constraint.name {
+        for index in &name.value().indexes {
+            names.extend(index.referenced_names());
+        }
+    }
+    if let Some(weight) = constraint.goal.as_ref().and_then(|g| g.weight.as_ref()) {
+        names.extend(weight.referenced_names());
+    }
+    for alternative in &constraint.alternatives {
+        collect_constraint_names(alternative, names);
+    }
+}
+
+fn collect_domain_names(domain: &VariablesDomainDeclaration, names: &mut Vec<String>) {
+    for variable in domain.variables() {
+        if let VariableToAssert::CompoundVariable(c) = variable.value() {
+            for index in &c.indexes {
+                names.extend(index.referenced_names());
+            }
+        }
+    }
+    let bounds: Vec<&PreExp> = match domain.get_type() {
+        PreVariableType::Boolean => vec![],
+        PreVariableType::IntegerRange(min, max) => vec![min, max],
+        PreVariableType::NonNegativeReal(min, max)
+        | PreVariableType::Real(min, max)
+        | PreVariableType::Integer(min, max) => min.iter().chain(max.iter()).collect(),
+    };
+    for bound in bounds {
+        names.extend(bound.referenced_names());
+    }
+    collect_iteration_names(domain.iteration(), names);
+    if let Some(initial_value) = domain.initial_value() {
+        names.extend(initial_value.referenced_names());
+    }
+}
@@ -1,6 +1,8 @@
+pub mod diagnostics;
 pub mod domain_declaration;
 pub mod il;
 pub mod iterable_utils;
+pub mod lint;
 pub mod model_transformer;
 pub mod pre_model;
 pub(crate) mod recursive_set_resolver;
@@ -3,21 +3,27 @@ use crate::domain_declaration::format_domain;
 use crate::prelude::*;
 use core::fmt;
 use indexmap::IndexMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::math::{BinOp, UnOp};
-use crate::math::{Comparison, OptimizationType};
+use crate::math::{Comparison, OptimizationType, VariableType};
 use crate::parser::il::PreExp;
 use crate::parser::il::{PreConstraint, PreObjective};
+use crate::parser::lint::Warning;
 use crate::parser::model_transformer::transform_error::TransformError;
 use crate::parser::model_transformer::transformer_context::{DomainVariable, TransformerContext};
 use crate::parser::pre_model::PreModel;
 use crate::parser::recursive_set_resolver::recursive_set_resolver;
-use crate::primitives::Constant;
+use crate::primitives::{check_constant_definitions, Constant};
 use crate::runtime_builtin::{make_std, make_std_constants, RoocFunction};
 use crate::traits::{escape_latex, ToLatex};
+use crate::transformers::exporters::{model_to_lp_format, model_to_mps_format, ExportError};
 use crate::type_checker::type_checker_context::FunctionContext;
-use crate::{primitives::Primitive, utils::Spanned};
+use crate::utils::format_number;
+use crate::{
+    primitives::Primitive,
+    utils::{InputSpan, Spanned},
+};
 
 /// Represents a mathematical expression in the optimization model.
 ///
@@ -28,7 +34,7 @@ use crate::{primitives::Primitive, utils::Spanned};
 /// - Min/max of multiple expressions
 /// - Binary operations (add, subtract, multiply, divide)
 /// - Unary operations (negation)
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Exp {
     /// A numeric literal value
     Number(f64),
@@ -44,6 +50,8 @@ pub enum Exp {
     BinOp(BinOp, Box<Exp>, Box<Exp>),
     /// Unary operation on an expression
     UnOp(UnOp, Box<Exp>),
+    /// Piecewise linear function of an expression, with its breakpoints and the values at each breakpoint
+    Piecewise(Box<Exp>, Vec<f64>, Vec<f64>),
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -78,6 +86,9 @@ export type SerializedExp = {
         op: UnOp,
         exp: SerializedExp
     }
+} | {
+    type: "Piecewise",
+    value: [SerializedExp, number[], number[]]
 }
 "#;
 
@@ -133,12 +144,26 @@ impl Exp {
                 let lhs = lhs.simplify();
                 let rhs = rhs.simplify();
                 match (op, lhs, rhs) {
-                    (op, Exp::Number(lhs), Exp::Number(rhs)) => match op {
-                        BinOp::Add => Exp::Number(lhs + rhs),
-                        BinOp::Sub => Exp::Number(lhs - rhs),
-                        BinOp::Mul => Exp::Number(lhs * rhs),
-                        BinOp::Div => Exp::Number(lhs / rhs),
+                    //boolean operators can't be folded on numbers, keep them as they are
+                    (op, lhs, rhs) if op.is_boolean() => {
+                        Exp::BinOp(*op, lhs.to_box(), rhs.to_box())
+                    }
+                    //this would be an error, keep it as it is
+                    (BinOp::Mod, lhs, Exp::Number(0.0)) => {
+                        Exp::BinOp(BinOp::Mod, lhs.to_box(), Exp::Number(0.0).to_box())
+                    }
+                    (op, Exp::Number(lhs), Exp::Number(rhs)) => match fold_numbers(*op, lhs, rhs) {
+                        Some(value) => Exp::Number(value),
+                        None => {
+                            Exp::BinOp(*op, Exp::Number(lhs).to_box(), Exp::Number(rhs).to_box())
+                        }
                     },
+                    // num1 * x * num2 = (num1 * num2) * x
+                    (BinOp::Mul, lhs, rhs)
+                        if count_constant_factors(&lhs) + count_constant_factors(&rhs) > 1 =>
+                    {
+                        fold_product(lhs, rhs)
+                    }
                     (BinOp::Add, Exp::Number(0.0), rhs) => rhs,
                     (BinOp::Add, lhs, Exp::Number(0.0)) => lhs,
                     (BinOp::Sub, lhs, Exp::Number(0.0)) => lhs,
@@ -159,27 +184,31 @@ impl Exp {
                     (op, Exp::Number(lhs), Exp::BinOp(op2, inner_lhs, inner_rhs)) => {
                         let inner_lhs = inner_lhs.simplify();
                         let inner_rhs = inner_rhs.simplify();
-                        if *op != op2 {
+                        //modulo is not associative, so it can't be folded
+                        if *op != op2 || *op == BinOp::Mod {
                             return Exp::BinOp(
                                 *op,
                                 Exp::Number(lhs).to_box(),
                                 Exp::BinOp(op2, inner_lhs.to_box(), inner_rhs.to_box()).to_box(),
                             );
                         }
-                        if let Exp::Number(rhs) = inner_lhs {
-                            let val = match op {
-                                BinOp::Add => lhs + rhs,
-                                BinOp::Sub => lhs - rhs,
-                                BinOp::Mul => lhs * rhs,
-                                BinOp::Div => lhs / rhs,
-                            };
-                            Exp::BinOp(op2, Exp::Number(val).to_box(), inner_rhs.to_box())
-                        } else {
-                            Exp::BinOp(
+                        match inner_lhs {
+                            Exp::Number(rhs) => match fold_numbers(*op, lhs, rhs) {
+                                Some(val) => {
+                                    Exp::BinOp(op2, Exp::Number(val).to_box(), inner_rhs.to_box())
+                                }
+                                None => Exp::BinOp(
+                                    *op,
+                                    Exp::Number(lhs).to_box(),
+                                    Exp::BinOp(op2, Exp::Number(rhs).to_box(), inner_rhs.to_box())
+                                        .to_box(),
+                                ),
+                            },
+                            inner_lhs => Exp::BinOp(
                                 *op,
                                 Exp::Number(lhs).to_box(),
                                 Exp::BinOp(op2, inner_lhs.to_box(), inner_rhs.to_box()).to_box(),
-                            )
+                            ),
                         }
                     }
                     //keep the rest equal
@@ -193,6 +222,7 @@ impl Exp {
                         Exp::Number(value) => Exp::Number(-value),
                         _ => Exp::UnOp(UnOp::Neg, exp.to_box()),
                     },
+                    UnOp::Not => Exp::UnOp(UnOp::Not, exp.to_box()),
                 }
             }
             Exp::Max(exps) => {
@@ -233,6 +263,21 @@ impl Exp {
                     None => Exp::Min(exps.iter().map(|exp| exp.simplify()).collect::<Vec<_>>()),
                 }
             }
+            Exp::Piecewise(exp, breakpoints, values) => {
+                let exp = exp.simplify();
+                //if the argument is a number inside the breakpoints, interpolate the value
+                if let Exp::Number(value) = exp {
+                    let segment = breakpoints
+                        .windows(2)
+                        .zip(values.windows(2))
+                        .find(|(b, _)| b[0] <= value && value <= b[1]);
+                    if let Some((b, v)) = segment {
+                        let t = (value - b[0]) / (b[1] - b[0]);
+                        return Exp::Number(v[0] + t * (v[1] - v[0]));
+                    }
+                }
+                Exp::Piecewise(exp.to_box(), breakpoints.clone(), values.clone())
+            }
             exp => exp.clone(),
         }
     }
@@ -276,6 +321,12 @@ impl Exp {
                 (BinOp::Mul, c, Exp::UnOp(op @ UnOp::Neg, rhs)) => {
                     Exp::UnOp(op, Exp::make_binop(BinOp::Mul, c, *rhs).flatten().to_box())
                 }
+                //this would be an error, keep it as it is
+                (BinOp::Div, lhs, Exp::Number(0.0)) => Exp::BinOp(
+                    BinOp::Div,
+                    lhs.flatten().to_box(),
+                    Exp::Number(0.0).to_box(),
+                ),
                 //(a +- b)/c = a/c +- b/c
                 (BinOp::Div, Exp::BinOp(inner_op @ (BinOp::Add | BinOp::Sub), lhs, rhs), c) => {
                     Exp::BinOp(
@@ -293,6 +344,40 @@ impl Exp {
         }
     }
 
+    /// Separates the constant terms of the outermost sums and differences from the rest
+    /// of the expression, like `3x + 5 - y` becoming `3x - y` and `5`.
+    ///
+    /// # Returns
+    /// The expression without the constant terms, and the sum of the constant terms
+    pub fn split_constant(self) -> (Exp, f64) {
+        match self {
+            Exp::Number(n) => (Exp::Number(0.0), n),
+            Exp::BinOp(op @ (BinOp::Add | BinOp::Sub), lhs, rhs) => {
+                let (lhs, lhs_constant) = lhs.split_constant();
+                let (rhs, rhs_constant) = rhs.split_constant();
+                let is_zero = |e: &Exp| matches!(e, Exp::Number(n) if *n == 0.0);
+                let exp = match (is_zero(&lhs), is_zero(&rhs)) {
+                    (_, true) => lhs,
+                    (true, false) if op == BinOp::Add => rhs,
+                    (true, false) => Exp::UnOp(UnOp::Neg, rhs.to_box()),
+                    (false, false) => Exp::BinOp(op, lhs.to_box(), rhs.to_box()),
+                };
+                match op {
+                    BinOp::Add => (exp, lhs_constant + rhs_constant),
+                    _ => (exp, lhs_constant - rhs_constant),
+                }
+            }
+            Exp::UnOp(UnOp::Neg, exp) => {
+                let (exp, constant) = exp.split_constant();
+                match exp {
+                    Exp::Number(0.0) => (exp, -constant),
+                    exp => (Exp::UnOp(UnOp::Neg, exp.to_box()), -constant),
+                }
+            }
+            _ => (self, 0.0),
+        }
+    }
+
     /// Checks if the expression is a leaf node (number or variable).
     ///
     /// # Returns
@@ -320,13 +405,11 @@ impl Exp {
                 } else {
                     //TODO improve this
                     match last_operator {
-                        BinOp::Add | BinOp::Mul | BinOp::Div => {
-                            format!("{} {} {}", string_lhs, op, string_rhs)
-                        }
-                        BinOp::Sub => match rhs.is_leaf() {
+                        BinOp::Sub | BinOp::Mod => match rhs.is_leaf() {
                             true => format!("{} {} {}", string_lhs, op, string_rhs),
                             false => format!("{} {} ({})", string_lhs, op, string_rhs),
                         },
+                        _ => format!("{} {} {}", string_lhs, op, string_rhs),
                     }
                 }
             }
@@ -338,7 +421,7 @@ impl Exp {
 impl fmt::Display for Exp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
-            Exp::Number(value) => value.to_string(),
+            Exp::Number(value) => format_number(*value),
             Exp::Variable(name) => name.clone(),
             Exp::Abs(exp) => format!("|{}|", exp),
             Exp::Min(exps) => format!(
@@ -368,18 +451,36 @@ impl fmt::Display for Exp {
                     format!("{}({})", op, exp)
                 }
             }
+            Exp::Piecewise(exp, breakpoints, values) => {
+                let format_points = |points: &Vec<f64>| {
+                    points
+                        .iter()
+                        .map(|p| format_number(*p))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                format!(
+                    "piecewise({}, [{}], [{}])",
+                    exp,
+                    format_points(breakpoints),
+                    format_points(values)
+                )
+            }
         };
         f.write_str(&s)
     }
 }
 
 /// Represents an optimization objective (minimize/maximize an expression).
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Objective {
     /// Type of optimization (minimize or maximize)
     pub objective_type: OptimizationType,
     /// Expression to optimize
     pub rhs: Exp,
+    /// Constant term of the objective, kept apart from the expression
+    #[serde(default)]
+    pub offset: f64,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -388,7 +489,8 @@ pub struct Objective {
 pub const IObjective: &'static str = r#"
 export type SerializedObjective = {
     objective_type: OptimizationType,
-    rhs: SerializedExp
+    rhs: SerializedExp,
+    offset: number
 }
 "#;
 
@@ -402,22 +504,56 @@ impl Objective {
         Self {
             objective_type,
             rhs,
+            offset: 0.0,
         }
     }
+
+    /// Sets the constant term of the objective.
+    ///
+    /// # Arguments
+    /// * `offset` - The constant added to the expression
+    pub fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
 }
 
 impl fmt::Display for Objective {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.objective_type, self.rhs)
+        let offset = self.offset;
+        match &self.rhs {
+            _ if offset == 0.0 => write!(f, "{} {}", self.objective_type, self.rhs),
+            Exp::Number(n) if *n == 0.0 => {
+                write!(f, "{} {}", self.objective_type, format_number(offset))
+            }
+            _ if offset < 0.0 => write!(
+                f,
+                "{} {} - {}",
+                self.objective_type,
+                self.rhs,
+                format_number(-offset)
+            ),
+            _ => write!(
+                f,
+                "{} {} + {}",
+                self.objective_type,
+                self.rhs,
+                format_number(offset)
+            ),
+        }
     }
 }
 
 /// Represents a constraint in the optimization model (lhs comparison rhs).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Constraint {
     lhs: Exp,
     constraint_type: Comparison,
     rhs: Exp,
+    alternatives: Vec<Constraint>,
+    name: Option<String>,
+    goal_weight: Option<f64>,
+    span: Option<InputSpan>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -427,7 +563,11 @@ pub const ICondition: &'static str = r#"
 export type SerializedCondition = {
     lhs: SerializedExp,
     constraint_type: Comparison,
-    rhs: SerializedExp
+    rhs: SerializedExp,
+    alternatives: SerializedCondition[],
+    name?: string,
+    goal_weight?: number,
+    span?: InputSpan
 }
 "#;
 
@@ -443,10 +583,91 @@ impl Constraint {
             lhs,
             constraint_type,
             rhs,
+            alternatives: vec![],
+            name: None,
+            goal_weight: None,
+            span: None,
         }
     }
 
-    /// Decomposes the constraint into its components.
+    /// Names the constraint.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the constraint
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Gets the name of the constraint, if it was given one.
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    /// Sets the constraints that can hold in place of this one, turning it into a disjunction.
+    ///
+    /// # Arguments
+    /// * `alternatives` - The other sides of the disjunction
+    pub fn with_alternatives(mut self, alternatives: Vec<Constraint>) -> Self {
+        self.alternatives = alternatives;
+        self
+    }
+
+    /// Gets the constraints that can hold in place of this one.
+    pub fn alternatives(&self) -> &Vec<Constraint> {
+        &self.alternatives
+    }
+
+    /// Turns the constraint into a goal, whose deviation is minimized instead of being enforced.
+    ///
+    /// # Arguments
+    /// * `weight` - Weight of the deviation in the objective
+    pub fn with_goal_weight(mut self, weight: f64) -> Self {
+        self.goal_weight = Some(weight);
+        self
+    }
+
+    /// Gets the weight of the deviation if the constraint is a goal.
+    pub fn goal_weight(&self) -> Option<f64> {
+        self.goal_weight
+    }
+
+    /// Sets the location in the source of the constraint that generated this one.
+    ///
+    /// # Arguments
+    /// * `span` - The span of the source constraint
+    pub fn with_span(mut self, span: InputSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Gets the location in the source of the constraint that generated this one, the
+    /// constraints added by the transformers have none.
+    pub fn span(&self) -> Option<&InputSpan> {
+        self.span.as_ref()
+    }
+
+    /// Gets the text of the source constraint that generated this one, like the whole
+    /// `x_i <= 2 for i in 0..3` for each of the constraints it expands to.
+    ///
+    /// # Arguments
+    /// * `source` - The source code the model was compiled from
+    pub fn source_text<'a>(&self, source: &'a str) -> Option<&'a str> {
+        self.span.as_ref()?.span_text(source).ok()
+    }
+
+    /// Returns true if the constraint is a disjunction of constraints.
+    pub fn is_disjunction(&self) -> bool {
+        !self.alternatives.is_empty()
+    }
+
+    /// Splits a disjunction into each of its constraints, the first one being this constraint.
+    pub fn into_disjuncts(mut self) -> Vec<Constraint> {
+        let alternatives = std::mem::take(&mut self.alternatives);
+        std::iter::once(self).chain(alternatives).collect()
+    }
+
+    /// Decomposes the constraint into its components, ignoring the alternatives.
     ///
     /// # Returns
     /// A tuple of (lhs, comparison, rhs)
@@ -457,7 +678,24 @@ impl Constraint {
 
 impl fmt::Display for Constraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.lhs, self.constraint_type, self.rhs)
+        if let Some(name) = &self.name {
+            write!(f, "{}: ", name)?;
+        }
+        match self.goal_weight {
+            Some(1.0) => write!(f, "{} ~ {}", self.lhs, self.rhs)?,
+            Some(weight) => write!(
+                f,
+                "@weight({}) {} ~ {}",
+                format_number(weight),
+                self.lhs,
+                self.rhs
+            )?,
+            None => write!(f, "{} {} {}", self.lhs, self.constraint_type, self.rhs)?,
+        }
+        for alternative in &self.alternatives {
+            write!(f, " or {}", alternative)?;
+        }
+        Ok(())
     }
 }
 
@@ -467,12 +705,15 @@ impl fmt::Display for Constraint {
 /// - An objective function to optimize
 /// - A set of constraints that must be satisfied
 /// - Domain information for variables
-#[derive(Debug, Serialize, Clone)]
+/// - The warnings found while transforming the source
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct Model {
     objective: Objective,
     constraints: Vec<Constraint>,
     domain: IndexMap<String, DomainVariable>,
+    #[serde(skip_deserializing, default)]
+    warnings: Vec<Warning>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -483,6 +724,7 @@ export type SerializedModel = {
     objective: SerializedObjective,
     constraints: SerializedCondition[]
     domain: Record<string, DomainVariable>
+    warnings: SerializedWarning[]
 }
 "#;
 
@@ -502,9 +744,25 @@ impl Model {
             objective,
             constraints,
             domain,
+            warnings: Vec::new(),
         }
     }
 
+    /// Sets the warnings found while transforming the source.
+    ///
+    /// # Arguments
+    /// * `warnings` - The warnings, in the order of the source
+    pub fn with_warnings(mut self, warnings: Vec<Warning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Gets the warnings found while transforming the source, like the constraints
+    /// that were removed because they always hold.
+    pub fn warnings(&self) -> &Vec<Warning> {
+        &self.warnings
+    }
+
     /// Decomposes the model into its components.
     ///
     /// # Returns
@@ -523,15 +781,86 @@ impl Model {
         &self.constraints
     }
 
+    /// Finds a constraint by the name it was given in the source.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the constraint, with its indexes already evaluated, like `capacity_1`
+    pub fn constraint_by_name(&self, name: &str) -> Option<&Constraint> {
+        self.constraints
+            .iter()
+            .find(|c| c.name().is_some_and(|n| n == name))
+    }
+
+    /// Checks that the objective, the constraints and the goals can be linearized, failing on the first
+    /// product involving a non boolean variable, division by a variable or modulo of a variable,
+    /// with the span of its constraint.
+    pub fn check_linearity(&self) -> Result<(), TransformError> {
+        if let Some(term) = find_nonlinear_term(&self.objective.rhs, &self.domain) {
+            return Err(TransformError::NonLinearTerm(term.to_string()));
+        }
+        for constraint in &self.constraints {
+            let term = std::iter::once(constraint)
+                .chain(constraint.alternatives())
+                .flat_map(|c| [&c.lhs, &c.rhs])
+                .find_map(|exp| find_nonlinear_term(exp, &self.domain));
+            if let Some(term) = term {
+                let error = TransformError::NonLinearTerm(term.to_string());
+                return Err(match constraint.span() {
+                    Some(span) => error.add_span(span),
+                    None => error,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Gets a reference to the variable domains.
     pub fn domain(&self) -> &IndexMap<String, DomainVariable> {
         &self.domain
     }
 
+    /// Iterates over the variables of the model, after the compound variables were flattened,
+    /// together with their domain.
+    pub fn variables(&self) -> impl Iterator<Item = (&String, &DomainVariable)> {
+        self.domain.iter()
+    }
+
     /// Gets a mutable reference to the variable domains.
     pub fn domain_mut(&mut self) -> &mut IndexMap<String, DomainVariable> {
         &mut self.domain
     }
+
+    /// Exports the model in the CPLEX LP text format.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The model in LP format
+    /// * `Err(ExportError)` - If the model is not linear
+    pub fn to_lp_format(&self) -> Result<String, ExportError> {
+        model_to_lp_format(self)
+    }
+
+    /// Exports the model in the fixed-column MPS format.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The model in MPS format
+    /// * `Err(ExportError)` - If the model is not linear
+    pub fn to_mps_format(&self) -> Result<String, ExportError> {
+        model_to_mps_format(self)
+    }
+
+    /// Serializes the model to JSON, so that it can be cached and restored with
+    /// [`Model::from_json`] without parsing and transforming the source again.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Restores a model that was serialized with [`Model::to_json`].
+    ///
+    /// # Arguments
+    /// * `json` - The serialized model
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
 }
 
 impl fmt::Display for Model {
@@ -643,7 +972,43 @@ pub fn transform_constraint(
 ) -> Result<Constraint, TransformError> {
     let lhs = constraint.lhs.into_exp(context, fn_context)?;
     let rhs = constraint.rhs.into_exp(context, fn_context)?;
-    Ok(Constraint::new(lhs, constraint.constraint_type, rhs))
+    let alternatives = constraint
+        .alternatives
+        .iter()
+        .map(|c| transform_constraint(c, context, fn_context))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut transformed = Constraint::new(lhs, constraint.constraint_type, rhs)
+        .with_alternatives(alternatives)
+        .with_span(constraint.span.clone());
+    if let Some(goal) = &constraint.goal {
+        let weight = match &goal.weight {
+            Some(weight) => weight
+                .as_number_cast(context, fn_context)
+                .map_err(|e| e.add_span(weight.span()))?,
+            None => 1.0,
+        };
+        if weight < 0.0 {
+            return Err(TransformError::Other(format!(
+                "the weight of a goal must be non negative, got {}",
+                weight
+            ))
+            .add_span(&constraint.span));
+        }
+        transformed = transformed.with_goal_weight(weight);
+    }
+    match &constraint.name {
+        Some(name) if name.indexes.is_empty() => Ok(transformed.with_name(name.name.clone())),
+        Some(name) => {
+            let indexes = name
+                .compute_indexes(context, fn_context)
+                .map_err(|e| e.add_span(name.span()))?;
+            let name = context
+                .flatten_compound_variable(&name.name, &indexes)
+                .map_err(|e| e.add_span(name.span()))?;
+            Ok(transformed.with_name(name))
+        }
+        None => Ok(transformed),
+    }
 }
 
 /// Transforms a pre-constraint with iteration into multiple constraints.
@@ -690,8 +1055,11 @@ pub fn transform_objective(
     context: &mut TransformerContext,
     fn_context: &FunctionContext,
 ) -> Result<Objective, TransformError> {
-    let rhs = objective.rhs.into_exp(context, fn_context)?;
-    Ok(Objective::new(objective.objective_type.clone(), rhs))
+    let (rhs, offset) = objective
+        .rhs
+        .into_exp(context, fn_context)?
+        .split_constant();
+    Ok(Objective::new(objective.objective_type.clone(), rhs).with_offset(offset))
 }
 
 /// Transforms a pre-model into a complete optimization model.
@@ -710,15 +1078,174 @@ pub fn transform_model(
 ) -> Result<Model, TransformError> {
     let objective = transform_objective(problem.objective(), &mut context, fn_context)?;
     let mut constraints: Vec<Constraint> = Vec::new();
+    let mut names: IndexMap<String, InputSpan> = IndexMap::new();
+    let mut warnings: Vec<Warning> = Vec::new();
     for constraint in problem.constraints().iter() {
         let transformed =
             transform_constraint_with_iteration(constraint, &mut context, fn_context)?;
         for transformed_constraint in transformed {
-            constraints.push(transformed_constraint);
+            if let (Some(name), Some(pre_name)) = (transformed_constraint.name(), &constraint.name)
+            {
+                if let Some(first) = names.get(name) {
+                    return Err(TransformError::DuplicateConstraintName {
+                        name: name.clone(),
+                        first: first.clone(),
+                        second: pre_name.span().clone(),
+                    }
+                    .add_span(pre_name.span()));
+                }
+                names.insert(name.clone(), pre_name.span().clone());
+            }
+            match constant_constraint_value(&transformed_constraint) {
+                Some(true) => warnings.push(Warning::AlwaysSatisfiedConstraint(Spanned::new(
+                    transformed_constraint.to_string(),
+                    constraint.span.clone(),
+                ))),
+                Some(false) => {
+                    return Err(TransformError::Other(format!(
+                        "constraint \"{}\" is always violated",
+                        transformed_constraint
+                    ))
+                    .add_span(&constraint.span))
+                }
+                None => constraints.push(transformed_constraint),
+            }
         }
     }
     let domain = context.into_components();
-    Ok(Model::new(objective, constraints, domain))
+    Ok(Model::new(objective, constraints, domain).with_warnings(warnings))
+}
+
+/// Folds both sides of a constraint without variables, returning whether it holds
+fn constant_constraint_value(constraint: &Constraint) -> Option<bool> {
+    if constraint.is_disjunction()
+        || constraint.goal_weight().is_some()
+        || constraint.lhs.has_variables()
+        || constraint.rhs.has_variables()
+    {
+        return None;
+    }
+    match (constraint.lhs.simplify(), constraint.rhs.simplify()) {
+        (Exp::Number(lhs), Exp::Number(rhs)) => Some(constraint.constraint_type.evaluate(lhs, rhs)),
+        _ => None,
+    }
+}
+
+/// Applies an arithmetic operator between two numbers, the boolean operators can't be folded
+fn fold_numbers(op: BinOp, lhs: f64, rhs: f64) -> Option<f64> {
+    match op {
+        BinOp::Add => Some(lhs + rhs),
+        BinOp::Sub => Some(lhs - rhs),
+        BinOp::Mul => Some(lhs * rhs),
+        BinOp::Div => Some(lhs / rhs),
+        BinOp::Mod => Some(lhs.rem_euclid(rhs)),
+        BinOp::And
+        | BinOp::Or
+        | BinOp::Equal
+        | BinOp::NotEqual
+        | BinOp::Less
+        | BinOp::LessOrEqual
+        | BinOp::Greater
+        | BinOp::GreaterOrEqual => None,
+    }
+}
+
+/// Counts the numbers among the factors of a chain of multiplications
+fn count_constant_factors(exp: &Exp) -> usize {
+    match exp {
+        Exp::BinOp(BinOp::Mul, lhs, rhs) => {
+            count_constant_factors(lhs) + count_constant_factors(rhs)
+        }
+        Exp::Number(_) => 1,
+        _ => 0,
+    }
+}
+
+fn collect_factors(exp: Exp, factors: &mut Vec<Exp>) {
+    match exp {
+        Exp::BinOp(BinOp::Mul, lhs, rhs) => {
+            collect_factors(*lhs, factors);
+            collect_factors(*rhs, factors);
+        }
+        exp => factors.push(exp),
+    }
+}
+
+/// Multiplies together the numbers of the product, keeping the other factors in the same order
+fn fold_product(lhs: Exp, rhs: Exp) -> Exp {
+    let mut factors = Vec::new();
+    collect_factors(lhs, &mut factors);
+    collect_factors(rhs, &mut factors);
+    let mut constant = 1.0;
+    let mut rest: Option<Exp> = None;
+    for factor in factors {
+        match factor {
+            Exp::Number(n) => constant *= n,
+            factor => {
+                rest = Some(match rest {
+                    Some(rest) => Exp::BinOp(BinOp::Mul, rest.to_box(), factor.to_box()),
+                    None => factor,
+                })
+            }
+        }
+    }
+    match rest {
+        None => Exp::Number(constant),
+        Some(_) if constant == 0.0 => Exp::Number(0.0),
+        Some(rest) if constant == 1.0 => rest,
+        Some(rest) => Exp::BinOp(BinOp::Mul, Exp::Number(constant).to_box(), rest.to_box()),
+    }
+}
+
+/// Finds a product between variables that are not all boolean, a division by a term with
+/// variables or a modulo of variables, which can't be linearized
+fn find_nonlinear_term<'a>(
+    exp: &'a Exp,
+    domain: &IndexMap<String, DomainVariable>,
+) -> Option<&'a Exp> {
+    match exp {
+        Exp::BinOp(op, lhs, rhs) => {
+            let nonlinear = match op {
+                BinOp::Mul => {
+                    lhs.has_variables()
+                        && rhs.has_variables()
+                        && !(has_only_boolean_variables(lhs, domain)
+                            && has_only_boolean_variables(rhs, domain))
+                }
+                BinOp::Div => rhs.has_variables(),
+                BinOp::Mod => lhs.has_variables() || rhs.has_variables(),
+                _ => false,
+            };
+            if nonlinear {
+                Some(exp)
+            } else {
+                find_nonlinear_term(lhs, domain).or_else(|| find_nonlinear_term(rhs, domain))
+            }
+        }
+        Exp::UnOp(_, exp) | Exp::Abs(exp) | Exp::Piecewise(exp, _, _) => {
+            find_nonlinear_term(exp, domain)
+        }
+        Exp::Min(exps) | Exp::Max(exps) => exps.iter().find_map(|e| find_nonlinear_term(e, domain)),
+        Exp::Number(_) | Exp::Variable(_) => None,
+    }
+}
+
+fn has_only_boolean_variables(exp: &Exp, domain: &IndexMap<String, DomainVariable>) -> bool {
+    match exp {
+        Exp::Number(_) => true,
+        Exp::Variable(name) => matches!(
+            domain.get(name).map(|v| v.get_type()),
+            Some(VariableType::Boolean)
+        ),
+        Exp::BinOp(_, lhs, rhs) => {
+            has_only_boolean_variables(lhs, domain) && has_only_boolean_variables(rhs, domain)
+        }
+        Exp::UnOp(_, exp) | Exp::Abs(exp) => has_only_boolean_variables(exp, domain),
+        Exp::Min(exps) | Exp::Max(exps) => {
+            exps.iter().all(|e| has_only_boolean_variables(e, domain))
+        }
+        Exp::Piecewise(_, _, _) => false,
+    }
 }
 
 /// Transforms a parsed problem into a complete optimization model.
@@ -735,11 +1262,21 @@ pub fn transform_parsed_problem(
     constants: Vec<Constant>,
     fns: &IndexMap<String, Box<dyn RoocFunction>>,
 ) -> Result<Model, TransformError> {
-    let std = make_std();
-    let fn_context = FunctionContext::new(fns, &std);
+    transform_parsed_problem_with_builtins(pre_problem, constants, fns, &make_std())
+}
+
+/// Transforms the problem using the given map as the builtin functions, in place of the standard library
+pub(crate) fn transform_parsed_problem_with_builtins(
+    pre_problem: PreModel,
+    constants: Vec<Constant>,
+    fns: &IndexMap<String, Box<dyn RoocFunction>>,
+    builtins: &IndexMap<String, Box<dyn RoocFunction>>,
+) -> Result<Model, TransformError> {
+    let fn_context = FunctionContext::new(fns, builtins);
     let mut c = make_std_constants();
     c.extend(constants);
     c.extend(pre_problem.constants().clone());
+    check_constant_definitions(&c)?;
     let context =
         TransformerContext::new_from_constants(c, pre_problem.domains().clone(), &fn_context)?;
     transform_model(pre_problem, context, &fn_context)
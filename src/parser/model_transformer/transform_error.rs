@@ -125,6 +125,24 @@ pub enum TransformError {
         max: i64,
     },
 
+    /// Error when two constraints are given the same name
+    DuplicateConstraintName {
+        /// The shared name
+        name: String,
+        /// Location of the first constraint with this name
+        first: InputSpan,
+        /// Location of the second constraint with this name
+        second: InputSpan,
+    },
+
+    /// Error when a domain variable is used where a constant value is needed,
+    /// like an array index or the condition of an if
+    DomainVariableInConstantContext(String),
+
+    /// Error when a term multiplies or divides variables in a way that cannot be linearized,
+    /// contains the offending term
+    NonLinearTerm(String),
+
     /// Generic error with custom message
     Other(String),
 }
@@ -218,6 +236,19 @@ export type SerializedTransformError = {
 } | {
     type: "NonExistentFunction",
     value: string
+} | {
+    type: "DomainVariableInConstantContext",
+    value: string
+} | {
+    type: "NonLinearTerm",
+    value: string
+} | {
+    type: "DuplicateConstraintName",
+    value: {
+        name: string,
+        first: InputSpan,
+        second: InputSpan
+    }
 }
 "#;
 
@@ -318,6 +349,22 @@ impl fmt::Display for TransformError {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            TransformError::DuplicateConstraintName {
+                name,
+                first,
+                second,
+            } => format!(
+                "[DuplicateConstraintName] constraint name \"{}\" is used at {}:{} and at {}:{}",
+                name, first.start_line, first.start_column, second.start_line, second.start_column
+            ),
+            TransformError::DomainVariableInConstantContext(name) => format!(
+                "[DomainVariableInConstantContext] Variable \"{}\" is a domain variable and cannot be used inside expression valuation",
+                name
+            ),
+            TransformError::NonLinearTerm(term) => format!(
+                "[NonLinearTerm] The term \"{}\" is not linear, only boolean variables can be multiplied together and variables cannot be divisors or operands of a modulo",
+                term
+            ),
             TransformError::Other(name) => format!("[Other] {}", name),
             TransformError::Unspreadable(kind) => {
                 format!("[Unspreadable] type \"{}\" is not spreadable", kind)
@@ -1,10 +1,12 @@
+use std::borrow::Cow;
+
 #[allow(unused_imports)]
 use crate::prelude::*;
 use indexmap::IndexMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::math::VariableType;
-use crate::parser::domain_declaration::VariablesDomainDeclaration;
+use crate::parser::domain_declaration::{VariableToAssert, VariablesDomainDeclaration};
 use crate::parser::il::AddressableAccess;
 use crate::parser::model_transformer::transform_error::TransformError;
 use crate::primitives::Constant;
@@ -15,7 +17,7 @@ use crate::utils::{InputSpan, Spanned};
 
 /// Represents a single scope frame containing variable bindings.
 /// Used to implement variable scoping and shadowing.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Frame<T> {
     pub variables: IndexMap<String, T>,
 }
@@ -116,11 +118,12 @@ impl<T> Default for Frame<T> {
 }
 
 /// Represents a variable in the domain of a model, tracking its type and usage.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomainVariable {
     as_type: VariableType,
     span: InputSpan,
     usage_count: usize,
+    initial_value: Option<f64>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -131,6 +134,7 @@ export interface DomainVariable {
     as_type: VariableType;
     span: InputSpan;
     usage_count: number;
+    initial_value?: number;
 }
 "#;
 
@@ -145,9 +149,21 @@ impl DomainVariable {
             as_type,
             span,
             usage_count: 0,
+            initial_value: None,
         }
     }
 
+    /// Sets the initial value of this variable, used as a warm start by the solvers.
+    pub fn with_initial_value(mut self, value: f64) -> Self {
+        self.initial_value = Some(value);
+        self
+    }
+
+    /// Returns the initial value of this variable, if it was given one.
+    pub fn initial_value(&self) -> Option<f64> {
+        self.initial_value
+    }
+
     /// Increments the usage count of this variable.
     pub fn increment_usage(&mut self) {
         self.usage_count += 1;
@@ -172,10 +188,15 @@ impl DomainVariable {
     pub fn get_type(&self) -> &VariableType {
         &self.as_type
     }
+
+    /// Replaces the type of this variable, like when its bounds are tightened.
+    pub fn set_type(&mut self, as_type: VariableType) {
+        self.as_type = as_type;
+    }
 }
 
 /// Maintains the context for transforming a model, including variable scopes and domains.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TransformerContext {
     frames: Vec<Frame<Primitive>>,
     domain: IndexMap<String, DomainVariable>,
@@ -228,21 +249,21 @@ impl TransformerContext {
             let name = constant.name.value();
             context.declare_variable(name, value, true)?; //TODO should this be strict or allow for redeclaration?
         }
+        let model_variables = domain
+            .iter()
+            .flat_map(|d| d.variables().iter().map(|v| v.value().clone()))
+            .collect::<Vec<_>>();
         let computed_domain = domain
             .into_iter()
-            .map(|d| d.compute_domain(&mut context, fn_context))
+            .map(|d| {
+                d.compute_domain(&mut context, fn_context)
+                    .map_err(|e| with_constant_bounds_error(e, &model_variables))
+            })
             .collect::<Result<Vec<_>, _>>()?
             .into_iter()
             .flatten()
             .collect::<Vec<_>>();
         assert_no_duplicates_in_domain(&computed_domain)?;
-        let computed_domain = computed_domain
-            .into_iter()
-            .map(|(name, as_type)| {
-                let (as_type, span) = as_type.into_tuple();
-                (name, DomainVariable::new(as_type, span))
-            })
-            .collect::<Vec<_>>();
         context.domain = IndexMap::from_iter(computed_domain);
         Ok(context)
     }
@@ -265,6 +286,7 @@ impl TransformerContext {
                 Primitive::Number(value) => Ok(value.to_string()),
                 Primitive::Integer(value) => Ok(value.to_string()),
                 Primitive::PositiveInteger(value) => Ok(value.to_string()),
+                Primitive::Rational(n, d) => Ok((*n as f64 / *d as f64).to_string()),
                 Primitive::Boolean(value) => Ok(if *value { "T" } else { "F" }.to_string()),
                 Primitive::String(value) => Ok(value.clone()),
                 Primitive::GraphNode(v) => Ok(v.name().clone()),
@@ -274,6 +296,7 @@ impl TransformerContext {
                         PrimitiveKind::Number,
                         PrimitiveKind::Integer,
                         PrimitiveKind::PositiveInteger,
+                        PrimitiveKind::Rational,
                         PrimitiveKind::String,
                         PrimitiveKind::GraphNode,
                     ],
@@ -510,10 +533,48 @@ impl TransformerContext {
                 let accesses = addressable_access
                     .accesses
                     .iter()
-                    .map(|access| access.as_usize_cast(self, fn_context))
+                    .map(|access| {
+                        access
+                            .as_primitive(self, fn_context)
+                            .map(|key| (key, access.span()))
+                    })
                     .collect::<Result<Vec<_>, TransformError>>()?;
-                let value = a.as_iterator()?.read(accesses)?;
-                Ok(value)
+                let mut current = Cow::Borrowed(a);
+                let mut remaining = &accesses[..];
+                while !remaining.is_empty() {
+                    let next = match current.as_ref() {
+                        Primitive::Map(map) => {
+                            let (key, span) = &remaining[0];
+                            remaining = &remaining[1..];
+                            let key = key.as_string().map_err(|e| e.add_span(span))?;
+                            map.get(key).cloned().ok_or_else(|| {
+                                TransformError::OutOfBounds(format!(
+                                    "cannot access key \"{}\" of {}",
+                                    key, map
+                                ))
+                                .add_span(span)
+                            })?
+                        }
+                        //consecutive indexes are read at once from nested iterables
+                        value => {
+                            let count = remaining
+                                .iter()
+                                .take_while(|(key, _)| !matches!(key, Primitive::String(_)))
+                                .count()
+                                .max(1);
+                            let indexes = remaining[..count]
+                                .iter()
+                                .map(|(key, span)| {
+                                    key.as_integer_cast().map_err(|e| e.add_span(span))
+                                })
+                                .collect::<Result<Vec<_>, TransformError>>()?;
+                            remaining = &remaining[count..];
+                            value.as_iterator()?.read_signed(indexes)?
+                        }
+                    };
+                    current = Cow::Owned(next);
+                }
+                Ok(current.into_owned())
             }
             None => Err(TransformError::UndeclaredVariable(
                 addressable_access.name.to_string(),
@@ -536,20 +597,21 @@ impl TransformerContext {
 /// * `Ok(())` if no duplicates found
 /// * `Err(TransformError)` if duplicates exist
 pub(crate) fn assert_no_duplicates_in_domain(
-    domain: &[(String, Spanned<VariableType>)],
+    domain: &[(String, DomainVariable)],
 ) -> Result<(), TransformError> {
     let acc: IndexMap<String, (i32, Spanned<VariableType>)> = IndexMap::new();
     let duplicates = domain
         .iter()
-        .fold(acc, |mut acc, (name, as_type)| {
+        .fold(acc, |mut acc, (name, variable)| {
             if let Some((count, saved_type)) = acc.get_mut(name) {
                 //ignore the type if it's the same
-                if saved_type.value() == as_type.value() {
+                if saved_type.value() == variable.get_type() {
                     return acc;
                 }
                 *count += 1;
             } else {
-                acc.insert(name.clone(), (1, as_type.clone()));
+                let as_type = Spanned::new(*variable.get_type(), variable.span().clone());
+                acc.insert(name.clone(), (1, as_type));
             }
             acc
         })
@@ -563,3 +625,34 @@ pub(crate) fn assert_no_duplicates_in_domain(
         Ok(())
     }
 }
+
+/// Replaces the error of an undeclared variable, found while computing the domain, with a
+/// clearer one if the variable is one of the variables of the model, as the bounds of a
+/// domain must be computed from constants only.
+///
+/// # Arguments
+/// * `error` - The error raised while computing the domain
+/// * `model_variables` - The variables declared in the domains of the model
+fn with_constant_bounds_error(
+    error: TransformError,
+    model_variables: &[VariableToAssert],
+) -> TransformError {
+    let name = match error.base_error() {
+        TransformError::UndeclaredVariable(name) => name.clone(),
+        _ => return error,
+    };
+    let is_model_variable = model_variables.iter().any(|v| match v {
+        VariableToAssert::Variable(v) => *v == name,
+        VariableToAssert::CompoundVariable(c) => name.starts_with(&format!("{}_", c.name)),
+    });
+    if !is_model_variable {
+        return error;
+    }
+    error.trace().iter().fold(
+        TransformError::Other(format!(
+            "The bounds of a domain must be constant, but they use the model variable \"{}\"",
+            name
+        )),
+        |error, (span, _)| error.add_span(span),
+    )
+}
@@ -11,8 +11,8 @@ use crate::math::PreVariableType;
 use crate::parser::il::{PreConstraint, PreObjective};
 use crate::parser::model_transformer::assert_no_duplicates_in_domain;
 use crate::parser::model_transformer::TransformError;
-use crate::parser::model_transformer::{transform_parsed_problem, Model};
-use crate::primitives::Constant;
+use crate::parser::model_transformer::{transform_parsed_problem, DomainVariable, Model};
+use crate::primitives::{check_constant_definitions, Constant};
 #[cfg(target_arch = "wasm32")]
 use crate::runtime_builtin::JsFunction;
 use crate::runtime_builtin::{make_std, make_std_constants, RoocFunction};
@@ -24,7 +24,8 @@ use crate::utils::{CompilationError, InputSpan, ParseError, Spanned};
 #[allow(unused)]
 use crate::{bail_missing_token, Primitive};
 
-use super::domain_declaration::VariablesDomainDeclaration;
+use super::domain_declaration::{VariableToAssert, VariablesDomainDeclaration};
+use super::lint::{lint_model, Warning};
 use super::rules_parser::{
     parse_constraint_list, parse_consts_declaration, parse_domains_declaration, parse_objective,
 };
@@ -110,6 +111,19 @@ impl PreModel {
     pub fn domains(&self) -> &Vec<VariablesDomainDeclaration> {
         &self.domains
     }
+    /// Returns the variables as they are declared in the `define` section, together with their
+    /// type, without evaluating the iterations of the compound variables, like `x_i` in
+    /// `x_i as Boolean for i in 0..3`.
+    pub fn declared_variables(&self) -> Vec<(&VariableToAssert, &PreVariableType)> {
+        self.domains
+            .iter()
+            .flat_map(|d| d.variables().iter().map(|v| (v.value(), d.get_type())))
+            .collect()
+    }
+    /// Finds the problems of the model that don't prevent it from compiling, like unused constants.
+    pub fn lint(&self) -> Vec<Warning> {
+        lint_model(self)
+    }
     pub fn transform(
         self,
         constants: Vec<Constant>,
@@ -133,13 +147,21 @@ impl PreModel {
     }
     pub fn create_type_checker(
         &self,
-        constants: &Vec<Constant>,
+        constants: &[Constant],
         fns: &IndexMap<String, Box<dyn RoocFunction>>,
+    ) -> Result<(), TransformError> {
+        self.create_type_checker_with_builtins(constants, fns, &make_std())
+    }
+    /// Type checks the model using the given map as the builtin functions, in place of the standard library
+    pub(crate) fn create_type_checker_with_builtins(
+        &self,
+        constants: &[Constant],
+        fns: &IndexMap<String, Box<dyn RoocFunction>>,
+        builtins: &IndexMap<String, Box<dyn RoocFunction>>,
     ) -> Result<(), TransformError> {
         let mut context = TypeCheckerContext::default();
         let domain = self.static_variables_domain();
-        let std = make_std();
-        let fn_context = FunctionContext::new(fns, &std);
+        let fn_context = FunctionContext::new(fns, builtins);
         //TODO add span
         assert_no_duplicates_in_domain(
             &domain
@@ -147,29 +169,32 @@ impl PreModel {
                 .map(|(k, v)| {
                     (
                         k.clone(),
-                        Spanned::new(v.to_variable_type_without_context(), v.span().clone()),
+                        DomainVariable::new(v.to_variable_type_without_context(), v.span().clone()),
                     )
                 })
                 .collect::<Vec<_>>(),
         )?;
         context.set_static_domain(domain);
-        for constant in make_std_constants() {
-            constant.type_check(&mut context, &fn_context)?
-        }
+        let constants = self.all_constants(constants);
+        check_constant_definitions(&constants)?;
         for constant in constants {
             constant.type_check(&mut context, &fn_context)?
         }
-        for constant in &self.constants {
-            constant.type_check(&mut context, &fn_context)?;
-        }
         for domain in &self.domains {
             domain.type_check(&mut context, &fn_context)?;
         }
         self.type_check(&mut context, &fn_context)
     }
+    /// Returns the std constants, the given ones and the ones of the model, in evaluation order
+    fn all_constants(&self, constants: &[Constant]) -> Vec<Constant> {
+        let mut all = make_std_constants();
+        all.extend(constants.iter().cloned());
+        all.extend(self.constants.iter().cloned());
+        all
+    }
     pub fn create_token_type_map(
         &self,
-        constants: &Vec<Constant>,
+        constants: &[Constant],
         fns: &IndexMap<String, Box<dyn RoocFunction>>,
     ) -> IndexMap<u32, TypedToken> {
         let mut context = TypeCheckerContext::default();
@@ -177,13 +202,7 @@ impl PreModel {
         let std = make_std();
         let fn_context = FunctionContext::new(fns, &std);
         context.set_static_domain(domain);
-        for constant in make_std_constants() {
-            constant.populate_token_type_map(&mut context, &fn_context);
-        }
-        for constant in constants {
-            constant.populate_token_type_map(&mut context, &fn_context);
-        }
-        for constant in &self.constants {
+        for constant in self.all_constants(constants) {
             constant.populate_token_type_map(&mut context, &fn_context);
         }
         for domain in &self.domains {
@@ -372,33 +391,35 @@ impl TransformErrorWrapper {
     }
 }
 
+/// Indents every line of an entry of a section, so that the entries spanning multiple lines,
+/// like graphs or blocks, stay inside of their section
+fn indent_entry(entry: &impl fmt::Display) -> String {
+    let lines = entry
+        .to_string()
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect::<Vec<_>>();
+    format!("    {}\n", lines.join("\n    "))
+}
+
+/// Formats the model in its canonical layout, with one header for each section
+/// and every entry of a section on its own indented line
 impl fmt::Display for PreModel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut s = self.objective.to_string();
-        s.push_str("\ns.t.\n");
-        for cond in &self.constraints {
-            s.push_str(&format!("    {}\n", cond));
+        let mut s = format!("{}\ns.t.\n", self.objective);
+        for constraint in &self.constraints {
+            s.push_str(&indent_entry(constraint));
         }
         if !self.constants.is_empty() {
             s.push_str("where\n");
             for constant in &self.constants {
-                let constant = constant
-                    .to_string()
-                    .split("\n")
-                    .collect::<Vec<_>>()
-                    .join("\n    ");
-                s.push_str(&format!("    {}\n", constant));
+                s.push_str(&indent_entry(constant));
             }
         }
         if !self.domains.is_empty() {
             s.push_str("define\n");
             for domain in &self.domains {
-                let domain = domain
-                    .to_string()
-                    .split("\n")
-                    .collect::<Vec<_>>()
-                    .join("\n    ");
-                s.push_str(&format!("    {}\n", domain));
+                s.push_str(&indent_entry(domain));
             }
         }
         f.write_str(&s)
@@ -2,6 +2,7 @@ use pest::iterators::Pair;
 use pest::pratt_parser::PrattParser;
 
 use crate::parser::il::PreExp;
+use crate::primitives::Primitive;
 use crate::{
     err_unexpected_token,
     math::{BinOp, UnOp},
@@ -10,19 +11,28 @@ use crate::{
 };
 
 use super::other_parser::{
-    parse_array_access, parse_block_function, parse_block_scoped_function, parse_compound_variable,
-    parse_function_call, parse_primitive,
+    parse_array_access, parse_array_comprehension, parse_block_function,
+    parse_block_scoped_function, parse_compound_variable, parse_dot, parse_function_call,
+    parse_if_else, parse_piecewise, parse_primitive,
 };
 
 lazy_static::lazy_static! {
     static ref PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::*, Op};
         PrattParser::new()
+            .op(Op::infix(Rule::or, Left))
+            .op(Op::infix(Rule::and, Left))
+            .op(Op::infix(Rule::equal, Left)
+                | Op::infix(Rule::not_equal, Left)
+                | Op::infix(Rule::less, Left)
+                | Op::infix(Rule::less_or_equal, Left)
+                | Op::infix(Rule::greater, Left)
+                | Op::infix(Rule::greater_or_equal, Left))
             .op(Op::infix(Rule::add, Left) | Op::infix(Rule::sub, Left))
-            .op(Op::infix(Rule::mul, Left) | Op::infix(Rule::div, Left))
+            .op(Op::infix(Rule::mul, Left) | Op::infix(Rule::div, Left) | Op::infix(Rule::rem, Left))
             //.op(Op::infix(Rule::pow, Right)) TODO should i add this?
             //.op(Op::infix(Rule::fac, Left)) TODO should i add this?
-            .op(Op::prefix(Rule::neg))
+            .op(Op::prefix(Rule::neg) | Op::prefix(Rule::not))
     };
 }
 //TODO add implicit multiplication: 2x = 2 * x, should this be as a preprocessor? or part of the grammar?
@@ -36,18 +46,32 @@ pub(crate) fn parse_exp(exp_to_parse: Pair<Rule>) -> Result<PreExp, CompilationE
                 Rule::sub => BinOp::Sub,
                 Rule::mul => BinOp::Mul,
                 Rule::div => BinOp::Div,
+                Rule::rem => BinOp::Mod,
+                Rule::and => BinOp::And,
+                Rule::or => BinOp::Or,
+                Rule::equal => BinOp::Equal,
+                Rule::not_equal => BinOp::NotEqual,
+                Rule::less => BinOp::Less,
+                Rule::less_or_equal => BinOp::LessOrEqual,
+                Rule::greater => BinOp::Greater,
+                Rule::greater_or_equal => BinOp::GreaterOrEqual,
                 _ => return err_unexpected_token!("found {}, expected op", op),
             };
+            let (lhs, rhs) = (lhs?, rhs?);
+            if let Some(rational) = fold_rational_literal(op, &lhs, &rhs) {
+                return Ok(rational);
+            }
             Ok(PreExp::BinaryOperation(
                 Spanned::new(op, span),
-                lhs?.to_boxed(),
-                rhs?.to_boxed(),
+                lhs.to_boxed(),
+                rhs.to_boxed(),
             ))
         })
         .map_prefix(|op, rhs| {
             let span = InputSpan::from_pair(&op);
             let op = match op.as_rule() {
                 Rule::neg => UnOp::Neg,
+                Rule::not => UnOp::Not,
                 _ => return err_unexpected_token!("found {}, expected op", op),
             };
             Ok(PreExp::UnaryOperation(
@@ -58,6 +82,34 @@ pub(crate) fn parse_exp(exp_to_parse: Pair<Rule>) -> Result<PreExp, CompilationE
         .parse(exp_to_parse.into_inner())
 }
 
+/// Turns a division between two integer literals, like `1/3`, into an exact rational literal.
+/// The fraction is kept as written so that formatting preserves it. This is an exception to
+/// the promotion rules of [`PrimitiveKind::binary_op_result`](crate::primitives::PrimitiveKind::binary_op_result), where a division of integers is a Number
+fn fold_rational_literal(op: BinOp, lhs: &PreExp, rhs: &PreExp) -> Option<PreExp> {
+    match (op, lhs, rhs) {
+        (BinOp::Div, PreExp::Primitive(num), PreExp::Primitive(den)) => {
+            match (num.value(), den.value()) {
+                (Primitive::Integer(n), Primitive::Integer(d)) if *d > 0 => {
+                    let (start, end) = (num.span(), den.span());
+                    let span = InputSpan {
+                        start_line: start.start_line,
+                        start_column: start.start_column,
+                        start: start.start,
+                        len: end.start + end.len - start.start,
+                        tempered: false,
+                    };
+                    Some(PreExp::Primitive(Spanned::new(
+                        Primitive::Rational(*n, *d),
+                        span,
+                    )))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 pub(crate) fn parse_exp_leaf(exp: Pair<Rule>) -> Result<PreExp, CompilationError> {
     let span = InputSpan::from_pair(&exp);
     match exp.as_rule() {
@@ -78,7 +130,11 @@ pub(crate) fn parse_exp_leaf(exp: Pair<Rule>) -> Result<PreExp, CompilationError
             Ok(PreExp::CompoundVariable(Spanned::new(variable, span)))
         }
         Rule::block_function => parse_block_function(&exp),
+        Rule::if_else => parse_if_else(&exp),
+        Rule::piecewise => parse_piecewise(&exp),
+        Rule::dot => parse_dot(&exp),
         Rule::block_scoped_function => parse_block_scoped_function(&exp),
+        Rule::array_comprehension => parse_array_comprehension(&exp),
         //also adding number since the implicit multiplication rule uses it without being part of the primitive
         Rule::primitive | Rule::float | Rule::integer => {
             let prim = parse_primitive(&exp)?;
@@ -116,7 +172,7 @@ pub(crate) fn parse_exp_leaf(exp: Pair<Rule>) -> Result<PreExp, CompilationError
             Ok(PreExp::ArrayAccess(Spanned::new(access, span)))
         }
         _ => err_unexpected_token!(
-            "found \"{}\"({:?}), expected exp, binary_op, unary_op, len, variable, sum, primitive, parenthesis, array_access, min, max, block function or scoped function",
+            "found \"{}\"({:?}), expected exp, binary_op, unary_op, len, variable, sum, primitive, parenthesis, array_access, min, max, block function, scoped function or if/else",
             exp, exp.as_rule()
         ),
     }
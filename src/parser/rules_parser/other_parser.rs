@@ -6,22 +6,25 @@ use crate::math::{Comparison, OptimizationType, PreVariableType};
 use crate::parser::domain_declaration::{VariableToAssert, VariablesDomainDeclaration};
 use crate::parser::il::IterableSet;
 use crate::parser::il::PreExp;
-use crate::parser::il::{AddressableAccess, CompoundVariable, PreConstraint, PreObjective};
 use crate::parser::il::{
-    BlockFunction, BlockFunctionKind, BlockScopedFunction, BlockScopedFunctionKind,
+    AddressableAccess, CompoundVariable, PreConstraint, PreGoal, PreObjective,
 };
-use crate::parser::iterable_utils::flatten_primitive_array_values;
+use crate::parser::il::{
+    ArrayComprehension, BlockFunction, BlockFunctionKind, BlockScopedFunction,
+    BlockScopedFunctionKind,
+};
+use crate::parser::iterable_utils::{check_array_rectangularity, flatten_primitive_array_values};
 use crate::parser::model_transformer::VariableKind;
 use crate::parser::pre_model::Rule;
 use crate::primitives::Constant;
-use crate::primitives::Primitive;
-use crate::primitives::{Graph, GraphEdge, GraphNode};
+use crate::primitives::{Graph, GraphEdge, GraphNode, Map};
+use crate::primitives::{Primitive, PrimitiveKind};
 use crate::utils::{CompilationError, InputSpan, ParseError, Spanned};
 
 use super::exp_parser::parse_exp;
 
 use crate::runtime_builtin::FunctionCall;
-use crate::{bail_missing_token, err_unexpected_token};
+use crate::{bail_missing_token, bail_semantic_error, err_unexpected_token};
 
 pub fn parse_objective(objective: Pair<Rule>) -> Result<PreObjective, CompilationError> {
     match objective.as_rule() {
@@ -96,9 +99,25 @@ pub fn parse_const_declaration(
             let name = pairs
                 .find_first_tagged("name")
                 .map(|n| Spanned::new(n.as_str().to_string(), InputSpan::from_span(n.as_span())));
-            let value = pairs.find_first_tagged("value").map(|v| parse_exp(v));
+            let value = pairs.find_first_tagged("value");
+            let annotation = pairs.find_first_tagged("type_annotation");
             match (name, value) {
-                (Some(name), Some(value)) => Ok(Constant::new(name, value?)),
+                (Some(name), Some(value)) => {
+                    let exp = parse_exp(value.clone())?;
+                    match annotation {
+                        Some(annotation) => {
+                            let kind = parse_type_annotation(&annotation)?;
+                            let span = value
+                                .as_span()
+                                .start_pos()
+                                .span(&annotation.as_span().end_pos());
+                            let exp =
+                                PreExp::Cast(InputSpan::from_span(span), exp.to_boxed(), kind);
+                            Ok(Constant::new(name, exp))
+                        }
+                        None => Ok(Constant::new(name, exp)),
+                    }
+                }
                 _ => bail_missing_token!("Missing constant body", const_declaration),
             }
         }
@@ -109,6 +128,34 @@ pub fn parse_const_declaration(
     }
 }
 
+pub fn parse_type_annotation(pair: &Pair<Rule>) -> Result<PrimitiveKind, CompilationError> {
+    let annotation = pair.as_str();
+    let name = annotation.trim_end_matches("[]");
+    let depth = (annotation.len() - name.len()) / 2;
+    let mut kind = match name {
+        "Number" => PrimitiveKind::Number,
+        "Integer" => PrimitiveKind::Integer,
+        "PositiveInteger" => PrimitiveKind::PositiveInteger,
+        "Rational" => PrimitiveKind::Rational,
+        "Boolean" => PrimitiveKind::Boolean,
+        "String" => PrimitiveKind::String,
+        "Graph" => PrimitiveKind::Graph,
+        "GraphNode" => PrimitiveKind::GraphNode,
+        "GraphEdge" => PrimitiveKind::GraphEdge,
+        "Any" => PrimitiveKind::Any,
+        _ => {
+            return err_unexpected_token!(
+                "Unknown type \"{}\", expected one of \"Number, Integer, PositiveInteger, Rational, Boolean, String, Graph, GraphNode, GraphEdge, Any\"",
+                pair
+            )
+        }
+    };
+    for _ in 0..depth {
+        kind = PrimitiveKind::Iterable(Box::new(kind));
+    }
+    Ok(kind)
+}
+
 pub fn parse_domains_declaration(
     domains_declarations: Pair<Rule>,
 ) -> Result<Vec<VariablesDomainDeclaration>, CompilationError> {
@@ -139,13 +186,20 @@ pub fn parse_domain_declaration(
             let as_type = inner
                 .find_first_tagged("as_type")
                 .map(|v| parse_as_assertion_type(&v));
+            let initial_value = inner
+                .find_first_tagged("initial_value")
+                .map(parse_exp)
+                .transpose()?;
             let span = InputSpan::from_pair(&rule);
             match (variables, as_type) {
                 (Some(variables), Some(as_type)) => {
                     let iteration = iteration.unwrap_or(Ok(vec![]));
-                    Ok(VariablesDomainDeclaration::new(
-                        variables?, as_type?, iteration?, span,
-                    ))
+                    let declaration =
+                        VariablesDomainDeclaration::new(variables?, as_type?, iteration?, span);
+                    Ok(match initial_value {
+                        Some(value) => declaration.with_initial_value(value),
+                        None => declaration,
+                    })
                 }
                 _ => bail_missing_token!("Missing domain declaration body", rule),
             }
@@ -197,6 +251,7 @@ pub fn parse_as_assertion_type(pair: &Pair<Rule>) -> Result<PreVariableType, Com
             }
             "NonNegativeReal" => return Ok(PreVariableType::NonNegativeReal(min, max)),
             "Real" => return Ok(PreVariableType::Real(min, max)),
+            "Integer" => return Ok(PreVariableType::Integer(min, max)),
             _ => {
                 return err_unexpected_token!(
                     "Unknown variable type \"{}\", expected one of \"{}\"",
@@ -277,8 +332,34 @@ pub fn parse_primitive(const_value: &Pair<Rule>) -> Result<Primitive, Compilatio
                 .into_inner()
                 .map(|v| parse_primitive(&v))
                 .collect::<Result<Vec<_>, CompilationError>>()?;
+            if let Err(e) = check_array_rectangularity(&values) {
+                return Err(CompilationError::from_pair(
+                    ParseError::SemanticError(e),
+                    const_value,
+                    false,
+                ));
+            }
             Ok(Primitive::Iterable(flatten_primitive_array_values(values)))
         }
+        Rule::map => {
+            let mut entries: Vec<(String, Primitive)> = Vec::new();
+            for entry in const_value.clone().into_inner() {
+                let mut inner = entry.clone().into_inner();
+                let (key, value) = match (inner.next(), inner.next()) {
+                    (Some(key), Some(value)) => (parse_primitive(&key)?, parse_primitive(&value)?),
+                    _ => return err_unexpected_token!("Expected map entry but got: {}", entry),
+                };
+                let key = match key {
+                    Primitive::String(key) => key,
+                    _ => return err_unexpected_token!("Expected string key but got: {}", entry),
+                };
+                if entries.iter().any(|(k, _)| *k == key) {
+                    return bail_semantic_error!("Duplicate key \"{key}\" in map", entry);
+                }
+                entries.push((key, value));
+            }
+            Ok(Primitive::Map(Map::new(entries)))
+        }
         Rule::graph => {
             let inner = const_value.clone().into_inner();
             let body = inner.find_first_tagged("body");
@@ -302,20 +383,28 @@ pub fn parse_graph_node(node: &Pair<Rule>) -> Result<GraphNode, CompilationError
     let inner = node.clone().into_inner();
     let name = inner.find_first_tagged("name");
     let edges = inner.find_first_tagged("edges");
-    match (name, edges) {
+    let weight = match inner.find_first_tagged("node_weight") {
+        Some(weight) => Some(parse_graph_weight(&weight)?),
+        None => None,
+    };
+    let node = match (name, edges) {
         (Some(name), Some(edges)) => {
             let name = name.as_str().to_string();
             let edges = edges
                 .into_inner()
                 .map(|e| parse_graph_edge(&e, &name))
                 .collect::<Result<Vec<GraphEdge>, CompilationError>>()?;
-            Ok(GraphNode::new(name, edges))
+            GraphNode::new(name, edges)
         }
         (Some(name), None) => {
             let name = name.as_str().to_string();
-            Ok(GraphNode::new(name, vec![]))
+            GraphNode::new(name, vec![])
         }
-        _ => err_unexpected_token!("Expected graph node but got: {}", node),
+        _ => return err_unexpected_token!("Expected graph node but got: {}", node),
+    };
+    match weight {
+        Some(weight) => Ok(node.with_weight(weight)),
+        None => Ok(node),
     }
 }
 
@@ -323,18 +412,7 @@ pub fn parse_graph_edge(edge: &Pair<Rule>, from: &str) -> Result<GraphEdge, Comp
     let inner = edge.clone().into_inner();
     let node = inner.find_first_tagged("node");
     let cost = match inner.find_first_tagged("cost") {
-        Some(cost) => {
-            let parsed = cost.as_str().to_string().parse::<f64>();
-            if parsed.is_err() {
-                let error = ParseError::UnexpectedToken(format!(
-                    "Expected number but got: {}, error: {}",
-                    cost,
-                    parsed.unwrap_err()
-                ));
-                return Err(CompilationError::from_pair(error, &cost, false));
-            }
-            Some(parsed.unwrap())
-        }
+        Some(cost) => Some(parse_graph_weight(&cost)?),
         None => None,
     };
     match node {
@@ -346,6 +424,19 @@ pub fn parse_graph_edge(edge: &Pair<Rule>, from: &str) -> Result<GraphEdge, Comp
     }
 }
 
+fn parse_graph_weight(weight: &Pair<Rule>) -> Result<f64, CompilationError> {
+    match weight.as_str().to_string().parse::<f64>() {
+        Ok(parsed) => Ok(parsed),
+        Err(e) => {
+            let error = ParseError::UnexpectedToken(format!(
+                "Expected number but got: {}, error: {}",
+                weight, e
+            ));
+            Err(CompilationError::from_pair(error, weight, false))
+        }
+    }
+}
+
 pub fn parse_constraint_list(
     constraint_list: &Pair<Rule>,
 ) -> Result<Vec<PreConstraint>, CompilationError> {
@@ -353,12 +444,113 @@ pub fn parse_constraint_list(
         Rule::constraint_list => constraint_list
             .clone()
             .into_inner()
-            .map(|c| parse_constraint(&c))
-            .collect(),
+            .map(|c| match c.as_rule() {
+                Rule::range_constraint => parse_range_constraint(&c),
+                Rule::goal_constraint => parse_goal_constraint(&c).map(|c| vec![c]),
+                _ => parse_constraint(&c).map(|c| vec![c]),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|c| c.into_iter().flatten().collect()),
         _ => err_unexpected_token!("Expected constraint list but got: {}", constraint_list),
     }
 }
 
+/// Expands a constraint like "0 <= x <= 10" into "0 <= x" and "x <= 10",
+/// both sides share the iteration of the range
+pub fn parse_range_constraint(range: &Pair<Rule>) -> Result<Vec<PreConstraint>, CompilationError> {
+    let inner = range.clone().into_inner();
+    let lower = inner.find_first_tagged("range_lower");
+    let lower_relation = inner.find_first_tagged("lower_relation");
+    let body = inner.find_first_tagged("range_body");
+    let upper_relation = inner.find_first_tagged("upper_relation");
+    let upper = inner.find_first_tagged("range_upper");
+    let iteration = inner.find_first_tagged("range_iteration");
+    let name = inner
+        .find_first_tagged("constraint_name")
+        .map(|n| parse_constraint_name(&n))
+        .transpose()?;
+    match (lower, lower_relation, body, upper_relation, upper) {
+        (Some(lower), Some(lower_relation), Some(body), Some(upper_relation), Some(upper)) => {
+            let lower_relation = parse_comparison(&lower_relation)?;
+            let upper_relation = parse_comparison(&upper_relation)?;
+            let same_direction = matches!(
+                (lower_relation, upper_relation),
+                (
+                    Comparison::LessOrEqual | Comparison::Less,
+                    Comparison::LessOrEqual | Comparison::Less
+                ) | (
+                    Comparison::GreaterOrEqual | Comparison::Greater,
+                    Comparison::GreaterOrEqual | Comparison::Greater
+                )
+            );
+            if !same_direction {
+                return bail_semantic_error!(
+                    "Both comparisons of a range constraint must go in the same direction",
+                    range
+                );
+            }
+            let iteration = match iteration {
+                Some(iteration) => parse_set_iterator_list(&iteration.into_inner())?,
+                None => vec![],
+            };
+            let body = parse_exp(body)?;
+            let span = InputSpan::from_pair(range);
+            let lower = PreConstraint::new(
+                parse_exp(lower)?,
+                lower_relation,
+                body.clone(),
+                iteration.clone(),
+                span.clone(),
+            );
+            let upper =
+                PreConstraint::new(body, upper_relation, parse_exp(upper)?, iteration, span);
+            match name {
+                Some(name) => Ok(vec![
+                    lower.with_name(range_side_name(&name, "lower")),
+                    upper.with_name(range_side_name(&name, "upper")),
+                ]),
+                None => Ok(vec![lower, upper]),
+            }
+        }
+        _ => bail_missing_token!("Missing range constraint body", range),
+    }
+}
+
+/// Names one side of a named range constraint, "cap_i" becomes "cap_lower_i"
+fn range_side_name(name: &Spanned<CompoundVariable>, side: &str) -> Spanned<CompoundVariable> {
+    Spanned::new(
+        CompoundVariable::new(format!("{}_{}", name.name, side), name.indexes.clone()),
+        name.span().clone(),
+    )
+}
+
+/// Parses a goal like "@weight(2) x + y ~ 10" into an equality constraint marked as a goal
+pub fn parse_goal_constraint(goal: &Pair<Rule>) -> Result<PreConstraint, CompilationError> {
+    let inner = goal.clone().into_inner();
+    let weight = inner.find_first_tagged("goal_weight");
+    let lhs = inner.find_first_tagged("goal_lhs");
+    let target = inner.find_first_tagged("goal_target");
+    let iteration = inner.find_first_tagged("goal_iteration");
+    match (lhs, target) {
+        (Some(lhs), Some(target)) => {
+            let iteration = match iteration {
+                Some(iteration) => parse_set_iterator_list(&iteration.into_inner())?,
+                None => vec![],
+            };
+            let weight = weight.map(parse_exp).transpose()?;
+            Ok(PreConstraint::new(
+                parse_exp(lhs)?,
+                Comparison::Equal,
+                parse_exp(target)?,
+                iteration,
+                InputSpan::from_pair(goal),
+            )
+            .with_goal(PreGoal::new(weight)))
+        }
+        _ => bail_missing_token!("Missing goal body", goal),
+    }
+}
+
 pub fn parse_constraint(constraint: &Pair<Rule>) -> Result<PreConstraint, CompilationError> {
     match constraint.as_rule() {
         Rule::constraint => {
@@ -367,27 +559,73 @@ pub fn parse_constraint(constraint: &Pair<Rule>) -> Result<PreConstraint, Compil
             let relation = inner.find_first_tagged("relation");
             let rhs = inner.find_first_tagged("rhs");
             let iteration = inner.find_first_tagged("iteration");
+            let name = inner
+                .find_first_tagged("constraint_name")
+                .map(|n| parse_constraint_name(&n))
+                .transpose()?;
             match (rhs, relation, lhs, iteration) {
                 (Some(rhs), Some(relation_type), Some(lhs), iteration) => {
                     let iteration = match iteration {
                         Some(iteration) => parse_set_iterator_list(&iteration.into_inner())?,
                         None => vec![],
                     };
-                    Ok(PreConstraint::new(
+                    let alternatives = inner
+                        .clone()
+                        .filter(|p| p.as_rule() == Rule::constraint_alternative)
+                        .map(|p| parse_constraint(&p))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let constraint = PreConstraint::new(
                         parse_exp(lhs)?,
                         parse_comparison(&relation_type)?,
                         parse_exp(rhs)?,
                         iteration,
                         InputSpan::from_pair(constraint),
-                    ))
+                    )
+                    .with_alternatives(alternatives);
+                    match name {
+                        Some(name) => Ok(constraint.with_name(name)),
+                        None => Ok(constraint),
+                    }
                 }
                 _ => bail_missing_token!("Missing constraint body", constraint),
             }
         }
+        Rule::constraint_alternative => {
+            let inner = constraint.clone().into_inner();
+            let lhs = inner.find_first_tagged("lhs");
+            let relation = inner.find_first_tagged("relation");
+            let rhs = inner.find_first_tagged("rhs");
+            match (rhs, relation, lhs) {
+                (Some(rhs), Some(relation_type), Some(lhs)) => Ok(PreConstraint::new(
+                    parse_exp(lhs)?,
+                    parse_comparison(&relation_type)?,
+                    parse_exp(rhs)?,
+                    vec![],
+                    InputSpan::from_pair(constraint),
+                )),
+                _ => bail_missing_token!("Missing constraint body", constraint),
+            }
+        }
         _ => err_unexpected_token!("Expected constraint but got: {}", constraint),
     }
 }
 
+pub fn parse_constraint_name(
+    name: &Pair<Rule>,
+) -> Result<Spanned<CompoundVariable>, CompilationError> {
+    let span = InputSpan::from_pair(name);
+    match name.clone().into_inner().next() {
+        Some(inner) if inner.as_rule() == Rule::compound_variable => {
+            Ok(Spanned::new(parse_compound_variable(&inner)?, span))
+        }
+        Some(inner) if inner.as_rule() == Rule::simple_variable => Ok(Spanned::new(
+            CompoundVariable::new(inner.as_str().to_string(), vec![]),
+            span,
+        )),
+        _ => err_unexpected_token!("Expected constraint name but got: {}", name),
+    }
+}
+
 pub fn parse_number(number: &Pair<Rule>) -> Result<Primitive, CompilationError> {
     match number.as_rule() {
         Rule::float => match number.as_str().parse::<f64>() {
@@ -441,6 +679,34 @@ pub fn parse_block_scoped_function(exp: &Pair<Rule>) -> Result<PreExp, Compilati
     Ok(PreExp::BlockScopedFunction(Spanned::new(fun, span)))
 }
 
+pub fn parse_array_comprehension(exp: &Pair<Rule>) -> Result<PreExp, CompilationError> {
+    let span = InputSpan::from_pair(exp);
+    let inner = exp.clone().into_inner();
+    let body = inner.clone().find(|p| p.as_rule() == Rule::tagged_exp);
+    let iters = inner
+        .clone()
+        .find(|p| p.as_rule() == Rule::iteration_declaration_list);
+    let guard = inner
+        .clone()
+        .find(|p| p.as_rule() == Rule::comprehension_guard);
+    match (body, iters) {
+        (Some(body), Some(iters)) => {
+            let iters = parse_set_iterator_list(&iters.into_inner())?;
+            let body = parse_exp(body)?.to_boxed();
+            let guard = match guard {
+                Some(guard) => Some(parse_exp(guard)?.to_boxed()),
+                None => None,
+            };
+            let comprehension = ArrayComprehension::new(body, iters, guard);
+            Ok(PreExp::ArrayComprehension(Spanned::new(
+                comprehension,
+                span,
+            )))
+        }
+        _ => err_unexpected_token!("found {}, expected array comprehension", exp),
+    }
+}
+
 pub fn parse_block_function(exp: &Pair<Rule>) -> Result<PreExp, CompilationError> {
     let span = InputSpan::from_pair(exp);
     let inner = exp.clone().into_inner();
@@ -459,6 +725,55 @@ pub fn parse_block_function(exp: &Pair<Rule>) -> Result<PreExp, CompilationError
     Ok(PreExp::BlockFunction(Spanned::new(fun, span)))
 }
 
+pub fn parse_if_else(exp: &Pair<Rule>) -> Result<PreExp, CompilationError> {
+    let span = InputSpan::from_pair(exp);
+    let inner = exp.clone().into_inner();
+    let condition = inner.find_first_tagged("condition");
+    let then_branch = inner.find_first_tagged("then_branch");
+    let else_branch = inner.find_first_tagged("else_branch");
+    match (condition, then_branch, else_branch) {
+        (Some(condition), Some(then_branch), Some(else_branch)) => Ok(PreExp::IfElse(
+            span,
+            parse_exp(condition)?.to_boxed(),
+            parse_exp(then_branch)?.to_boxed(),
+            parse_exp(else_branch)?.to_boxed(),
+        )),
+        _ => err_unexpected_token!("found {}, expected if/else expression", exp),
+    }
+}
+
+pub fn parse_piecewise(exp: &Pair<Rule>) -> Result<PreExp, CompilationError> {
+    let span = InputSpan::from_pair(exp);
+    let inner = exp.clone().into_inner();
+    let argument = inner.find_first_tagged("argument");
+    let breakpoints = inner.find_first_tagged("breakpoints");
+    let values = inner.find_first_tagged("values");
+    match (argument, breakpoints, values) {
+        (Some(argument), Some(breakpoints), Some(values)) => Ok(PreExp::Piecewise(
+            span,
+            parse_exp(argument)?.to_boxed(),
+            parse_exp(breakpoints)?.to_boxed(),
+            parse_exp(values)?.to_boxed(),
+        )),
+        _ => err_unexpected_token!("found {}, expected piecewise expression", exp),
+    }
+}
+
+pub fn parse_dot(exp: &Pair<Rule>) -> Result<PreExp, CompilationError> {
+    let span = InputSpan::from_pair(exp);
+    let inner = exp.clone().into_inner();
+    let left = inner.find_first_tagged("left");
+    let right = inner.find_first_tagged("right");
+    match (left, right) {
+        (Some(left), Some(right)) => Ok(PreExp::Dot(
+            span,
+            parse_exp(left)?.to_boxed(),
+            parse_exp(right)?.to_boxed(),
+        )),
+        _ => err_unexpected_token!("found {}, expected dot expression", exp),
+    }
+}
+
 pub fn parse_compound_variable(
     compound_variable: &Pair<Rule>,
 ) -> Result<CompoundVariable, CompilationError> {
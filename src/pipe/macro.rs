@@ -9,6 +9,64 @@ mod pipe_macros {
         };
     }
 
+    /// Creates a `PipeRunner` from a list of pipes, or from one of the presets:
+    ///
+    /// * `to_linear_model()` - compiles the source up to the linear model
+    /// * `to_standard_form()` - compiles the source up to the standard form of the linear model
+    /// * `to_tableau()` - compiles the source up to the simplex tableau
+    /// * `solve_real()`, `solve_binary()`, `solve_integer_binary()`, `solve_milp()`, `solve_auto()` -
+    ///   compiles the source and solves it with the corresponding solver
+    ///
+    /// # Example
+    /// ```rust
+    /// use indexmap::IndexMap;
+    /// use rooc::pipe::{PipeContext, PipeableData};
+    /// use rooc::rooc_pipe;
+    ///
+    /// let runner = rooc_pipe!(solve_milp());
+    /// let explicit = rooc_pipe![CompilerPipe, PreModelPipe, ModelPipe];
+    /// let result = runner
+    ///     .run(
+    ///         PipeableData::String("max x\ns.t.\n    x <= 2\ndefine\n    x as IntegerRange(0, 5)".to_string()),
+    ///         &PipeContext::new(vec![], &IndexMap::new()),
+    ///     )
+    ///     .unwrap();
+    /// println!("{}", result.last().unwrap());
+    /// ```
+    #[macro_export]
+    macro_rules! rooc_pipe {
+        (to_linear_model()) => {
+            $crate::rooc_pipe!(@compiled)
+        };
+        (to_standard_form()) => {
+            $crate::rooc_pipe!(@compiled StandardLinearModelPipe)
+        };
+        (to_tableau()) => {
+            $crate::rooc_pipe!(@compiled StandardLinearModelPipe, TableauPipe)
+        };
+        (solve_real()) => {
+            $crate::rooc_pipe!(@compiled RealSolver)
+        };
+        (solve_binary()) => {
+            $crate::rooc_pipe!(@compiled BinarySolverPipe)
+        };
+        (solve_integer_binary()) => {
+            $crate::rooc_pipe!(@compiled IntegerBinarySolverPipe)
+        };
+        (solve_milp()) => {
+            $crate::rooc_pipe!(@compiled MILPSolverPipe)
+        };
+        (solve_auto()) => {
+            $crate::rooc_pipe!(@compiled AutoSolverPipe)
+        };
+        (@compiled $($pipe:ident),*) => {
+            $crate::rooc_pipe![CompilerPipe, PreModelPipe, ModelPipe, LinearModelPipe $(, $pipe)*]
+        };
+        ($($pipe:ident),+ $(,)?) => {
+            $crate::pipe::PipeRunner::new(vec![$(Box::new($crate::pipe::$pipe::new())),+])
+        };
+    }
+
     #[macro_export]
     macro_rules! match_pipe_data_to {
         ($to:expr,$type:ident, $expected:ident ) => {
@@ -10,12 +10,14 @@ use crate::primitives::Constant;
 use crate::runtime_builtin::RoocFunction;
 use crate::solvers::IntOrBoolValue;
 use crate::solvers::{
-    CanonicalTransformError, OptimalTableau, OptimalTableauWithSteps, SimplexError, Tableau,
+    CanonicalTransformError, MILPOptions, OptimalTableau, OptimalTableauWithSteps, SimplexError,
+    SimplexOptions, Tableau,
 };
 use crate::solvers::{LpSolution, SolverError};
+use crate::transformers::ExportError;
 use crate::transformers::LinearModel;
 use crate::transformers::LinearizationError;
-use crate::transformers::StandardLinearModel;
+use crate::transformers::{PresolvedModel, StandardLinearModel};
 use crate::utils::CompilationError;
 use crate::{match_pipe_data_to, MILPValue, RoocParser};
 
@@ -26,6 +28,7 @@ pub enum PipeableData {
     Parser(RoocParser),
     PreModel(PreModel),
     Model(Model),
+    PresolvedModel(PresolvedModel),
     LinearModel(LinearModel),
     StandardLinearModel(StandardLinearModel),
     Tableau(Tableau),
@@ -46,6 +49,7 @@ impl PipeableData {
             PipeableData::String(_) => PipeDataType::String,
             PipeableData::Parser(_) => PipeDataType::Parser,
             PipeableData::Model(_) => PipeDataType::Model,
+            PipeableData::PresolvedModel(_) => PipeDataType::PresolvedModel,
             PipeableData::LinearModel(_) => PipeDataType::LinearModel,
             PipeableData::StandardLinearModel(_) => PipeDataType::StandardLinearModel,
             PipeableData::Tableau(_) => PipeDataType::Tableau,
@@ -68,8 +72,15 @@ impl PipeableData {
     pub fn to_pre_model(self) -> Result<PreModel, PipeError> {
         match_pipe_data_to!(self, PreModel, PreModel)
     }
+    /// Gets the model, a presolved model can be used wherever a model is expected
     pub fn to_model(self) -> Result<Model, PipeError> {
-        match_pipe_data_to!(self, Model, Model)
+        match self {
+            PipeableData::PresolvedModel(m) => Ok(m.into_model()),
+            _ => match_pipe_data_to!(self, Model, Model),
+        }
+    }
+    pub fn to_presolved_model(self) -> Result<PresolvedModel, PipeError> {
+        match_pipe_data_to!(self, PresolvedModel, PresolvedModel)
     }
     pub fn to_linear_model(self) -> Result<LinearModel, PipeError> {
         match_pipe_data_to!(self, LinearModel, LinearModel)
@@ -119,7 +130,13 @@ impl PipeableData {
         match_pipe_data_to!(self, PreModel, PreModel)
     }
     pub fn as_model(&self) -> Result<&Model, PipeError> {
-        match_pipe_data_to!(self, Model, Model)
+        match self {
+            PipeableData::PresolvedModel(m) => Ok(m.model()),
+            _ => match_pipe_data_to!(self, Model, Model),
+        }
+    }
+    pub fn as_presolved_model(&self) -> Result<&PresolvedModel, PipeError> {
+        match_pipe_data_to!(self, PresolvedModel, PresolvedModel)
     }
     pub fn as_linear_model(&self) -> Result<&LinearModel, PipeError> {
         match_pipe_data_to!(self, LinearModel, LinearModel)
@@ -142,6 +159,7 @@ impl Display for PipeableData {
             PipeableData::Parser(p) => write!(f, "{}", p.source),
             PipeableData::PreModel(p) => write!(f, "{}", p),
             PipeableData::Model(m) => write!(f, "{}", m),
+            PipeableData::PresolvedModel(m) => write!(f, "{}", m.model()),
             PipeableData::LinearModel(m) => write!(f, "{}", m),
             PipeableData::StandardLinearModel(m) => write!(f, "{}", m),
             PipeableData::Tableau(t) => write!(f, "{}", t),
@@ -172,6 +190,7 @@ pub enum PipeDataType {
     IntegerBinarySolution,
     RealSolution,
     MILPSolution,
+    PresolvedModel,
 }
 impl Display for PipeDataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -189,6 +208,7 @@ impl Display for PipeDataType {
             PipeDataType::IntegerBinarySolution => "IntegerBinarySolution".to_string(),
             PipeDataType::RealSolution => "RealSolution".to_string(),
             PipeDataType::MILPSolution => "MILPSolution".to_string(),
+            PipeDataType::PresolvedModel => "PresolvedModel".to_string(),
         };
 
         f.write_str(&s)
@@ -214,7 +234,9 @@ pub enum PipeError {
     StandardizationError(SolverError),
     CanonicalizationError(CanonicalTransformError),
     StepByStepSimplexError(SimplexError, Tableau),
+    OptimalTableauError(SimplexError, Tableau),
     SolverError(SolverError),
+    ExportError(ExportError),
     Other(String),
 }
 impl Display for PipeError {
@@ -239,8 +261,19 @@ impl Display for PipeError {
             PipeError::LinearizationError(e) => write!(f, "{}", e),
             PipeError::StandardizationError(e) => write!(f, "{}", e),
             PipeError::CanonicalizationError(e) => write!(f, "{}", e),
-            PipeError::StepByStepSimplexError(e, _) => write!(f, "{}", e),
+            PipeError::StepByStepSimplexError(e, tableau)
+            | PipeError::OptimalTableauError(e, tableau) => {
+                match (e, tableau.unbounded_variable()) {
+                    (SimplexError::Unbounded, Some(variable)) => write!(
+                        f,
+                        "{}, the variable \"{}\" can increase indefinitely",
+                        e, variable
+                    ),
+                    _ => write!(f, "{}", e),
+                }
+            }
             PipeError::SolverError(e) => write!(f, "{}", e),
+            PipeError::ExportError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -251,17 +284,47 @@ impl Display for PipeError {
 pub struct PipeContext<'a> {
     functions: &'a IndexMap<String, Box<dyn RoocFunction>>,
     constants: Vec<Constant>,
+    simplex_options: SimplexOptions,
+    milp_options: MILPOptions,
+    constraint_prefix: Option<String>,
 }
 impl PipeContext<'_> {
     pub fn new(
         constants: Vec<Constant>,
         fns: &IndexMap<String, Box<dyn RoocFunction>>,
-    ) -> PipeContext {
+    ) -> PipeContext<'_> {
         PipeContext {
             constants,
             functions: fns,
+            simplex_options: SimplexOptions::default(),
+            milp_options: MILPOptions::default(),
+            constraint_prefix: None,
         }
     }
+    /// Sets the options used by the pipes that run the tableau simplex
+    pub fn with_simplex_options(mut self, options: SimplexOptions) -> Self {
+        self.simplex_options = options;
+        self
+    }
+    pub fn simplex_options(&self) -> &SimplexOptions {
+        &self.simplex_options
+    }
+    /// Sets the options used by the pipes that run the MILP branch and bound
+    pub fn with_milp_options(mut self, options: MILPOptions) -> Self {
+        self.milp_options = options;
+        self
+    }
+    pub fn milp_options(&self) -> &MILPOptions {
+        &self.milp_options
+    }
+    /// Sets the prefix used to name the unnamed constraints when the model is linearized
+    pub fn with_constraint_prefix(mut self, prefix: String) -> Self {
+        self.constraint_prefix = Some(prefix);
+        self
+    }
+    pub fn constraint_prefix(&self) -> Option<&String> {
+        self.constraint_prefix.as_ref()
+    }
     pub fn constants(&self) -> &Vec<Constant> {
         &self.constants
     }
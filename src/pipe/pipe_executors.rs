@@ -1,3 +1,4 @@
+use crate::parser::model_transformer::Model;
 use crate::pipe::pipe_definitions::{PipeError, Pipeable, PipeableData};
 use crate::pipe::PipeContext;
 #[allow(unused_imports)]
@@ -5,8 +6,13 @@ use crate::prelude::*;
 use crate::solvers::{
     solve_binary_lp_problem, solve_integer_binary_lp_problem, solve_real_lp_problem_clarabel,
 };
-use crate::transformers::Linearizer;
-use crate::{auto_solver, solve_milp_lp_problem, RoocParser};
+use crate::traits::ToLatex;
+use crate::transformers::{
+    linear_model_to_lp_format, linear_model_to_mps_format, presolve_bounds, LinearModel,
+    Linearizer, PresolvedModel,
+};
+use crate::{auto_solver, solve_milp_lp_problem_with_options, RoocParser};
+use std::borrow::Cow;
 
 #[cfg(target_arch = "wasm32")]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
@@ -24,6 +30,36 @@ pub enum Pipes {
     IntegerBinarySolverPipe,
     MILPSolverPipe,
     AutoSolverPipe,
+    LpFormatPipe,
+    MpsFormatPipe,
+    OptimalTableauPipe,
+    LatexPipe,
+    PresolvePipe,
+}
+
+/// Linearizes the model, naming the unnamed constraints with the prefix of the context
+#[allow(clippy::result_large_err)]
+fn linearize(model: &Model, pipe_context: &PipeContext) -> Result<LinearModel, PipeError> {
+    let mut linear = Linearizer::linearize(model.clone()).map_err(PipeError::LinearizationError)?;
+    if let Some(prefix) = pipe_context.constraint_prefix() {
+        linear.set_constraint_prefix(prefix.clone());
+    }
+    Ok(linear)
+}
+
+/// Returns the linear model to solve, linearizing the data first if it is still a model
+#[allow(clippy::result_large_err)]
+fn linear_model_of<'a>(
+    data: &'a PipeableData,
+    pipe_context: &PipeContext,
+) -> Result<Cow<'a, LinearModel>, PipeError> {
+    match data {
+        PipeableData::Model(model) => linearize(model, pipe_context).map(Cow::Owned),
+        PipeableData::PresolvedModel(presolved) => {
+            linearize(presolved.model(), pipe_context).map(Cow::Owned)
+        }
+        _ => data.as_linear_model().map(Cow::Borrowed),
+    }
 }
 
 //-------------------- Source Compiler --------------------
@@ -130,14 +166,14 @@ impl LinearModelPipe {
     }
 }
 impl Pipeable for LinearModelPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
         let model = data.as_model()?;
-        let model = model.clone();
-        let linearizer = Linearizer::linearize(model);
-        match linearizer {
-            Ok(linear) => Ok(PipeableData::LinearModel(linear)),
-            Err(e) => Err(PipeError::LinearizationError(e)),
-        }
+        let linear = linearize(model, pipe_context)?;
+        Ok(PipeableData::LinearModel(linear))
     }
 }
 //-------------------- Standard Linear Model --------------------
@@ -179,17 +215,27 @@ impl TableauPipe {
     }
 }
 impl Pipeable for TableauPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
-        let standard_linear_model = data.as_standard_linear_model()?.clone();
-        let tableau = standard_linear_model.into_tableau();
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let standard_linear_model = data.as_standard_linear_model()?;
+        let tableau = standard_linear_model
+            .clone()
+            .into_tableau_with_options(pipe_context.simplex_options());
+        //the infeasibility of the model is found by the first phase, so the tableaus given
+        //to the simplex pipes are always feasible and only this pipe looks for the conflict
         match tableau {
             Ok(tableau) => Ok(PipeableData::Tableau(tableau)),
-            Err(e) => Err(PipeError::CanonicalizationError(e)),
+            Err(e) => Err(PipeError::CanonicalizationError(
+                e.with_infeasible_subset(standard_linear_model),
+            )),
         }
     }
 }
 //-------------------- Simplex --------------------
-/// Pipe that solves the linear model using a real solver
+/// Pipe that solves the linear model (or the model, after linearizing it) using a real solver
 pub struct RealSolver {}
 impl Default for RealSolver {
     fn default() -> Self {
@@ -204,13 +250,17 @@ impl RealSolver {
 }
 
 impl Pipeable for RealSolver {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
-        let model = data.as_linear_model()?.clone();
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let model = linear_model_of(data, pipe_context)?;
         //solve_real_lp_problem
         let assignment = solve_real_lp_problem_clarabel(&model);
         match assignment {
             Ok(optimal) => Ok(PipeableData::RealSolution(optimal)),
-            Err(e) => Err(PipeError::SolverError(e)),
+            Err(e) => Err(PipeError::SolverError(e.with_infeasible_subset(&model))),
         }
     }
 }
@@ -229,9 +279,14 @@ impl StepByStepSimplexPipe {
     }
 }
 impl Pipeable for StepByStepSimplexPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
         let mut tableau = data.as_tableau()?.clone();
-        let optimal_tableau = tableau.solve_step_by_step(1000);
+        let optimal_tableau =
+            tableau.solve_step_by_step_with_options(pipe_context.simplex_options());
         match optimal_tableau {
             Ok(optimal_tableau) => Ok(PipeableData::OptimalTableauWithSteps(optimal_tableau)),
             Err(e) => Err(PipeError::StepByStepSimplexError(e, tableau)),
@@ -239,8 +294,38 @@ impl Pipeable for StepByStepSimplexPipe {
     }
 }
 
+//-------------------- Optimal tableau --------------------
+/// Pipe that solves the tableau with the simplex algorithm, returning only the optimal tableau
+pub struct OptimalTableauPipe {}
+impl Default for OptimalTableauPipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OptimalTableauPipe {
+    pub fn new() -> OptimalTableauPipe {
+        OptimalTableauPipe {}
+    }
+}
+impl Pipeable for OptimalTableauPipe {
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let mut tableau = data.as_tableau()?.clone();
+        let optimal_tableau = tableau.solve_with_options(pipe_context.simplex_options());
+        match optimal_tableau {
+            Ok(optimal_tableau) => Ok(PipeableData::OptimalTableau(optimal_tableau)),
+            Err(e) => Err(PipeError::OptimalTableauError(e, tableau)),
+        }
+    }
+}
+
 //-------------------- Dual --------------------
 
+#[allow(dead_code)]
 struct DualPipe {}
 impl Default for DualPipe {
     fn default() -> Self {
@@ -248,6 +333,7 @@ impl Default for DualPipe {
     }
 }
 
+#[allow(dead_code)]
 impl DualPipe {
     pub fn new() -> DualPipe {
         DualPipe {}
@@ -264,7 +350,7 @@ impl Pipeable for DualPipe {
 
 //-------------------- Binary solver --------------------
 
-/// Pipe that solves the linear model using a binary solver
+/// Pipe that solves the linear model (or the model, after linearizing it) using a binary solver
 pub struct BinarySolverPipe {}
 impl Default for BinarySolverPipe {
     fn default() -> Self {
@@ -278,9 +364,13 @@ impl BinarySolverPipe {
     }
 }
 impl Pipeable for BinarySolverPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
-        let linear_model = data.as_linear_model()?;
-        let binary_solution = solve_binary_lp_problem(linear_model);
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let linear_model = linear_model_of(data, pipe_context)?;
+        let binary_solution = solve_binary_lp_problem(&linear_model);
         match binary_solution {
             Ok(solution) => Ok(PipeableData::BinarySolution(solution)),
             Err(e) => Err(PipeError::SolverError(e)),
@@ -288,7 +378,8 @@ impl Pipeable for BinarySolverPipe {
     }
 }
 //-------------------- Integer Binary solver --------------------
-/// Pipe that solves the linear model using an integer binary solver
+/// Pipe that solves the linear model (or the model, after linearizing it) using an integer
+/// binary solver
 pub struct IntegerBinarySolverPipe {}
 impl Default for IntegerBinarySolverPipe {
     fn default() -> Self {
@@ -302,9 +393,13 @@ impl IntegerBinarySolverPipe {
     }
 }
 impl Pipeable for IntegerBinarySolverPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
-        let linear_model = data.as_linear_model()?;
-        let integer_binary_solution = solve_integer_binary_lp_problem(linear_model);
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let linear_model = linear_model_of(data, pipe_context)?;
+        let integer_binary_solution = solve_integer_binary_lp_problem(&linear_model);
         match integer_binary_solution {
             Ok(solution) => Ok(PipeableData::IntegerBinarySolution(solution)),
             Err(e) => Err(PipeError::SolverError(e)),
@@ -313,7 +408,7 @@ impl Pipeable for IntegerBinarySolverPipe {
 }
 
 //-------------------- MILP solver --------------------
-/// Pipe that solves linear models using a MILP solver
+/// Pipe that solves linear models (or models, after linearizing them) using a MILP solver
 pub struct MILPSolverPipe {}
 impl Default for MILPSolverPipe {
     fn default() -> Self {
@@ -327,18 +422,24 @@ impl MILPSolverPipe {
     }
 }
 impl Pipeable for MILPSolverPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
-        let linear_model = data.as_linear_model()?;
-        let integer_binary_solution = solve_milp_lp_problem(linear_model);
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let linear_model = linear_model_of(data, pipe_context)?;
+        let integer_binary_solution =
+            solve_milp_lp_problem_with_options(&linear_model, pipe_context.milp_options());
         match integer_binary_solution {
-            Ok(solution) => Ok(PipeableData::MILPSolution(solution)),
+            Ok((solution, _)) => Ok(PipeableData::MILPSolution(solution)),
             Err(e) => Err(PipeError::SolverError(e)),
         }
     }
 }
 
 //-------------------- Auto solver --------------------
-/// Pipe that solves linear models by automatically picking the right solver
+/// Pipe that solves linear models (or models, after linearizing them) by automatically picking
+/// the right solver
 pub struct AutoSolverPipe {}
 impl Default for AutoSolverPipe {
     fn default() -> Self {
@@ -352,12 +453,116 @@ impl AutoSolverPipe {
     }
 }
 impl Pipeable for AutoSolverPipe {
-    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
-        let linear_model = data.as_linear_model()?;
-        let solution = auto_solver(linear_model);
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let linear_model = linear_model_of(data, pipe_context)?;
+        let solution = auto_solver(&linear_model);
         match solution {
             Ok(solution) => Ok(PipeableData::MILPSolution(solution)),
             Err(e) => Err(PipeError::SolverError(e)),
         }
     }
 }
+
+//-------------------- LP format --------------------
+/// Pipe that exports the model in the CPLEX LP format
+pub struct LpFormatPipe {}
+impl Default for LpFormatPipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LpFormatPipe {
+    pub fn new() -> LpFormatPipe {
+        LpFormatPipe {}
+    }
+}
+impl Pipeable for LpFormatPipe {
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let linear = linear_model_of(data, pipe_context)?;
+        match linear_model_to_lp_format(&linear) {
+            Ok(lp) => Ok(PipeableData::String(lp)),
+            Err(e) => Err(PipeError::ExportError(e)),
+        }
+    }
+}
+
+//-------------------- MPS format --------------------
+/// Pipe that exports the model in the fixed-column MPS format
+pub struct MpsFormatPipe {}
+impl Default for MpsFormatPipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MpsFormatPipe {
+    pub fn new() -> MpsFormatPipe {
+        MpsFormatPipe {}
+    }
+}
+impl Pipeable for MpsFormatPipe {
+    fn pipe(
+        &self,
+        data: &mut PipeableData,
+        pipe_context: &PipeContext,
+    ) -> Result<PipeableData, PipeError> {
+        let linear = linear_model_of(data, pipe_context)?;
+        match linear_model_to_mps_format(&linear) {
+            Ok(mps) => Ok(PipeableData::String(mps)),
+            Err(e) => Err(PipeError::ExportError(e)),
+        }
+    }
+}
+
+//-------------------- LaTeX --------------------
+/// Pipe that exports the pre model to LaTeX, keeping the iteration clauses of the constraints
+pub struct LatexPipe {}
+impl Default for LatexPipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatexPipe {
+    pub fn new() -> LatexPipe {
+        LatexPipe {}
+    }
+}
+impl Pipeable for LatexPipe {
+    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
+        let pre_model = data.as_pre_model()?;
+        Ok(PipeableData::String(pre_model.to_latex()))
+    }
+}
+
+//-------------------- Presolve --------------------
+/// Pipe that folds the constraints with a single variable into the bounds of its domain
+pub struct PresolvePipe {}
+impl Default for PresolvePipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresolvePipe {
+    pub fn new() -> PresolvePipe {
+        PresolvePipe {}
+    }
+}
+impl Pipeable for PresolvePipe {
+    fn pipe(&self, data: &mut PipeableData, _: &PipeContext) -> Result<PipeableData, PipeError> {
+        let (model, removed) = presolve_bounds(data.as_model()?);
+        Ok(PipeableData::PresolvedModel(PresolvedModel::new(
+            model, removed,
+        )))
+    }
+}
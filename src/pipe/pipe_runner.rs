@@ -16,6 +16,7 @@ impl PipeRunner {
     /// # Arguments
     /// * `data` - The data to be piped to the first pipe
     /// * `context` - The context that will be available to all pipes
+    #[allow(clippy::result_large_err)]
     pub fn run(
         &self,
         data: PipeableData,
@@ -9,14 +9,16 @@ use {
     crate::parser::pre_model::PreModel,
     crate::pipe::pipe_definitions::{PipeDataType, PipeError, Pipeable, PipeableData},
     crate::pipe::pipe_executors::{
-        AutoSolverPipe, BinarySolverPipe, CompilerPipe, IntegerBinarySolverPipe, LinearModelPipe,
-        MILPSolverPipe, ModelPipe, Pipes, PreModelPipe, RealSolver, StandardLinearModelPipe,
+        AutoSolverPipe, BinarySolverPipe, CompilerPipe, IntegerBinarySolverPipe, LatexPipe,
+        LinearModelPipe, LpFormatPipe, MILPSolverPipe, ModelPipe, MpsFormatPipe,
+        OptimalTableauPipe, Pipes, PreModelPipe, PresolvePipe, RealSolver, StandardLinearModelPipe,
         StepByStepSimplexPipe, TableauPipe,
     },
     crate::pipe::pipe_runner::PipeRunner,
     crate::pipe::PipeContext,
     crate::solvers::{OptimalTableau, OptimalTableauWithSteps, Tableau},
     crate::transformers::LinearModel,
+    crate::transformers::PresolvedModel,
     crate::transformers::StandardLinearModel,
     crate::RoocParser,
     crate::{Constant, Primitive},
@@ -48,6 +50,11 @@ impl WasmPipeRunner {
                     Pipes::IntegerBinarySolverPipe => Box::new(IntegerBinarySolverPipe::new()),
                     Pipes::MILPSolverPipe => Box::new(MILPSolverPipe::new()),
                     Pipes::AutoSolverPipe => Box::new(AutoSolverPipe::new()),
+                    Pipes::LpFormatPipe => Box::new(LpFormatPipe::new()),
+                    Pipes::MpsFormatPipe => Box::new(MpsFormatPipe::new()),
+                    Pipes::OptimalTableauPipe => Box::new(OptimalTableauPipe::new()),
+                    Pipes::LatexPipe => Box::new(LatexPipe::new()),
+                    Pipes::PresolvePipe => Box::new(PresolvePipe::new()),
                 };
                 item
             })
@@ -159,6 +166,11 @@ impl WasmPipableData {
             .to_model()
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
+    pub fn to_presolved_model(self) -> Result<PresolvedModel, JsValue> {
+        self.data
+            .to_presolved_model()
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
     pub fn to_linear_model(self) -> Result<LinearModel, JsValue> {
         self.data
             .to_linear_model()
@@ -1,4 +1,4 @@
-use crate::math::{BinOp, UnOp};
+use crate::math::{float_eq, float_ge, float_gt, float_le, float_lt, float_ne, BinOp, UnOp};
 use crate::parser::model_transformer::TransformError;
 
 use super::{
@@ -8,6 +8,144 @@ use super::{
 
 /* --------- ApplyOp --------- */
 
+/// Applies a comparison between two numbers, the other operators are not supported
+fn compare_numbers(
+    op: BinOp,
+    lhs: f64,
+    rhs: f64,
+    kind: PrimitiveKind,
+) -> Result<Primitive, OperatorError> {
+    let result = match op {
+        BinOp::Equal => float_eq(lhs, rhs),
+        BinOp::NotEqual => float_ne(lhs, rhs),
+        BinOp::Less => float_lt(lhs, rhs),
+        BinOp::LessOrEqual => float_le(lhs, rhs),
+        BinOp::Greater => float_gt(lhs, rhs),
+        BinOp::GreaterOrEqual => float_ge(lhs, rhs),
+        BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod | BinOp::And | BinOp::Or => {
+            return Err(OperatorError::unsupported_bin_operation(op, kind))
+        }
+    };
+    Ok(Primitive::Boolean(result))
+}
+
+/// Modulo that always returns a non negative value (Euclidean remainder), also for
+/// a negative divisor, so that it can be used for cyclic indexing
+fn float_mod(lhs: f64, rhs: f64) -> Result<Primitive, OperatorError> {
+    if rhs == 0.0 {
+        return Err(OperatorError::division_by_zero(BinOp::Mod));
+    }
+    Ok(Primitive::Number(lhs.rem_euclid(rhs)))
+}
+
+/// Modulo between two integers, computed with a wider type so that operands that don't
+/// fit in an i64, like big positive integers, are not truncated
+fn integer_mod(lhs: i128, rhs: i128) -> Result<Primitive, OperatorError> {
+    let result = lhs
+        .checked_rem_euclid(rhs)
+        .ok_or(OperatorError::division_by_zero(BinOp::Mod))?;
+    i64::try_from(result)
+        .map(Primitive::Integer)
+        .map_err(|_| OperatorError::integer_overflow(BinOp::Mod, lhs, rhs))
+}
+
+/// Negates an integer, the overflow is reported as the subtraction `0 - value`
+fn integer_neg(value: i128) -> Result<Primitive, OperatorError> {
+    value
+        .checked_neg()
+        .and_then(|v| i64::try_from(v).ok())
+        .map(Primitive::Integer)
+        .ok_or(OperatorError::integer_overflow(BinOp::Sub, 0, value))
+}
+
+fn positive_integer_mod(lhs: u64, rhs: u64) -> Result<Primitive, OperatorError> {
+    lhs.checked_rem(rhs)
+        .map(Primitive::PositiveInteger)
+        .ok_or(OperatorError::division_by_zero(BinOp::Mod))
+}
+
+/// Applies an arithmetic operator between two integers, erroring instead of wrapping
+/// if the result does not fit in an i64
+fn integer_arithmetic(lhs: i128, op: BinOp, rhs: i128) -> Result<Primitive, OperatorError> {
+    let result = match op {
+        BinOp::Add => lhs.checked_add(rhs),
+        BinOp::Sub => lhs.checked_sub(rhs),
+        BinOp::Mul => lhs.checked_mul(rhs),
+        _ => {
+            return Err(OperatorError::unsupported_bin_operation(
+                op,
+                PrimitiveKind::Integer,
+            ))
+        }
+    };
+    result
+        .and_then(|r| i64::try_from(r).ok())
+        .map(Primitive::Integer)
+        .ok_or(OperatorError::integer_overflow(op, lhs, rhs))
+}
+
+/// Same as `integer_arithmetic`, but additions and multiplications stay positive integers,
+/// erroring if the result does not fit in an u64
+fn positive_integer_arithmetic(lhs: u64, op: BinOp, rhs: u64) -> Result<Primitive, OperatorError> {
+    let result = match op {
+        BinOp::Add => lhs.checked_add(rhs),
+        BinOp::Mul => lhs.checked_mul(rhs),
+        _ => return integer_arithmetic(lhs as i128, op, rhs as i128),
+    };
+    result
+        .map(Primitive::PositiveInteger)
+        .ok_or(OperatorError::integer_overflow(
+            op,
+            lhs as i128,
+            rhs as i128,
+        ))
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Builds a rational in lowest terms with a positive denominator, falling back
+/// to a float if the reduced fraction does not fit in an i64
+fn make_rational(num: i128, den: i128, op: BinOp) -> Result<Primitive, OperatorError> {
+    if den == 0 {
+        return Err(OperatorError::division_by_zero(op));
+    }
+    let g = gcd(num, den);
+    let (num, den) = (den.signum() * num / g, den.signum() * den / g);
+    match (i64::try_from(num), i64::try_from(den)) {
+        (Ok(num), Ok(den)) => Ok(Primitive::Rational(num, den)),
+        _ => Ok(Primitive::Number(num as f64 / den as f64)),
+    }
+}
+
+fn rational_op(lhs: (i64, i64), op: BinOp, rhs: (i64, i64)) -> Result<Primitive, OperatorError> {
+    let (a, b) = (lhs.0 as i128, lhs.1 as i128);
+    let (c, d) = (rhs.0 as i128, rhs.1 as i128);
+    match op {
+        BinOp::Add => make_rational(a * d + c * b, b * d, op),
+        BinOp::Sub => make_rational(a * d - c * b, b * d, op),
+        BinOp::Mul => make_rational(a * c, b * d, op),
+        BinOp::Div => make_rational(a * d, b * c, op),
+        BinOp::Mod => {
+            if c == 0 {
+                return Err(OperatorError::division_by_zero(op));
+            }
+            make_rational((a * d).rem_euclid(c * b), b * d, op)
+        }
+        _ => compare_numbers(
+            op,
+            lhs.0 as f64 / lhs.1 as f64,
+            rhs.0 as f64 / rhs.1 as f64,
+            PrimitiveKind::Rational,
+        ),
+    }
+}
+
 impl ApplyOp for String {
     type TargetType = PrimitiveKind;
     type Target = Primitive;
@@ -16,6 +154,8 @@ impl ApplyOp for String {
         match to {
             Primitive::String(s) => match op {
                 BinOp::Add => Ok(Primitive::String(format!("{}{}", self, s))),
+                BinOp::Equal => Ok(Primitive::Boolean(self == s)),
+                BinOp::NotEqual => Ok(Primitive::Boolean(self != s)),
                 _ => Err(OperatorError::unsupported_bin_operation(
                     op,
                     PrimitiveKind::String,
@@ -34,8 +174,9 @@ impl ApplyOp for String {
             PrimitiveKind::String,
         ))
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(to, PrimitiveKind::String)
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        matches!(op, BinOp::Add | BinOp::Equal | BinOp::NotEqual)
+            && matches!(to, PrimitiveKind::String)
     }
     fn can_apply_unary_op(_op: UnOp) -> bool {
         false
@@ -46,23 +187,40 @@ impl ApplyOp for bool {
     type TargetType = PrimitiveKind;
     type Target = Primitive;
     type Error = OperatorError;
-    fn apply_binary_op(&self, op: BinOp, _to: &Primitive) -> Result<Primitive, OperatorError> {
-        Err(OperatorError::unsupported_bin_operation(
-            op,
-            PrimitiveKind::Boolean,
-        ))
+    fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
+        match to {
+            Primitive::Boolean(b) => match op {
+                BinOp::And => Ok(Primitive::Boolean(*self && *b)),
+                BinOp::Or => Ok(Primitive::Boolean(*self || *b)),
+                BinOp::Equal => Ok(Primitive::Boolean(*self == *b)),
+                BinOp::NotEqual => Ok(Primitive::Boolean(*self != *b)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Boolean,
+                )),
+            },
+            _ => Err(OperatorError::incompatible_type(
+                op,
+                PrimitiveKind::Boolean,
+                to.get_type(),
+            )),
+        }
     }
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
-        Err(OperatorError::unsupported_un_operation(
-            op,
-            PrimitiveKind::Boolean,
-        ))
+        match op {
+            UnOp::Not => Ok(Primitive::Boolean(!self)),
+            UnOp::Neg => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Boolean,
+            )),
+        }
     }
-    fn can_apply_binary_op(_op: BinOp, _to: Self::TargetType) -> bool {
-        false
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        matches!(op, BinOp::And | BinOp::Or | BinOp::Equal | BinOp::NotEqual)
+            && matches!(to, PrimitiveKind::Boolean)
     }
-    fn can_apply_unary_op(_op: UnOp) -> bool {
-        false
+    fn can_apply_unary_op(op: UnOp) -> bool {
+        matches!(op, UnOp::Not)
     }
 }
 
@@ -77,24 +235,35 @@ impl ApplyOp for f64 {
                 BinOp::Sub => Ok(Primitive::Number(self - n)),
                 BinOp::Mul => Ok(Primitive::Number(self * n)),
                 BinOp::Div => Ok(Primitive::Number(self / n)),
+                BinOp::Mod => float_mod(*self, *n),
+                _ => compare_numbers(op, *self, *n, PrimitiveKind::Number),
             },
             Primitive::Integer(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(*self + (*n as f64))),
                 BinOp::Sub => Ok(Primitive::Number(*self - (*n as f64))),
                 BinOp::Mul => Ok(Primitive::Number(*self * (*n as f64))),
                 BinOp::Div => Ok(Primitive::Number(*self / (*n as f64))),
+                BinOp::Mod => float_mod(*self, *n as f64),
+                _ => compare_numbers(op, *self, *n as f64, PrimitiveKind::Number),
             },
             Primitive::PositiveInteger(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(*self + (*n as f64))),
                 BinOp::Sub => Ok(Primitive::Number(*self - (*n as f64))),
                 BinOp::Mul => Ok(Primitive::Number(*self * (*n as f64))),
                 BinOp::Div => Ok(Primitive::Number(*self / (*n as f64))),
+                BinOp::Mod => float_mod(*self, *n as f64),
+                _ => compare_numbers(op, *self, *n as f64, PrimitiveKind::Number),
             },
+            Primitive::Rational(n, d) => {
+                self.apply_binary_op(op, &Primitive::Number(*n as f64 / *d as f64))
+            }
             Primitive::Boolean(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(*self + (*n as i8 as f64))),
                 BinOp::Sub => Ok(Primitive::Number(*self - (*n as i8 as f64))),
                 BinOp::Mul => Ok(Primitive::Number(*self * (*n as i8 as f64))),
                 BinOp::Div => Ok(Primitive::Number(*self / (*n as i8 as f64))),
+                BinOp::Mod => float_mod(*self, *n as i8 as f64),
+                _ => compare_numbers(op, *self, *n as i8 as f64, PrimitiveKind::Number),
             },
             _ => Err(OperatorError::incompatible_type(
                 op,
@@ -106,16 +275,22 @@ impl ApplyOp for f64 {
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
         match op {
             UnOp::Neg => Ok(Primitive::Number(-self)),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Number,
+            )),
         }
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(
-            to,
-            PrimitiveKind::Number
-                | PrimitiveKind::Integer
-                | PrimitiveKind::PositiveInteger
-                | PrimitiveKind::Boolean
-        )
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Rational
+                    | PrimitiveKind::Boolean
+            )
     }
     fn can_apply_unary_op(op: UnOp) -> bool {
         matches!(op, UnOp::Neg)
@@ -129,28 +304,37 @@ impl ApplyOp for i64 {
     fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
         match to {
             Primitive::Integer(n) => match op {
-                BinOp::Add => Ok(Primitive::Integer(self + n)),
-                BinOp::Sub => Ok(Primitive::Integer(self - n)),
-                BinOp::Mul => Ok(Primitive::Integer(self * n)),
+                BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                    integer_arithmetic(*self as i128, op, *n as i128)
+                }
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                BinOp::Mod => integer_mod(*self as i128, *n as i128),
+                _ => compare_numbers(op, *self as f64, *n as f64, PrimitiveKind::Integer),
             },
             Primitive::Number(n) => match op {
                 BinOp::Add => Ok(Primitive::Number((*self as f64) + n)),
                 BinOp::Sub => Ok(Primitive::Number((*self as f64) - n)),
                 BinOp::Mul => Ok(Primitive::Number((*self as f64) * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / n)),
+                BinOp::Mod => float_mod(*self as f64, *n),
+                _ => compare_numbers(op, *self as f64, *n, PrimitiveKind::Integer),
             },
             Primitive::PositiveInteger(n) => match op {
-                BinOp::Add => Ok(Primitive::Integer(*self + (*n as i64))),
-                BinOp::Sub => Ok(Primitive::Integer(*self - (*n as i64))),
-                BinOp::Mul => Ok(Primitive::Integer(*self * (*n as i64))),
+                BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                    integer_arithmetic(*self as i128, op, *n as i128)
+                }
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                BinOp::Mod => integer_mod(*self as i128, *n as i128),
+                _ => compare_numbers(op, *self as f64, *n as f64, PrimitiveKind::Integer),
             },
+            Primitive::Rational(n, d) => rational_op((*self, 1), op, (*n, *d)),
             Primitive::Boolean(n) => match op {
-                BinOp::Add => Ok(Primitive::Integer(*self + (*n as i64))),
-                BinOp::Sub => Ok(Primitive::Integer(*self - (*n as i64))),
-                BinOp::Mul => Ok(Primitive::Integer(*self)),
-                BinOp::Div => Ok(Primitive::Integer(*self)),
+                BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                    integer_arithmetic(*self as i128, op, *n as i128)
+                }
+                BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as i8 as f64))),
+                BinOp::Mod => integer_mod(*self as i128, *n as i128),
+                _ => compare_numbers(op, *self as f64, *n as i8 as f64, PrimitiveKind::Integer),
             },
             _ => Err(OperatorError::incompatible_type(
                 op,
@@ -161,17 +345,23 @@ impl ApplyOp for i64 {
     }
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
         match op {
-            UnOp::Neg => Ok(Primitive::Integer(-self)),
+            UnOp::Neg => integer_neg(*self as i128),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Integer,
+            )),
         }
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(
-            to,
-            PrimitiveKind::Number
-                | PrimitiveKind::Integer
-                | PrimitiveKind::PositiveInteger
-                | PrimitiveKind::Boolean
-        )
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Rational
+                    | PrimitiveKind::Boolean
+            )
     }
     fn can_apply_unary_op(op: UnOp) -> bool {
         matches!(op, UnOp::Neg)
@@ -185,28 +375,43 @@ impl ApplyOp for u64 {
     fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
         match to {
             Primitive::PositiveInteger(n) => match op {
-                BinOp::Add => Ok(Primitive::PositiveInteger(self + n)),
-                BinOp::Sub => Ok(Primitive::Integer((*self as i64) - (*n as i64))),
-                BinOp::Mul => Ok(Primitive::PositiveInteger(self * n)),
+                BinOp::Add | BinOp::Sub | BinOp::Mul => positive_integer_arithmetic(*self, op, *n),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                BinOp::Mod => positive_integer_mod(*self, *n),
+                _ => compare_numbers(op, *self as f64, *n as f64, PrimitiveKind::PositiveInteger),
             },
             Primitive::Integer(n) => match op {
-                BinOp::Add => Ok(Primitive::Integer((*self as i64) + n)),
-                BinOp::Sub => Ok(Primitive::Integer((*self as i64) - n)),
-                BinOp::Mul => Ok(Primitive::Integer((*self as i64) * n)),
+                BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                    integer_arithmetic(*self as i128, op, *n as i128)
+                }
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                BinOp::Mod => integer_mod(*self as i128, *n as i128),
+                _ => compare_numbers(op, *self as f64, *n as f64, PrimitiveKind::PositiveInteger),
             },
             Primitive::Number(n) => match op {
                 BinOp::Add => Ok(Primitive::Number((*self as f64) + n)),
                 BinOp::Sub => Ok(Primitive::Number((*self as f64) - n)),
                 BinOp::Mul => Ok(Primitive::Number((*self as f64) * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / n)),
+                BinOp::Mod => float_mod(*self as f64, *n),
+                _ => compare_numbers(op, *self as f64, *n, PrimitiveKind::PositiveInteger),
+            },
+            Primitive::Rational(n, d) => match i64::try_from(*self) {
+                Ok(v) => rational_op((v, 1), op, (*n, *d)),
+                Err(_) => (*self as f64).apply_binary_op(op, to),
             },
             Primitive::Boolean(n) => match op {
-                BinOp::Add => Ok(Primitive::PositiveInteger(*self + (*n as u64))),
-                BinOp::Sub => Ok(Primitive::Integer((*self as i64) - (*n as i64))),
-                BinOp::Mul => Ok(Primitive::PositiveInteger(*self)),
-                BinOp::Div => Ok(Primitive::PositiveInteger(*self)),
+                BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                    positive_integer_arithmetic(*self, op, *n as u64)
+                }
+                BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as i8 as f64))),
+                BinOp::Mod => positive_integer_mod(*self, *n as u64),
+                _ => compare_numbers(
+                    op,
+                    *self as f64,
+                    *n as i8 as f64,
+                    PrimitiveKind::PositiveInteger,
+                ),
             },
             _ => Err(OperatorError::incompatible_type(
                 op,
@@ -217,17 +422,69 @@ impl ApplyOp for u64 {
     }
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
         match op {
-            UnOp::Neg => Ok(Primitive::Integer(-(*self as i64))),
+            UnOp::Neg => integer_neg(*self as i128),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::PositiveInteger,
+            )),
+        }
+    }
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Rational
+                    | PrimitiveKind::Boolean
+            )
+    }
+    fn can_apply_unary_op(op: UnOp) -> bool {
+        matches!(op, UnOp::Neg)
+    }
+}
+
+impl ApplyOp for (i64, i64) {
+    type TargetType = PrimitiveKind;
+    type Target = Primitive;
+    type Error = OperatorError;
+    fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
+        match to {
+            Primitive::Rational(n, d) => rational_op(*self, op, (*n, *d)),
+            Primitive::Integer(n) => rational_op(*self, op, (*n, 1)),
+            Primitive::PositiveInteger(n) => match i64::try_from(*n) {
+                Ok(n) => rational_op(*self, op, (n, 1)),
+                Err(_) => (self.0 as f64 / self.1 as f64).apply_binary_op(op, to),
+            },
+            Primitive::Boolean(n) => rational_op(*self, op, (*n as i64, 1)),
+            Primitive::Number(_) => (self.0 as f64 / self.1 as f64).apply_binary_op(op, to),
+            _ => Err(OperatorError::incompatible_type(
+                op,
+                PrimitiveKind::Rational,
+                to.get_type(),
+            )),
+        }
+    }
+    fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
+        match op {
+            UnOp::Neg => Ok(Primitive::Rational(-self.0, self.1)),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Rational,
+            )),
         }
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(
-            to,
-            PrimitiveKind::Number
-                | PrimitiveKind::Integer
-                | PrimitiveKind::PositiveInteger
-                | PrimitiveKind::Boolean
-        )
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Rational
+                    | PrimitiveKind::Boolean
+            )
     }
     fn can_apply_unary_op(op: UnOp) -> bool {
         matches!(op, UnOp::Neg)
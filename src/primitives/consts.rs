@@ -2,6 +2,7 @@ use core::fmt;
 
 #[allow(unused_imports)]
 use crate::prelude::*;
+use indexmap::IndexMap;
 use serde::Serialize;
 
 use super::primitive::{Primitive, PrimitiveKind};
@@ -63,6 +64,89 @@ impl Constant {
     }
 }
 
+/// Checks that the constants can be evaluated in order of declaration, where each constant
+/// can only refer to the constants declared before it.
+///
+/// # Returns
+/// * `Ok(())` - If every constant only refers to the ones before it
+/// * `Err(TransformError)` - If the definitions form a cycle, like `A = B + 1` and `B = A + 1`,
+///   or if a constant refers to one that is declared after it
+pub(crate) fn check_constant_definitions(constants: &[Constant]) -> Result<(), TransformError> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        Pending,
+        InProgress,
+        Done,
+    }
+    let mut by_name: IndexMap<&str, usize> = IndexMap::new();
+    for (i, constant) in constants.iter().enumerate() {
+        by_name.entry(constant.name.value()).or_insert(i);
+    }
+    let dependencies = constants
+        .iter()
+        .map(|c| {
+            c.value
+                .referenced_names()
+                .iter()
+                .filter_map(|name| by_name.get(name.as_str()).copied())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    //cycles are looked for first, as they can't be fixed by reordering the declarations
+    let mut state = vec![Visit::Pending; constants.len()];
+    for start in 0..constants.len() {
+        if state[start] != Visit::Pending {
+            continue;
+        }
+        //the path from the start to the current constant, with the next dependency to visit
+        let mut path = vec![(start, 0)];
+        state[start] = Visit::InProgress;
+        while let Some((current, next)) = path.last_mut() {
+            let current = *current;
+            match dependencies[current].get(*next) {
+                Some(&dependency) => {
+                    *next += 1;
+                    match state[dependency] {
+                        Visit::Done => {}
+                        Visit::Pending => {
+                            state[dependency] = Visit::InProgress;
+                            path.push((dependency, 0));
+                        }
+                        Visit::InProgress => {
+                            let cycle_start = path.iter().position(|(i, _)| *i == dependency);
+                            let cycle = path[cycle_start.unwrap_or(0)..]
+                                .iter()
+                                .map(|(i, _)| constants[*i].name.value().as_str())
+                                .chain(std::iter::once(constants[dependency].name.value().as_str()))
+                                .collect::<Vec<_>>();
+                            return Err(TransformError::Other(format!(
+                                "cyclic constant definition: {}",
+                                cycle.join(" -> ")
+                            ))
+                            .add_span(constants[dependency].name.span()));
+                        }
+                    }
+                }
+                None => {
+                    state[current] = Visit::Done;
+                    path.pop();
+                }
+            }
+        }
+    }
+    for (i, constant) in constants.iter().enumerate() {
+        if let Some(&later) = dependencies[i].iter().find(|d| **d > i) {
+            return Err(TransformError::Other(format!(
+                "constant \"{}\" uses \"{}\" before it is defined",
+                constant.name.value(),
+                constants[later].name.value()
+            ))
+            .add_span(constant.name.span()));
+        }
+    }
+    Ok(())
+}
+
 impl WithType for Constant {
     fn get_type(
         &self,
@@ -81,6 +165,10 @@ impl TypeCheckable for Constant {
     ) {
         self.value.populate_token_type_map(context, fn_context);
         let value = self.value.get_type(context, fn_context);
+        let value = match context.assign_constant(&self.name, &self.value, fn_context) {
+            Ok(Some(kind)) if value.has_unknown() => kind,
+            _ => value,
+        };
         context.add_token_type_or_undefined(
             value,
             self.name.span().clone(),
@@ -94,6 +182,11 @@ impl TypeCheckable for Constant {
     ) -> Result<(), TransformError> {
         self.value.type_check(context, fn_context)?;
         let value = self.value.get_type(context, fn_context);
+        //the static type is refined with the type of the value when it is not fully known
+        let value = match context.assign_constant(&self.name, &self.value, fn_context)? {
+            Some(kind) if value.has_unknown() => kind,
+            _ => value,
+        };
         context.add_token_type(
             value,
             self.name.span().clone(),
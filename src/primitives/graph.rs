@@ -1,7 +1,7 @@
 #[allow(unused_imports)]
 use crate::prelude::*;
 use core::fmt;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
 
 use crate::math::{BinOp, UnOp};
@@ -61,6 +61,7 @@ impl fmt::Display for GraphEdge {
 pub struct GraphNode {
     name: String,
     edges: IndexMap<String, GraphEdge>,
+    weight: Option<f64>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -69,7 +70,8 @@ pub struct GraphNode {
 const IGraphNode: &'static str = r#"
 export type SerializedGraphNode = {
     name: string,
-    edges: { [key: string]: SerializedGraphEdge }
+    edges: { [key: string]: SerializedGraphEdge },
+    weight?: number
 }
 "#;
 
@@ -79,7 +81,17 @@ impl GraphNode {
             .into_iter()
             .map(|edge| (edge.to.clone(), edge))
             .collect::<IndexMap<String, GraphEdge>>();
-        Self { name, edges }
+        Self {
+            name,
+            edges,
+            weight: None,
+        }
+    }
+
+    /// Sets the weight of the node, like the capacity of a facility.
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = Some(weight);
+        self
     }
     pub fn to_edges(self) -> Vec<GraphEdge> {
         self.edges.into_values().collect()
@@ -87,6 +99,9 @@ impl GraphNode {
     pub fn name(&self) -> &String {
         &self.name
     }
+    pub fn weight(&self) -> Option<f64> {
+        self.weight
+    }
 }
 
 impl ToLatex for GraphNode {
@@ -97,10 +112,14 @@ impl ToLatex for GraphNode {
             .map(|edge| edge.to_latex())
             .collect::<Vec<_>>()
             .join(",\\ ");
+        let name = match self.weight {
+            Some(w) => format!("{}(w={})", self.name, w),
+            None => self.name.clone(),
+        };
         if edges.is_empty() {
-            self.name.clone()
+            name
         } else {
-            format!("{}\\to\\left\\{{{}\\right\\}}", self.name, edges)
+            format!("{}\\to\\left\\{{{}\\right\\}}", name, edges)
         }
     }
 }
@@ -113,10 +132,14 @@ impl fmt::Display for GraphNode {
             .map(|edge| edge.to_string())
             .collect::<Vec<_>>()
             .join(", ");
+        let name = match self.weight {
+            Some(w) => format!("{}: w={}", self.name, w),
+            None => self.name.clone(),
+        };
         if edges.is_empty() {
-            return f.write_str(&self.name);
+            return f.write_str(&name);
         }
-        write!(f, "{} -> [ {} ]", self.name, edges)
+        write!(f, "{} -> [ {} ]", name, edges)
     }
 }
 
@@ -180,6 +203,348 @@ impl Graph {
             ))),
         }
     }
+
+    /// Gets the weight of a node.
+    ///
+    /// # Arguments
+    /// * `node_name` - Name of the node
+    ///
+    /// # Returns
+    /// * `Ok(f64)` - The weight of the node
+    /// * `Err(TransformError)` - If the node is not in the graph or has no weight
+    pub fn node_weight(&self, node_name: &str) -> Result<f64, TransformError> {
+        let node = self.vertices.iter().find(|n| n.name == node_name);
+        match node {
+            Some(node) => node
+                .weight
+                .ok_or_else(|| TransformError::Other(format!("node {} has no weight", node_name))),
+            None => Err(TransformError::Other(format!(
+                "node {} not found in graph",
+                node_name
+            ))),
+        }
+    }
+
+    /// Counts the edges originating from a node.
+    ///
+    /// # Arguments
+    /// * `node_name` - Name of the node
+    pub fn out_degree(&self, node_name: &str) -> Result<usize, TransformError> {
+        Ok(self.neighbour_of(node_name)?.len())
+    }
+
+    /// Counts the edges that target a node.
+    ///
+    /// # Arguments
+    /// * `node_name` - Name of the node
+    pub fn in_degree(&self, node_name: &str) -> Result<usize, TransformError> {
+        if !self.vertices.iter().any(|n| n.name == node_name) {
+            return Err(TransformError::Other(format!(
+                "node {} not found in graph",
+                node_name
+            )));
+        }
+        Ok(self
+            .vertices
+            .iter()
+            .flat_map(|n| n.edges.values())
+            .filter(|e| e.to == node_name)
+            .count())
+    }
+
+    /// Finds the shortest path between two nodes using Dijkstra's algorithm.
+    ///
+    /// Every edge of the graph must have a non-negative cost. If `to` cannot be
+    /// reached from `from`, an empty path is returned.
+    ///
+    /// # Arguments
+    /// * `from` - Name of the starting node
+    /// * `to` - Name of the destination node
+    ///
+    /// # Returns
+    /// * `Ok(Vec<GraphEdge>)` - The edges of the path, in order
+    /// * `Err(TransformError)` - If a node is missing or an edge has no valid cost
+    pub fn shortest_path(&self, from: &str, to: &str) -> Result<Vec<GraphEdge>, TransformError> {
+        for name in [from, to] {
+            if !self.vertices.iter().any(|n| n.name == name) {
+                return Err(TransformError::Other(format!(
+                    "node {} not found in graph",
+                    name
+                )));
+            }
+        }
+        for edge in self.vertices.iter().flat_map(|n| n.edges.values()) {
+            match edge.weight {
+                Some(w) if w >= 0.0 => {}
+                Some(w) => {
+                    return Err(TransformError::Other(format!(
+                        "edge {} -> {} has negative cost {}, cannot compute shortest path",
+                        edge.from, edge.to, w
+                    )))
+                }
+                None => {
+                    return Err(TransformError::Other(format!(
+                        "edge {} -> {} has no cost, cannot compute shortest path",
+                        edge.from, edge.to
+                    )))
+                }
+            }
+        }
+        let mut distances: IndexMap<&str, f64> = self
+            .vertices
+            .iter()
+            .map(|n| (n.name.as_str(), f64::INFINITY))
+            .collect();
+        let mut previous: IndexMap<&str, &GraphEdge> = IndexMap::new();
+        let mut visited: Vec<&str> = Vec::new();
+        distances.insert(from, 0.0);
+        loop {
+            let current = distances
+                .iter()
+                .filter(|(name, d)| !visited.contains(name) && d.is_finite())
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(name, d)| (*name, *d));
+            let (current, distance) = match current {
+                Some(c) => c,
+                None => break,
+            };
+            if current == to {
+                break;
+            }
+            visited.push(current);
+            let node = self.vertices.iter().find(|n| n.name == current).unwrap();
+            for edge in node.edges.values() {
+                let next = distance + edge.weight.unwrap_or(0.0);
+                match distances.get_mut(edge.to.as_str()) {
+                    Some(d) if next < *d => {
+                        *d = next;
+                        previous.insert(edge.to.as_str(), edge);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let mut path = Vec::new();
+        let mut current = to;
+        while let Some(edge) = previous.get(current) {
+            path.push((*edge).clone());
+            current = edge.from.as_str();
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    /// Finds the nodes that can be reached from a node by following at most `k` edges.
+    ///
+    /// The search is a breadth first visit over the outgoing edges, the starting node
+    /// is not part of the result unless it can be reached again through a cycle.
+    ///
+    /// # Arguments
+    /// * `node_name` - Name of the starting node
+    /// * `k` - Maximum number of edges to follow
+    ///
+    /// # Returns
+    /// * `Ok(Vec<GraphNode>)` - The reached nodes, in the order they were visited
+    /// * `Err(TransformError)` - If the node is not in the graph
+    pub fn neighbours_within(
+        &self,
+        node_name: &str,
+        k: usize,
+    ) -> Result<Vec<GraphNode>, TransformError> {
+        if !self.vertices.iter().any(|n| n.name == node_name) {
+            return Err(TransformError::Other(format!(
+                "node {} not found in graph",
+                node_name
+            )));
+        }
+        let mut visited: IndexSet<&str> = IndexSet::new();
+        let mut frontier: Vec<&str> = vec![node_name];
+        for _ in 0..k {
+            let mut next = Vec::new();
+            for name in frontier {
+                let node = match self.vertices.iter().find(|n| n.name == name) {
+                    Some(node) => node,
+                    None => continue,
+                };
+                for edge in node.edges.values() {
+                    if visited.insert(edge.to.as_str()) {
+                        next.push(edge.to.as_str());
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        Ok(visited
+            .into_iter()
+            .filter_map(|name| self.vertices.iter().find(|n| n.name == name))
+            .cloned()
+            .collect())
+    }
+
+    /// Builds the subgraph induced by a set of nodes, keeping only the edges between them.
+    ///
+    /// The nodes keep the order in which they appear in the graph.
+    ///
+    /// # Arguments
+    /// * `node_names` - Names of the nodes to keep
+    ///
+    /// # Returns
+    /// * `Ok(Graph)` - The induced subgraph
+    /// * `Err(TransformError)` - If one of the nodes is not in the graph
+    pub fn subgraph(&self, node_names: &[String]) -> Result<Graph, TransformError> {
+        for name in node_names {
+            if !self.vertices.iter().any(|n| &n.name == name) {
+                return Err(TransformError::Other(format!(
+                    "node {} not found in graph",
+                    name
+                )));
+            }
+        }
+        let vertices = self
+            .vertices
+            .iter()
+            .filter(|n| node_names.contains(&n.name))
+            .map(|n| GraphNode {
+                name: n.name.clone(),
+                edges: n
+                    .edges
+                    .iter()
+                    .filter(|(to, _)| node_names.contains(to))
+                    .map(|(to, edge)| (to.clone(), edge.clone()))
+                    .collect(),
+                weight: n.weight,
+            })
+            .collect();
+        Ok(Graph::new(vertices))
+    }
+
+    /// Groups the nodes of the graph in connected components, treating every edge as undirected.
+    ///
+    /// Components are ordered by their first node, and the nodes of each component
+    /// keep the order in which they appear in the graph.
+    pub fn connected_components(&self) -> Vec<Vec<String>> {
+        let indexes: IndexMap<&str, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.name.as_str(), i))
+            .collect();
+        let mut parents: Vec<usize> = (0..self.vertices.len()).collect();
+        fn find(parents: &mut [usize], i: usize) -> usize {
+            let mut root = i;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            let mut current = i;
+            while parents[current] != root {
+                let next = parents[current];
+                parents[current] = root;
+                current = next;
+            }
+            root
+        }
+        for edge in self.vertices.iter().flat_map(|n| n.edges.values()) {
+            if let (Some(&from), Some(&to)) = (
+                indexes.get(edge.from.as_str()),
+                indexes.get(edge.to.as_str()),
+            ) {
+                let from = find(&mut parents, from);
+                let to = find(&mut parents, to);
+                //the smallest index is kept as root so components follow the node order
+                parents[from.max(to)] = from.min(to);
+            }
+        }
+        let mut components: IndexMap<usize, Vec<String>> = IndexMap::new();
+        for (i, node) in self.vertices.iter().enumerate() {
+            let root = find(&mut parents, i);
+            components.entry(root).or_default().push(node.name.clone());
+        }
+        components.into_values().collect()
+    }
+
+    /// Checks if every node of the graph can be reached from any other, treating every edge as undirected.
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
+
+    /// Builds the adjacency matrix of the graph, rows and columns follow the order of the nodes.
+    ///
+    /// # Arguments
+    /// * `use_costs` - If true, the entries are the costs of the edges, otherwise they are 0/1
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Vec<f64>>)` - The matrix, where `m[i][j]` describes the edge from node `i` to node `j`
+    /// * `Err(TransformError)` - If costs are requested and an edge has none
+    pub fn adjacency_matrix(&self, use_costs: bool) -> Result<Vec<Vec<f64>>, TransformError> {
+        let indexes: IndexMap<&str, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.name.as_str(), i))
+            .collect();
+        let mut matrix = vec![vec![0.0; self.vertices.len()]; self.vertices.len()];
+        for (i, node) in self.vertices.iter().enumerate() {
+            for edge in node.edges.values() {
+                let j = match indexes.get(edge.to.as_str()) {
+                    Some(j) => *j,
+                    None => continue,
+                };
+                matrix[i][j] = match (use_costs, edge.weight) {
+                    (false, _) => 1.0,
+                    (true, Some(w)) => w,
+                    (true, None) => {
+                        return Err(TransformError::Other(format!(
+                            "edge {} -> {} has no cost, cannot build the cost matrix",
+                            edge.from, edge.to
+                        )))
+                    }
+                };
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Renders the graph in the GraphViz DOT format.
+    ///
+    /// Every node is declared, then every edge is written as a directed edge,
+    /// labeled with its cost when it has one.
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec!["digraph {".to_string()];
+        for node in &self.vertices {
+            lines.push(format!("    {};", dot_id(&node.name)));
+        }
+        for edge in self.vertices.iter().flat_map(|n| n.edges.values()) {
+            match edge.weight {
+                Some(w) => lines.push(format!(
+                    "    {} -> {} [label=\"{}\"];",
+                    dot_id(&edge.from),
+                    dot_id(&edge.to),
+                    w
+                )),
+                None => lines.push(format!(
+                    "    {} -> {};",
+                    dot_id(&edge.from),
+                    dot_id(&edge.to)
+                )),
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn graph_to_dot(graph: JsValue) -> Result<String, JsValue> {
+    let graph: Graph = serde_wasm_bindgen::from_value(graph)?;
+    Ok(graph.to_dot())
 }
 
 //TODO decide if this is a nice enough representation
@@ -123,6 +123,26 @@ impl IterableKind {
             ),
         }
     }
+
+    /// Creates an empty iterable whose elements are of the given kind.
+    pub fn empty_of(kind: &PrimitiveKind) -> IterableKind {
+        match kind {
+            PrimitiveKind::Number => IterableKind::Numbers(vec![]),
+            PrimitiveKind::Integer => IterableKind::Integers(vec![]),
+            PrimitiveKind::PositiveInteger => IterableKind::PositiveIntegers(vec![]),
+            PrimitiveKind::String => IterableKind::Strings(vec![]),
+            PrimitiveKind::GraphEdge => IterableKind::Edges(vec![]),
+            PrimitiveKind::GraphNode => IterableKind::Nodes(vec![]),
+            PrimitiveKind::Graph => IterableKind::Graphs(vec![]),
+            PrimitiveKind::Tuple(_) => IterableKind::Tuples(vec![]),
+            PrimitiveKind::Boolean => IterableKind::Booleans(vec![]),
+            PrimitiveKind::Iterable(_) => IterableKind::Iterables(vec![]),
+            PrimitiveKind::Rational
+            | PrimitiveKind::Map(_)
+            | PrimitiveKind::Any
+            | PrimitiveKind::Undefined => IterableKind::Anys(vec![]),
+        }
+    }
     pub fn len(&self) -> usize {
         match self {
             IterableKind::Numbers(v) => v.len(),
@@ -178,6 +198,19 @@ impl IterableKind {
     /// * `Ok(Primitive)` - The value at the specified indexes
     /// * `Err(TransformError)` - If the indexes are out of bounds
     pub fn read(&self, indexes: Vec<usize>) -> Result<Primitive, TransformError> {
+        self.read_signed(indexes.into_iter().map(|i| i as i64).collect())
+    }
+
+    /// Reads a value from the iterable at the specified indexes, where negative
+    /// indexes count from the end of the iterable they are applied to.
+    ///
+    /// # Arguments
+    /// * `indexes` - Vector of signed indexes specifying the path to the desired element
+    ///
+    /// # Returns
+    /// * `Ok(Primitive)` - The value at the specified indexes
+    /// * `Err(TransformError)` - If the indexes are out of bounds
+    pub fn read_signed(&self, indexes: Vec<i64>) -> Result<Primitive, TransformError> {
         if indexes.is_empty() {
             return Ok(Primitive::Undefined);
         }
@@ -185,7 +218,18 @@ impl IterableKind {
         let mut current = self;
         let mut indexes = indexes;
         while !indexes.is_empty() {
-            let i = indexes.remove(0);
+            let signed = indexes.remove(0);
+            let len = current.len() as i64;
+            let i = match signed {
+                i if i < 0 && i + len >= 0 => (i + len) as usize,
+                i if i >= 0 => i as usize,
+                _ => {
+                    return Err(TransformError::OutOfBounds(format!(
+                        "cannot access index {} of {}",
+                        signed, self
+                    )));
+                }
+            };
             let ended = indexes.is_empty();
             if ended {
                 let val = match current {
@@ -221,18 +265,17 @@ impl IterableKind {
                 };
                 return Ok(val);
             } else {
-                match current {
-                    IterableKind::Iterables(v) => {
-                        if i < v.len() {
-                            current = &v[i];
-                        } else {
-                            return Err(TransformError::OutOfBounds(format!(
-                                "cannot access index {} of {}",
-                                i, self
-                            )));
-                        }
-                    }
-                    _ => {
+                let next = match current {
+                    IterableKind::Iterables(v) => v.get(i),
+                    IterableKind::Anys(v) => match v.get(i) {
+                        Some(Primitive::Iterable(inner)) => Some(inner),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match next {
+                    Some(next) => current = next,
+                    None => {
                         return Err(TransformError::OutOfBounds(format!(
                             "cannot access index {} of {}",
                             i, self
@@ -264,6 +307,20 @@ impl IterableKind {
         depth
     }
 
+    /// Returns the length of each dimension of the iterable, following the first element
+    /// of every nested iterable.
+    ///
+    /// For example `[[1, 2, 3], [4, 5, 6]]` has shape `[2, 3]`.
+    pub fn shape(&self) -> Vec<usize> {
+        let mut shape = vec![self.len()];
+        if let IterableKind::Iterables(v) = self {
+            if let Some(first) = v.first() {
+                shape.extend(first.shape());
+            }
+        }
+        shape
+    }
+
     /// Returns a string representation of the iterable with proper indentation.
     ///
     /// # Arguments
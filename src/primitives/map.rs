@@ -0,0 +1,140 @@
+use core::fmt;
+
+#[allow(unused_imports)]
+use crate::prelude::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::math::{BinOp, UnOp};
+use crate::traits::ToLatex;
+
+use super::{
+    primitive::{Primitive, PrimitiveKind},
+    primitive_traits::{ApplyOp, OperatorError},
+};
+
+/// A collection of primitive values keyed by strings, keeping the order of insertion.
+///
+/// Maps are used to store parameters associated to a name, like the cost of each node of a graph.
+///
+/// # Example
+/// ```rust
+/// use rooc::{Map, Primitive};
+///
+/// let map = Map::new(vec![
+///     ("a".to_string(), Primitive::Integer(1)),
+///     ("b".to_string(), Primitive::Integer(2)),
+/// ]);
+/// assert_eq!(map.get("b"), Some(&Primitive::Integer(2)));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Map(pub IndexMap<String, Primitive>);
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IMap: &'static str = r#"
+export type SerializedMap = Record<string, SerializedPrimitive>
+"#;
+
+impl Map {
+    pub fn new(entries: Vec<(String, Primitive)>) -> Self {
+        Self(entries.into_iter().collect())
+    }
+
+    /// Retrieves a reference to the value associated to the key.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the value to retrieve
+    ///
+    /// # Returns
+    /// `Some(&Primitive)` if the key exists, `None` otherwise
+    pub fn get(&self, key: &str) -> Option<&Primitive> {
+        self.0.get(key)
+    }
+
+    /// Returns the keys of the map, in insertion order.
+    pub fn keys(&self) -> Vec<String> {
+        self.0.keys().cloned().collect()
+    }
+
+    /// Returns the values of the map, in insertion order.
+    pub fn values(&self) -> Vec<Primitive> {
+        self.0.values().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the type of this map as a `PrimitiveKind`.
+    ///
+    /// # Returns
+    /// A `PrimitiveKind::Map` containing the type of the values
+    pub fn get_type(&self) -> PrimitiveKind {
+        PrimitiveKind::Map(Box::new(self.value_type()))
+    }
+
+    /// Returns the type of the values of the map, which is `Any` if they have different types.
+    pub fn value_type(&self) -> PrimitiveKind {
+        let mut types = self.0.values().map(|v| v.get_type());
+        match types.next() {
+            Some(first) if types.all(|t| t == first) => first,
+            _ => PrimitiveKind::Any,
+        }
+    }
+}
+
+impl ToLatex for Map {
+    fn to_latex(&self) -> String {
+        format!(
+            "\\{{{}\\}}",
+            self.0
+                .iter()
+                .map(|(k, v)| format!("{}: {}", k.to_latex(), v.to_latex()))
+                .collect::<Vec<_>>()
+                .join(",\\ ")
+        )
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("{}");
+        }
+        let s = format!(
+            "{{ {} }}",
+            self.0
+                .iter()
+                .map(|(k, v)| format!("\"{}\": {}", k, v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        f.write_str(&s)
+    }
+}
+
+impl ApplyOp for Map {
+    type TargetType = PrimitiveKind;
+    type Target = Primitive;
+    type Error = OperatorError;
+    fn apply_binary_op(&self, op: BinOp, _to: &Primitive) -> Result<Primitive, OperatorError> {
+        Err(OperatorError::unsupported_bin_operation(
+            op,
+            self.get_type(),
+        ))
+    }
+    fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
+        Err(OperatorError::unsupported_un_operation(op, self.get_type()))
+    }
+    fn can_apply_binary_op(_: BinOp, _: Self::TargetType) -> bool {
+        false
+    }
+    fn can_apply_unary_op(_: UnOp) -> bool {
+        false
+    }
+}
@@ -2,6 +2,7 @@ pub mod builtin_primitive_traits_impl;
 pub mod consts;
 pub mod graph;
 pub mod iterable;
+pub mod map;
 pub mod primitive;
 pub mod primitive_traits;
 pub mod tuple;
@@ -11,6 +12,7 @@ pub use builtin_primitive_traits_impl::*;
 pub use consts::*;
 pub use graph::*;
 pub use iterable::*;
+pub use map::*;
 pub use primitive::*;
 pub use primitive_traits::*;
 pub use tuple::*;
@@ -8,8 +8,10 @@ use serde::{Deserialize, Serialize};
 use super::{
     graph::{Graph, GraphEdge, GraphNode},
     iterable::IterableKind,
+    map::Map,
     tuple::Tuple,
 };
+use crate::iterable_utils::flatten_primitive_array_values;
 use crate::math::{float_lt, float_ne};
 use crate::parser::model_transformer::TransformError;
 use crate::traits::ToLatex;
@@ -42,6 +44,8 @@ pub enum Primitive {
     Integer(i64),
     /// An unsigned integer
     PositiveInteger(u64),
+    /// An exact fraction, stored as numerator and a positive denominator
+    Rational(i64, i64),
     /// A text string
     String(String),
     /// An iterable collection of values
@@ -54,6 +58,8 @@ pub enum Primitive {
     GraphNode(GraphNode),
     /// An ordered collection of primitives
     Tuple(Tuple),
+    /// A collection of primitives keyed by strings
+    Map(Map),
     /// A boolean value
     Boolean(bool),
     /// Represents an undefined value
@@ -68,12 +74,14 @@ export type SerializedPrimitive =
     | { type: 'Number', value: number }
     | { type: 'Integer', value: number }
     | { type: 'PositiveInteger', value: number }
+    | { type: 'Rational', value: [number, number] }
     | { type: 'String', value: string }
     | { type: 'Iterable', value: SerializedIterable }
     | { type: 'Graph', value: SerializedGraph }
     | { type: 'GraphEdge', value: SerializedGraphEdge }
     | { type: 'GraphNode', value: SerializedGraphNode }
     | { type: 'Tuple', value: SerializedTuple }
+    | { type: 'Map', value: SerializedMap }
     | { type: 'Boolean', value: boolean }
     | { type: 'Undefined' }
 "#;
@@ -87,6 +95,8 @@ pub enum PrimitiveKind {
     Integer,
     /// Unsigned integer type
     PositiveInteger,
+    /// Exact fraction type
+    Rational,
     /// String type
     String,
     /// Iterable type containing elements of the specified kind
@@ -99,6 +109,8 @@ pub enum PrimitiveKind {
     GraphNode,
     /// Tuple type containing a sequence of primitive kinds
     Tuple(Vec<PrimitiveKind>),
+    /// Map type with string keys and values of the specified kind
+    Map(Box<PrimitiveKind>),
     /// Boolean type
     Boolean,
     /// Undefined type
@@ -115,12 +127,14 @@ export type SerializedPrimitiveKind =
     | { type: 'Number' }
     | { type: 'Integer' }
     | { type: 'PositiveInteger' }
+    | { type: 'Rational' }
     | { type: 'String' }
     | { type: 'Iterable', value: SerializedPrimitiveKind }
     | { type: 'Graph' }
     | { type: 'GraphEdge' }
     | { type: 'GraphNode' }
     | { type: 'Tuple', value: SerializedPrimitiveKind[] }
+    | { type: 'Map', value: SerializedPrimitiveKind }
     | { type: 'Boolean' }
     | { type: 'Undefined' }
     | { type: 'Any' }
@@ -139,24 +153,27 @@ impl PrimitiveKind {
             Primitive::Number(_) => PrimitiveKind::Number,
             Primitive::Integer(_) => PrimitiveKind::Integer,
             Primitive::PositiveInteger(_) => PrimitiveKind::PositiveInteger,
+            Primitive::Rational(_, _) => PrimitiveKind::Rational,
             Primitive::String(_) => PrimitiveKind::String,
             Primitive::Iterable(p) => p.get_type(),
             Primitive::Graph(_) => PrimitiveKind::Graph,
             Primitive::GraphEdge(_) => PrimitiveKind::GraphEdge,
             Primitive::GraphNode(_) => PrimitiveKind::GraphNode,
             Primitive::Tuple(t) => t.get_type(),
+            Primitive::Map(m) => m.get_type(),
             Primitive::Boolean(_) => PrimitiveKind::Boolean,
             Primitive::Undefined => PrimitiveKind::Undefined,
         }
     }
 
-    /// Checks if the type is numeric (Number, Integer, PositiveInteger, or Boolean).
+    /// Checks if the type is numeric (Number, Integer, PositiveInteger, Rational, or Boolean).
     pub fn is_numeric(&self) -> bool {
         matches!(
             self,
             PrimitiveKind::Number
                 | PrimitiveKind::Integer
                 | PrimitiveKind::PositiveInteger
+                | PrimitiveKind::Rational
                 | PrimitiveKind::Boolean
         )
     }
@@ -167,6 +184,30 @@ impl PrimitiveKind {
         matches!(self, PrimitiveKind::Iterable(_))
     }
 
+    /// Checks if the type, or any of the types it contains, is not known (Any or Undefined).
+    pub fn has_unknown(&self) -> bool {
+        match self {
+            PrimitiveKind::Any | PrimitiveKind::Undefined => true,
+            PrimitiveKind::Iterable(kind) | PrimitiveKind::Map(kind) => kind.has_unknown(),
+            PrimitiveKind::Tuple(kinds) => kinds.iter().any(|k| k.has_unknown()),
+            _ => false,
+        }
+    }
+
+    /// Checks if a value of this kind can be cast to another kind.
+    ///
+    /// Numeric kinds can be cast between each other, iterables can be cast
+    /// if their elements can, and `Any` can be cast to and from everything.
+    pub fn can_cast_to(&self, target: &PrimitiveKind) -> bool {
+        match (self, target) {
+            (a, b) if a == b => true,
+            (PrimitiveKind::Any, _) | (_, PrimitiveKind::Any) => true,
+            (PrimitiveKind::Iterable(a), PrimitiveKind::Iterable(b)) => a.can_cast_to(b),
+            (PrimitiveKind::Map(a), PrimitiveKind::Map(b)) => a.can_cast_to(b),
+            (a, b) => a.is_numeric() && b.is_numeric() && *b != PrimitiveKind::Boolean,
+        }
+    }
+
     /// Returns the types that this primitive kind can be spread into.
     ///
     /// # Returns
@@ -191,28 +232,59 @@ impl PrimitiveKind {
             PrimitiveKind::Undefined => false,
             PrimitiveKind::Integer => i64::can_apply_binary_op(op, to),
             PrimitiveKind::PositiveInteger => u64::can_apply_binary_op(op, to),
+            PrimitiveKind::Rational => <(i64, i64)>::can_apply_binary_op(op, to),
             PrimitiveKind::Number => f64::can_apply_binary_op(op, to),
             PrimitiveKind::Boolean => bool::can_apply_binary_op(op, to),
             PrimitiveKind::Graph => Graph::can_apply_binary_op(op, to),
             PrimitiveKind::GraphEdge => GraphEdge::can_apply_binary_op(op, to),
             PrimitiveKind::GraphNode => GraphNode::can_apply_binary_op(op, to),
             PrimitiveKind::Tuple(_) => Tuple::can_apply_binary_op(op, to),
+            PrimitiveKind::Map(_) => Map::can_apply_binary_op(op, to),
             PrimitiveKind::Iterable(_) => IterableKind::can_apply_binary_op(op, to),
             PrimitiveKind::String => String::can_apply_binary_op(op, to),
         }
     }
+    /// Returns the kind of the result of applying the operator between a value of this kind
+    /// and one of the other kind, following the promotion rules of the numeric types:
+    /// integers stay integers except for divisions, which produce numbers, rationals stay
+    /// exact unless paired with a number, and anything paired with a number becomes a number.
+    ///
+    /// The division of two integer literals, like `5 / 2`, never reaches these rules: the parser
+    /// folds it into an exact [`Primitive::Rational`], so its kind is `Rational` while `n / 2`
+    /// with `let n = 5` is a `Number`. Both have the same value.
+    pub fn binary_op_result(&self, op: BinOp, to: &PrimitiveKind) -> PrimitiveKind {
+        use PrimitiveKind::*;
+        if op.is_boolean() {
+            return Boolean;
+        }
+        match (self, to) {
+            (Any, _) | (_, Any) => Any,
+            (Number, to) | (to, Number) if to.is_numeric() => Number,
+            (Rational, to) | (to, Rational) if to.is_numeric() => Rational,
+            (Integer | PositiveInteger, Integer | PositiveInteger | Boolean)
+                if op == BinOp::Div =>
+            {
+                Number
+            }
+            (PositiveInteger, PositiveInteger | Boolean) if op != BinOp::Sub => PositiveInteger,
+            (Integer | PositiveInteger, Integer | PositiveInteger | Boolean) => Integer,
+            _ => self.clone(),
+        }
+    }
     pub fn can_apply_unary_op(&self, op: UnOp) -> bool {
         match self {
             PrimitiveKind::Any => true, //make it fail at runtime
             PrimitiveKind::Undefined => false,
             PrimitiveKind::Integer => i64::can_apply_unary_op(op),
             PrimitiveKind::PositiveInteger => u64::can_apply_unary_op(op),
+            PrimitiveKind::Rational => <(i64, i64)>::can_apply_unary_op(op),
             PrimitiveKind::Number => f64::can_apply_unary_op(op),
             PrimitiveKind::Boolean => bool::can_apply_unary_op(op),
             PrimitiveKind::Graph => Graph::can_apply_unary_op(op),
             PrimitiveKind::GraphEdge => GraphEdge::can_apply_unary_op(op),
             PrimitiveKind::GraphNode => GraphNode::can_apply_unary_op(op),
             PrimitiveKind::Tuple(_) => Tuple::can_apply_unary_op(op),
+            PrimitiveKind::Map(_) => Map::can_apply_unary_op(op),
             PrimitiveKind::Iterable(_) => IterableKind::can_apply_unary_op(op),
             PrimitiveKind::String => String::can_apply_unary_op(op),
         }
@@ -226,6 +298,7 @@ impl Display for PrimitiveKind {
             PrimitiveKind::String => "String".to_string(),
             PrimitiveKind::Integer => "Integer".to_string(),
             PrimitiveKind::PositiveInteger => "PositiveInteger".to_string(),
+            PrimitiveKind::Rational => "Rational".to_string(),
             PrimitiveKind::Iterable(i) => format!("{}[]", i),
             PrimitiveKind::Graph => "Graph".to_string(),
             PrimitiveKind::GraphEdge => "GraphEdge".to_string(),
@@ -237,6 +310,7 @@ impl Display for PrimitiveKind {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            PrimitiveKind::Map(v) => format!("Map<{}>", v),
             PrimitiveKind::Boolean => "Boolean".to_string(),
             PrimitiveKind::Undefined => "Undefined".to_string(),
             PrimitiveKind::Any => "Any".to_string(),
@@ -270,10 +344,50 @@ impl Primitive {
             Primitive::Number(n) => Ok(*n),
             Primitive::Integer(n) => Ok(*n as f64),
             Primitive::PositiveInteger(n) => Ok(*n as f64),
+            Primitive::Rational(n, d) => Ok(*n as f64 / *d as f64),
             Primitive::Boolean(b) => Ok(*b as u8 as f64),
             _ => bail_wrong_argument!(PrimitiveKind::Number, self),
         }
     }
+
+    /// Casts the value to another kind, converting numbers and the elements of iterables.
+    ///
+    /// Empty iterables take the element kind of the target, so they can be iterated
+    /// as if they were declared with values.
+    ///
+    /// # Returns
+    /// * `Ok(Primitive)` - The converted value
+    /// * `Err(TransformError)` - If the value cannot be represented as the target kind
+    pub fn cast_to(&self, kind: &PrimitiveKind) -> Result<Primitive, TransformError> {
+        match kind {
+            PrimitiveKind::Any => Ok(self.clone()),
+            PrimitiveKind::Number => Ok(Primitive::Number(self.as_number_cast()?)),
+            PrimitiveKind::Integer => Ok(Primitive::Integer(self.as_integer_cast()?)),
+            PrimitiveKind::Rational => match self {
+                Primitive::Rational(_, _) => Ok(self.clone()),
+                _ => Ok(Primitive::Rational(self.as_integer_cast()?, 1)),
+            },
+            PrimitiveKind::PositiveInteger => {
+                Ok(Primitive::PositiveInteger(self.as_usize_cast()? as u64))
+            }
+            PrimitiveKind::Iterable(inner) => {
+                let values = self
+                    .as_iterator()?
+                    .clone()
+                    .to_primitives()
+                    .iter()
+                    .map(|v| v.cast_to(inner))
+                    .collect::<Result<Vec<_>, TransformError>>()?;
+                if values.is_empty() {
+                    Ok(Primitive::Iterable(IterableKind::empty_of(inner)))
+                } else {
+                    Ok(Primitive::Iterable(flatten_primitive_array_values(values)))
+                }
+            }
+            _ if self.get_type() == *kind => Ok(self.clone()),
+            _ => bail_wrong_argument!(kind.clone(), self),
+        }
+    }
     pub fn as_integer(&self) -> Result<i64, TransformError> {
         match_or_bail!(PrimitiveKind::Integer,
             Primitive::Integer(n) => Ok(*n)
@@ -289,6 +403,7 @@ impl Primitive {
         match self {
             Primitive::Integer(n) => Ok(*n),
             Primitive::PositiveInteger(n) => Ok(*n as i64),
+            Primitive::Rational(n, d) if n % d == 0 => Ok(n / d),
             Primitive::Boolean(b) => Ok(*b as u8 as i64),
             Primitive::Number(n) => {
                 if float_ne(n.fract(), 0.0) {
@@ -321,6 +436,7 @@ impl Primitive {
                     Ok(*n as usize)
                 }
             }
+            Primitive::Rational(n, d) if n % d == 0 && n / d >= 0 => Ok((n / d) as usize),
             Primitive::Boolean(b) => Ok(*b as u8 as usize),
             Primitive::Number(n) => {
                 if float_ne(n.fract(), 0.0) || float_lt(*n, 0.0) {
@@ -369,6 +485,13 @@ impl Primitive {
             (self)
         )
     }
+    pub fn as_map(&self) -> Result<&Map, TransformError> {
+        match_or_bail!(
+            PrimitiveKind::Map(Box::new(PrimitiveKind::Any)),
+            Primitive::Map(m) => Ok(m);
+            (self)
+        )
+    }
     pub fn as_tuple(&self) -> Result<&Vec<Primitive>, TransformError> {
         match_or_bail!(
             PrimitiveKind::Tuple(vec![]),
@@ -384,12 +507,15 @@ impl ToLatex for Primitive {
             Primitive::Number(n) => n.to_latex(),
             Primitive::Integer(n) => n.to_latex(),
             Primitive::PositiveInteger(n) => n.to_latex(),
+            Primitive::Rational(n, 1) => n.to_latex(),
+            Primitive::Rational(n, d) => format!("\\frac{{{}}}{{{}}}", n, d),
             Primitive::String(s) => s.to_latex(),
             Primitive::Iterable(i) => i.to_latex(),
             Primitive::Graph(g) => g.to_latex(),
             Primitive::GraphEdge(e) => e.to_latex(),
             Primitive::GraphNode(n) => n.to_latex(),
             Primitive::Tuple(v) => v.to_latex(),
+            Primitive::Map(m) => m.to_latex(),
             Primitive::Boolean(b) => b.to_string(),
             Primitive::Undefined => "undefined".to_string(),
         }
@@ -402,12 +528,15 @@ impl fmt::Display for Primitive {
             Primitive::Number(n) => n.to_string(),
             Primitive::Integer(n) => n.to_string(),
             Primitive::PositiveInteger(n) => n.to_string(),
+            Primitive::Rational(n, 1) => n.to_string(),
+            Primitive::Rational(n, d) => format!("{}/{}", n, d),
             Primitive::String(s) => format!("\"{}\"", s),
             Primitive::Iterable(i) => i.to_string(),
             Primitive::Graph(g) => g.to_string(),
             Primitive::GraphEdge(e) => e.to_string(),
             Primitive::GraphNode(n) => n.to_string(),
             Primitive::Tuple(v) => format!("{:?}", v),
+            Primitive::Map(m) => m.to_string(),
             Primitive::Boolean(b) => b.to_string(),
             Primitive::Undefined => "undefined".to_string(),
         };
@@ -52,6 +52,14 @@ pub enum OperatorError {
     },
     /// An undefined value was used in an operation
     UndefinedUse,
+    /// The right hand side of a division-like operator was zero
+    DivisionByZero { operator: BinOp },
+    /// The result of an integer operation does not fit in the integer type
+    IntegerOverflow {
+        operator: BinOp,
+        lhs: i128,
+        rhs: i128,
+    },
 }
 
 impl OperatorError {
@@ -92,6 +100,28 @@ impl OperatorError {
             found,
         }
     }
+
+    /// Creates a new division by zero error.
+    ///
+    /// # Arguments
+    /// * `op` - The binary operator that was used
+    pub fn division_by_zero(op: BinOp) -> Self {
+        OperatorError::DivisionByZero { operator: op }
+    }
+
+    /// Creates a new integer overflow error.
+    ///
+    /// # Arguments
+    /// * `op` - The binary operator that was used
+    /// * `lhs` - The left operand
+    /// * `rhs` - The right operand
+    pub fn integer_overflow(op: BinOp, lhs: i128, rhs: i128) -> Self {
+        OperatorError::IntegerOverflow {
+            operator: op,
+            lhs,
+            rhs,
+        }
+    }
 }
 
 impl fmt::Display for OperatorError {
@@ -114,6 +144,13 @@ impl fmt::Display for OperatorError {
                 operator, found
             ),
             OperatorError::UndefinedUse => "Used \"Undefined\" in operation".to_string(),
+            OperatorError::DivisionByZero { operator } => {
+                format!("Division by zero in operator \"{}\"", operator)
+            }
+            OperatorError::IntegerOverflow { operator, lhs, rhs } => format!(
+                "Integer overflow in operator \"{}\" between {} and {}",
+                operator, lhs, rhs
+            ),
         };
         f.write_str(&s)
     }
@@ -128,6 +165,7 @@ impl ApplyOp for Primitive {
             Primitive::Boolean(b) => b.apply_binary_op(op, to),
             Primitive::String(s) => s.apply_binary_op(op, to),
             Primitive::Tuple(t) => t.apply_binary_op(op, to),
+            Primitive::Map(m) => m.apply_binary_op(op, to),
             Primitive::GraphNode(gn) => gn.apply_binary_op(op, to),
             Primitive::GraphEdge(ge) => ge.apply_binary_op(op, to),
             Primitive::Graph(g) => g.apply_binary_op(op, to),
@@ -135,6 +173,7 @@ impl ApplyOp for Primitive {
             Primitive::Number(i) => i.apply_binary_op(op, to),
             Primitive::Integer(i) => i.apply_binary_op(op, to),
             Primitive::PositiveInteger(i) => i.apply_binary_op(op, to),
+            Primitive::Rational(n, d) => (*n, *d).apply_binary_op(op, to),
             Primitive::Undefined => Err(OperatorError::UndefinedUse),
         }
     }
@@ -149,6 +188,7 @@ impl ApplyOp for Primitive {
             Primitive::Boolean(b) => b.apply_unary_op(op),
             Primitive::String(s) => s.apply_unary_op(op),
             Primitive::Tuple(t) => t.apply_unary_op(op),
+            Primitive::Map(m) => m.apply_unary_op(op),
             Primitive::GraphNode(gn) => gn.apply_unary_op(op),
             Primitive::GraphEdge(ge) => ge.apply_unary_op(op),
             Primitive::Graph(g) => g.apply_unary_op(op),
@@ -156,6 +196,7 @@ impl ApplyOp for Primitive {
             Primitive::Number(i) => i.apply_unary_op(op),
             Primitive::Integer(i) => i.apply_unary_op(op),
             Primitive::PositiveInteger(i) => i.apply_unary_op(op),
+            Primitive::Rational(n, d) => (*n, *d).apply_unary_op(op),
             Primitive::Undefined => Err(OperatorError::UndefinedUse),
         }
     }
@@ -167,6 +208,7 @@ impl Spreadable for Primitive {
             Primitive::Boolean(b) => b.to_primitive_set(),
             Primitive::String(s) => s.to_primitive_set(),
             Primitive::Tuple(t) => t.to_primitive_set(),
+            Primitive::Map(m) => Err(TransformError::Unspreadable(m.get_type())),
             Primitive::GraphNode(gn) => gn.to_primitive_set(),
             Primitive::GraphEdge(ge) => ge.to_primitive_set(),
             Primitive::Graph(g) => g.to_primitive_set(),
@@ -174,6 +216,7 @@ impl Spreadable for Primitive {
             Primitive::Number(i) => i.to_primitive_set(),
             Primitive::Integer(i) => i.to_primitive_set(),
             Primitive::PositiveInteger(i) => i.to_primitive_set(),
+            Primitive::Rational(_, _) => Err(TransformError::Unspreadable(PrimitiveKind::Rational)),
             Primitive::Undefined => Err(TransformError::Unspreadable(PrimitiveKind::Undefined)),
         }
     }
@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+
+use crate::parser::il::PreExp;
+use crate::parser::model_transformer::{TransformError, TransformerContext};
+use crate::primitives::{Primitive, PrimitiveKind};
+use crate::runtime_builtin::{make_std, RoocFunction, TokenType};
+use crate::type_checker::type_checker_context::{FunctionContext, TypeCheckerContext};
+
+lazy_static! {
+    static ref STD_FUNCTION_NAMES: HashSet<String> = make_std().into_keys().collect();
+}
+
+/// A collection of host defined functions that are made available to a model
+/// together with the functions of the standard library.
+///
+/// Names are checked when a function is registered, so that a function can't
+/// shadow one of the standard library or another registered function.
+///
+/// # Example
+/// ```rust
+/// use rooc::{FunctionRegistry, RoocParser};
+///
+/// let registry = FunctionRegistry::new();
+/// assert!(registry.is_empty());
+/// let parser = RoocParser::new("min 1\ns.t.\n    1 <= 2".to_string()).with_functions(registry);
+/// assert!(parser.functions().is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FunctionRegistry {
+    functions: IndexMap<String, Arc<dyn RoocFunction + Send + Sync>>,
+}
+
+impl FunctionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a function under the given name.
+    ///
+    /// # Arguments
+    /// * `name` - The name the function is called with inside the model
+    /// * `function` - The implementation of the function
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the function was registered
+    /// * `Err(TransformError::AlreadyDefined)` - If the name is already used by a standard library
+    ///   function or by another registered function
+    pub fn register(
+        &mut self,
+        name: &str,
+        function: impl RoocFunction + Send + Sync + 'static,
+    ) -> Result<(), TransformError> {
+        if self.functions.contains_key(name) || STD_FUNCTION_NAMES.contains(name) {
+            return Err(TransformError::AlreadyDefined {
+                name: name.to_string(),
+                kind: TokenType::Function,
+            });
+        }
+        self.functions.insert(name.to_string(), Arc::new(function));
+        Ok(())
+    }
+
+    /// Registers a function under the given name, returning the registry to allow chaining.
+    pub fn with_function(
+        mut self,
+        name: &str,
+        function: impl RoocFunction + Send + Sync + 'static,
+    ) -> Result<Self, TransformError> {
+        self.register(name, function)?;
+        Ok(self)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Returns the functions of the standard library followed by the registered ones.
+    pub fn make_std_with(&self) -> IndexMap<String, Box<dyn RoocFunction>> {
+        let mut std = make_std();
+        for (name, function) in &self.functions {
+            std.insert(name.clone(), Box::new(SharedFunction(function.clone())));
+        }
+        std
+    }
+}
+
+/// A registered function, shared between the clones of a registry
+#[derive(Debug)]
+struct SharedFunction(Arc<dyn RoocFunction + Send + Sync>);
+
+impl RoocFunction for SharedFunction {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        self.0.call(args, context, fn_context)
+    }
+
+    fn type_signature(
+        &self,
+        args: &[PreExp],
+        context: &TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        self.0.type_signature(args, context, fn_context)
+    }
+
+    fn return_type(
+        &self,
+        args: &[PreExp],
+        context: &TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        self.0.return_type(args, context, fn_context)
+    }
+
+    fn function_name(&self) -> String {
+        self.0.function_name()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        self.0.type_check(args, context, fn_context)
+    }
+}
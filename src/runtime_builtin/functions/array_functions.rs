@@ -1,12 +1,13 @@
 use serde::Serialize;
 
 use super::function_traits::{default_wrong_number_of_arguments, default_wrong_type, RoocFunction};
+use crate::math::BinOp;
 use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{
-    primitives::{IterableKind, Primitive, PrimitiveKind, Tuple},
+    primitives::{ApplyOp, IterableKind, Primitive, PrimitiveKind, Tuple},
     type_checker::type_checker_context::{TypeCheckerContext, WithType},
 };
 
@@ -99,6 +100,8 @@ impl RoocFunction for EnumerateArray {
     }
 }
 
+/// Returns the number of elements of an iterable, the number of nodes of a graph
+/// or the number of characters of a string
 #[derive(Debug, Serialize, Clone)]
 pub struct LenOfIterableFn {}
 
@@ -111,8 +114,15 @@ impl RoocFunction for LenOfIterableFn {
     ) -> Result<Primitive, TransformError> {
         match args[..] {
             [ref of_iterable] => {
-                let value = of_iterable.as_iterator(context, fn_context)?;
-                Ok(Primitive::PositiveInteger(value.len() as u64))
+                let len = match of_iterable.as_primitive(context, fn_context)? {
+                    Primitive::Graph(graph) => graph.nodes().len(),
+                    Primitive::String(string) => string.chars().count(),
+                    value => value
+                        .as_iterator()
+                        .map_err(|e| e.add_span(of_iterable.span()))?
+                        .len(),
+                };
+                Ok(Primitive::PositiveInteger(len as u64))
             }
             _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
         }
@@ -152,7 +162,13 @@ impl RoocFunction for LenOfIterableFn {
         match args[..] {
             [ref of_iterable] => {
                 let arg_type = of_iterable.get_type(context, fn_context);
-                if !matches!(arg_type, PrimitiveKind::Iterable(_)) {
+                if !matches!(
+                    arg_type,
+                    PrimitiveKind::Iterable(_)
+                        | PrimitiveKind::Graph
+                        | PrimitiveKind::String
+                        | PrimitiveKind::Any
+                ) {
                     return Err(TransformError::from_wrong_type(
                         PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
                         arg_type,
@@ -166,6 +182,99 @@ impl RoocFunction for LenOfIterableFn {
     }
 }
 
+/// Checks whether an iterable contains a value, numbers are compared by value
+/// regardless of their kind, so `contains([1, 2], 2.0)` is true.
+#[derive(Debug, Serialize, Clone)]
+pub struct ContainsFn {}
+
+impl RoocFunction for ContainsFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref iterable, ref value] => {
+                let items = iterable.as_iterator(context, fn_context)?.to_primitives();
+                let value = value.as_primitive(context, fn_context)?;
+                let found = items.iter().any(|item| {
+                    matches!(
+                        item.apply_binary_op(BinOp::Equal, &value),
+                        Ok(Primitive::Boolean(true))
+                    ) || *item == value
+                });
+                Ok(Primitive::Boolean(found))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            (
+                "iterable".to_string(),
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
+            ),
+            ("value".to_string(), PrimitiveKind::Any),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Boolean
+    }
+
+    fn function_name(&self) -> String {
+        "contains".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref iterable, ref value] => {
+                let element_type = match iterable.get_type(context, fn_context) {
+                    PrimitiveKind::Iterable(kind) => *kind,
+                    PrimitiveKind::Any => PrimitiveKind::Any,
+                    kind => {
+                        return Err(TransformError::from_wrong_type(
+                            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
+                            kind,
+                            iterable.span().clone(),
+                        ))
+                    }
+                };
+                let value_type = value.get_type(context, fn_context);
+                if !element_type.can_cast_to(&value_type) && !value_type.can_cast_to(&element_type)
+                {
+                    return Err(TransformError::from_wrong_type(
+                        element_type,
+                        value_type,
+                        value.span().clone(),
+                    ));
+                }
+                Ok(())
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Iterates multiple iterables in lockstep, producing a tuple for each position.
+/// All the iterables must have the same length.
 #[derive(Debug, Serialize, Clone)]
 pub struct ZipArrays {}
 
@@ -191,9 +300,16 @@ impl RoocFunction for ZipArrays {
                     .into_iter()
                     .map(|iter| iter.to_primitives())
                     .collect::<Vec<_>>();
-                let shortest = primitives.iter().map(|p| p.len()).min().unwrap();
+                let first_len = primitives[0].len();
+                if let Some(other) = primitives.iter().find(|p| p.len() != first_len) {
+                    return Err(TransformError::Other(format!(
+                        "Cannot zip iterables of different lengths, got {} and {}",
+                        first_len,
+                        other.len()
+                    )));
+                }
                 let mut result = Vec::new();
-                for i in 0..shortest {
+                for i in 0..first_len {
                     result.push(Tuple::new(
                         primitives.iter().map(|p| p[i].clone()).collect::<Vec<_>>(),
                     ));
@@ -424,3 +540,435 @@ impl RoocFunction for ArrayIntersection {
         "intersection".to_string()
     }
 }
+
+/// Transposes a 2D array, turning its rows into columns.
+#[derive(Debug, Serialize, Clone)]
+pub struct TransposeMatrix {}
+
+impl RoocFunction for TransposeMatrix {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref matrix] => {
+                let rows = match matrix.as_iterator(context, fn_context)? {
+                    IterableKind::Iterables(rows) => rows,
+                    IterableKind::Anys(v) if v.is_empty() => vec![],
+                    other => {
+                        return Err(TransformError::from_wrong_type(
+                            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                                PrimitiveKind::Any,
+                            )))),
+                            other.get_type(),
+                            matrix.span().clone(),
+                        ))
+                    }
+                };
+                let rows = rows
+                    .into_iter()
+                    .map(|row| row.to_primitives())
+                    .collect::<Vec<_>>();
+                let width = rows.first().map(|r| r.len()).unwrap_or(0);
+                if let Some((i, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+                    return Err(TransformError::Other(format!(
+                        "Cannot transpose a ragged matrix, row 0 has {} elements but row {} has {}",
+                        width,
+                        i,
+                        row.len()
+                    ))
+                    .add_span(matrix.span()));
+                }
+                let mut columns: Vec<Vec<Primitive>> = vec![Vec::with_capacity(rows.len()); width];
+                for row in rows {
+                    for (column, value) in columns.iter_mut().zip(row) {
+                        column.push(value);
+                    }
+                }
+                let columns = columns
+                    .into_iter()
+                    .map(|c| IterableKind::Anys(c).flatten())
+                    .collect();
+                Ok(Primitive::Iterable(IterableKind::Iterables(columns)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "matrix".to_string(),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                PrimitiveKind::Any,
+            )))),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        args: &[PreExp],
+        context: &TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        match args.first().map(|a| a.get_type(context, fn_context)) {
+            Some(PrimitiveKind::Iterable(inner)) if inner.is_iterable() => {
+                PrimitiveKind::Iterable(inner)
+            }
+            _ => PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                PrimitiveKind::Any,
+            )))),
+        }
+    }
+
+    fn function_name(&self) -> String {
+        "transpose".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref matrix] => {
+                let arg_type = matrix.get_type(context, fn_context);
+                match &arg_type {
+                    PrimitiveKind::Iterable(inner) if inner.is_iterable() || inner.is_any() => {
+                        Ok(())
+                    }
+                    _ => Err(TransformError::from_wrong_type(
+                        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                            PrimitiveKind::Any,
+                        )))),
+                        arg_type,
+                        matrix.span().clone(),
+                    )),
+                }
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Collapses one level of nesting of an iterable of iterables, concatenating its rows.
+#[derive(Debug, Serialize, Clone)]
+pub struct FlattenFn {}
+
+impl RoocFunction for FlattenFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref iterable] => {
+                let rows = match iterable.as_iterator(context, fn_context)? {
+                    IterableKind::Iterables(rows) => rows,
+                    IterableKind::Anys(v) if v.is_empty() => vec![],
+                    other => {
+                        return Err(TransformError::from_wrong_type(
+                            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                                PrimitiveKind::Any,
+                            )))),
+                            other.get_type(),
+                            iterable.span().clone(),
+                        ))
+                    }
+                };
+                let values = rows
+                    .into_iter()
+                    .flat_map(|row| row.to_primitives())
+                    .collect::<Vec<_>>();
+                Ok(Primitive::Iterable(IterableKind::Anys(values).flatten()))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "of_iterable".to_string(),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                PrimitiveKind::Any,
+            )))),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        args: &[PreExp],
+        context: &TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        match args.first().map(|a| a.get_type(context, fn_context)) {
+            Some(PrimitiveKind::Iterable(inner)) if inner.is_iterable() => *inner,
+            _ => PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
+        }
+    }
+
+    fn function_name(&self) -> String {
+        "flatten".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref iterable] => {
+                let arg_type = iterable.get_type(context, fn_context);
+                match &arg_type {
+                    PrimitiveKind::Iterable(inner) if inner.is_iterable() || inner.is_any() => {
+                        Ok(())
+                    }
+                    _ => Err(TransformError::from_wrong_type(
+                        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                            PrimitiveKind::Any,
+                        )))),
+                        arg_type,
+                        iterable.span().clone(),
+                    )),
+                }
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+fn numeric_iterable_values(
+    iterable: &PreExp,
+    context: &TransformerContext,
+    fn_context: &FunctionContext,
+) -> Result<Vec<f64>, TransformError> {
+    match iterable.as_iterator(context, fn_context)? {
+        IterableKind::Numbers(v) => Ok(v),
+        IterableKind::Integers(v) => Ok(v.into_iter().map(|n| n as f64).collect()),
+        IterableKind::PositiveIntegers(v) => Ok(v.into_iter().map(|n| n as f64).collect()),
+        IterableKind::Booleans(v) => Ok(v.into_iter().map(|b| b as u8 as f64).collect()),
+        IterableKind::Anys(v) => v
+            .iter()
+            .map(|p| p.as_number_cast())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.add_span(iterable.span())),
+        other => Err(TransformError::from_wrong_type(
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+            other.get_type(),
+            iterable.span().clone(),
+        )),
+    }
+}
+
+fn type_check_numeric_iterable(
+    function: &dyn RoocFunction,
+    args: &[PreExp],
+    context: &mut TypeCheckerContext,
+    fn_context: &FunctionContext,
+) -> Result<(), TransformError> {
+    match args {
+        [iterable] => {
+            let arg_type = iterable.get_type(context, fn_context);
+            match &arg_type {
+                PrimitiveKind::Iterable(inner) if inner.is_numeric() || inner.is_any() => Ok(()),
+                _ => Err(TransformError::from_wrong_type(
+                    PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+                    arg_type,
+                    iterable.span().clone(),
+                )),
+            }
+        }
+        _ => Err(default_wrong_type(args, function, context, fn_context)),
+    }
+}
+
+/// The reductions of a numeric iterable into a single number
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum NumericFold {
+    Sum,
+    Prod,
+    Avg,
+    Min,
+    Max,
+}
+
+impl NumericFold {
+    /// Reduces the values, the average, minimum and maximum of no values are not defined
+    fn apply(&self, values: &[f64]) -> Option<f64> {
+        match self {
+            NumericFold::Sum => Some(values.iter().sum()),
+            NumericFold::Prod => Some(values.iter().product()),
+            NumericFold::Avg | NumericFold::Min | NumericFold::Max if values.is_empty() => None,
+            NumericFold::Avg => Some(values.iter().sum::<f64>() / values.len() as f64),
+            NumericFold::Min => Some(values.iter().copied().fold(f64::INFINITY, f64::min)),
+            NumericFold::Max => Some(values.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            NumericFold::Sum => "sum",
+            NumericFold::Prod => "product",
+            NumericFold::Avg => "average",
+            NumericFold::Min => "minimum",
+            NumericFold::Max => "maximum",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NumericFoldFn {
+    pub fold: NumericFold,
+}
+
+impl RoocFunction for NumericFoldFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref iterable] => {
+                let values = numeric_iterable_values(iterable, context, fn_context)?;
+                match self.fold.apply(&values) {
+                    Some(value) => Ok(Primitive::Number(value)),
+                    None => Err(TransformError::Other(format!(
+                        "Cannot compute the {} of an empty iterable",
+                        self.fold.description()
+                    ))
+                    .add_span(iterable.span())),
+                }
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "of_iterable".to_string(),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Number
+    }
+
+    fn function_name(&self) -> String {
+        match self.fold {
+            NumericFold::Sum => "sum",
+            NumericFold::Prod => "prod",
+            NumericFold::Avg => "avg",
+            NumericFold::Min => "min",
+            NumericFold::Max => "max",
+        }
+        .to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        type_check_numeric_iterable(self, args, context, fn_context)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ReverseArray {}
+
+impl RoocFunction for ReverseArray {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref iterable] => {
+                let iterable = iterable.as_iterator(context, fn_context)?;
+                let reversed = match iterable {
+                    IterableKind::Numbers(v) => {
+                        IterableKind::Numbers(v.into_iter().rev().collect())
+                    }
+                    IterableKind::Integers(v) => {
+                        IterableKind::Integers(v.into_iter().rev().collect())
+                    }
+                    IterableKind::PositiveIntegers(v) => {
+                        IterableKind::PositiveIntegers(v.into_iter().rev().collect())
+                    }
+                    IterableKind::Strings(v) => {
+                        IterableKind::Strings(v.into_iter().rev().collect())
+                    }
+                    IterableKind::Edges(v) => IterableKind::Edges(v.into_iter().rev().collect()),
+                    IterableKind::Nodes(v) => IterableKind::Nodes(v.into_iter().rev().collect()),
+                    IterableKind::Graphs(v) => IterableKind::Graphs(v.into_iter().rev().collect()),
+                    IterableKind::Tuples(v) => IterableKind::Tuples(v.into_iter().rev().collect()),
+                    IterableKind::Booleans(v) => {
+                        IterableKind::Booleans(v.into_iter().rev().collect())
+                    }
+                    IterableKind::Iterables(v) => {
+                        IterableKind::Iterables(v.into_iter().rev().collect())
+                    }
+                    IterableKind::Anys(v) => IterableKind::Anys(v.into_iter().rev().collect()),
+                };
+                Ok(Primitive::Iterable(reversed))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "of_iterable".to_string(),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        args: &[PreExp],
+        context: &TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        match args.first().map(|a| a.get_type(context, fn_context)) {
+            Some(kind @ PrimitiveKind::Iterable(_)) => kind,
+            _ => PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
+        }
+    }
+
+    fn function_name(&self) -> String {
+        "reverse".to_string()
+    }
+}
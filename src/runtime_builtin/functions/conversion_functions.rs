@@ -0,0 +1,211 @@
+use serde::Serialize;
+
+use super::function_traits::{default_wrong_number_of_arguments, RoocFunction};
+use crate::parser::il::PreExp;
+use crate::parser::model_transformer::TransformError;
+use crate::parser::model_transformer::TransformerContext;
+use crate::type_checker::type_checker_context::FunctionContext;
+use crate::{
+    primitives::{Primitive, PrimitiveKind},
+    type_checker::type_checker_context::{TypeCheckerContext, WithType},
+};
+
+/// Checks that the argument is a string or a number, the values that can be converted to numbers
+fn type_check_convertible_to_number(
+    fun: &dyn RoocFunction,
+    args: &[PreExp],
+    context: &TypeCheckerContext,
+    fn_context: &FunctionContext,
+) -> Result<(), TransformError> {
+    match args[..] {
+        [ref value] => {
+            let arg_type = value.get_type(context, fn_context);
+            if arg_type.is_numeric() || arg_type.is_any() || arg_type == PrimitiveKind::String {
+                Ok(())
+            } else {
+                Err(TransformError::WrongExpectedArgument {
+                    got: arg_type,
+                    one_of: vec![PrimitiveKind::Number, PrimitiveKind::String],
+                }
+                .add_span(value.span()))
+            }
+        }
+        _ => Err(default_wrong_number_of_arguments(fun, args, fn_context)),
+    }
+}
+
+fn parse_error(value: &str, kind: PrimitiveKind, arg: &PreExp) -> TransformError {
+    TransformError::Other(format!("Cannot convert \"{}\" to {}", value, kind)).add_span(arg.span())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ToNumberFn {}
+
+impl RoocFunction for ToNumberFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref value] => match value.as_primitive(context, fn_context)? {
+                Primitive::String(s) => match s.trim().parse::<f64>() {
+                    Ok(n) if n.is_finite() => Ok(Primitive::Number(n)),
+                    _ => Err(parse_error(&s, PrimitiveKind::Number, value)),
+                },
+                p => p
+                    .as_number_cast()
+                    .map(Primitive::Number)
+                    .map_err(|e| e.add_span(value.span())),
+            },
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("value".to_string(), PrimitiveKind::Any)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Number
+    }
+
+    fn function_name(&self) -> String {
+        "to_number".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        type_check_convertible_to_number(self, args, context, fn_context)
+    }
+}
+
+/// Converts a number to an integer by dropping its fractional part
+fn truncate_to_integer(value: f64, arg: &PreExp) -> Result<Primitive, TransformError> {
+    let truncated = value.trunc();
+    if !truncated.is_finite() || truncated < i64::MIN as f64 || truncated >= i64::MAX as f64 {
+        return Err(parse_error(&value.to_string(), PrimitiveKind::Integer, arg));
+    }
+    Ok(Primitive::Integer(truncated as i64))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ToIntegerFn {}
+
+impl RoocFunction for ToIntegerFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref value] => match value.as_primitive(context, fn_context)? {
+                Primitive::Integer(i) => Ok(Primitive::Integer(i)),
+                Primitive::String(s) => {
+                    let trimmed = s.trim();
+                    match trimmed.parse::<i64>() {
+                        Ok(i) => Ok(Primitive::Integer(i)),
+                        Err(_) => match trimmed.parse::<f64>() {
+                            Ok(n) => truncate_to_integer(n, value),
+                            Err(_) => Err(parse_error(&s, PrimitiveKind::Integer, value)),
+                        },
+                    }
+                }
+                p => {
+                    let n = p.as_number_cast().map_err(|e| e.add_span(value.span()))?;
+                    truncate_to_integer(n, value)
+                }
+            },
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("value".to_string(), PrimitiveKind::Any)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Integer
+    }
+
+    fn function_name(&self) -> String {
+        "to_integer".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        type_check_convertible_to_number(self, args, context, fn_context)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ToStringFn {}
+
+impl RoocFunction for ToStringFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref value] => match value.as_primitive(context, fn_context)? {
+                Primitive::String(s) => Ok(Primitive::String(s)),
+                p => Ok(Primitive::String(p.to_string())),
+            },
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("value".to_string(), PrimitiveKind::Any)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::String
+    }
+
+    fn function_name(&self) -> String {
+        "to_string".to_string()
+    }
+}
@@ -72,6 +72,12 @@ pub fn default_type_check(
         {
             continue;
         }
+        //same for maps
+        if *kind == PrimitiveKind::Map(Box::new(PrimitiveKind::Any))
+            && matches!(arg_type, PrimitiveKind::Map(_))
+        {
+            continue;
+        }
         //allow anything that can be converted to a number
         if kind == &PrimitiveKind::Number && arg_type.is_numeric() {
             continue;
@@ -12,7 +12,7 @@ use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{
-    primitives::{IterableKind, Primitive, PrimitiveKind},
+    primitives::{GraphEdge, IterableKind, Primitive, PrimitiveKind},
     type_checker::type_checker_context::{TypeCheckerContext, WithType},
 };
 
@@ -254,3 +254,904 @@ impl RoocFunction for NeighboursOfNodeInGraphFn {
         }
     }
 }
+
+/// Reads a node identifier from an expression, accepting either a node or its name
+fn node_name_of(
+    exp: &PreExp,
+    context: &TransformerContext,
+    fn_context: &FunctionContext,
+) -> Result<String, TransformError> {
+    match exp.as_primitive(context, fn_context)? {
+        Primitive::String(name) => Ok(name),
+        Primitive::GraphNode(node) => Ok(node.name().clone()),
+        p => Err(TransformError::WrongExpectedArgument {
+            got: p.get_type(),
+            one_of: vec![PrimitiveKind::String, PrimitiveKind::GraphNode],
+        }
+        .add_span(exp.span())),
+    }
+}
+
+/// Checks that the first argument is a graph and the others are node identifiers
+fn type_check_graph_and_nodes(
+    in_graph: &PreExp,
+    nodes: &[&PreExp],
+    context: &TypeCheckerContext,
+    fn_context: &FunctionContext,
+) -> Result<(), TransformError> {
+    let graph_type = in_graph.get_type(context, fn_context);
+    if !matches!(graph_type, PrimitiveKind::Graph) {
+        return Err(TransformError::from_wrong_type(
+            PrimitiveKind::Graph,
+            graph_type,
+            in_graph.span().clone(),
+        ));
+    }
+    for node in nodes {
+        let node_type = node.get_type(context, fn_context);
+        if !matches!(
+            node_type,
+            PrimitiveKind::String | PrimitiveKind::GraphNode | PrimitiveKind::Any
+        ) {
+            return Err(TransformError::WrongExpectedArgument {
+                got: node_type,
+                one_of: vec![PrimitiveKind::String, PrimitiveKind::GraphNode],
+            }
+            .add_span(node.span()));
+        }
+    }
+    Ok(())
+}
+
+/// Computes the edges of the shortest path between two nodes of a graph.
+/// Every edge must have a cost, if the nodes are not connected the result is empty.
+#[derive(Debug, Serialize, Clone)]
+pub struct ShortestPathFn {}
+
+impl RoocFunction for ShortestPathFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref from, ref to] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let from = node_name_of(from, context, fn_context)?;
+                let to = node_name_of(to, context, fn_context)?;
+                let path = graph.shortest_path(&from, &to)?;
+                Ok(Primitive::Iterable(IterableKind::Edges(path)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            ("from".to_string(), PrimitiveKind::GraphNode),
+            ("to".to_string(), PrimitiveKind::GraphNode),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphEdge))
+    }
+
+    fn function_name(&self) -> String {
+        "shortest_path".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref from, ref to] => {
+                type_check_graph_and_nodes(in_graph, &[from, to], context, fn_context)
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Collects the nodes of a graph that can be reached from a node within `k` edges
+#[derive(Debug, Serialize, Clone)]
+pub struct NeighborsWithinFn {}
+
+impl RoocFunction for NeighborsWithinFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node, ref k] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let node = node_name_of(of_node, context, fn_context)?;
+                let k = k.as_usize_cast(context, fn_context)?;
+                let nodes = graph.neighbours_within(&node, k)?;
+                Ok(Primitive::Iterable(IterableKind::Nodes(nodes)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            ("of_node".to_string(), PrimitiveKind::GraphNode),
+            ("k".to_string(), PrimitiveKind::PositiveInteger),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphNode))
+    }
+
+    fn function_name(&self) -> String {
+        "neighbors_within".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node, ref k] => {
+                type_check_graph_and_nodes(in_graph, &[of_node], context, fn_context)?;
+                let k_type = k.get_type(context, fn_context);
+                if !matches!(
+                    k_type,
+                    PrimitiveKind::Integer | PrimitiveKind::PositiveInteger | PrimitiveKind::Any
+                ) {
+                    return Err(TransformError::from_wrong_type(
+                        PrimitiveKind::PositiveInteger,
+                        k_type,
+                        k.span().clone(),
+                    ));
+                }
+                Ok(())
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Builds the subgraph induced by a list of nodes of a graph
+#[derive(Debug, Serialize, Clone)]
+pub struct SubgraphFn {}
+
+impl RoocFunction for SubgraphFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_nodes] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let names = match of_nodes.as_iterator(context, fn_context)? {
+                    IterableKind::Strings(names) => names,
+                    IterableKind::Nodes(nodes) => nodes.iter().map(|n| n.name().clone()).collect(),
+                    IterableKind::Anys(values) => values
+                        .into_iter()
+                        .map(|p| match p {
+                            Primitive::String(name) => Ok(name),
+                            Primitive::GraphNode(node) => Ok(node.name().clone()),
+                            p => Err(TransformError::WrongExpectedArgument {
+                                got: p.get_type(),
+                                one_of: vec![PrimitiveKind::String, PrimitiveKind::GraphNode],
+                            }
+                            .add_span(of_nodes.span())),
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    i => {
+                        return Err(TransformError::WrongExpectedArgument {
+                            got: PrimitiveKind::Iterable(Box::new(i.inner_type())),
+                            one_of: vec![
+                                PrimitiveKind::Iterable(Box::new(PrimitiveKind::String)),
+                                PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphNode)),
+                            ],
+                        }
+                        .add_span(of_nodes.span()))
+                    }
+                };
+                Ok(Primitive::Graph(graph.subgraph(&names)?))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            (
+                "of_nodes".to_string(),
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphNode)),
+            ),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Graph
+    }
+
+    fn function_name(&self) -> String {
+        "subgraph".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_nodes] => {
+                type_check_graph_and_nodes(in_graph, &[], context, fn_context)?;
+                let nodes_type = of_nodes.get_type(context, fn_context);
+                match &nodes_type {
+                    PrimitiveKind::Any => Ok(()),
+                    PrimitiveKind::Iterable(inner)
+                        if matches!(
+                            **inner,
+                            PrimitiveKind::String | PrimitiveKind::GraphNode | PrimitiveKind::Any
+                        ) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(TransformError::WrongExpectedArgument {
+                        got: nodes_type,
+                        one_of: vec![
+                            PrimitiveKind::Iterable(Box::new(PrimitiveKind::String)),
+                            PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphNode)),
+                        ],
+                    }
+                    .add_span(of_nodes.span())),
+                }
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Counts the edges entering and leaving a node of a graph
+#[derive(Debug, Serialize, Clone)]
+pub struct DegreeFn {}
+
+impl RoocFunction for DegreeFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let node = node_name_of(of_node, context, fn_context)?;
+                let degree = graph.in_degree(&node)? + graph.out_degree(&node)?;
+                Ok(Primitive::Integer(degree as i64))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            ("of_node".to_string(), PrimitiveKind::GraphNode),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Integer
+    }
+
+    fn function_name(&self) -> String {
+        "degree".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                type_check_graph_and_nodes(in_graph, &[of_node], context, fn_context)
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Counts the edges of a graph that target a node
+#[derive(Debug, Serialize, Clone)]
+pub struct InDegreeFn {}
+
+impl RoocFunction for InDegreeFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let node = node_name_of(of_node, context, fn_context)?;
+                let degree = graph.in_degree(&node)?;
+                Ok(Primitive::Integer(degree as i64))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            ("of_node".to_string(), PrimitiveKind::GraphNode),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Integer
+    }
+
+    fn function_name(&self) -> String {
+        "in_degree".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                type_check_graph_and_nodes(in_graph, &[of_node], context, fn_context)
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Counts the edges of a graph that originate from a node
+#[derive(Debug, Serialize, Clone)]
+pub struct OutDegreeFn {}
+
+impl RoocFunction for OutDegreeFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let node = node_name_of(of_node, context, fn_context)?;
+                let degree = graph.out_degree(&node)?;
+                Ok(Primitive::Integer(degree as i64))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            ("of_node".to_string(), PrimitiveKind::GraphNode),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Integer
+    }
+
+    fn function_name(&self) -> String {
+        "out_degree".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                type_check_graph_and_nodes(in_graph, &[of_node], context, fn_context)
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Gets the weight of a node of a graph, like `A: w=5`
+#[derive(Debug, Serialize, Clone)]
+pub struct NodeWeightFn {}
+
+impl RoocFunction for NodeWeightFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                let graph = in_graph.as_graph(context, fn_context)?;
+                let node = node_name_of(of_node, context, fn_context)?;
+                let weight = graph
+                    .node_weight(&node)
+                    .map_err(|e| e.add_span(of_node.span()))?;
+                Ok(Primitive::Number(weight))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("in_graph".to_string(), PrimitiveKind::Graph),
+            ("of_node".to_string(), PrimitiveKind::GraphNode),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Number
+    }
+
+    fn function_name(&self) -> String {
+        "node_weight".to_string()
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref in_graph, ref of_node] => {
+                type_check_graph_and_nodes(in_graph, &[of_node], context, fn_context)
+            }
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
+
+/// Renders a graph in the GraphViz DOT format
+#[derive(Debug, Serialize, Clone)]
+pub struct ToDotFn {}
+
+impl RoocFunction for ToDotFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                Ok(Primitive::String(graph.to_dot()))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_graph".to_string(), PrimitiveKind::Graph)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::String
+    }
+
+    fn function_name(&self) -> String {
+        "to_dot".to_string()
+    }
+}
+
+/// Groups the nodes of a graph in connected components, ignoring the direction of the edges
+#[derive(Debug, Serialize, Clone)]
+pub struct ConnectedComponentsFn {}
+
+impl RoocFunction for ConnectedComponentsFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                let components = graph
+                    .connected_components()
+                    .into_iter()
+                    .map(IterableKind::Strings)
+                    .collect();
+                Ok(Primitive::Iterable(IterableKind::Iterables(components)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_graph".to_string(), PrimitiveKind::Graph)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+            PrimitiveKind::String,
+        ))))
+    }
+
+    fn function_name(&self) -> String {
+        "connected_components".to_string()
+    }
+}
+
+/// Checks if a graph is connected, ignoring the direction of the edges
+#[derive(Debug, Serialize, Clone)]
+pub struct IsConnectedFn {}
+
+impl RoocFunction for IsConnectedFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                Ok(Primitive::Boolean(graph.is_connected()))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_graph".to_string(), PrimitiveKind::Graph)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Boolean
+    }
+
+    fn function_name(&self) -> String {
+        "is_connected".to_string()
+    }
+}
+
+/// Builds the adjacency matrix of a graph, following the order of `nodes(G)`.
+/// The optional mode selects between "binary" (0/1) entries, the default, and "cost" entries
+#[derive(Debug, Serialize, Clone)]
+pub struct AdjacencyMatrixFn {}
+
+impl RoocFunction for AdjacencyMatrixFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        let (of_graph, use_costs) = match args[..] {
+            [ref of_graph] => (of_graph, false),
+            [ref of_graph, ref mode] => match mode.as_string(context, fn_context)?.as_str() {
+                "binary" => (of_graph, false),
+                "cost" => (of_graph, true),
+                m => {
+                    return Err(TransformError::Other(format!(
+                        "unknown adjacency matrix mode \"{}\", expected \"binary\" or \"cost\"",
+                        m
+                    ))
+                    .add_span(mode.span()))
+                }
+            },
+            _ => return Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        };
+        let graph = of_graph.as_graph(context, fn_context)?;
+        let matrix = graph
+            .adjacency_matrix(use_costs)?
+            .into_iter()
+            .map(IterableKind::Numbers)
+            .collect();
+        Ok(Primitive::Iterable(IterableKind::Iterables(matrix)))
+    }
+
+    fn type_signature(
+        &self,
+        args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        let mut signature = vec![("of_graph".to_string(), PrimitiveKind::Graph)];
+        if args.len() == 2 {
+            signature.push(("mode".to_string(), PrimitiveKind::String));
+        }
+        signature
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+            PrimitiveKind::Number,
+        ))))
+    }
+
+    fn function_name(&self) -> String {
+        "adjacency_matrix".to_string()
+    }
+}
+
+/// Gets the edge passed as argument
+fn edge_of(
+    exp: &PreExp,
+    context: &TransformerContext,
+    fn_context: &FunctionContext,
+) -> Result<GraphEdge, TransformError> {
+    match exp.as_primitive(context, fn_context)? {
+        Primitive::GraphEdge(edge) => Ok(edge),
+        p => Err(TransformError::from_wrong_type(
+            PrimitiveKind::GraphEdge,
+            p.get_type(),
+            exp.span().clone(),
+        )),
+    }
+}
+
+/// Gets the cost of an edge, erroring if the edge has none
+#[derive(Debug, Serialize, Clone)]
+pub struct CostOfEdgeFn {}
+
+impl RoocFunction for CostOfEdgeFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_edge] => {
+                let edge = edge_of(of_edge, context, fn_context)?;
+                match edge.weight {
+                    Some(w) => Ok(Primitive::Number(w)),
+                    None => Err(TransformError::Other(format!(
+                        "edge {} -> {} has no cost",
+                        edge.from, edge.to
+                    ))
+                    .add_span(of_edge.span())),
+                }
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_edge".to_string(), PrimitiveKind::GraphEdge)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Number
+    }
+
+    fn function_name(&self) -> String {
+        "cost".to_string()
+    }
+}
+
+/// Gets the name of the node an edge starts from
+#[derive(Debug, Serialize, Clone)]
+pub struct SourceOfEdgeFn {}
+
+impl RoocFunction for SourceOfEdgeFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_edge] => {
+                let edge = edge_of(of_edge, context, fn_context)?;
+                Ok(Primitive::String(edge.from))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_edge".to_string(), PrimitiveKind::GraphEdge)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::String
+    }
+
+    fn function_name(&self) -> String {
+        "source".to_string()
+    }
+}
+
+/// Gets the name of the node an edge points to
+#[derive(Debug, Serialize, Clone)]
+pub struct TargetOfEdgeFn {}
+
+impl RoocFunction for TargetOfEdgeFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_edge] => {
+                let edge = edge_of(of_edge, context, fn_context)?;
+                Ok(Primitive::String(edge.to))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_edge".to_string(), PrimitiveKind::GraphEdge)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::String
+    }
+
+    fn function_name(&self) -> String {
+        "target".to_string()
+    }
+}
@@ -0,0 +1,111 @@
+use serde::Serialize;
+
+use super::function_traits::{default_wrong_number_of_arguments, RoocFunction};
+use crate::parser::il::PreExp;
+use crate::parser::iterable_utils::flatten_primitive_array_values;
+use crate::parser::model_transformer::TransformError;
+use crate::parser::model_transformer::TransformerContext;
+use crate::type_checker::type_checker_context::FunctionContext;
+use crate::{
+    primitives::{IterableKind, Primitive, PrimitiveKind},
+    type_checker::type_checker_context::{TypeCheckerContext, WithType},
+};
+
+/// Returns the keys of a map, in the order they were declared.
+#[derive(Debug, Serialize, Clone)]
+pub struct KeysOfMapFn {}
+
+impl RoocFunction for KeysOfMapFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_map] => {
+                let value = of_map.as_primitive(context, fn_context)?;
+                let map = value.as_map().map_err(|e| e.add_span(of_map.span()))?;
+                Ok(Primitive::Iterable(IterableKind::Strings(map.keys())))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "of_map".to_string(),
+            PrimitiveKind::Map(Box::new(PrimitiveKind::Any)),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::String))
+    }
+
+    fn function_name(&self) -> String {
+        "keys".to_string()
+    }
+}
+
+/// Returns the values of a map, in the order their keys were declared.
+#[derive(Debug, Serialize, Clone)]
+pub struct ValuesOfMapFn {}
+
+impl RoocFunction for ValuesOfMapFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_map] => {
+                let value = of_map.as_primitive(context, fn_context)?;
+                let map = value.as_map().map_err(|e| e.add_span(of_map.span()))?;
+                Ok(Primitive::Iterable(flatten_primitive_array_values(
+                    map.values(),
+                )))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "of_map".to_string(),
+            PrimitiveKind::Map(Box::new(PrimitiveKind::Any)),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        args: &[PreExp],
+        context: &TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        match args.first().map(|a| a.get_type(context, fn_context)) {
+            Some(PrimitiveKind::Map(kind)) => PrimitiveKind::Iterable(kind),
+            _ => PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
+        }
+    }
+
+    fn function_name(&self) -> String {
+        "values".to_string()
+    }
+}
@@ -1,12 +1,18 @@
 pub(crate) mod array_functions;
+pub(crate) mod conversion_functions;
 pub mod function_traits;
 pub(crate) mod graph_functions;
 pub(crate) mod js_function;
+pub(crate) mod map_functions;
 pub(crate) mod number_functions;
+pub(crate) mod string_functions;
 
 pub(crate) use array_functions::*;
+pub(crate) use conversion_functions::*;
 pub use function_traits::*;
 pub(crate) use graph_functions::*;
 #[allow(unused)]
 pub use js_function::*;
+pub(crate) use map_functions::*;
 pub(crate) use number_functions::*;
+pub(crate) use string_functions::*;
@@ -0,0 +1,106 @@
+use serde::Serialize;
+
+use super::function_traits::{default_wrong_number_of_arguments, RoocFunction};
+use crate::parser::il::PreExp;
+use crate::parser::model_transformer::TransformError;
+use crate::parser::model_transformer::TransformerContext;
+use crate::type_checker::type_checker_context::FunctionContext;
+use crate::{
+    primitives::{IterableKind, Primitive, PrimitiveKind},
+    type_checker::type_checker_context::TypeCheckerContext,
+};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConcatStrings {}
+
+impl RoocFunction for ConcatStrings {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        let parts = args
+            .iter()
+            .map(|arg| arg.as_string(context, fn_context))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Primitive::String(parts.concat()))
+    }
+
+    fn type_signature(
+        &self,
+        args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        (0..args.len().max(1))
+            .map(|i| (format!("str_{}", i), PrimitiveKind::String))
+            .collect()
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::String
+    }
+
+    fn function_name(&self) -> String {
+        "concat".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SplitString {}
+
+impl RoocFunction for SplitString {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_string, ref separator] => {
+                let string = of_string.as_string(context, fn_context)?;
+                let sep = separator.as_string(context, fn_context)?;
+                if sep.is_empty() {
+                    return Err(TransformError::Other(
+                        "Cannot split a string with an empty separator".to_string(),
+                    )
+                    .add_span(separator.span()));
+                }
+                let parts = string.split(sep.as_str()).map(|s| s.to_string()).collect();
+                Ok(Primitive::Iterable(IterableKind::Strings(parts)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("of_string".to_string(), PrimitiveKind::String),
+            ("separator".to_string(), PrimitiveKind::String),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::String))
+    }
+
+    fn function_name(&self) -> String {
+        "split".to_string()
+    }
+}
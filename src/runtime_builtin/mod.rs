@@ -1,7 +1,9 @@
+mod function_registry;
 pub mod functions;
 mod reserved_tokens;
 mod rooc_std;
 
+pub use function_registry::*;
 pub use functions::*;
 pub(crate) use reserved_tokens::*;
 pub(crate) use rooc_std::*;
@@ -37,6 +37,10 @@ impl Display for TokenType {
     }
 }
 
+/// Builtin functions whose names are commonly used for parameters, like the values and costs
+/// of a knapsack problem. Calls are never ambiguous with variables, so they are not reserved
+const UNRESERVED_FUNCTIONS: [&str; 5] = ["keys", "values", "cost", "source", "target"];
+
 lazy_static! {
     static ref RESERVED_TOKEN: HashMap<String, TokenType> = {
         let mut m = HashMap::new();
@@ -49,10 +53,14 @@ lazy_static! {
         m.insert("as".to_string(), TokenType::Keyword);
         m.insert("if".to_string(), TokenType::Keyword);
         m.insert("else".to_string(), TokenType::Keyword);
+        m.insert("or".to_string(), TokenType::Keyword);
         m.insert("solve".to_string(), TokenType::Keyword);
         m.insert("true".to_string(), TokenType::Literal);
         m.insert("false".to_string(), TokenType::Literal);
 
+        m.insert("piecewise".to_string(), TokenType::Function);
+        m.insert("dot".to_string(), TokenType::Function);
+
         m.insert("Graph".to_string(), TokenType::Type);
 
         for v in BlockFunctionKind::kinds_to_string() {
@@ -64,6 +72,7 @@ lazy_static! {
 
         let builtin_fn = make_std()
             .keys()
+            .filter(|x| !UNRESERVED_FUNCTIONS.contains(&x.as_str()))
             .map(|x| x.to_string())
             .collect::<Vec<String>>();
         for v in builtin_fn {
@@ -2,15 +2,24 @@ use crate::functions::ZipArrays;
 use crate::parser::il::PreExp;
 use crate::runtime_builtin::functions::NumericRange;
 use crate::runtime_builtin::functions::{
-    EdgesOfGraphFn, NeighbourOfNodeFn, NeighboursOfNodeInGraphFn, NodesOfGraphFn,
+    AdjacencyMatrixFn, ConnectedComponentsFn, CostOfEdgeFn, DegreeFn, EdgesOfGraphFn, InDegreeFn,
+    IsConnectedFn, NeighborsWithinFn, NeighbourOfNodeFn, NeighboursOfNodeInGraphFn, NodeWeightFn,
+    NodesOfGraphFn, OutDegreeFn, ShortestPathFn, SourceOfEdgeFn, SubgraphFn, TargetOfEdgeFn,
+    ToDotFn,
 };
+use crate::runtime_builtin::functions::{ConcatStrings, SplitString};
 use crate::runtime_builtin::functions::{EnumerateArray, LenOfIterableFn};
 use crate::runtime_builtin::functions::{FunctionCall, RoocFunction};
+use crate::runtime_builtin::functions::{KeysOfMapFn, ValuesOfMapFn};
+use crate::runtime_builtin::functions::{ToIntegerFn, ToNumberFn, ToStringFn};
 use crate::traits::ToLatex;
 use crate::{Constant, Primitive};
 use indexmap::IndexMap;
 
-use super::{ArrayDifference, ArrayIntersection, ArrayUnion};
+use super::{
+    ArrayDifference, ArrayIntersection, ArrayUnion, ContainsFn, FlattenFn, NumericFold,
+    NumericFoldFn, ReverseArray, TransposeMatrix,
+};
 
 pub fn make_std() -> IndexMap<String, Box<dyn RoocFunction>> {
     let mut m: IndexMap<String, Box<dyn RoocFunction>> = IndexMap::new();
@@ -75,11 +84,75 @@ pub fn make_std() -> IndexMap<String, Box<dyn RoocFunction>> {
             shorthand_name: true,
         }),
     );
+    m.insert("shortest_path".to_string(), Box::new(ShortestPathFn {}));
+    m.insert(
+        "neighbors_within".to_string(),
+        Box::new(NeighborsWithinFn {}),
+    );
+    m.insert("subgraph".to_string(), Box::new(SubgraphFn {}));
+    m.insert("degree".to_string(), Box::new(DegreeFn {}));
+    m.insert("in_degree".to_string(), Box::new(InDegreeFn {}));
+    m.insert("out_degree".to_string(), Box::new(OutDegreeFn {}));
+    m.insert("node_weight".to_string(), Box::new(NodeWeightFn {}));
+    m.insert("cost".to_string(), Box::new(CostOfEdgeFn {}));
+    m.insert("source".to_string(), Box::new(SourceOfEdgeFn {}));
+    m.insert("target".to_string(), Box::new(TargetOfEdgeFn {}));
+    m.insert("to_dot".to_string(), Box::new(ToDotFn {}));
+    m.insert(
+        "connected_components".to_string(),
+        Box::new(ConnectedComponentsFn {}),
+    );
+    m.insert("is_connected".to_string(), Box::new(IsConnectedFn {}));
+    m.insert(
+        "adjacency_matrix".to_string(),
+        Box::new(AdjacencyMatrixFn {}),
+    );
     m.insert("range".to_string(), Box::new(NumericRange {}));
     m.insert("zip".to_string(), Box::new(ZipArrays {}));
     m.insert("difference".to_string(), Box::new(ArrayDifference {}));
     m.insert("union".to_string(), Box::new(ArrayUnion {}));
     m.insert("intersection".to_string(), Box::new(ArrayIntersection {}));
+    m.insert("contains".to_string(), Box::new(ContainsFn {}));
+    m.insert("keys".to_string(), Box::new(KeysOfMapFn {}));
+    m.insert("values".to_string(), Box::new(ValuesOfMapFn {}));
+    m.insert("transpose".to_string(), Box::new(TransposeMatrix {}));
+    m.insert("reverse".to_string(), Box::new(ReverseArray {}));
+    m.insert("flatten".to_string(), Box::new(FlattenFn {}));
+    m.insert("concat".to_string(), Box::new(ConcatStrings {}));
+    m.insert("split".to_string(), Box::new(SplitString {}));
+    m.insert("to_number".to_string(), Box::new(ToNumberFn {}));
+    m.insert("to_integer".to_string(), Box::new(ToIntegerFn {}));
+    m.insert("to_string".to_string(), Box::new(ToStringFn {}));
+    m.insert(
+        "sum".to_string(),
+        Box::new(NumericFoldFn {
+            fold: NumericFold::Sum,
+        }),
+    );
+    m.insert(
+        "prod".to_string(),
+        Box::new(NumericFoldFn {
+            fold: NumericFold::Prod,
+        }),
+    );
+    m.insert(
+        "avg".to_string(),
+        Box::new(NumericFoldFn {
+            fold: NumericFold::Avg,
+        }),
+    );
+    m.insert(
+        "min".to_string(),
+        Box::new(NumericFoldFn {
+            fold: NumericFold::Min,
+        }),
+    );
+    m.insert(
+        "max".to_string(),
+        Box::new(NumericFoldFn {
+            fold: NumericFold::Max,
+        }),
+    );
     m
 }
 
@@ -1,13 +1,62 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    solve_binary_lp_problem, solve_milp_lp_problem, solve_real_lp_problem_clarabel, Assignment,
-    IntOrBoolValue, LinearModel, LpSolution, MILPValue, SolverError, VariableType,
+    solve_binary_lp_problem, solve_integer_binary_lp_problem, solve_milp_lp_problem,
+    solve_real_lp_problem_clarabel, solve_real_lp_problem_slow_simplex_with_options, Assignment,
+    IntOrBoolValue, LinearModel, LpSolution, MILPValue, SimplexOptions, SolverError, VariableType,
 };
 
+/// The solvers that a linear model can be explicitly sent to with [`solve_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolverKind {
+    /// The tableau simplex solver, only accepts real and non negative real variables
+    Simplex,
+    /// The constraint programming solver, only accepts boolean and integer variables
+    BinaryAndInteger,
+    /// The branch and bound solver, accepts any kind of variable
+    MILP,
+    /// The interior point solver, only accepts real and non negative real variables
+    Real,
+}
+
+/// Solves a linear programming problem with the given solver.
+///
+/// The model is never relaxed to fit the solver, if it contains variables that the solver
+/// cannot handle, like integers sent to the simplex, an [`SolverError::InvalidDomain`] is returned
+/// listing the offending variables.
+///
+/// # Arguments
+/// * `lp` - The linear programming model to solve
+/// * `kind` - The solver to use
+///
+/// # Returns
+/// * `Ok(LpSolution<MILPValue>)` - The optimal solution if found
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+pub fn solve_with(
+    lp: &LinearModel,
+    kind: SolverKind,
+) -> Result<LpSolution<MILPValue>, SolverError> {
+    match kind {
+        SolverKind::Simplex => {
+            solve_real_lp_problem_slow_simplex_with_options(lp, &SimplexOptions::default())
+                .map(real_to_milp)
+        }
+        SolverKind::BinaryAndInteger => solve_integer_binary_lp_problem(lp).map(int_bool_to_milp),
+        SolverKind::MILP => solve_milp_lp_problem(lp),
+        SolverKind::Real => solve_real_lp_problem_clarabel(lp).map(real_to_milp),
+    }
+}
+
 /// Solves a any kind of linear programming problem by picking the right solver for the model.
 ///
 /// Takes a linear model containing real, non-negative real, boolean, and integer variables and returns
 /// an optimal solution or an error if the problem cannot be solved.
 ///
+/// The solver is picked by looking at the types of the variables in the domain:
+/// - only boolean variables use the binary solver
+/// - only real variables use the interior point solver, like [`SolverKind::Real`]
+/// - any integer variable, or a mix of boolean and real variables, uses [`SolverKind::MILP`]
+///
 /// # Arguments
 /// * `lp` - Any kind of linear programming model to solve
 ///
@@ -46,9 +95,12 @@ pub fn auto_solver(lp: &LinearModel) -> Result<LpSolution<MILPValue>, SolverErro
     let has_binary = domain
         .values()
         .any(|v| *v.get_type() == VariableType::Boolean);
-    let has_integer = domain
-        .values()
-        .any(|v| matches!(v.get_type(), VariableType::IntegerRange(_, _)));
+    let has_integer = domain.values().any(|v| {
+        matches!(
+            v.get_type(),
+            VariableType::IntegerRange(_, _) | VariableType::Integer(_, _)
+        )
+    });
     let has_real = domain.values().any(|v| {
         matches!(
             v.get_type(),
@@ -79,7 +131,6 @@ fn bool_to_milp(val: LpSolution<bool>) -> LpSolution<MILPValue> {
     LpSolution::new(values, val.value())
 }
 
-#[allow(unused)]
 fn int_bool_to_milp(val: LpSolution<IntOrBoolValue>) -> LpSolution<MILPValue> {
     let values = val
         .assignment()
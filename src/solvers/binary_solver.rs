@@ -126,8 +126,10 @@ pub fn solve_binary_lp_problem(lp: &LinearModel) -> Result<LpSolution<bool>, Sol
                 .collect::<Vec<Assignment<bool>>>();
             let value = solution[objective] as f64 + lp.objective_offset();
             assignment.sort_by(|a, b| a.name.cmp(&b.name));
-            let sol = LpSolution::new(assignment, value);
-            Ok(sol)
+            match lp.optimization_type() {
+                OptimizationType::Satisfy => Ok(LpSolution::new_feasible(assignment)),
+                _ => Ok(LpSolution::new(assignment, value)),
+            }
         }
     }
 }
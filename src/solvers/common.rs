@@ -1,5 +1,7 @@
 use crate::math::{Comparison, OptimizationType, VariableType};
 use crate::parser::model_transformer::DomainVariable;
+use crate::solvers::{find_irreducible_infeasible_subset, SolveStats};
+use crate::transformers::LinearModel;
 use copper::views::{Times, ViewExt};
 use copper::{VarId, VarIdBinary};
 use indexmap::IndexMap;
@@ -33,6 +35,10 @@ pub enum SolverError {
     /// The problem has no feasible solution.
     Infisible,
 
+    /// The problem has no feasible solution, caused by the conflict between the given constraints.
+    /// - `constraints`: Names of the constraints in the irreducible infeasible subset
+    InfeasibleSubset { constraints: Vec<String> },
+
     /// A general error with a custom message.
     Other(String),
 
@@ -109,6 +115,13 @@ impl std::fmt::Display for SolverError {
             SolverError::Infisible => {
                 write!(f, "The problem is infeasible")
             }
+            SolverError::InfeasibleSubset { constraints } => {
+                write!(
+                    f,
+                    "The problem is infeasible, the conflicting constraints are: {}",
+                    constraints.join(", ")
+                )
+            }
             SolverError::UnimplementedOptimizationType { expected, got } => {
                 write!(
                     f,
@@ -120,6 +133,33 @@ impl std::fmt::Display for SolverError {
     }
 }
 
+impl SolverError {
+    /// If the error is an infeasibility, computes the irreducible infeasible subset of the model
+    /// and returns it as an [`SolverError::InfeasibleSubset`], otherwise returns the error unchanged.
+    ///
+    /// # Arguments
+    /// * `lp` - The model that produced the error
+    pub fn with_infeasible_subset(self, lp: &LinearModel) -> SolverError {
+        match self {
+            SolverError::Infisible => match find_irreducible_infeasible_subset(lp) {
+                Ok(constraints) if !constraints.is_empty() => {
+                    SolverError::InfeasibleSubset { constraints }
+                }
+                _ => SolverError::Infisible,
+            },
+            e => e,
+        }
+    }
+
+    /// Returns the names of the conflicting constraints if the error carries an infeasible subset.
+    pub fn infeasible_subset(&self) -> Option<&Vec<String>> {
+        match self {
+            SolverError::InfeasibleSubset { constraints } => Some(constraints),
+            _ => None,
+        }
+    }
+}
+
 /// Represents a variable assignment in a solution.
 /// - `T`: The type of the variable's value
 #[derive(Debug, Clone, Serialize)]
@@ -140,11 +180,17 @@ impl<T: Clone + Serialize + Copy + Display> Display for Assignment<T> {
 pub struct LpSolution<T: Clone + Serialize + Copy + Display> {
     assignment: Vec<Assignment<T>>,
     value: f64,
+    feasibility_only: bool,
+    stats: Option<SolveStats>,
 }
 
 impl<T: Clone + Serialize + Copy + Display> Display for LpSolution<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Optimal value: {}\n\n", self.value)?;
+        if self.feasibility_only {
+            write!(f, "Feasible solution found\n\n")?;
+        } else {
+            write!(f, "Optimal value: {}\n\n", self.value)?;
+        }
         write!(
             f,
             "Variables:\n{}",
@@ -164,7 +210,48 @@ impl<T: Clone + Serialize + Copy + Display> LpSolution<T> {
     /// * `assignment` - Vector of variable assignments
     /// * `value` - The objective function value at this solution
     pub fn new(assignment: Vec<Assignment<T>>, value: f64) -> Self {
-        Self { assignment, value }
+        Self {
+            assignment,
+            value,
+            feasibility_only: false,
+            stats: None,
+        }
+    }
+
+    /// Creates a solution of a `satisfy` problem, which is any feasible point and has no objective value.
+    ///
+    /// # Arguments
+    /// * `assignment` - Vector of variable assignments
+    pub fn new_feasible(assignment: Vec<Assignment<T>>) -> Self {
+        Self {
+            assignment,
+            value: 0.0,
+            feasibility_only: true,
+            stats: None,
+        }
+    }
+
+    /// Replaces the assignments of the solution, keeping its objective value and statistics.
+    pub(crate) fn with_assignment(mut self, assignment: Vec<Assignment<T>>) -> Self {
+        self.assignment = assignment;
+        self
+    }
+
+    /// Attaches the statistics of the search that found this solution.
+    pub fn with_stats(mut self, stats: SolveStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Returns the statistics of the search that found this solution, if the solver collects them.
+    pub fn stats(&self) -> Option<&SolveStats> {
+        self.stats.as_ref()
+    }
+
+    /// Returns true if the solution is only a feasible point of a `satisfy` problem,
+    /// rather than the optimum of an objective.
+    pub fn is_feasibility_only(&self) -> bool {
+        self.feasibility_only
     }
 
     /// Returns a reference to the vector of variable assignments.
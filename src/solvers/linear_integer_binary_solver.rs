@@ -4,6 +4,7 @@ use crate::solvers::common::{
     SolverError,
 };
 use crate::transformers::LinearModel;
+use copper::views::ViewExt;
 use copper::*;
 use indexmap::IndexMap;
 use num_traits::ToPrimitive;
@@ -33,6 +34,7 @@ impl Display for IntOrBoolValue {
 /// Takes a linear model containing boolean and integer variables and returns an optimal solution
 /// or an error if the problem cannot be solved.
 /// All coefficients and constant numbers will be converted into an i32
+/// and every constraint, equalities included, is checked exactly on the values of the solution.
 ///
 /// # Arguments
 /// * `lp` - The linear programming model to solve
@@ -103,6 +105,63 @@ pub fn solve_integer_binary_lp_problem(
         })
         .collect::<IndexMap<_, _>>();
 
+    let CopperModel {
+        model,
+        vars_binary,
+        vars_integer,
+        objective,
+    } = build_copper_model(lp, &binary_variables, &integer_variables)?;
+    let solution = match lp.optimization_type() {
+        OptimizationType::Max => model.maximize(objective),
+        OptimizationType::Min => model.minimize(objective),
+        OptimizationType::Satisfy => model.solve(),
+    };
+
+    match solution {
+        None => Err(SolverError::DidNotSolve),
+        Some(solution) => {
+            let values = variable_values(
+                &solution,
+                &vars_binary,
+                &vars_integer,
+                &binary_variables,
+                &integer_variables,
+            );
+            let vars = lp.variables();
+            let mut assignment = binary_variables
+                .values()
+                .map(|i| Assignment {
+                    name: vars[*i].clone(),
+                    value: IntOrBoolValue::Bool(values[*i] != 0),
+                })
+                .chain(integer_variables.values().map(|i| Assignment {
+                    name: vars[*i].clone(),
+                    value: IntOrBoolValue::Int(values[*i] as i32),
+                }))
+                .collect::<Vec<Assignment<IntOrBoolValue>>>();
+            assignment.sort_by(|a, b| a.name.cmp(&b.name));
+            let value = solution[objective] as f64 + lp.objective_offset();
+            match lp.optimization_type() {
+                OptimizationType::Satisfy => Ok(LpSolution::new_feasible(assignment)),
+                _ => Ok(LpSolution::new(assignment, value)),
+            }
+        }
+    }
+}
+
+/// The copper model of a linear model, with its variables and objective expression
+struct CopperModel {
+    model: Model,
+    vars_binary: Vec<VarIdBinary>,
+    vars_integer: Vec<VarId>,
+    objective: VarId,
+}
+
+fn build_copper_model(
+    lp: &LinearModel,
+    binary_variables: &IndexMap<String, usize>,
+    integer_variables: &IndexMap<String, usize>,
+) -> Result<CopperModel, SolverError> {
     let mut m = Model::default();
     let vars_binary: Vec<_> = m.new_vars_binary(binary_variables.len()).collect();
     let vars_integer: Option<Vec<_>> = integer_variables
@@ -127,27 +186,35 @@ pub fn solve_integer_binary_lp_problem(
     };
     let vars = lp.variables();
     for (i, constraint) in lp.constraints().iter().enumerate() {
-        let lhs_binary =
-            process_variables_binary(constraint.coefficients().iter(), vars_binary.iter(), |i| {
-                binary_variables.get(&vars[i]).is_some()
-            });
-        let lhs_integer =
-            process_variables(constraint.coefficients().iter(), vars_integer.iter(), |i| {
-                integer_variables.get(&vars[i]).is_some()
-            });
-        if lhs_binary.is_none() || lhs_integer.is_none() {
-            return Err(SolverError::TooLarge {
-                name: format!("variable in constraint {}", i + 1),
-                value: *constraint
-                    .coefficients()
-                    .iter()
-                    .find(|c| c.to_f64().is_none())
-                    .unwrap_or(&0.0),
-            });
-        }
+        let coefficients = constraint
+            .coefficients()
+            .iter()
+            .map(|c| c.to_i32())
+            .collect::<Option<Vec<_>>>();
+        let coefficients = match coefficients {
+            Some(coefficients) => coefficients,
+            None => {
+                return Err(SolverError::TooLarge {
+                    name: format!("variable in constraint {}", i + 1),
+                    value: *constraint
+                        .coefficients()
+                        .iter()
+                        .find(|c| c.to_i32().is_none())
+                        .unwrap_or(&0.0),
+                })
+            }
+        };
+        let lhs_binary = binary_variables
+            .values()
+            .zip(vars_binary.iter())
+            .map(|(i, v)| v.times(coefficients[*i]))
+            .collect::<Vec<_>>();
+        let lhs_integer = integer_variables
+            .values()
+            .zip(vars_integer.iter())
+            .map(|(i, v)| v.times(coefficients[*i]))
+            .collect::<Vec<_>>();
         let mut exprs = vec![];
-        let lhs_binary = lhs_binary.unwrap();
-        let lhs_integer = lhs_integer.unwrap();
         if !lhs_binary.is_empty() {
             exprs.push(m.sum_iter(lhs_binary));
         }
@@ -217,54 +284,41 @@ pub fn solve_integer_binary_lp_problem(
         1 => obj_exprs[0],
         _ => m.sum(&obj_exprs),
     };
+    //workaround for copper accepting a solution as soon as every variable is assigned, without
+    //propagating the constraints that were still scheduled, so it can return assignments that
+    //violate them. Copper branches on the first unassigned variable, so this one stays unassigned
+    //until every constraint on the others was propagated. Removing it makes the
+    //should_enforce_equality_exactly_in_integer_binary_solver test accept infeasible assignments
+    let _guard = m.new_var(0, 1);
 
-    let solution = match lp.optimization_type() {
-        OptimizationType::Max => m.maximize(objective),
-        OptimizationType::Min => m.minimize(objective),
-        OptimizationType::Satisfy => m.solve(),
-    };
-
-    let rev_binary_variables = binary_variables
-        .iter()
-        .map(|(name, i)| (i, name))
-        .collect::<IndexMap<_, _>>();
-    let rev_integer_variables = integer_variables
-        .iter()
-        .map(|(name, i)| (i, name))
-        .collect::<IndexMap<_, _>>();
+    Ok(CopperModel {
+        model: m,
+        vars_binary,
+        vars_integer,
+        objective,
+    })
+}
 
-    match solution {
-        None => Err(SolverError::DidNotSolve),
-        Some(solution) => {
-            let mut assignment = solution
-                .get_values_binary(&vars_binary)
-                .iter()
-                .enumerate()
-                .filter_map(|(v, n)| {
-                    let name = rev_binary_variables.get(&v);
-                    name.map(|name| Assignment {
-                        name: (*name).clone(),
-                        value: IntOrBoolValue::Bool(*n),
-                    })
-                })
-                .chain(
-                    solution
-                        .get_values(&vars_integer)
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(v, n)| {
-                            let name = rev_integer_variables.get(&v);
-                            name.map(|name| Assignment {
-                                name: (*name).clone(),
-                                value: IntOrBoolValue::Int(*n),
-                            })
-                        }),
-                )
-                .collect::<Vec<Assignment<IntOrBoolValue>>>();
-            assignment.sort_by(|a, b| a.name.cmp(&b.name));
-            let value = solution[objective] as f64 + lp.objective_offset();
-            let sol = LpSolution::new(assignment, value);
-            Ok(sol)
-        }
+/// Values of the variables of the model, by their index, taken from a copper solution
+fn variable_values(
+    solution: &Solution,
+    vars_binary: &[VarIdBinary],
+    vars_integer: &[VarId],
+    binary_variables: &IndexMap<String, usize>,
+    integer_variables: &IndexMap<String, usize>,
+) -> Vec<i64> {
+    let mut values = vec![0; binary_variables.len() + integer_variables.len()];
+    for (i, value) in binary_variables
+        .values()
+        .zip(solution.get_values_binary(vars_binary))
+    {
+        values[*i] = value as i64;
+    }
+    for (i, value) in integer_variables
+        .values()
+        .zip(solution.get_values(vars_integer))
+    {
+        values[*i] = value as i64;
     }
+    values
 }
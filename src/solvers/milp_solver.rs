@@ -1,6 +1,10 @@
+#[allow(unused_imports)]
+use crate::prelude::*;
 use crate::solvers::common::{LpSolution, SolverError};
+use crate::solvers::{generate_gomory_cuts_for, SimplexOptions};
 use crate::transformers::LinearModel;
 use crate::{Assignment, Comparison, OptimizationType, VariableType};
+use indexmap::IndexMap;
 use microlp::{ComparisonOp, Error, OptimizationDirection, Problem};
 use serde::Serialize;
 use std::fmt::{Display, Formatter};
@@ -25,7 +29,559 @@ impl Display for MILPValue {
         }
     }
 }
-/// Solves a mixed-integer linear programming problem using the MicroLP solver.
+/// Options used to configure the branch and bound of the MILP solver.
+#[derive(Debug, Clone)]
+pub struct MILPOptions {
+    /// Seed used to break ties when picking the variable to branch on.
+    /// When `None`, ties are broken by always picking the first candidate.
+    pub seed: Option<u64>,
+    /// Rounds the root relaxation to try to find an incumbent before branching
+    pub rounding_heuristic: bool,
+    /// Feasible assignment of every variable, used as the first incumbent to prune the search.
+    /// When `None`, the initial values declared in the model are used if every variable has one
+    pub warm_start: Option<IndexMap<String, f64>>,
+    /// Stops the search once the absolute difference between the best bound and the
+    /// incumbent objective is at most this value
+    pub mip_gap_abs: Option<f64>,
+    /// Stops the search once the difference between the best bound and the incumbent
+    /// objective, relative to the incumbent objective, is at most this value
+    pub mip_gap_rel: Option<f64>,
+    /// Maximum number of rounds of Gomory cuts added to the root relaxation before branching,
+    /// zero disables the cuts
+    pub cut_rounds: usize,
+    /// Returns the first feasible solution found by the search, without proving that it is optimal
+    pub first_feasible: bool,
+    /// Distance from the nearest integer within which a value is considered integer, and by
+    /// which a constraint can be violated while still being considered satisfied
+    pub epsilon: f64,
+}
+
+impl Default for MILPOptions {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            rounding_heuristic: false,
+            warm_start: None,
+            mip_gap_abs: None,
+            mip_gap_rel: None,
+            cut_rounds: 0,
+            first_feasible: false,
+            epsilon: DEFAULT_EPSILON,
+        }
+    }
+}
+
+impl MILPOptions {
+    /// Creates the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the seed used to break ties during the search
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Enables or disables the rounding heuristic applied to the root relaxation
+    pub fn with_rounding_heuristic(mut self, enabled: bool) -> Self {
+        self.rounding_heuristic = enabled;
+        self
+    }
+
+    /// Sets the assignment used as the starting incumbent of the search
+    pub fn with_warm_start(mut self, assignment: IndexMap<String, f64>) -> Self {
+        self.warm_start = Some(assignment);
+        self
+    }
+
+    /// Sets the absolute gap at which the search returns the incumbent
+    pub fn with_mip_gap_abs(mut self, gap: f64) -> Self {
+        self.mip_gap_abs = Some(gap);
+        self
+    }
+
+    /// Sets the relative gap at which the search returns the incumbent
+    pub fn with_mip_gap_rel(mut self, gap: f64) -> Self {
+        self.mip_gap_rel = Some(gap);
+        self
+    }
+
+    /// Sets the maximum number of rounds of Gomory cuts added before branching
+    pub fn with_cut_rounds(mut self, max_rounds: usize) -> Self {
+        self.cut_rounds = max_rounds;
+        self
+    }
+
+    /// Enables or disables returning as soon as a feasible solution is found
+    pub fn with_first_feasible(mut self, enabled: bool) -> Self {
+        self.first_feasible = enabled;
+        self
+    }
+
+    /// Sets the tolerance used to decide if a value is integer and if a constraint is satisfied
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Whether the difference between a bound and an incumbent objective is within the gap tolerances
+    fn is_within_gap(&self, bound: f64, value: f64) -> bool {
+        self.mip_gap_abs
+            .is_some_and(|gap| (bound - value).abs() <= gap)
+            || self
+                .mip_gap_rel
+                .is_some_and(|gap| relative_gap(bound, value).is_some_and(|r| r <= gap))
+    }
+}
+
+/// Difference between a bound and an objective value relative to the objective value,
+/// missing when the objective value is zero
+fn relative_gap(bound: f64, value: f64) -> Option<f64> {
+    if value.abs() > INTEGRALITY_TOLERANCE {
+        Some((bound - value).abs() / value.abs())
+    } else if bound == value {
+        Some(0.0)
+    } else {
+        None
+    }
+}
+
+/// Statistics collected while searching for the solution of a MILP problem.
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+pub struct SolveStats {
+    /// Number of branch and bound nodes whose relaxation was solved
+    pub nodes_explored: usize,
+    /// Number of nodes explored when the first feasible solution was found,
+    /// zero if it was given as a warm start
+    pub nodes_to_first_incumbent: Option<usize>,
+    /// Number of times a better feasible solution was found, including the warm start
+    pub incumbents_found: usize,
+    /// Bound on the objective value proven by the search when it stopped, no feasible solution
+    /// can be better than it. It equals the objective value of the solution when the search was
+    /// run to optimality
+    pub best_bound: Option<f64>,
+    /// Relative gap between the best bound and the objective value of the solution,
+    /// zero for an optimal solution and missing when the objective value is zero.
+    /// It is the same gap that is compared against the relative gap tolerance
+    pub gap: Option<f64>,
+    /// Number of Gomory cuts added to the root relaxation
+    pub cuts_added: usize,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const ISolveStats: &'static str = r#"
+export type SolveStats = {
+    nodes_explored: number
+    nodes_to_first_incumbent: number | null
+    incumbents_found: number
+    best_bound: number | null
+    gap: number | null
+    cuts_added: number
+}
+"#;
+
+const INTEGRALITY_TOLERANCE: f64 = 1e-6;
+const DEFAULT_EPSILON: f64 = 1e-9;
+
+/// Small xorshift generator, used to make the arbitrary choices of the search reproducible.
+struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    fn new(seed: u64) -> Self {
+        //splitmix64 step so that close seeds produce unrelated states
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Self {
+            state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z },
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    fn next_below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+struct Relaxation {
+    values: Vec<f64>,
+    objective: f64,
+}
+
+fn is_integer_variable(var_type: &VariableType) -> bool {
+    var_type.is_integer()
+}
+
+fn is_better(value: f64, other: f64, optimization_type: &OptimizationType) -> bool {
+    match optimization_type {
+        OptimizationType::Max => value > other + INTEGRALITY_TOLERANCE,
+        OptimizationType::Min | OptimizationType::Satisfy => value < other - INTEGRALITY_TOLERANCE,
+    }
+}
+
+/// Solves the continuous relaxation with the tableau simplex and returns its Gomory cuts,
+/// written in terms of the variables of the model.
+///
+/// The slack variables of the tableau are replaced with the constraint they come from, a slack
+/// is integer only if its constraint has integer coefficients and only integer variables.
+/// The values within `epsilon` of an integer are considered integral.
+fn gomory_cut_round(lp: &LinearModel, epsilon: f64) -> Option<Vec<(Vec<f64>, f64)>> {
+    let variables = lp.variables();
+    let domain = lp.domain();
+    let mut relaxation = LinearModel::new();
+    let mut integer_variables = Vec::with_capacity(variables.len());
+    for name in variables {
+        let (relaxed, is_integer) = match domain.get(name)?.get_type() {
+            VariableType::Boolean => (VariableType::NonNegativeReal(0.0, 1.0), true),
+            VariableType::IntegerRange(min, max) if *min >= 0 => {
+                let max = if *max == i32::MAX {
+                    f64::INFINITY
+                } else {
+                    *max as f64
+                };
+                (VariableType::NonNegativeReal(*min as f64, max), true)
+            }
+            VariableType::Integer(min, max) if *min >= 0.0 => {
+                (VariableType::NonNegativeReal(*min, *max), true)
+            }
+            VariableType::NonNegativeReal(min, max) => {
+                (VariableType::NonNegativeReal(*min, *max), false)
+            }
+            //variables that could be negative are split by the standardizer
+            _ => return None,
+        };
+        relaxation.add_variable(name, relaxed);
+        integer_variables.push(is_integer);
+    }
+    for constraint in lp.constraints() {
+        relaxation.add_constraint(
+            constraint.coefficients().clone(),
+            *constraint.constraint_type(),
+            constraint.rhs(),
+        );
+    }
+    relaxation.set_objective(lp.objective().clone(), lp.optimization_type().clone());
+    let optimal = relaxation
+        .into_standard_form()
+        .ok()?
+        .into_tableau()
+        .ok()?
+        .solve_with_options(&SimplexOptions::default())
+        .ok()?;
+    let tableau = optimal.tableau();
+    let n = variables.len();
+    if tableau.variables().len() < n || tableau.variables()[..n] != variables[..] {
+        return None;
+    }
+    let original_a = tableau.original_a_matrix();
+    //the right hand side of the standard model, as the current solution satisfies it
+    let original_b = original_a
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(optimal.variables_values().iter())
+                .map(|(a, x)| a * x)
+                .sum::<f64>()
+        })
+        .collect::<Vec<_>>();
+    let is_integral = |v: &f64| (v - v.round()).abs() <= epsilon;
+    //the row of the standard model each slack variable belongs to
+    let slack_rows = (n..tableau.variables().len())
+        .map(|k| {
+            let rows = (0..original_a.len())
+                .filter(|i| original_a[*i][k].abs() > INTEGRALITY_TOLERANCE)
+                .collect::<Vec<_>>();
+            match rows[..] {
+                [row] => Some(row),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let mut integer_columns = integer_variables.clone();
+    for (offset, row) in slack_rows.iter().enumerate() {
+        let k = n + offset;
+        let integer_row = original_a[*row].iter().enumerate().all(|(j, a)| {
+            j == k
+                || (is_integral(a)
+                    && (a.abs() <= INTEGRALITY_TOLERANCE || j < n && integer_variables[j]))
+        });
+        integer_columns.push(
+            integer_row && is_integral(&original_b[*row]) && is_integral(&original_a[*row][k]),
+        );
+    }
+    let cuts = generate_gomory_cuts_for(&optimal, &integer_columns, epsilon)
+        .into_iter()
+        .filter_map(|cut| {
+            let mut coefficients = cut.coefficients()[..n].to_vec();
+            let mut rhs = cut.rhs();
+            for (offset, row) in slack_rows.iter().enumerate() {
+                let k = n + offset;
+                let coefficient = cut.coefficients()[k];
+                if coefficient == 0.0 {
+                    continue;
+                }
+                let slack = original_a[*row][k];
+                //the slack is (b - a * x) / slack, which must not depend on other slack variables
+                if original_a[*row][n..]
+                    .iter()
+                    .enumerate()
+                    .any(|(j, a)| n + j != k && a.abs() > INTEGRALITY_TOLERANCE)
+                {
+                    return None;
+                }
+                for (j, c) in coefficients.iter_mut().enumerate() {
+                    *c -= coefficient * original_a[*row][j] / slack;
+                }
+                rhs -= coefficient * original_b[*row] / slack;
+            }
+            Some((coefficients, rhs))
+        })
+        .collect();
+    Some(cuts)
+}
+
+/// Adds rounds of Gomory cuts to a copy of the model, until no more cuts are found or the
+/// maximum number of rounds is reached, returning it with the number of cuts added
+fn add_gomory_cuts(lp: &LinearModel, max_rounds: usize, epsilon: f64) -> (LinearModel, usize) {
+    let mut model = lp.clone();
+    let mut cuts_added = 0;
+    for _ in 0..max_rounds {
+        let cuts = match gomory_cut_round(&model, epsilon) {
+            Some(cuts) if !cuts.is_empty() => cuts,
+            _ => break,
+        };
+        cuts_added += cuts.len();
+        for (coefficients, rhs) in cuts {
+            model.add_constraint(coefficients, Comparison::GreaterOrEqual, rhs);
+        }
+    }
+    (model, cuts_added)
+}
+
+/// Returns the type of each variable of the model, together with its lower and upper bounds
+fn variable_bounds(lp: &LinearModel) -> (Vec<VariableType>, Vec<f64>, Vec<f64>) {
+    let domain = lp.domain();
+    let types = lp
+        .variables()
+        .iter()
+        .map(|var| *domain.get(var).unwrap().get_type())
+        .collect::<Vec<_>>();
+    let (lower, upper): (Vec<f64>, Vec<f64>) = types
+        .iter()
+        .map(|t| match t {
+            VariableType::Boolean => (0.0, 1.0),
+            VariableType::IntegerRange(min, max) => (*min as f64, *max as f64),
+            VariableType::Real(min, max)
+            | VariableType::NonNegativeReal(min, max)
+            | VariableType::Integer(min, max) => (*min, *max),
+        })
+        .unzip();
+    (types, lower, upper)
+}
+
+/// Converts the values of a relaxation into the assignment of the variables, following their types
+fn to_assignment(
+    values: &[f64],
+    variables: &[String],
+    types: &[VariableType],
+) -> Vec<Assignment<MILPValue>> {
+    values
+        .iter()
+        .zip(variables)
+        .zip(types.iter())
+        .map(|((value, name), var_type)| {
+            let value = match var_type {
+                VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _) => {
+                    MILPValue::Real(*value)
+                }
+                VariableType::IntegerRange(_, _) | VariableType::Integer(_, _) => {
+                    MILPValue::Int(value.round() as i32)
+                }
+                VariableType::Boolean => MILPValue::Bool(value.round() != 0.0),
+            };
+            Assignment {
+                name: name.clone(),
+                value,
+            }
+        })
+        .collect()
+}
+
+/// Returns whichever of the two objective values is better
+fn best_of(value: f64, other: f64, optimization_type: &OptimizationType) -> f64 {
+    if is_better(other, value, optimization_type) {
+        other
+    } else {
+        value
+    }
+}
+
+/// Solves the continuous relaxation of the problem, using the given bounds for the variables
+fn solve_relaxation(
+    lp: &LinearModel,
+    lower: &[f64],
+    upper: &[f64],
+) -> Result<Relaxation, SolverError> {
+    let opt_type = match lp.optimization_type() {
+        OptimizationType::Max => OptimizationDirection::Maximize,
+        OptimizationType::Min => OptimizationDirection::Minimize,
+        OptimizationType::Satisfy => OptimizationDirection::Minimize,
+    };
+    let mut problem = Problem::new(opt_type);
+    let microlp_vars = lp
+        .objective()
+        .iter()
+        .enumerate()
+        .map(|(i, coeff)| problem.add_var(*coeff, (lower[i], upper[i])))
+        .collect::<Vec<_>>();
+    for constraint in lp.constraints() {
+        let microlp_comparison_type = match constraint.constraint_type() {
+            Comparison::LessOrEqual => ComparisonOp::Le,
+            Comparison::GreaterOrEqual => ComparisonOp::Ge,
+            Comparison::Equal => ComparisonOp::Eq,
+            c => {
+                return Err(SolverError::UnavailableComparison {
+                    got: *c,
+                    expected: vec![
+                        Comparison::LessOrEqual,
+                        Comparison::GreaterOrEqual,
+                        Comparison::Equal,
+                    ],
+                })
+            }
+        };
+        let microlp_coeffs = microlp_vars
+            .iter()
+            .zip(constraint.coefficients().iter())
+            .map(|(v, c)| (*v, *c))
+            .collect::<Vec<_>>();
+        problem.add_constraint(microlp_coeffs, microlp_comparison_type, constraint.rhs());
+    }
+    match problem.solve() {
+        Ok(s) => Ok(Relaxation {
+            values: microlp_vars.iter().map(|v| *s.var_value(*v)).collect(),
+            objective: s.objective(),
+        }),
+        Err(e) => Err(match e {
+            Error::InternalError(s) => SolverError::Other(s),
+            Error::Unbounded => SolverError::Unbounded,
+            Error::Infeasible => SolverError::Infisible,
+        }),
+    }
+}
+
+/// Checks that the warm start assigns every variable a value inside its bounds, integral when
+/// required, and that it satisfies the constraints, returning it as an incumbent
+fn warm_start_incumbent(
+    lp: &LinearModel,
+    assignment: &IndexMap<String, f64>,
+    types: &[VariableType],
+    lower: &[f64],
+    upper: &[f64],
+    epsilon: f64,
+) -> Result<Relaxation, SolverError> {
+    let variables = lp.variables();
+    if let Some(name) = assignment.keys().find(|name| !variables.contains(name)) {
+        return Err(SolverError::Other(format!(
+            "Invalid warm start, variable \"{}\" is not part of the model",
+            name
+        )));
+    }
+    let mut values = Vec::with_capacity(variables.len());
+    for (i, name) in variables.iter().enumerate() {
+        let value = match assignment.get(name) {
+            Some(v) => *v,
+            None => {
+                return Err(SolverError::Other(format!(
+                    "Invalid warm start, missing value for variable \"{}\"",
+                    name
+                )))
+            }
+        };
+        if value < lower[i] - epsilon || value > upper[i] + epsilon {
+            return Err(SolverError::Other(format!(
+                "Invalid warm start, value {} of variable \"{}\" is outside of its domain {}",
+                value, name, types[i]
+            )));
+        }
+        if is_integer_variable(&types[i]) && (value - value.round()).abs() > epsilon {
+            return Err(SolverError::Other(format!(
+                "Invalid warm start, value {} of variable \"{}\" must be an integer",
+                value, name
+            )));
+        }
+        values.push(value);
+    }
+    for (i, constraint) in lp.constraints().iter().enumerate() {
+        let lhs = constraint
+            .coefficients()
+            .iter()
+            .zip(values.iter())
+            .map(|(c, v)| c * v)
+            .sum::<f64>();
+        let rhs = constraint.rhs();
+        let satisfied = match constraint.constraint_type() {
+            Comparison::LessOrEqual | Comparison::Less => lhs <= rhs + epsilon,
+            Comparison::GreaterOrEqual | Comparison::Greater => lhs >= rhs - epsilon,
+            Comparison::Equal => (lhs - rhs).abs() <= epsilon,
+        };
+        if !satisfied {
+            return Err(SolverError::Other(format!(
+                "Invalid warm start, constraint {} is not satisfied",
+                i + 1
+            )));
+        }
+    }
+    let objective = lp
+        .objective()
+        .iter()
+        .zip(values.iter())
+        .map(|(c, v)| c * v)
+        .sum();
+    Ok(Relaxation { values, objective })
+}
+
+/// Rounds the integer variables of the relaxation to the nearest value in their bounds
+/// and solves the problem with them fixed, returning the solution if it is feasible
+fn round_relaxation(
+    lp: &LinearModel,
+    relaxation: &Relaxation,
+    types: &[VariableType],
+    lower: &[f64],
+    upper: &[f64],
+) -> Result<Option<Relaxation>, SolverError> {
+    let mut lower = lower.to_vec();
+    let mut upper = upper.to_vec();
+    for (i, var_type) in types.iter().enumerate() {
+        if is_integer_variable(var_type) {
+            let rounded = relaxation.values[i].round().clamp(lower[i], upper[i]);
+            lower[i] = rounded;
+            upper[i] = rounded;
+        }
+    }
+    match solve_relaxation(lp, &lower, &upper) {
+        Ok(r) => Ok(Some(r)),
+        Err(SolverError::Infisible) | Err(SolverError::Unbounded) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Solves a mixed-integer linear programming problem using branch and bound,
+/// the relaxations are solved with the MicroLP solver.
 ///
 /// Takes a linear model containing real, non-negative real, boolean, and integer variables and returns
 /// an optimal solution or an error if the problem cannot be solved.
@@ -64,84 +620,338 @@ impl Display for MILPValue {
 /// let solution = solve_milp_lp_problem(&model).unwrap();
 /// ```
 pub fn solve_milp_lp_problem(lp: &LinearModel) -> Result<LpSolution<MILPValue>, SolverError> {
+    solve_milp_lp_problem_with_options(lp, &MILPOptions::default()).map(|(solution, _)| solution)
+}
+
+/// Solves a mixed-integer linear programming problem starting from a known feasible assignment.
+///
+/// The assignment is used as the first incumbent, so that the branch and bound can prune
+/// every node whose relaxation is not better than it. It must give a value to every variable
+/// of the model, inside its domain and integral for integer and boolean variables, and satisfy
+/// all the constraints, otherwise the warm start is rejected with an error.
+///
+/// # Arguments
+/// * `lp` - The mixed-integer linear programming model to solve
+/// * `assignment` - The value of each variable of the model, by name
+///
+/// # Returns
+/// * `Ok((LpSolution<MILPValue>, SolveStats))` - The optimal solution and the search statistics
+/// * `Err(SolverError)` - If the warm start is invalid or the problem cannot be solved
+pub fn solve_with_warm_start(
+    lp: &LinearModel,
+    assignment: IndexMap<String, f64>,
+) -> Result<(LpSolution<MILPValue>, SolveStats), SolverError> {
+    solve_milp_lp_problem_with_options(lp, &MILPOptions::new().with_warm_start(assignment))
+}
+
+/// Solves a mixed-integer linear programming problem using the given options,
+/// returning the solution together with the statistics of the search.
+///
+/// Runs with the same options (and seed) always explore the same nodes in the same order.
+/// When a gap tolerance is set, the search stops as soon as no remaining node can improve
+/// the incumbent by more than the tolerance, and the gap it achieved is reported in the statistics.
+/// With `first_feasible` the search stops at the first incumbent, the gap is then measured
+/// against the root relaxation.
+///
+/// # Arguments
+/// * `lp` - The mixed-integer linear programming model to solve
+/// * `options` - The options of the branch and bound
+///
+/// # Returns
+/// * `Ok((LpSolution<MILPValue>, SolveStats))` - The optimal solution and the search statistics
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+pub fn solve_milp_lp_problem_with_options(
+    lp: &LinearModel,
+    options: &MILPOptions,
+) -> Result<(LpSolution<MILPValue>, SolveStats), SolverError> {
+    let mut stats = SolveStats::default();
+    let model_with_cuts;
+    let lp = if options.cut_rounds > 0 {
+        let (model, cuts_added) = add_gomory_cuts(lp, options.cut_rounds, options.epsilon);
+        stats.cuts_added = cuts_added;
+        model_with_cuts = model;
+        &model_with_cuts
+    } else {
+        lp
+    };
     let variables = lp.variables();
-    let domain = lp.domain();
-    let objective = lp.objective();
-    let mut microlp_vars = Vec::with_capacity(variables.len());
-    let opt_type = match lp.optimization_type() {
-        OptimizationType::Max => OptimizationDirection::Maximize,
-        OptimizationType::Min => OptimizationDirection::Minimize,
-        OptimizationType::Satisfy => OptimizationDirection::Minimize,
+    let (types, lower, upper) = variable_bounds(lp);
+    let optimization_type = lp.optimization_type();
+    let mut rng = options.seed.map(SeededRng::new);
+    let mut incumbent = match &options.warm_start {
+        Some(assignment) => Some(warm_start_incumbent(
+            lp,
+            assignment,
+            &types,
+            &lower,
+            &upper,
+            options.epsilon,
+        )?),
+        //the initial values of the variables are only a hint, so they are skipped if infeasible
+        None => lp.initial_assignment().and_then(|assignment| {
+            warm_start_incumbent(lp, &assignment, &types, &lower, &upper, options.epsilon).ok()
+        }),
     };
-    let mut problem = Problem::new(opt_type);
-    for (i, var) in variables.iter().enumerate() {
-        let var_domain = domain.get(var).unwrap();
-        let coeff = objective[i];
-        let added_var = match var_domain.get_type() {
-            VariableType::Real(min, max) => problem.add_var(coeff, (*min, *max)),
-            VariableType::Boolean => problem.add_binary_var(coeff),
-            VariableType::IntegerRange(min, max) => problem.add_integer_var(coeff, (*min, *max)),
-            VariableType::NonNegativeReal(min, max) => problem.add_var(coeff, (*min, *max)),
-        };
-        microlp_vars.push(added_var);
+    if incumbent.is_some() {
+        stats.nodes_to_first_incumbent = Some(0);
+        stats.incumbents_found += 1;
     }
-
-    for constraint in lp.constraints() {
-        let coeffs = constraint.coefficients();
-        let rhs = constraint.rhs();
-        let comparison_type = constraint.constraint_type();
-        let microlp_comparison_type = match comparison_type {
-            Comparison::LessOrEqual => ComparisonOp::Le,
-            Comparison::GreaterOrEqual => ComparisonOp::Ge,
-            Comparison::Equal => ComparisonOp::Eq,
-            c => {
-                return Err(SolverError::UnavailableComparison {
-                    got: *c,
-                    expected: vec![
-                        Comparison::LessOrEqual,
-                        Comparison::GreaterOrEqual,
-                        Comparison::Equal,
-                    ],
-                })
+    let offset = lp.objective_offset();
+    //objective of the root relaxation, no node of the search can do better than it
+    let mut root_bound: Option<f64> = None;
+    //best relaxation objective of the nodes that were pruned because they were within the gap
+    let mut gap_bound: Option<f64> = None;
+    let mut bound_known = true;
+    //each node keeps the relaxation objective of its parent, which bounds its subtree
+    let mut stack: Vec<(Vec<f64>, Vec<f64>, Option<f64>)> = vec![(lower, upper, None)];
+    loop {
+        if options.first_feasible && incumbent.is_some() {
+            //a warm start stops the search before the root relaxation, so nothing is proven
+            bound_known = root_bound.is_some();
+            gap_bound = root_bound;
+            break;
+        }
+        if let Some(best) = &incumbent {
+            let open_bounds = stack.iter().map(|(_, _, b)| *b).collect::<Option<Vec<_>>>();
+            if let Some(open_bounds) = open_bounds {
+                let bound = open_bounds
+                    .into_iter()
+                    .chain(gap_bound)
+                    .fold(best.objective, |a, b| best_of(a, b, optimization_type));
+                if options.is_within_gap(bound + offset, best.objective + offset) {
+                    gap_bound = Some(bound);
+                    break;
+                }
             }
+        }
+        let (lower, upper, _) = match stack.pop() {
+            Some(node) => node,
+            None => break,
         };
-        let microlp_coeffs = microlp_vars
+        stats.nodes_explored += 1;
+        let relaxation = match solve_relaxation(lp, &lower, &upper) {
+            Ok(r) => r,
+            Err(SolverError::Infisible) => continue,
+            Err(e) => return Err(e),
+        };
+        if stats.nodes_explored == 1 {
+            root_bound = Some(relaxation.objective);
+        }
+        if let Some(best) = &incumbent {
+            if !is_better(relaxation.objective, best.objective, optimization_type) {
+                continue;
+            }
+            if options.is_within_gap(relaxation.objective + offset, best.objective + offset) {
+                gap_bound = Some(match gap_bound {
+                    Some(bound) => best_of(bound, relaxation.objective, optimization_type),
+                    None => relaxation.objective,
+                });
+                continue;
+            }
+        }
+        //branch on the most fractional variables
+        let fractionalities = relaxation
+            .values
             .iter()
-            .zip(coeffs.iter())
-            .map(|(v, c)| (*v, *c))
+            .zip(types.iter())
+            .enumerate()
+            .filter(|(_, (v, t))| {
+                is_integer_variable(t) && (*v - v.round()).abs() > options.epsilon
+            })
+            .map(|(i, (v, _))| (i, (*v - v.floor() - 0.5).abs()))
             .collect::<Vec<_>>();
-        problem.add_constraint(microlp_coeffs, microlp_comparison_type, rhs);
+        let best_fractionality = match fractionalities
+            .iter()
+            .map(|(_, f)| *f)
+            .min_by(|a, b| a.total_cmp(b))
+        {
+            Some(f) => f,
+            None => {
+                incumbent = Some(relaxation);
+                stats.incumbents_found += 1;
+                stats
+                    .nodes_to_first_incumbent
+                    .get_or_insert(stats.nodes_explored);
+                continue;
+            }
+        };
+        if options.rounding_heuristic && incumbent.is_none() && stats.nodes_explored == 1 {
+            if let Some(rounded) = round_relaxation(lp, &relaxation, &types, &lower, &upper)? {
+                incumbent = Some(rounded);
+                stats.incumbents_found += 1;
+                stats.nodes_to_first_incumbent = Some(stats.nodes_explored);
+            }
+        }
+        let candidates = fractionalities
+            .iter()
+            .filter(|(_, f)| *f <= best_fractionality + INTEGRALITY_TOLERANCE)
+            .map(|(i, _)| *i)
+            .collect::<Vec<_>>();
+        let chosen = match &mut rng {
+            Some(rng) => candidates[rng.next_below(candidates.len())],
+            None => candidates[0],
+        };
+        let value = relaxation.values[chosen];
+        let mut down_upper = upper.clone();
+        down_upper[chosen] = value.floor();
+        let mut up_lower = lower.clone();
+        up_lower[chosen] = value.ceil();
+        let down = (lower, down_upper, Some(relaxation.objective));
+        let up = (up_lower, upper, Some(relaxation.objective));
+        //the branch closest to the relaxation value is explored first
+        if value - value.floor() < 0.5 {
+            stack.push(up);
+            stack.push(down);
+        } else {
+            stack.push(down);
+            stack.push(up);
+        }
     }
-    match problem.solve() {
-        Ok(s) => {
-            let assignment = microlp_vars
-                .iter()
-                .zip(variables)
-                .map(|(v, name)| {
-                    let value = s.var_value_rounded(*v);
-                    let var_domain = domain.get(name).unwrap();
-                    let value = match var_domain.get_type() {
-                        VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _) => {
-                            MILPValue::Real(value)
-                        }
-                        VariableType::IntegerRange(_, _) => MILPValue::Int(value as i32),
-                        VariableType::Boolean => MILPValue::Bool(value != 0.0),
-                    };
-                    Assignment {
-                        name: name.clone(),
-                        value,
-                    }
-                })
-                .collect();
-            Ok(LpSolution::new(
-                assignment,
-                s.objective() + lp.objective_offset(),
-            ))
+    let incumbent = incumbent.ok_or(SolverError::Infisible)?;
+    let assignment = to_assignment(&incumbent.values, variables, &types);
+    let value = incumbent.objective + offset;
+    let proven_bound = match gap_bound {
+        Some(bound) => best_of(bound, incumbent.objective, optimization_type) + offset,
+        None => value,
+    };
+    if bound_known {
+        stats.best_bound = Some(proven_bound);
+    }
+    stats.gap = stats
+        .best_bound
+        .and_then(|bound| relative_gap(bound, value));
+    let solution = match optimization_type {
+        OptimizationType::Satisfy => LpSolution::new_feasible(assignment),
+        _ => LpSolution::new(assignment, value),
+    };
+    Ok((solution.with_stats(stats.clone()), stats))
+}
+
+/// Collects up to `k` distinct feasible solutions of a mixed-integer linear programming problem,
+/// sorted from the best to the worst objective value.
+///
+/// The branch and bound keeps going after finding an optimum, so that problems with alternative
+/// optima return all of them. Two solutions are distinct if they differ in the value of at least
+/// one integer or boolean variable, real variables are not enumerated.
+///
+/// # Arguments
+/// * `lp` - The mixed-integer linear programming model to solve
+/// * `k` - The maximum number of solutions to return
+/// * `tolerance` - When present, only the solutions whose objective value is at most this far
+///   from the optimum are returned, otherwise the `k` best solutions are returned
+/// * `epsilon` - The distance from an integer under which the value of an integer variable is
+///   integral, like [`MILPOptions::epsilon`]
+///
+/// # Returns
+/// * `Ok(Vec<LpSolution<MILPValue>>)` - The solutions found, the first one is optimal
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+pub fn solve_all(
+    lp: &LinearModel,
+    k: usize,
+    tolerance: Option<f64>,
+    epsilon: f64,
+) -> Result<Vec<LpSolution<MILPValue>>, SolverError> {
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+    let variables = lp.variables();
+    let (types, lower, upper) = variable_bounds(lp);
+    let optimization_type = lp.optimization_type();
+    //objective value and integer values of each solution, kept sorted from best to worst
+    let mut pool: Vec<(Relaxation, Vec<i64>)> = Vec::new();
+    let mut stack = vec![(lower, upper)];
+    while let Some((lower, upper)) = stack.pop() {
+        let relaxation = match solve_relaxation(lp, &lower, &upper) {
+            Ok(r) => r,
+            Err(SolverError::Infisible) => continue,
+            Err(e) => return Err(e),
+        };
+        if pool.len() == k {
+            let (worst, _) = &pool[k - 1];
+            if !is_better(relaxation.objective, worst.objective, optimization_type) {
+                continue;
+            }
         }
-        Err(e) => Err(match e {
-            Error::InternalError(s) => SolverError::Other(s),
-            Error::Unbounded => SolverError::Unbounded,
-            Error::Infeasible => SolverError::Infisible,
-        }),
+        if let (Some(tolerance), Some((best, _))) = (tolerance, pool.first()) {
+            if (relaxation.objective - best.objective).abs() > tolerance + INTEGRALITY_TOLERANCE
+                && is_better(best.objective, relaxation.objective, optimization_type)
+            {
+                continue;
+            }
+        }
+        let fractional = relaxation
+            .values
+            .iter()
+            .zip(types.iter())
+            .position(|(v, t)| is_integer_variable(t) && (*v - v.round()).abs() > epsilon);
+        if let Some(i) = fractional {
+            let value = relaxation.values[i];
+            let mut down_upper = upper.clone();
+            down_upper[i] = value.floor();
+            let mut up_lower = lower.clone();
+            up_lower[i] = value.ceil();
+            stack.push((up_lower, upper));
+            stack.push((lower, down_upper));
+            continue;
+        }
+        let key = relaxation
+            .values
+            .iter()
+            .zip(types.iter())
+            .filter(|(_, t)| is_integer_variable(t))
+            .map(|(v, _)| v.round() as i64)
+            .collect::<Vec<_>>();
+        //the other solutions in this node differ from this one in at least one unfixed variable
+        let unfixed =
+            (0..types.len()).find(|i| is_integer_variable(&types[*i]) && lower[*i] < upper[*i]);
+        if let Some(i) = unfixed {
+            let value = relaxation.values[i].round();
+            if value > lower[i] {
+                let mut down_upper = upper.clone();
+                down_upper[i] = value - 1.0;
+                stack.push((lower.clone(), down_upper));
+            }
+            if value < upper[i] {
+                let mut up_lower = lower.clone();
+                up_lower[i] = value + 1.0;
+                stack.push((up_lower, upper.clone()));
+            }
+            let mut fixed_lower = lower;
+            let mut fixed_upper = upper;
+            fixed_lower[i] = value;
+            fixed_upper[i] = value;
+            stack.push((fixed_lower, fixed_upper));
+        }
+        if pool.iter().any(|(_, other)| *other == key) {
+            continue;
+        }
+        let position = pool
+            .iter()
+            .position(|(other, _)| {
+                is_better(relaxation.objective, other.objective, optimization_type)
+            })
+            .unwrap_or(pool.len());
+        pool.insert(position, (relaxation, key));
+        pool.truncate(k);
     }
+    let best = match pool.first() {
+        Some((best, _)) => best.objective,
+        None => return Err(SolverError::Infisible),
+    };
+    Ok(pool
+        .iter()
+        .filter(|(solution, _)| match tolerance {
+            Some(tolerance) => {
+                (solution.objective - best).abs() <= tolerance + INTEGRALITY_TOLERANCE
+            }
+            None => true,
+        })
+        .map(|(solution, _)| {
+            let assignment = to_assignment(&solution.values, variables, &types);
+            match optimization_type {
+                OptimizationType::Satisfy => LpSolution::new_feasible(assignment),
+                _ => LpSolution::new(assignment, solution.objective + lp.objective_offset()),
+            }
+        })
+        .collect())
 }
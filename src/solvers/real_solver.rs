@@ -129,7 +129,10 @@ pub fn solve_real_lp_problem_clarabel(lp: &LinearModel) -> Result<LpSolution<f64
                 .fold(lp.objective_offset(), |acc, (i, a)| {
                     acc + a.value * coeffs[i]
                 });
-            Ok(LpSolution::new(vars, value))
+            match lp.optimization_type() {
+                OptimizationType::Satisfy => Ok(LpSolution::new_feasible(vars)),
+                _ => Ok(LpSolution::new(vars, value)),
+            }
         }
         Err(e) => match e {
             ResolutionError::Unbounded => Err(SolverError::Unbounded),
@@ -68,7 +68,7 @@ impl FractionalTableau {
             .map(|a| a.iter().map(|a| a.pretty()).collect())
             .collect();
         let b: Vec<String> = self.b.iter().map(|b| b.pretty()).collect();
-        let v = PrettyFraction::new(self.value * -1.0).pretty();
+        let v = PrettyFraction::new(-self.value).pretty();
         header.push(v);
         let mut table = vec![header];
         for i in 0..a.len() {
@@ -0,0 +1,74 @@
+use crate::math::{float_eq, Comparison};
+use crate::solvers::OptimalTableau;
+use crate::transformers::LinearConstraint;
+
+/// The distance from an integer under which a value of [`generate_gomory_cuts`] is integral
+const INTEGRALITY_EPSILON: f64 = 1e-5;
+
+/// Returns the fractional part of the value, treating values within `epsilon` of an integer
+/// as integral
+fn fractional_part(value: f64, epsilon: f64) -> f64 {
+    let fraction = value - value.floor();
+    if fraction <= epsilon || fraction >= 1.0 - epsilon {
+        0.0
+    } else {
+        fraction
+    }
+}
+
+/// Generates the Gomory fractional cuts of the rows of an optimal tableau whose basic variable
+/// has a fractional value.
+///
+/// For a row `x_B + sum(a_j * x_j) = b`, the cut is `sum(frac(a_j) * x_j) >= frac(b)` over the
+/// non basic variables. It is satisfied by every point where all the variables are non negative
+/// integers, while it cuts off the current solution, where the non basic variables are zero.
+///
+/// Every variable of the tableau is assumed to be integer, so the tableau must come from a pure
+/// integer problem with integer coefficients, where the slack variables are integer too.
+///
+/// # Arguments
+/// * `optimal` - The optimal tableau of the continuous relaxation
+///
+/// # Returns
+/// The cuts, with the coefficients referring to the variables of the tableau
+pub fn generate_gomory_cuts(optimal: &OptimalTableau) -> Vec<LinearConstraint> {
+    let integer_variables = vec![true; optimal.tableau().variables().len()];
+    generate_gomory_cuts_for(optimal, &integer_variables, INTEGRALITY_EPSILON)
+}
+
+/// Generates the Gomory fractional cuts of the rows whose basic variable is integer and has a
+/// fractional value, skipping the rows that depend on a non basic continuous variable,
+/// as the cut would not be valid for them.
+/// The values within `epsilon` of an integer are considered integral.
+pub(crate) fn generate_gomory_cuts_for(
+    optimal: &OptimalTableau,
+    integer_variables: &[bool],
+    epsilon: f64,
+) -> Vec<LinearConstraint> {
+    let tableau = optimal.tableau();
+    let basis = tableau.in_basis();
+    tableau
+        .a_matrix()
+        .iter()
+        .zip(tableau.b_vec().iter())
+        .zip(basis.iter())
+        .filter(|((_, b), basic)| integer_variables[**basic] && fractional_part(**b, epsilon) > 0.0)
+        .filter_map(|((row, b), _)| {
+            let mut coefficients = vec![0.0; row.len()];
+            for (j, value) in row.iter().enumerate() {
+                if basis.contains(&j) || float_eq(*value, 0.0) {
+                    continue;
+                }
+                if !integer_variables[j] {
+                    return None;
+                }
+                coefficients[j] = fractional_part(*value, epsilon);
+            }
+            Some(LinearConstraint::new(
+                coefficients,
+                Comparison::GreaterOrEqual,
+                fractional_part(*b, epsilon),
+            ))
+        })
+        .collect()
+}
@@ -1,12 +1,18 @@
 pub mod fractional_tableau;
+pub mod gomory_cuts;
 pub mod optimal_tableau;
+#[cfg(feature = "rational-simplex")]
+mod rational_tableau;
 pub mod simplex_enums;
 pub mod simplex_solver;
 pub mod simplex_utils;
 pub mod tableau;
 
 pub use fractional_tableau::*;
+pub use gomory_cuts::*;
 pub use optimal_tableau::*;
+#[cfg(feature = "rational-simplex")]
+pub(crate) use rational_tableau::*;
 pub use simplex_enums::*;
 pub use simplex_solver::*;
 pub use simplex_utils::*;
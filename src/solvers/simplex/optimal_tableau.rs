@@ -1,9 +1,57 @@
+use crate::math::Comparison;
+use crate::math::{float_eq, float_gt, float_lt};
 #[allow(unused_imports)]
 use crate::prelude::*;
-use crate::solvers::{LpSolution, Tableau};
+use crate::solvers::{solve_linear_system, LpSolution, SimplexError, SimplexOptions, Tableau};
+use crate::transformers::{recombine_free_variables, LinearConstraint};
 use core::fmt;
+use indexmap::IndexMap;
+use serde::Serialize;
 use std::fmt::Display;
 
+/// The range over which the right hand side of a constraint can change
+/// while the optimal basis stays the same.
+#[derive(Debug, Clone, Serialize)]
+pub struct RhsRange {
+    pub constraint: String,
+    pub value: f64,
+    pub allowable_increase: f64,
+    pub allowable_decrease: f64,
+}
+
+/// The range over which the objective coefficient of a variable can change
+/// while the optimal basis stays the same.
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectiveRange {
+    pub variable: String,
+    pub value: f64,
+    pub allowable_increase: f64,
+    pub allowable_decrease: f64,
+}
+
+/// Sensitivity ranging of the optimal basis, an infinite allowance means
+/// that the value can change indefinitely in that direction.
+#[derive(Debug, Clone, Serialize)]
+pub struct SensitivityAnalysis {
+    pub rhs: Vec<RhsRange>,
+    pub objective: Vec<ObjectiveRange>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const ISensitivityAnalysis: &'static str = r#"
+export type SerializedRange = {
+    value: number,
+    allowable_increase: number,
+    allowable_decrease: number,
+}
+export type SerializedSensitivityAnalysis = {
+    rhs: (SerializedRange & { constraint: string })[],
+    objective: (SerializedRange & { variable: string })[],
+}
+"#;
+
 #[derive(Debug, Clone)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct OptimalTableau {
@@ -26,12 +74,203 @@ impl OptimalTableau {
     }
     pub fn optimal_value(&self) -> f64 {
         let flip = if self.flip_result { -1.0 } else { 1.0 };
-        ((self.tableau.current_value() + self.tableau.value_offset()) * -1.0) * flip
+        -self.tableau.current_value() * flip + self.tableau.value_offset()
     }
     pub fn tableau(&self) -> &Tableau {
         &self.tableau
     }
+    /// Returns true if the tableau solves a `satisfy` problem, so the values are
+    /// only a feasible point and there is no optimal value
+    pub fn is_feasibility_only(&self) -> bool {
+        self.tableau.is_feasibility_only()
+    }
 
+    /// Adds a constraint to the solved problem and re-optimizes from the current optimal basis
+    /// with the dual simplex, instead of solving the problem again from scratch.
+    ///
+    /// The coefficients of the constraint refer to the variables of the tableau, missing ones are zero.
+    /// Each inequality gets a new slack variable, while an equality is added as two inequalities.
+    ///
+    /// # Arguments
+    /// * `constraint` - The constraint to add, it can't be a strict inequality
+    ///
+    /// # Returns
+    /// * `Ok(OptimalTableau)` - The optimal tableau of the tightened problem
+    /// * `Err(SimplexError)` - [`SimplexError::Infeasible`] if the tightened problem has no solution
+    pub fn add_constraint_and_resolve(
+        &self,
+        constraint: &LinearConstraint,
+    ) -> Result<OptimalTableau, SimplexError> {
+        let coefficients = constraint.coefficients();
+        if coefficients.len() > self.tableau.variables().len() {
+            return Err(SimplexError::Other);
+        }
+        let negated = coefficients.iter().map(|c| -c).collect::<Vec<_>>();
+        let rhs = constraint.rhs();
+        let mut tableau = self.tableau.clone();
+        match constraint.constraint_type() {
+            Comparison::LessOrEqual => tableau.add_less_or_equal_row(coefficients, rhs),
+            Comparison::GreaterOrEqual => tableau.add_less_or_equal_row(&negated, -rhs),
+            Comparison::Equal => {
+                tableau.add_less_or_equal_row(coefficients, rhs);
+                tableau.add_less_or_equal_row(&negated, -rhs);
+            }
+            Comparison::Less | Comparison::Greater => return Err(SimplexError::Other),
+        }
+        tableau.solve_dual_with_options(&SimplexOptions::default())
+    }
+
+    /// Returns the dual value (shadow price) of each constraint of the model, keyed by the name
+    /// of the constraint and computed as `c_B * B^-1` from the columns of the optimal basis.
+    ///
+    /// The values are relative to the original objective, so they are the change of the
+    /// optimal value for a unit increase of the right hand side of the constraint, also for the
+    /// rows that were negated to make their right hand side non negative.
+    /// The rows added by the solver, like the bounds of the variables, are not included.
+    pub fn dual_values(&self) -> IndexMap<String, f64> {
+        let a = self.tableau.original_a_matrix();
+        let c = self.tableau.original_c_vec();
+        let basis = self.tableau.in_basis();
+        let transposed_basis = basis
+            .iter()
+            .map(|j| a.iter().map(|row| row[*j]).collect())
+            .collect();
+        let basis_costs = basis.iter().map(|j| c[*j]).collect();
+        let flip = if self.flip_result { -1.0 } else { 1.0 };
+        let duals = solve_linear_system(transposed_basis, basis_costs)
+            .unwrap_or_else(|| vec![f64::NAN; a.len()]);
+        self.tableau
+            .constraint_names()
+            .iter()
+            .zip(self.tableau.negated_rows())
+            .zip(duals)
+            .filter_map(|((name, negated), y)| {
+                //the dual of a negated row is relative to the negated right hand side
+                let sign = if *negated { -1.0 } else { 1.0 };
+                name.as_ref().map(|name| (name.clone(), y * flip * sign))
+            })
+            .collect()
+    }
+    /// Returns the reduced cost of each variable of the model, keyed by the name of the variable
+    /// and read from the objective row of the optimal tableau.
+    ///
+    /// The values are relative to the original objective, so they are the change of the
+    /// optimal value for a unit increase of the variable. The slack variables are not included,
+    /// while the free variables use the reduced cost of their positive part.
+    pub fn reduced_costs(&self) -> IndexMap<String, f64> {
+        let flip = if self.flip_result { -1.0 } else { 1.0 };
+        let c = self.tableau.c_vec();
+        self.tableau
+            .original_columns()
+            .iter()
+            .map(|(name, column)| (name.clone(), c[*column] * flip))
+            .collect()
+    }
+
+    /// Computes the ranges over which each right hand side and each objective coefficient
+    /// can change while the current basis stays optimal.
+    ///
+    /// The right hand side ranges use the columns of the basis inverse, while the objective
+    /// ranges use the rows of the optimal tableau and the reduced costs. Both are relative to the
+    /// model, so only its constraints and variables are ranged, with the rows negated by the
+    /// standardizer and the free variables mapped back to the original ones.
+    pub fn sensitivity_analysis(&self) -> SensitivityAnalysis {
+        let a = self.tableau.original_a_matrix();
+        let basis = self.tableau.in_basis();
+        let b = self.tableau.b_vec();
+        let basis_matrix = a
+            .iter()
+            .map(|row| basis.iter().map(|j| row[*j]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let rhs = self
+            .tableau
+            .constraint_names()
+            .iter()
+            .zip(self.tableau.negated_rows())
+            .enumerate()
+            .filter_map(|(i, (name, negated))| name.as_ref().map(|name| (i, name, *negated)))
+            .map(|(i, name, negated)| {
+                let mut unit = vec![0.0; a.len()];
+                unit[i] = 1.0;
+                //column i of the basis inverse, how the basic variables change with the rhs i
+                let column = solve_linear_system(basis_matrix.clone(), unit)
+                    .unwrap_or_else(|| vec![f64::NAN; a.len()]);
+                let value = basis_matrix[i]
+                    .iter()
+                    .zip(b.iter())
+                    .fold(0.0, |acc, (coeff, b)| acc + coeff * b);
+                let (increase, decrease) = ratio_limits(b, &column);
+                //increasing the rhs of a negated row decreases the rhs of the constraint
+                let (value, allowable_increase, allowable_decrease) = if negated {
+                    (-value, decrease, increase)
+                } else {
+                    (value, increase, decrease)
+                };
+                RhsRange {
+                    constraint: name.clone(),
+                    value: clean_zero(value),
+                    allowable_increase,
+                    allowable_decrease,
+                }
+            })
+            .collect();
+        let flip = if self.flip_result { -1.0 } else { 1.0 };
+        let reduced_costs = self.tableau.c_vec();
+        let original_c = self.tableau.original_c_vec();
+        let objective = self
+            .tableau
+            .original_columns()
+            .iter()
+            .map(|(name, column)| {
+                //a free variable is the difference of two columns, so its cost moves both of them
+                let mut direction = vec![(*column, 1.0)];
+                if let Some((_, minus)) = self.tableau.free_variables().get(name) {
+                    if let Some(minus) = self.tableau.variables().iter().position(|v| v == minus) {
+                        direction.push((minus, -1.0));
+                    }
+                }
+                //limits on the change of the cost in the minimization problem of the tableau,
+                //each non basic reduced cost changes by the given rate and must stay non negative
+                let mut increase = f64::INFINITY;
+                let mut decrease = f64::INFINITY;
+                for l in (0..reduced_costs.len()).filter(|l| !basis.contains(l)) {
+                    let direct = direction
+                        .iter()
+                        .find(|(j, _)| *j == l)
+                        .map_or(0.0, |(_, d)| *d);
+                    let through_basis = direction
+                        .iter()
+                        .filter_map(|(j, d)| {
+                            let row = basis.iter().position(|b| b == j)?;
+                            Some(d * self.tableau.a_matrix()[row][l])
+                        })
+                        .sum::<f64>();
+                    let rate = direct - through_basis;
+                    if float_gt(rate, 0.0) {
+                        decrease = decrease.min(reduced_costs[l] / rate);
+                    } else if float_lt(rate, 0.0) {
+                        increase = increase.min(reduced_costs[l] / -rate);
+                    }
+                }
+                //maximization problems are solved with the negated costs
+                let (allowable_increase, allowable_decrease) = if self.flip_result {
+                    (decrease, increase)
+                } else {
+                    (increase, decrease)
+                };
+                ObjectiveRange {
+                    variable: name.clone(),
+                    value: clean_zero(original_c[*column] * flip),
+                    allowable_increase: clean_zero(allowable_increase),
+                    allowable_decrease: clean_zero(allowable_decrease),
+                }
+            })
+            .collect();
+        SensitivityAnalysis { rhs, objective }
+    }
+
+    /// Returns the solution of the tableau, with the free variables that were split by the
+    /// standardizer recombined into the variables of the model
     pub fn as_lp_solution(&self) -> LpSolution<f64> {
         let values = self.variables_values().clone();
         let value = self.optimal_value();
@@ -45,7 +284,12 @@ impl OptimalTableau {
                 value: *val,
             })
             .collect();
-        LpSolution::new(assignment, value)
+        let solution = if self.is_feasibility_only() {
+            LpSolution::new_feasible(assignment)
+        } else {
+            LpSolution::new(assignment, value)
+        };
+        recombine_free_variables(solution, self.tableau.free_variables())
     }
 }
 
@@ -58,15 +302,76 @@ impl OptimalTableau {
     pub fn wasm_get_optimal_value(&self) -> f64 {
         self.optimal_value()
     }
+    pub fn wasm_is_feasibility_only(&self) -> bool {
+        self.is_feasibility_only()
+    }
     pub fn wasm_get_tableau(&self) -> Tableau {
         self.tableau.clone()
     }
+    pub fn wasm_get_dual_values(&self) -> JsValue {
+        crate::utils::serialize_json_compatible(&self.dual_values()).unwrap()
+    }
+    pub fn wasm_get_reduced_costs(&self) -> JsValue {
+        crate::utils::serialize_json_compatible(&self.reduced_costs()).unwrap()
+    }
+    pub fn wasm_get_sensitivity_analysis(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.sensitivity_analysis()).unwrap()
+    }
+    pub fn wasm_get_lp_solution(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.as_lp_solution()).unwrap()
+    }
+}
+
+//avoids printing -0 for values that are zero up to rounding
+fn clean_zero(value: f64) -> f64 {
+    if float_eq(value, 0.0) {
+        0.0
+    } else {
+        value
+    }
+}
+
+//finds how much the values can move along the direction before one of them becomes negative,
+//returning the allowed increase and decrease of the step
+fn ratio_limits(values: &[f64], direction: &[f64]) -> (f64, f64) {
+    let mut increase = f64::INFINITY;
+    let mut decrease = f64::INFINITY;
+    for (value, d) in values.iter().zip(direction.iter()) {
+        if float_lt(*d, 0.0) {
+            increase = increase.min(value / -d);
+        } else if float_gt(*d, 0.0) {
+            decrease = decrease.min(value / d);
+        }
+    }
+    (clean_zero(increase), clean_zero(decrease))
 }
 
 impl Display for OptimalTableau {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let tableau = self.tableau.to_string();
-        write!(f, "{}\n\nOptimal Value: {}", tableau, self.optimal_value(),)
+        if self.is_feasibility_only() {
+            return write!(f, "{}\n\nFeasible solution found", tableau);
+        }
+        let duals = self
+            .dual_values()
+            .iter()
+            .map(|(name, y)| format!("    {}: {}", name, clean_zero(*y)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let reduced_costs = self
+            .reduced_costs()
+            .iter()
+            .map(|(name, r)| format!("    {}: {}", name, clean_zero(*r)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        write!(
+            f,
+            "{}\n\nOptimal Value: {}\n\nDual values:\n{}\n\nReduced costs:\n{}",
+            tableau,
+            self.optimal_value(),
+            duals,
+            reduced_costs
+        )
     }
 }
 
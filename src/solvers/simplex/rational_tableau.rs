@@ -0,0 +1,226 @@
+use crate::solvers::{PivotRule, SimplexError, Tableau};
+use num_rational::Rational64;
+use num_traits::{CheckedDiv, CheckedMul, CheckedSub, Signed, Zero};
+
+/// The outcome of a single pivot of a [`RationalTableau`]
+pub(crate) enum RationalStep {
+    Pivot,
+    Finished,
+    /// No row limits the entering variable
+    Unbounded,
+}
+
+/// The values of a [`Tableau`] as exact fractions, pivoting it does not accumulate
+/// the rounding errors of floating point arithmetic.
+pub(crate) struct RationalTableau {
+    c: Vec<Rational64>,
+    a: Vec<Vec<Rational64>>,
+    b: Vec<Rational64>,
+    in_basis: Vec<usize>,
+    current_value: Rational64,
+}
+
+fn checked<T>(value: Option<T>) -> Result<T, SimplexError> {
+    value.ok_or(SimplexError::ArithmeticOverflow)
+}
+
+/// Finds the simplest fraction that is within a tiny distance of the value, so that numbers like
+/// `0.1` or `1/3`, which can't be represented exactly as floats, become the intended fractions
+fn to_fraction(value: f64) -> Result<Rational64, SimplexError> {
+    if !value.is_finite() {
+        return Err(SimplexError::ArithmeticOverflow);
+    }
+    let target = value.abs();
+    let tolerance = 1e-12 * target.max(1.0);
+    //convergents of the continued fraction of the value
+    let (mut n0, mut d0, mut n1, mut d1) = (0i64, 1i64, 1i64, 0i64);
+    let mut rest = target;
+    loop {
+        let whole = rest.floor();
+        if whole >= i64::MAX as f64 {
+            return Err(SimplexError::ArithmeticOverflow);
+        }
+        let whole_int = whole as i64;
+        let n2 = checked(whole_int.checked_mul(n1).and_then(|n| n.checked_add(n0)))?;
+        let d2 = checked(whole_int.checked_mul(d1).and_then(|d| d.checked_add(d0)))?;
+        (n0, d0, n1, d1) = (n1, d1, n2, d2);
+        if (n1 as f64 / d1 as f64 - target).abs() <= tolerance {
+            break;
+        }
+        rest = 1.0 / (rest - whole);
+    }
+    let numerator = if value < 0.0 { -n1 } else { n1 };
+    Ok(Rational64::new(numerator, d1))
+}
+
+fn to_float(value: &Rational64) -> f64 {
+    *value.numer() as f64 / *value.denom() as f64
+}
+
+impl RationalTableau {
+    /// Converts the values of the tableau to fractions
+    pub(crate) fn new(tableau: &Tableau) -> Result<RationalTableau, SimplexError> {
+        Ok(RationalTableau {
+            c: tableau
+                .c_vec()
+                .iter()
+                .map(|c| to_fraction(*c))
+                .collect::<Result<_, _>>()?,
+            a: tableau
+                .a_matrix()
+                .iter()
+                .map(|row| row.iter().map(|a| to_fraction(*a)).collect())
+                .collect::<Result<_, _>>()?,
+            b: tableau
+                .b_vec()
+                .iter()
+                .map(|b| to_fraction(*b))
+                .collect::<Result<_, _>>()?,
+            in_basis: tableau.in_basis().clone(),
+            current_value: to_fraction(tableau.current_value())?,
+        })
+    }
+
+    pub(crate) fn is_optimal(&self) -> bool {
+        self.c.iter().all(|c| !c.is_negative())
+    }
+
+    /// Performs a single pivot, with the same rules used by [`Tableau::step_with_rule`]
+    pub(crate) fn step(
+        &mut self,
+        variables_to_avoid: &[usize],
+        pivot_rule: PivotRule,
+    ) -> Result<RationalStep, SimplexError> {
+        if self.is_optimal() {
+            return Ok(RationalStep::Finished);
+        }
+        let h = match pivot_rule {
+            PivotRule::Dantzig => self.find_h(),
+            PivotRule::Bland => self.find_h_bland(),
+        };
+        match h {
+            None => Ok(RationalStep::Unbounded),
+            Some(h) => match self.find_t(h, variables_to_avoid)? {
+                None => Ok(RationalStep::Unbounded),
+                Some(t) => {
+                    self.pivot(t, h)?;
+                    Ok(RationalStep::Pivot)
+                }
+            },
+        }
+    }
+
+    fn find_h(&self) -> Option<usize> {
+        self.c
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| !self.in_basis.contains(i) && c.is_negative())
+            .min_by(|(_, c1), (_, c2)| c1.cmp(c2))
+            .map(|(i, _)| i)
+    }
+
+    fn find_h_bland(&self) -> Option<usize> {
+        self.c
+            .iter()
+            .enumerate()
+            .find(|(i, c)| !self.in_basis.contains(i) && c.is_negative())
+            .map(|(i, _)| i)
+    }
+
+    //minimum ratio test, ties are broken like in the float tableau
+    fn find_t(
+        &self,
+        h: usize,
+        variables_to_prefer: &[usize],
+    ) -> Result<Option<usize>, SimplexError> {
+        let basis = &self.in_basis;
+        let mut min: Option<(usize, Rational64)> = None;
+        for (i, row) in self.a.iter().enumerate() {
+            if !row[h].is_positive() {
+                continue;
+            }
+            let ratio = checked(self.b[i].checked_div(&row[h]))?;
+            min = match min {
+                None => Some((i, ratio)),
+                Some((current, current_ratio)) if ratio == current_ratio => {
+                    let to_prefer = variables_to_prefer.contains(&basis[i])
+                        && !variables_to_prefer.contains(&basis[current]);
+                    if basis[i] < basis[current] || to_prefer {
+                        Some((i, ratio))
+                    } else {
+                        Some((current, current_ratio))
+                    }
+                }
+                Some((_, current_ratio)) if ratio < current_ratio => Some((i, ratio)),
+                current => current,
+            };
+        }
+        Ok(min.map(|(t, _)| t))
+    }
+
+    //performs the pivot operation where variable h enters the basis and variable B(t) leaves the basis
+    fn pivot(&mut self, t: usize, h: usize) -> Result<(), SimplexError> {
+        let pivot = self.a[t][h];
+        //normalize the pivot row
+        for value in self.a[t].iter_mut() {
+            *value = checked(value.checked_div(&pivot))?;
+        }
+        self.b[t] = checked(self.b[t].checked_div(&pivot))?;
+        let pivot_row = self.a[t].clone();
+        let pivot_b = self.b[t];
+        //normalize the pivot column
+        for i in 0..self.a.len() {
+            let factor = self.a[i][h];
+            if i == t || factor.is_zero() {
+                continue;
+            }
+            for (value, pivot_value) in self.a[i].iter_mut().zip(pivot_row.iter()) {
+                *value = checked(
+                    factor
+                        .checked_mul(pivot_value)
+                        .and_then(|v| value.checked_sub(&v)),
+                )?;
+            }
+            self.b[i] = checked(
+                factor
+                    .checked_mul(&pivot_b)
+                    .and_then(|v| self.b[i].checked_sub(&v)),
+            )?;
+        }
+        //normalize the objective function
+        let factor = self.c[h];
+        for (value, pivot_value) in self.c.iter_mut().zip(pivot_row.iter()) {
+            *value = checked(
+                factor
+                    .checked_mul(pivot_value)
+                    .and_then(|v| value.checked_sub(&v)),
+            )?;
+        }
+        self.current_value = checked(
+            factor
+                .checked_mul(&pivot_b)
+                .and_then(|v| self.current_value.checked_sub(&v)),
+        )?;
+        self.in_basis[t] = h;
+        Ok(())
+    }
+
+    pub(crate) fn c_vec(&self) -> Vec<f64> {
+        self.c.iter().map(to_float).collect()
+    }
+    pub(crate) fn a_matrix(&self) -> Vec<Vec<f64>> {
+        self.a
+            .iter()
+            .map(|row| row.iter().map(to_float).collect())
+            .collect()
+    }
+    pub(crate) fn b_vec(&self) -> Vec<f64> {
+        self.b.iter().map(to_float).collect()
+    }
+    pub(crate) fn in_basis(&self) -> &Vec<usize> {
+        &self.in_basis
+    }
+    pub(crate) fn current_value(&self) -> f64 {
+        to_float(&self.current_value)
+    }
+}
@@ -1,5 +1,6 @@
 #[allow(unused_imports)]
 use crate::prelude::*;
+use crate::transformers::StandardLinearModel;
 use core::fmt;
 use serde::Serialize;
 use std::fmt::Display;
@@ -17,18 +18,26 @@ pub enum StepAction {
 #[derive(Debug)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub enum SimplexError {
+    /// The objective can improve indefinitely, [`Tableau::unbounded_variable`](crate::solvers::Tableau::unbounded_variable)
+    /// finds the variable that can increase without limit
     Unbounded,
+    Infeasible,
     IterationLimitReached,
+    /// The fractions of the exact simplex grew too large to be represented
+    ArithmeticOverflow,
     Other,
 }
 impl Display for SimplexError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            SimplexError::Unbounded => "Unbounded Problem",
-            SimplexError::IterationLimitReached => "Iteration Limit Reached",
-            SimplexError::Other => "Other",
-        };
-        f.write_str(s)
+        match self {
+            SimplexError::Unbounded => f.write_str("Unbounded Problem"),
+            SimplexError::Infeasible => f.write_str("Infeasible Problem"),
+            SimplexError::IterationLimitReached => f.write_str("Iteration Limit Reached"),
+            SimplexError::ArithmeticOverflow => f.write_str(
+                "Arithmetic Overflow, the fractions of the exact simplex grew too large",
+            ),
+            SimplexError::Other => f.write_str("Other"),
+        }
     }
 }
 
@@ -37,15 +46,50 @@ pub enum CanonicalTransformError {
     Raw(String),
     InvalidBasis(String),
     Infesible(String),
+    /// The problem is infeasible, caused by the conflict between the given constraints
+    InfeasibleSubset(Vec<String>),
     SimplexError(String),
 }
 
+impl CanonicalTransformError {
+    /// If the error is an infeasibility, looks for the constraints of the model that conflict with each other
+    /// and returns them as a [`CanonicalTransformError::InfeasibleSubset`], otherwise returns the error unchanged.
+    ///
+    /// # Arguments
+    /// * `model` - The standard model that produced the error
+    pub fn with_infeasible_subset(self, model: &StandardLinearModel) -> CanonicalTransformError {
+        match self {
+            CanonicalTransformError::Infesible(s) => {
+                match model.find_irreducible_infeasible_subset() {
+                    Ok(constraints) if !constraints.is_empty() => {
+                        CanonicalTransformError::InfeasibleSubset(constraints)
+                    }
+                    _ => CanonicalTransformError::Infesible(s),
+                }
+            }
+            e => e,
+        }
+    }
+
+    /// Returns the names of the conflicting constraints if the error carries an infeasible subset.
+    pub fn infeasible_subset(&self) -> Option<&Vec<String>> {
+        match self {
+            CanonicalTransformError::InfeasibleSubset(constraints) => Some(constraints),
+            _ => None,
+        }
+    }
+}
+
 impl Display for CanonicalTransformError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             Self::Raw(s) => s.clone(),
             Self::InvalidBasis(s) => format!("Invalid Basis: {}", s),
             Self::Infesible(s) => format!("Infesible: {}", s),
+            Self::InfeasibleSubset(constraints) => format!(
+                "The problem is infeasible, the conflicting constraints are: {}",
+                constraints.join(", ")
+            ),
             Self::SimplexError(s) => format!("Simplex Error: {}", s),
         };
         f.write_str(&s)
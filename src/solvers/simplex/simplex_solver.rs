@@ -1,8 +1,69 @@
 use crate::math::{Comparison, OptimizationType, VariableType};
-use crate::solvers::{find_invalid_variables, Assignment, LpSolution, SimplexError, SolverError};
+use crate::solvers::{
+    find_invalid_variables, Assignment, CanonicalTransformError, LpSolution, PivotRule,
+    SimplexArithmetic, SimplexError, SolverError,
+};
 use crate::transformers::LinearModel;
 use microlp::{OptimizationDirection, Problem};
 
+/// Options used to configure the tableau simplex.
+#[derive(Debug, Clone)]
+pub struct SimplexOptions {
+    /// Maximum number of pivots before giving up with [`SimplexError::IterationLimitReached`],
+    /// when `None` the simplex runs until it terminates.
+    pub max_iterations: Option<usize>,
+    /// Maximum number of pivots of the first phase of the two-phase method, used to find
+    /// the initial tableau, when `None` it runs until it terminates.
+    pub phase_one_max_iterations: Option<usize>,
+    /// The rule used to pick the entering variable, [`PivotRule::Bland`] prevents cycling on
+    /// degenerate problems at the cost of more iterations
+    pub pivot_rule: PivotRule,
+    /// The numbers used to pivot the tableau, [`SimplexArithmetic::Float`] by default
+    pub arithmetic: SimplexArithmetic,
+}
+
+impl Default for SimplexOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations: Some(1000),
+            phase_one_max_iterations: Some(10000),
+            pivot_rule: PivotRule::Dantzig,
+            arithmetic: SimplexArithmetic::Float,
+        }
+    }
+}
+
+impl SimplexOptions {
+    /// Creates the default options, with a limit of 1000 iterations, and 10000 for the first phase
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of iterations, `None` removes the limit
+    pub fn with_max_iterations(mut self, max_iterations: Option<usize>) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the maximum number of iterations of the first phase, `None` removes the limit
+    pub fn with_phase_one_max_iterations(mut self, max_iterations: Option<usize>) -> Self {
+        self.phase_one_max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the rule used to pick the entering variable
+    pub fn with_pivot_rule(mut self, pivot_rule: PivotRule) -> Self {
+        self.pivot_rule = pivot_rule;
+        self
+    }
+
+    /// Sets the numbers used to pivot the tableau
+    pub fn with_arithmetic(mut self, arithmetic: SimplexArithmetic) -> Self {
+        self.arithmetic = arithmetic;
+        self
+    }
+}
+
 /// Solves a linear programming problem with real variables using a basic simplex algorithm.
 ///
 /// This is a slower implementation that uses a custom tableau-based simplex method.
@@ -36,18 +97,45 @@ use microlp::{OptimizationDirection, Problem};
 pub fn solve_real_lp_problem_slow_simplex(
     lp: &LinearModel,
     limit: i64,
+) -> Result<LpSolution<f64>, SolverError> {
+    let options = SimplexOptions::new().with_max_iterations(Some(limit.max(0) as usize));
+    solve_real_lp_problem_slow_simplex_with_options(lp, &options)
+}
+
+/// Solves a linear programming problem with real variables using the tableau simplex,
+/// configured with the given options.
+///
+/// # Arguments
+/// * `lp` - The linear programming model to solve
+/// * `options` - The iteration limit and pivoting rule of the simplex
+///
+/// # Returns
+/// * `Ok(LpSolution<f64>)` - The optimal solution if found
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution,
+///   [`SolverError::LimitReached`] if the iteration limit was exceeded
+pub fn solve_real_lp_problem_slow_simplex_with_options(
+    lp: &LinearModel,
+    options: &SimplexOptions,
 ) -> Result<LpSolution<f64>, SolverError> {
     let standard = lp.clone().into_standard_form()?;
     let mut canonical_form = standard
-        .into_tableau()
-        .map_err(|e| SolverError::Other(e.to_string()))?;
+        .into_tableau_with_options(options)
+        .map_err(|e| match e {
+            CanonicalTransformError::Infesible(_) => SolverError::Infisible,
+            CanonicalTransformError::InfeasibleSubset(constraints) => {
+                SolverError::InfeasibleSubset { constraints }
+            }
+            e => SolverError::Other(e.to_string()),
+        })?;
 
-    let solution = canonical_form.solve(limit);
+    let solution = canonical_form.solve_with_options(options);
     match solution {
         Ok(optimal_tableau) => Ok(optimal_tableau.as_lp_solution()),
         Err(e) => match e {
             SimplexError::IterationLimitReached => Err(SolverError::LimitReached),
             SimplexError::Unbounded => Err(SolverError::Unbounded),
+            SimplexError::Infeasible => Err(SolverError::Infisible),
+            e @ SimplexError::ArithmeticOverflow => Err(SolverError::Other(e.to_string())),
             SimplexError::Other => Err(SolverError::Other("An error occoured".to_string())),
         },
     }
@@ -98,20 +186,20 @@ pub fn solve_real_lp_problem_micro_lp(lp: &LinearModel) -> Result<LpSolution<f64
             got: invalid_variables,
         });
     }
+    let is_satisfy = *lp.optimization_type() == OptimizationType::Satisfy;
     let opt_type = match lp.optimization_type() {
-        OptimizationType::Min => OptimizationDirection::Minimize,
+        OptimizationType::Min | OptimizationType::Satisfy => OptimizationDirection::Minimize,
         OptimizationType::Max => OptimizationDirection::Maximize,
-        OptimizationType::Satisfy => {
-            return Err(SolverError::UnimplementedOptimizationType {
-                expected: vec![OptimizationType::Min, OptimizationType::Max],
-                got: OptimizationType::Satisfy,
-            })
-        }
     };
     let mut problem = Problem::new(opt_type);
     let variables = lp.variables();
 
-    let obj = lp.objective();
+    //a satisfy problem only looks for a feasible point, so it has no objective
+    let obj = if is_satisfy {
+        vec![0.0; lp.objective().len()]
+    } else {
+        lp.objective().clone()
+    };
     let mut vars_microlp = Vec::with_capacity(obj.len());
     for (i, name) in variables.iter().enumerate() {
         let domain = if let Some(domain) = domain.get(name) {
@@ -180,7 +268,11 @@ pub fn solve_real_lp_problem_micro_lp(lp: &LinearModel) -> Result<LpSolution<f64
                     value: optimal_solution[*c],
                 })
                 .collect();
-            Ok(LpSolution::new(coeffs, obj))
+            if is_satisfy {
+                Ok(LpSolution::new_feasible(coeffs))
+            } else {
+                Ok(LpSolution::new(coeffs, obj))
+            }
         }
         Err(e) => match e {
             microlp::Error::Unbounded => Err(SolverError::Unbounded),
@@ -189,3 +281,121 @@ pub fn solve_real_lp_problem_micro_lp(lp: &LinearModel) -> Result<LpSolution<f64
         },
     }
 }
+
+/// Checks if the continuous relaxation of the model restricted to the given constraints is feasible,
+/// the objective is ignored and integer variables are relaxed to their bounds.
+fn is_relaxation_feasible(lp: &LinearModel, constraints: &[usize]) -> Result<bool, SolverError> {
+    let domain = lp.domain();
+    let mut problem = Problem::new(OptimizationDirection::Minimize);
+    let mut vars_microlp = Vec::with_capacity(lp.variables().len());
+    for name in lp.variables() {
+        let bounds = match domain.get(name).map(|d| d.get_type()) {
+            Some(VariableType::Boolean) => (0.0, 1.0),
+            Some(VariableType::IntegerRange(min, max)) => (*min as f64, *max as f64),
+            Some(VariableType::Real(min, max))
+            | Some(VariableType::NonNegativeReal(min, max))
+            | Some(VariableType::Integer(min, max)) => (*min, *max),
+            None => {
+                return Err(SolverError::Other(format!(
+                    "Variable {} not found in domain",
+                    name
+                )))
+            }
+        };
+        vars_microlp.push(problem.add_var(0.0, bounds));
+    }
+    for i in constraints {
+        let cons = &lp.constraints()[*i];
+        let comparison = match cons.constraint_type() {
+            Comparison::LessOrEqual => microlp::ComparisonOp::Le,
+            Comparison::Equal => microlp::ComparisonOp::Eq,
+            Comparison::GreaterOrEqual => microlp::ComparisonOp::Ge,
+            Comparison::Less | Comparison::Greater => {
+                return Err(SolverError::UnavailableComparison {
+                    expected: vec![
+                        Comparison::LessOrEqual,
+                        Comparison::Equal,
+                        Comparison::GreaterOrEqual,
+                    ],
+                    got: *cons.constraint_type(),
+                })
+            }
+        };
+        let coeffs = cons
+            .coefficients()
+            .iter()
+            .zip(vars_microlp.iter())
+            .map(|(c, v)| (*v, *c))
+            .collect::<Vec<_>>();
+        problem.add_constraint(&coeffs, comparison, cons.rhs());
+    }
+    match problem.solve() {
+        Ok(_) | Err(microlp::Error::Unbounded) => Ok(true),
+        Err(microlp::Error::Infeasible) => Ok(false),
+        Err(microlp::Error::InternalError(s)) => Err(SolverError::Other(s)),
+    }
+}
+
+/// Finds an irreducible infeasible subset (IIS) of the constraints of an infeasible model.
+///
+/// Uses a deletion filter: each constraint is removed in turn and the model is re-solved,
+/// if the remaining constraints are still infeasible the constraint is dropped for good,
+/// otherwise it is kept as part of the conflict. The resulting set is infeasible, while
+/// removing any of its constraints makes it feasible.
+///
+/// Integer variables are relaxed to their bounds, so the subset explains the infeasibility
+/// of the continuous relaxation.
+///
+/// # Arguments
+/// * `lp` - The linear programming model to analyze
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The names of the conflicting constraints, empty if the model is feasible
+/// * `Err(SolverError)` - If the model could not be solved
+///
+/// # Example
+/// ```rust
+/// use rooc::{VariableType, Comparison, OptimizationType, find_irreducible_infeasible_subset, LinearModel};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+///
+/// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 1.0);
+/// model.add_constraint(vec![1.0], Comparison::GreaterOrEqual, 2.0);
+/// model.set_objective(vec![1.0], OptimizationType::Max);
+///
+/// let iis = find_irreducible_infeasible_subset(&model).unwrap();
+/// assert_eq!(iis, vec!["c0", "c1"]);
+/// ```
+pub fn find_irreducible_infeasible_subset(lp: &LinearModel) -> Result<Vec<String>, SolverError> {
+    let constraints = (0..lp.constraints().len()).collect::<Vec<_>>();
+    let names = lp.constraint_names();
+    Ok(irreducible_infeasible_rows(lp, constraints, &[])?
+        .into_iter()
+        .map(|i| names[i].clone())
+        .collect())
+}
+
+/// Finds the indexes of an irreducible infeasible subset of the candidate constraints, with a
+/// deletion filter. The fixed constraints are always part of the problem but never of the subset.
+pub(crate) fn irreducible_infeasible_rows(
+    lp: &LinearModel,
+    mut candidates: Vec<usize>,
+    fixed: &[usize],
+) -> Result<Vec<usize>, SolverError> {
+    let with_fixed = |constraints: &[usize]| [constraints, fixed].concat();
+    if is_relaxation_feasible(lp, &with_fixed(&candidates))? {
+        return Ok(Vec::new());
+    }
+    let mut i = 0;
+    while i < candidates.len() {
+        let mut without = candidates.clone();
+        without.remove(i);
+        if is_relaxation_feasible(lp, &with_fixed(&without))? {
+            i += 1;
+        } else {
+            candidates = without;
+        }
+    }
+    Ok(candidates)
+}
@@ -1,5 +1,71 @@
+use crate::math::float_eq;
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::Serialize;
+
+/// The rule used by the simplex to pick the variable that enters the basis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub enum PivotRule {
+    /// Picks the variable with the most negative reduced cost, usually the fastest
+    /// but it can cycle on degenerate problems
+    #[default]
+    Dantzig,
+    /// Picks the variable with the lowest index among the ones with a negative reduced cost,
+    /// which guarantees termination
+    Bland,
+}
+
+/// The numbers used by the simplex to pivot the tableau.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub enum SimplexArithmetic {
+    /// Pivots with floating point numbers, rounding errors can accumulate over the pivots
+    #[default]
+    Float,
+    /// Pivots with exact fractions of 64 bit integers, slower but without rounding errors,
+    /// the simplex fails with [`SimplexError::ArithmeticOverflow`](crate::solvers::SimplexError::ArithmeticOverflow)
+    /// if the fractions grow too large. Only suited for small models
+    #[cfg(feature = "rational-simplex")]
+    Rational,
+}
+
 pub fn divide_matrix_row_by(matrix: &mut [Vec<f64>], row: usize, value: f64) {
     for i in 0..matrix[row].len() {
         matrix[row][i] /= value;
     }
 }
+
+/// Solves the square linear system `matrix * x = rhs` with gaussian elimination and partial pivoting,
+/// returns `None` if the matrix is singular
+pub fn solve_linear_system(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let n = rhs.len();
+    for col in 0..n {
+        let pivot_row = (col..n).max_by(|a, b| {
+            matrix[*a][col]
+                .abs()
+                .partial_cmp(&matrix[*b][col].abs())
+                .unwrap()
+        })?;
+        if float_eq(matrix[pivot_row][col], 0.0) {
+            return None;
+        }
+        matrix.swap(col, pivot_row);
+        rhs.swap(col, pivot_row);
+        let (top, bottom) = matrix.split_at_mut(col + 1);
+        let pivot = &top[col];
+        for (offset, row) in bottom.iter_mut().enumerate() {
+            let factor = row[col] / pivot[col];
+            for (value, pivot_value) in row.iter_mut().zip(pivot.iter()).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            rhs[col + 1 + offset] -= factor * rhs[col];
+        }
+    }
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let known = ((row + 1)..n).fold(0.0, |acc, k| acc + matrix[row][k] * solution[k]);
+        solution[row] = (rhs[row] - known) / matrix[row][row];
+    }
+    Some(solution)
+}
@@ -1,11 +1,15 @@
-use crate::math::{float_ge, float_gt, float_le, float_lt};
+use crate::math::{float_ge, float_gt, float_le, float_lt, float_ne};
 #[allow(unused_imports)]
 use crate::prelude::*;
 use crate::solvers::{
-    FractionalTableau, OptimalTableau, OptimalTableauWithSteps, SimplexError, SimplexStep,
-    StepAction,
+    FractionalTableau, OptimalTableau, OptimalTableauWithSteps, PivotRule, SimplexError,
+    SimplexOptions, SimplexStep, StepAction,
 };
+#[cfg(feature = "rational-simplex")]
+use crate::solvers::{RationalStep, RationalTableau, SimplexArithmetic};
+use crate::utils::remove_many;
 use core::fmt;
+use indexmap::IndexMap;
 use std::fmt::Display;
 use term_table::row::Row;
 use term_table::table_cell::TableCell;
@@ -15,6 +19,7 @@ use term_table::Table;
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct Tableau {
     flip_result: bool,
+    feasibility_only: bool,
     variables: Vec<String>,
     c: Vec<f64>,
     a: Vec<Vec<f64>>,
@@ -22,6 +27,21 @@ pub struct Tableau {
     in_basis: Vec<usize>,
     current_value: f64,
     value_offset: f64,
+    //the constraint matrix and costs of the problem before pivoting, used to compute the duals
+    original_a: Vec<Vec<f64>>,
+    original_c: Vec<f64>,
+    //the name of the model constraint of each row, missing for the rows added by the solver
+    constraint_names: Vec<Option<String>>,
+    //the rows that were multiplied by -1 to make their right hand side non negative
+    negated_rows: Vec<bool>,
+    //the column of each variable of the model the tableau was derived from
+    original_columns: IndexMap<String, usize>,
+    //the positive and negative parts of the free variables that were split by the standardizer
+    free_variables: IndexMap<String, (String, String)>,
+}
+
+fn limit_options(limit: i64) -> SimplexOptions {
+    SimplexOptions::new().with_max_iterations(Some(limit.max(0) as usize))
 }
 
 impl Display for Tableau {
@@ -75,6 +95,14 @@ impl Tableau {
         self.step(&variables_to_avoid)
             .map(|action| serde_wasm_bindgen::to_value(&action).unwrap())
     }
+    pub fn wasm_step_with_rule(
+        &mut self,
+        variables_to_avoid: Vec<usize>,
+        pivot_rule: PivotRule,
+    ) -> Result<JsValue, SimplexError> {
+        self.step_with_rule(&variables_to_avoid, pivot_rule)
+            .map(|action| serde_wasm_bindgen::to_value(&action).unwrap())
+    }
     pub fn wasm_to_string(&self) -> String {
         self.to_string()
     }
@@ -93,6 +121,16 @@ impl Tableau {
         flip_result: bool,
     ) -> Tableau {
         Tableau {
+            original_a: a.clone(),
+            original_c: c.clone(),
+            constraint_names: (0..b.len()).map(|i| Some(format!("c{}", i))).collect(),
+            negated_rows: vec![false; b.len()],
+            original_columns: variables
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (v.clone(), i))
+                .collect(),
+            free_variables: IndexMap::new(),
             c,
             a,
             b,
@@ -101,15 +139,74 @@ impl Tableau {
             value_offset,
             variables,
             flip_result,
+            feasibility_only: false,
         }
     }
 
+    /// Marks the tableau as coming from a `satisfy` problem, so its solution is only a feasible point
+    pub(crate) fn with_feasibility_only(mut self, feasibility_only: bool) -> Tableau {
+        self.feasibility_only = feasibility_only;
+        self
+    }
+
+    /// Returns true if the tableau comes from a `satisfy` problem and has no objective
+    pub fn is_feasibility_only(&self) -> bool {
+        self.feasibility_only
+    }
+
+    /// Sets the constraint matrix and costs of the problem the tableau was derived from,
+    /// they are needed when the tableau was already pivoted into canonical form
+    pub(crate) fn with_original_problem(
+        mut self,
+        original_a: Vec<Vec<f64>>,
+        original_c: Vec<f64>,
+    ) -> Tableau {
+        self.original_a = original_a;
+        self.original_c = original_c;
+        self
+    }
+
+    /// Sets the names of the model the tableau was derived from, by default the rows are named
+    /// after their index and the variables are the ones of the tableau
+    ///
+    /// # Arguments
+    /// * `constraint_names` - The name of the constraint of each row, `None` for the rows that are not part of the model
+    /// * `negated_rows` - Whether each row is the negation of its constraint
+    /// * `original_columns` - The column of each variable of the model
+    pub(crate) fn with_model_names(
+        mut self,
+        constraint_names: Vec<Option<String>>,
+        negated_rows: Vec<bool>,
+        original_columns: IndexMap<String, usize>,
+    ) -> Tableau {
+        self.constraint_names = constraint_names;
+        self.negated_rows = negated_rows;
+        self.original_columns = original_columns;
+        self
+    }
+
+    /// Sets the free variables of the model that were split in a positive and negative part,
+    /// they are recombined in the solution of the tableau
+    pub(crate) fn with_free_variables(
+        mut self,
+        free_variables: IndexMap<String, (String, String)>,
+    ) -> Tableau {
+        self.free_variables = free_variables;
+        self
+    }
+
     pub fn flip_result(&self) -> bool {
         self.flip_result
     }
     pub fn solve(&mut self, limit: i64) -> Result<OptimalTableau, SimplexError> {
         self.solve_avoiding(limit, &[])
     }
+    pub fn solve_with_options(
+        &mut self,
+        options: &SimplexOptions,
+    ) -> Result<OptimalTableau, SimplexError> {
+        self.solve_avoiding_with_options(options, &[])
+    }
     pub fn variables(&self) -> &Vec<String> {
         &self.variables
     }
@@ -117,13 +214,23 @@ impl Tableau {
     pub fn solve_step_by_step(
         &mut self,
         limit: i64,
+    ) -> Result<OptimalTableauWithSteps, SimplexError> {
+        self.solve_step_by_step_with_options(&limit_options(limit))
+    }
+
+    pub fn solve_step_by_step_with_options(
+        &mut self,
+        options: &SimplexOptions,
     ) -> Result<OptimalTableauWithSteps, SimplexError> {
         let mut iteration = 0;
         let empty = vec![];
         let mut steps = vec![];
-        while iteration <= limit {
+        loop {
+            if self.reached_iteration_limit(options, iteration) {
+                return Err(SimplexError::IterationLimitReached);
+            }
             let prev = self.clone();
-            match self.step(&empty) {
+            match self.step_with_rule(&empty, options.pivot_rule) {
                 Ok(StepAction::Pivot {
                     entering,
                     leaving,
@@ -143,7 +250,6 @@ impl Tableau {
                 }
             }
         }
-        Err(SimplexError::IterationLimitReached)
     }
 
     pub fn solve_avoiding(
@@ -151,9 +257,24 @@ impl Tableau {
         limit: i64,
         variables_to_avoid: &[usize],
     ) -> Result<OptimalTableau, SimplexError> {
+        self.solve_avoiding_with_options(&limit_options(limit), variables_to_avoid)
+    }
+
+    pub fn solve_avoiding_with_options(
+        &mut self,
+        options: &SimplexOptions,
+        variables_to_avoid: &[usize],
+    ) -> Result<OptimalTableau, SimplexError> {
+        #[cfg(feature = "rational-simplex")]
+        if options.arithmetic == SimplexArithmetic::Rational {
+            return self.solve_rational_avoiding(options, variables_to_avoid);
+        }
         let mut iteration = 0;
-        while iteration <= limit {
-            match self.step(variables_to_avoid) {
+        loop {
+            if self.reached_iteration_limit(options, iteration) {
+                return Err(SimplexError::IterationLimitReached);
+            }
+            match self.step_with_rule(variables_to_avoid, options.pivot_rule) {
                 Ok(StepAction::Pivot { .. }) => {
                     iteration += 1;
                 }
@@ -165,13 +286,65 @@ impl Tableau {
                 }
             }
         }
-        Err(SimplexError::IterationLimitReached)
     }
+    /// Solves the tableau pivoting with exact fractions, the values are converted back
+    /// to floats once the simplex stops
+    #[cfg(feature = "rational-simplex")]
+    fn solve_rational_avoiding(
+        &mut self,
+        options: &SimplexOptions,
+        variables_to_avoid: &[usize],
+    ) -> Result<OptimalTableau, SimplexError> {
+        let mut exact = RationalTableau::new(self)?;
+        let mut iteration = 0;
+        loop {
+            if options.max_iterations.is_some_and(|max| iteration >= max) && !exact.is_optimal() {
+                self.load_rational(&exact);
+                return Err(SimplexError::IterationLimitReached);
+            }
+            match exact.step(variables_to_avoid, options.pivot_rule)? {
+                RationalStep::Pivot => {
+                    iteration += 1;
+                }
+                RationalStep::Finished => {
+                    self.load_rational(&exact);
+                    return Ok(OptimalTableau::new(self.variables_values(), self.clone()));
+                }
+                RationalStep::Unbounded => {
+                    self.load_rational(&exact);
+                    return Err(SimplexError::Unbounded);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "rational-simplex")]
+    fn load_rational(&mut self, exact: &RationalTableau) {
+        self.c = exact.c_vec();
+        self.a = exact.a_matrix();
+        self.b = exact.b_vec();
+        self.in_basis = exact.in_basis().clone();
+        self.current_value = exact.current_value();
+    }
+
     pub fn step(&mut self, variables_to_avoid: &[usize]) -> Result<StepAction, SimplexError> {
+        self.step_with_rule(variables_to_avoid, PivotRule::Dantzig)
+    }
+
+    /// Performs a single pivot, picking the entering variable with the given rule
+    pub fn step_with_rule(
+        &mut self,
+        variables_to_avoid: &[usize],
+        pivot_rule: PivotRule,
+    ) -> Result<StepAction, SimplexError> {
         if self.is_optimal() {
             return Ok(StepAction::Finished);
         }
-        match self.find_h(variables_to_avoid) {
+        let h = match pivot_rule {
+            PivotRule::Dantzig => self.find_h(variables_to_avoid),
+            PivotRule::Bland => self.find_h_bland(),
+        };
+        match h {
             None => Err(SimplexError::Unbounded),
             Some(h) => {
                 let t = self.find_t(h, variables_to_avoid);
@@ -190,6 +363,10 @@ impl Tableau {
         }
     }
 
+    fn reached_iteration_limit(&self, options: &SimplexOptions, iteration: usize) -> bool {
+        options.max_iterations.is_some_and(|max| iteration >= max) && !self.is_optimal()
+    }
+
     fn is_optimal(&self) -> bool {
         self.c.iter().all(|c| float_ge(*c, 0.0))
     }
@@ -199,10 +376,36 @@ impl Tableau {
         self.a.iter().all(|a| float_le(a[h], 0.0))
     }
 
+    /// Finds the variable that can increase indefinitely while improving the objective, after the
+    /// simplex stopped with [`SimplexError::Unbounded`]. It is `None` if the tableau is not unbounded
+    pub fn unbounded_variable(&self) -> Option<String> {
+        (0..self.c.len())
+            .filter(|h| !self.in_basis.contains(h) && float_lt(self.c[*h], 0.0))
+            .find(|h| self.a.iter().all(|row| float_le(row[*h], 0.0)))
+            .map(|h| self.ray_variable(h))
+    }
+
+    //no row limits the entering variable h, so it can grow without bound together with the basic
+    //variables that have a negative coefficient in its column, prefers the variables of the model
+    //over the ones added by the linearizer and the standardizer, which start with $
+    fn ray_variable(&self, h: usize) -> String {
+        let mut ray = std::iter::once(h).chain(
+            self.a
+                .iter()
+                .zip(self.in_basis.iter())
+                .filter(|(row, _)| float_lt(row[h], 0.0))
+                .map(|(_, basic)| *basic),
+        );
+        let variable = ray
+            .find(|v| !self.variables[*v].starts_with('$'))
+            .unwrap_or(h);
+        self.variables[variable].clone()
+    }
+
     //finds the variable that will enter the basis
     #[allow(unused)]
     fn find_h(&self, variables_to_avoid: &[usize]) -> Option<usize> {
-        //uses the Bland's rule for anti-cycling
+        //uses Dantzig's rule, picking the most negative reduced cost
         let min = self
             .c
             .iter()
@@ -212,6 +415,15 @@ impl Tableau {
         min.map(|(i, _)| i)
     }
 
+    //finds the variable that will enter the basis with Bland's rule, picking the lowest index with a negative reduced cost
+    fn find_h_bland(&self) -> Option<usize> {
+        self.c
+            .iter()
+            .enumerate()
+            .find(|(i, c)| !self.in_basis.contains(i) && float_lt(**c, 0.0))
+            .map(|(i, _)| i)
+    }
+
     //finds the variable that will leave the basis, prioritize variabls_to_prefer
     fn find_t(&self, h: usize, variables_to_prefer: &[usize]) -> Option<(usize, f64)> {
         //use the Bland's rule for anti-cycling
@@ -244,6 +456,111 @@ impl Tableau {
         }
     }
 
+    /// Adds the constraint `coefficients * x <= rhs` with a new slack variable that enters the basis.
+    /// The row is rewritten in terms of the non basic variables so that the tableau stays canonical,
+    /// its right hand side is negative when the current solution violates the constraint.
+    pub(crate) fn add_less_or_equal_row(&mut self, coefficients: &[f64], rhs: f64) {
+        let slack = self.c.len();
+        let mut row = coefficients.to_vec();
+        row.resize(slack, 0.0);
+        row.push(1.0);
+        let original_row = row.clone();
+        let mut rhs = rhs;
+        for (t, &basic) in self.in_basis.iter().enumerate() {
+            let coefficient = row[basic];
+            if float_ne(coefficient, 0.0) {
+                for (j, value) in row.iter_mut().enumerate().take(slack) {
+                    *value -= coefficient * self.a[t][j];
+                }
+                rhs -= coefficient * self.b[t];
+            }
+        }
+        for existing in self.a.iter_mut() {
+            existing.push(0.0);
+        }
+        for existing in self.original_a.iter_mut() {
+            existing.push(0.0);
+        }
+        self.a.push(row);
+        self.original_a.push(original_row);
+        self.b.push(rhs);
+        self.c.push(0.0);
+        self.original_c.push(0.0);
+        self.in_basis.push(slack);
+        self.constraint_names.push(None);
+        self.negated_rows.push(false);
+        self.variables.push(format!("$cut_{}", self.b.len()));
+    }
+
+    /// Runs the dual simplex on a tableau whose costs are all non negative, pivoting out the
+    /// basic variables with a negative value until the tableau is feasible again
+    pub(crate) fn solve_dual_with_options(
+        &mut self,
+        options: &SimplexOptions,
+    ) -> Result<OptimalTableau, SimplexError> {
+        let mut iteration = 0;
+        loop {
+            let leaving = self
+                .b
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| float_lt(**b, 0.0))
+                .min_by(|(_, b1), (_, b2)| b1.total_cmp(b2))
+                .map(|(t, _)| t);
+            let t = match leaving {
+                Some(t) => t,
+                None => return Ok(OptimalTableau::new(self.variables_values(), self.clone())),
+            };
+            if options.max_iterations.is_some_and(|max| iteration >= max) {
+                return Err(SimplexError::IterationLimitReached);
+            }
+            //the entering variable keeps the costs non negative, with the minimum ratio
+            let entering = self.a[t]
+                .iter()
+                .enumerate()
+                .filter(|(j, a)| !self.in_basis.contains(j) && float_lt(**a, 0.0))
+                .map(|(j, a)| (j, self.c[j] / -a))
+                .min_by(|(_, r1), (_, r2)| r1.total_cmp(r2))
+                .map(|(j, _)| j);
+            match entering {
+                Some(h) => {
+                    self.pivot(t, h).map_err(|_| SimplexError::Other)?;
+                    iteration += 1;
+                }
+                None => return Err(SimplexError::Infeasible),
+            }
+        }
+    }
+
+    /// Pivots the given variables out of the basis, replacing each of them with a variable of its row
+    /// that is not in the list. Rows where every other coefficient is zero come from redundant
+    /// constraints, so they are removed from the tableau. Returns the indexes of the removed rows.
+    pub(crate) fn drive_out_of_basis(
+        &mut self,
+        variables: &[usize],
+    ) -> Result<Vec<usize>, SimplexError> {
+        let mut redundant = Vec::new();
+        for t in 0..self.in_basis.len() {
+            if !variables.contains(&self.in_basis[t]) {
+                continue;
+            }
+            let h = (0..self.c.len()).find(|j| {
+                !variables.contains(j) && !self.in_basis.contains(j) && float_ne(self.a[t][*j], 0.0)
+            });
+            match h {
+                //the basic variable is zero, so pivoting on a negative value keeps the tableau feasible
+                Some(h) => self.pivot(t, h).map_err(|_| SimplexError::Other)?,
+                None => redundant.push(t),
+            }
+        }
+        remove_many(&mut self.a, &redundant);
+        remove_many(&mut self.b, &redundant);
+        remove_many(&mut self.in_basis, &redundant);
+        remove_many(&mut self.constraint_names, &redundant);
+        remove_many(&mut self.negated_rows, &redundant);
+        Ok(redundant)
+    }
+
     fn variables_values(&self) -> Vec<f64> {
         let mut values = vec![0.0; self.c.len()];
         for (i, &j) in self.in_basis.iter().enumerate() {
@@ -303,4 +620,26 @@ impl Tableau {
     pub fn in_basis(&self) -> &Vec<usize> {
         &self.in_basis
     }
+    pub fn original_a_matrix(&self) -> &Vec<Vec<f64>> {
+        &self.original_a
+    }
+    pub fn original_c_vec(&self) -> &Vec<f64> {
+        &self.original_c
+    }
+    /// The name of the model constraint of each row, `None` for the rows added by the solver
+    pub fn constraint_names(&self) -> &Vec<Option<String>> {
+        &self.constraint_names
+    }
+    /// Whether each row is the negation of its model constraint, done to make the right hand side non negative
+    pub fn negated_rows(&self) -> &Vec<bool> {
+        &self.negated_rows
+    }
+    /// The column of each variable of the model the tableau was derived from
+    pub fn original_columns(&self) -> &IndexMap<String, usize> {
+        &self.original_columns
+    }
+    /// The positive and negative parts of each free variable of the model
+    pub fn free_variables(&self) -> &IndexMap<String, (String, String)> {
+        &self.free_variables
+    }
 }
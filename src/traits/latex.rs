@@ -1,3 +1,4 @@
+use crate::utils::format_number;
 use std::fmt::Debug;
 
 pub trait ToLatex: Debug {
@@ -12,7 +13,7 @@ impl ToLatex for String {
 
 impl ToLatex for f64 {
     fn to_latex(&self) -> String {
-        format!("{}", self)
+        format_number(*self)
     }
 }
 
@@ -0,0 +1,455 @@
+use std::fmt::Display;
+
+use crate::math::{float_eq, float_lt, Comparison, OptimizationType, VariableType};
+use crate::parser::model_transformer::Model;
+use crate::transformers::linear_model::LinearModel;
+use crate::transformers::linearizer::{LinearizationError, Linearizer};
+
+/// Errors that can occur when exporting a model to an external format.
+#[derive(Debug)]
+pub enum ExportError {
+    /// The model could not be converted to a linear model
+    Linearization(LinearizationError),
+    /// The target format does not support this comparison
+    UnsupportedComparison(Comparison),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Linearization(e) => write!(f, "{}", e),
+            ExportError::UnsupportedComparison(c) => {
+                write!(f, "Comparison \"{}\" is not supported by the exporter", c)
+            }
+        }
+    }
+}
+
+/// Converts a model into a python script that builds and solves it using PuLP.
+///
+/// Only linear models are supported, the model is linearized before being exported.
+///
+/// # Arguments
+/// * `model` - The model to export
+///
+/// # Returns
+/// * `Ok(String)` - The python source code
+/// * `Err(ExportError)` - If the model is not linear or uses unsupported comparisons
+pub fn model_to_pulp(model: &Model) -> Result<String, ExportError> {
+    let linear = Linearizer::linearize(model.clone()).map_err(ExportError::Linearization)?;
+    linear_model_to_pulp(&linear)
+}
+
+/// Converts a linear model into a python script that builds and solves it using PuLP.
+///
+/// # Arguments
+/// * `model` - The linear model to export
+pub fn linear_model_to_pulp(model: &LinearModel) -> Result<String, ExportError> {
+    let sense = match model.optimization_type() {
+        OptimizationType::Max => "LpMaximize",
+        OptimizationType::Min | OptimizationType::Satisfy => "LpMinimize",
+    };
+    let mut lines = vec![
+        "from pulp import LpProblem, LpVariable, LpMinimize, LpMaximize, LpStatus, lpSum, value"
+            .to_string(),
+        "".to_string(),
+        format!("problem = LpProblem(\"rooc_model\", {})", sense),
+        "".to_string(),
+        "variables = {}".to_string(),
+    ];
+    for name in model.variables() {
+        let as_type = model
+            .domain()
+            .get(name)
+            .map(|d| *d.get_type())
+            .unwrap_or(VariableType::non_negative_real());
+        lines.push(format!(
+            "variables[\"{name}\"] = {}",
+            pulp_variable(name, &as_type)
+        ));
+    }
+    lines.push("".to_string());
+    let objective = match model.optimization_type() {
+        OptimizationType::Satisfy => "lpSum([])".to_string(),
+        _ => {
+            let terms = pulp_expression(model.variables(), model.objective());
+            let offset = model.objective_offset();
+            if float_eq(offset, 0.0) {
+                terms
+            } else if float_lt(offset, 0.0) {
+                format!("{} - {}", terms, -offset)
+            } else {
+                format!("{} + {}", terms, offset)
+            }
+        }
+    };
+    lines.push(format!("problem += {}, \"objective\"", objective));
+    for (i, constraint) in model.constraints().iter().enumerate() {
+        let comparison = match constraint.constraint_type() {
+            Comparison::LessOrEqual => "<=",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Equal => "==",
+            c => return Err(ExportError::UnsupportedComparison(*c)),
+        };
+        lines.push(format!(
+            "problem += {} {} {}, \"c{}\"",
+            pulp_expression(model.variables(), constraint.coefficients()),
+            comparison,
+            constraint.rhs(),
+            i
+        ));
+    }
+    lines.extend([
+        "".to_string(),
+        "problem.solve()".to_string(),
+        "print(LpStatus[problem.status])".to_string(),
+        "for name, variable in variables.items():".to_string(),
+        "    print(name, \"=\", variable.varValue)".to_string(),
+        "print(\"objective =\", value(problem.objective))".to_string(),
+    ]);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn pulp_bound(value: f64) -> String {
+    if value.is_infinite() {
+        "None".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn pulp_variable(name: &str, as_type: &VariableType) -> String {
+    match as_type {
+        VariableType::Boolean => format!("LpVariable(\"{}\", cat=\"Binary\")", name),
+        VariableType::IntegerRange(min, max) => format!(
+            "LpVariable(\"{}\", lowBound={}, upBound={}, cat=\"Integer\")",
+            name, min, max
+        ),
+        VariableType::Integer(min, max) => format!(
+            "LpVariable(\"{}\", lowBound={}, upBound={}, cat=\"Integer\")",
+            name,
+            pulp_bound(*min),
+            pulp_bound(*max)
+        ),
+        VariableType::NonNegativeReal(min, max) | VariableType::Real(min, max) => format!(
+            "LpVariable(\"{}\", lowBound={}, upBound={}, cat=\"Continuous\")",
+            name,
+            pulp_bound(*min),
+            pulp_bound(*max)
+        ),
+    }
+}
+
+fn pulp_expression(variables: &[String], coefficients: &[f64]) -> String {
+    let mut result = String::new();
+    for (name, coefficient) in variables.iter().zip(coefficients) {
+        if float_eq(*coefficient, 0.0) {
+            continue;
+        }
+        let term = if float_eq(coefficient.abs(), 1.0) {
+            format!("variables[\"{}\"]", name)
+        } else {
+            format!("{} * variables[\"{}\"]", coefficient.abs(), name)
+        };
+        if result.is_empty() {
+            if float_lt(*coefficient, 0.0) {
+                result.push('-');
+            }
+            result.push_str(&term);
+        } else if float_lt(*coefficient, 0.0) {
+            result.push_str(&format!(" - {}", term));
+        } else {
+            result.push_str(&format!(" + {}", term));
+        }
+    }
+    if result.is_empty() {
+        "lpSum([])".to_string()
+    } else {
+        result
+    }
+}
+
+/// Converts a model into the CPLEX LP text format.
+///
+/// Only linear models are supported, the model is linearized before being exported.
+/// Constraints keep the name they were given in the source, the others are named with
+/// [`LinearModel::constraint_name`].
+///
+/// # Arguments
+/// * `model` - The model to export
+///
+/// # Returns
+/// * `Ok(String)` - The model in LP format
+/// * `Err(ExportError)` - If the model is not linear or uses unsupported comparisons
+pub fn model_to_lp_format(model: &Model) -> Result<String, ExportError> {
+    let linear = Linearizer::linearize(model.clone()).map_err(ExportError::Linearization)?;
+    linear_model_to_lp_format(&linear)
+}
+
+/// Converts a linear model into the CPLEX LP text format.
+///
+/// # Arguments
+/// * `model` - The linear model to export
+pub fn linear_model_to_lp_format(model: &LinearModel) -> Result<String, ExportError> {
+    let sense = match model.optimization_type() {
+        OptimizationType::Max => "Maximize",
+        OptimizationType::Min | OptimizationType::Satisfy => "Minimize",
+    };
+    let mut lines = vec!["\\ rooc_model".to_string(), sense.to_string()];
+    let objective = match model.optimization_type() {
+        OptimizationType::Satisfy => "0".to_string(),
+        _ => {
+            let terms = lp_expression(model.variables(), model.objective());
+            let offset = model.objective_offset();
+            if float_eq(offset, 0.0) {
+                terms
+            } else if float_lt(offset, 0.0) {
+                format!("{} - {}", terms, -offset)
+            } else {
+                format!("{} + {}", terms, offset)
+            }
+        }
+    };
+    lines.push(format!(" obj: {}", objective));
+    lines.push("Subject To".to_string());
+    let names = model.constraint_names();
+    for (constraint, name) in model.constraints().iter().zip(&names) {
+        let comparison = match constraint.constraint_type() {
+            Comparison::LessOrEqual => "<=",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Equal => "=",
+            c => return Err(ExportError::UnsupportedComparison(*c)),
+        };
+        lines.push(format!(
+            " {}: {} {} {}",
+            name,
+            lp_expression(model.variables(), constraint.coefficients()),
+            comparison,
+            constraint.rhs()
+        ));
+    }
+    let mut bounds = Vec::new();
+    let mut general = Vec::new();
+    let mut binary = Vec::new();
+    for name in model.variables() {
+        let as_type = model
+            .domain()
+            .get(name)
+            .map(|d| *d.get_type())
+            .unwrap_or(VariableType::non_negative_real());
+        match as_type {
+            VariableType::Boolean => binary.push(name.clone()),
+            VariableType::IntegerRange(min, max) => {
+                bounds.push(format!(" {} <= {} <= {}", min, name, max));
+                general.push(name.clone());
+            }
+            VariableType::Integer(min, max) => {
+                if min.is_infinite() && max.is_infinite() {
+                    bounds.push(format!(" {} free", name));
+                } else if !(float_eq(min, 0.0) && max.is_infinite()) {
+                    bounds.push(format!(
+                        " {} <= {} <= {}",
+                        lp_bound(min),
+                        name,
+                        lp_bound(max)
+                    ));
+                }
+                general.push(name.clone());
+            }
+            VariableType::NonNegativeReal(min, max) | VariableType::Real(min, max) => {
+                if min.is_infinite() && max.is_infinite() {
+                    bounds.push(format!(" {} free", name));
+                } else if !(float_eq(min, 0.0) && max.is_infinite()) {
+                    bounds.push(format!(
+                        " {} <= {} <= {}",
+                        lp_bound(min),
+                        name,
+                        lp_bound(max)
+                    ));
+                }
+            }
+        }
+    }
+    if !bounds.is_empty() {
+        lines.push("Bounds".to_string());
+        lines.extend(bounds);
+    }
+    if !general.is_empty() {
+        lines.push("General".to_string());
+        lines.push(format!(" {}", general.join(" ")));
+    }
+    if !binary.is_empty() {
+        lines.push("Binary".to_string());
+        lines.push(format!(" {}", binary.join(" ")));
+    }
+    lines.push("End".to_string());
+    Ok(lines.join("\n") + "\n")
+}
+
+fn lp_bound(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn lp_expression(variables: &[String], coefficients: &[f64]) -> String {
+    let mut result = String::new();
+    for (name, coefficient) in variables.iter().zip(coefficients) {
+        if float_eq(*coefficient, 0.0) {
+            continue;
+        }
+        let term = if float_eq(coefficient.abs(), 1.0) {
+            name.clone()
+        } else {
+            format!("{} {}", coefficient.abs(), name)
+        };
+        if result.is_empty() {
+            if float_lt(*coefficient, 0.0) {
+                result.push_str("- ");
+            }
+            result.push_str(&term);
+        } else if float_lt(*coefficient, 0.0) {
+            result.push_str(&format!(" - {}", term));
+        } else {
+            result.push_str(&format!(" + {}", term));
+        }
+    }
+    if result.is_empty() {
+        "0".to_string()
+    } else {
+        result
+    }
+}
+
+/// Converts a model into the fixed-column MPS format.
+///
+/// Only linear models are supported, the model is linearized before being exported.
+/// Constraints are named like in [`model_to_lp_format`] and the objective row is named `obj`.
+///
+/// # Arguments
+/// * `model` - The model to export
+///
+/// # Returns
+/// * `Ok(String)` - The model in MPS format
+/// * `Err(ExportError)` - If the model is not linear or uses unsupported comparisons
+pub fn model_to_mps_format(model: &Model) -> Result<String, ExportError> {
+    let linear = Linearizer::linearize(model.clone()).map_err(ExportError::Linearization)?;
+    linear_model_to_mps_format(&linear)
+}
+
+/// Converts a linear model into the fixed-column MPS format.
+///
+/// # Arguments
+/// * `model` - The linear model to export
+pub fn linear_model_to_mps_format(model: &LinearModel) -> Result<String, ExportError> {
+    let mut lines = vec![format!("{:<14}{}", "NAME", "ROOC_MODEL")];
+    if *model.optimization_type() == OptimizationType::Max {
+        lines.push("OBJSENSE".to_string());
+        lines.push("    MAX".to_string());
+    }
+    lines.push("ROWS".to_string());
+    lines.push(mps_row("N", "obj"));
+    let names = model.constraint_names();
+    for (constraint, name) in model.constraints().iter().zip(&names) {
+        let row_type = match constraint.constraint_type() {
+            Comparison::LessOrEqual => "L",
+            Comparison::GreaterOrEqual => "G",
+            Comparison::Equal => "E",
+            c => return Err(ExportError::UnsupportedComparison(*c)),
+        };
+        lines.push(mps_row(row_type, name));
+    }
+    lines.push("COLUMNS".to_string());
+    let is_satisfy = *model.optimization_type() == OptimizationType::Satisfy;
+    for (j, name) in model.variables().iter().enumerate() {
+        let objective = if is_satisfy {
+            0.0
+        } else {
+            model.objective()[j]
+        };
+        if !float_eq(objective, 0.0) {
+            lines.push(mps_entry("", name, "obj", objective));
+        }
+        for (constraint, row) in model.constraints().iter().zip(&names) {
+            let coefficient = constraint.coefficients()[j];
+            if !float_eq(coefficient, 0.0) {
+                lines.push(mps_entry("", name, row, coefficient));
+            }
+        }
+    }
+    lines.push("RHS".to_string());
+    let offset = model.objective_offset();
+    if !is_satisfy && !float_eq(offset, 0.0) {
+        //the rhs of the objective row is the negated objective constant
+        lines.push(mps_entry("", "RHS", "obj", -offset));
+    }
+    for (constraint, name) in model.constraints().iter().zip(&names) {
+        if !float_eq(constraint.rhs(), 0.0) {
+            lines.push(mps_entry("", "RHS", name, constraint.rhs()));
+        }
+    }
+    lines.push("BOUNDS".to_string());
+    for name in model.variables() {
+        let as_type = model
+            .domain()
+            .get(name)
+            .map(|d| *d.get_type())
+            .unwrap_or(VariableType::non_negative_real());
+        match as_type {
+            VariableType::Boolean => lines.push(mps_bound("BV", name, None)),
+            VariableType::IntegerRange(min, max) => {
+                lines.push(mps_bound("LI", name, Some(min as f64)));
+                lines.push(mps_bound("UI", name, Some(max as f64)));
+            }
+            VariableType::Integer(min, max) => {
+                //the integer bounds also mark the column as integer, so they are always written
+                let min = if min.is_infinite() {
+                    -MPS_INFINITY
+                } else {
+                    min
+                };
+                let max = if max.is_infinite() { MPS_INFINITY } else { max };
+                lines.push(mps_bound("LI", name, Some(min)));
+                lines.push(mps_bound("UI", name, Some(max)));
+            }
+            VariableType::NonNegativeReal(min, max) | VariableType::Real(min, max) => {
+                if min.is_infinite() && max.is_infinite() {
+                    lines.push(mps_bound("FR", name, None));
+                    continue;
+                }
+                if min.is_infinite() {
+                    lines.push(mps_bound("MI", name, None));
+                } else if !float_eq(min, 0.0) {
+                    lines.push(mps_bound("LO", name, Some(min)));
+                }
+                if !max.is_infinite() {
+                    lines.push(mps_bound("UP", name, Some(max)));
+                }
+            }
+        }
+    }
+    lines.push("ENDATA".to_string());
+    Ok(lines.join("\n") + "\n")
+}
+
+fn mps_row(row_type: &str, name: &str) -> String {
+    format!(" {:<2} {}", row_type, name)
+}
+
+fn mps_entry(kind: &str, name: &str, row: &str, value: f64) -> String {
+    format!(" {:<2} {:<8}  {:<8}  {}", kind, name, row, value)
+}
+
+/// Value that MPS readers treat as an infinite bound
+const MPS_INFINITY: f64 = 1e30;
+
+fn mps_bound(kind: &str, name: &str, value: Option<f64>) -> String {
+    match value {
+        Some(value) => mps_entry(kind, "BND", name, value),
+        None => format!(" {:<2} {:<8}  {}", kind, "BND", name),
+    }
+}
@@ -0,0 +1,90 @@
+use crate::math::{BinOp, Comparison, OptimizationType, VariableType};
+use crate::parser::model_transformer::{Constraint, DomainVariable, Exp, Model, Objective};
+use crate::utils::InputSpan;
+
+/// Turns the goals of the model, like `x + y ~ 10`, into equality constraints with a pair of
+/// deviation variables, `x + y + $goal_0_u - $goal_0_o = 10`, and adds the weighted sum of the
+/// deviations to the objective so that they are minimized.
+///
+/// Maximization problems subtract the deviations from the objective, while `solve` problems
+/// become the minimization of the total deviation.
+///
+/// # Arguments
+/// * `model` - The model whose goals should be expanded
+///
+/// # Returns
+/// The model without goals, which is the same as the original if it had none
+pub fn expand_goals(model: Model) -> Model {
+    if model
+        .constraints()
+        .iter()
+        .all(|c| c.goal_weight().is_none())
+    {
+        return model;
+    }
+    let (objective, constraints, mut domain) = model.into_components();
+    let mut deviations: Option<Exp> = None;
+    let mut expanded = Vec::with_capacity(constraints.len());
+    let mut goal_count = 0;
+    for constraint in constraints {
+        let weight = match constraint.goal_weight() {
+            Some(weight) => weight,
+            None => {
+                expanded.push(constraint);
+                continue;
+            }
+        };
+        let under = format!("$goal_{}_u", goal_count);
+        let over = format!("$goal_{}_o", goal_count);
+        goal_count += 1;
+        for name in [&under, &over] {
+            let mut var = DomainVariable::new(
+                VariableType::NonNegativeReal(0.0, f64::INFINITY),
+                InputSpan::default(),
+            );
+            var.increment_usage();
+            domain.insert(name.clone(), var);
+        }
+        let name = constraint.name().cloned();
+        let span = constraint.span().cloned();
+        let (lhs, _, rhs) = constraint.into_parts();
+        let lhs = Exp::BinOp(
+            BinOp::Sub,
+            Exp::make_binop(BinOp::Add, lhs, Exp::Variable(under.clone())),
+            Exp::Variable(over.clone()).to_box(),
+        );
+        let mut goal = Constraint::new(lhs, Comparison::Equal, rhs);
+        if let Some(name) = name {
+            goal = goal.with_name(name);
+        }
+        if let Some(span) = span {
+            goal = goal.with_span(span);
+        }
+        expanded.push(goal);
+        let deviation = Exp::BinOp(
+            BinOp::Mul,
+            Exp::Number(weight).to_box(),
+            Exp::make_binop(BinOp::Add, Exp::Variable(under), Exp::Variable(over)),
+        );
+        deviations = Some(match deviations {
+            Some(sum) => Exp::BinOp(BinOp::Add, sum.to_box(), deviation.to_box()),
+            None => deviation,
+        });
+    }
+    let deviations = deviations.unwrap_or(Exp::Number(0.0));
+    let objective = match objective.objective_type {
+        OptimizationType::Min => Objective::new(
+            OptimizationType::Min,
+            Exp::BinOp(BinOp::Add, objective.rhs.to_box(), deviations.to_box()),
+        )
+        .with_offset(objective.offset),
+        OptimizationType::Max => Objective::new(
+            OptimizationType::Max,
+            Exp::BinOp(BinOp::Sub, objective.rhs.to_box(), deviations.to_box()),
+        )
+        .with_offset(objective.offset),
+        //the objective of a satisfy problem is only a placeholder
+        OptimizationType::Satisfy => Objective::new(OptimizationType::Min, deviations),
+    };
+    Model::new(objective, expanded, domain)
+}
@@ -3,6 +3,7 @@
 use crate::prelude::*;
 use indexmap::IndexMap;
 use num_traits::Zero;
+use std::collections::HashSet;
 use std::fmt::Display;
 
 use crate::domain_declaration::format_domain;
@@ -28,6 +29,8 @@ pub struct LinearConstraint {
     coefficients: Vec<f64>,
     rhs: f64,
     constraint_type: Comparison,
+    name: Option<String>,
+    span: Option<InputSpan>,
 }
 
 impl LinearConstraint {
@@ -42,9 +45,48 @@ impl LinearConstraint {
             coefficients,
             rhs,
             constraint_type,
+            name: None,
+            span: None,
         }
     }
 
+    /// Gives the constraint a name, used in place of the default `c{index}` one.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the constraint
+    pub fn with_name(mut self, name: String) -> LinearConstraint {
+        self.name = Some(name);
+        self
+    }
+
+    /// Returns the name of the constraint, if it was given one.
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    /// Sets the location in the source of the constraint this one was linearized from.
+    ///
+    /// # Arguments
+    /// * `span` - The span of the source constraint
+    pub fn with_span(mut self, span: InputSpan) -> LinearConstraint {
+        self.span = Some(span);
+        self
+    }
+
+    /// Returns the location in the source of the constraint this one was linearized from,
+    /// the constraints added by the transformers and solvers have none.
+    pub fn span(&self) -> Option<&InputSpan> {
+        self.span.as_ref()
+    }
+
+    /// Returns the text of the source constraint this one was linearized from.
+    ///
+    /// # Arguments
+    /// * `source` - The source code the model was compiled from
+    pub fn source_text<'a>(&self, source: &'a str) -> Option<&'a str> {
+        self.span.as_ref()?.span_text(source).ok()
+    }
+
     /// Returns a reference to the coefficient vector.
     pub fn coefficients(&self) -> &Vec<f64> {
         &self.coefficients
@@ -104,6 +146,22 @@ impl LinearConstraint {
     }
 }
 
+/// A constraint of a [`LinearModel`] where only the variables with a non zero coefficient are listed.
+///
+/// For example: 2x_1 - 3x_2 <= 5 would be represented as:
+/// - terms: [("x_1", 2.0), ("x_2", -3.0)]
+/// - constraint_type: LessOrEqual
+/// - rhs: 5.0
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseConstraint {
+    /// The name of the constraint, as given by [`LinearModel::constraint_name`]
+    pub name: String,
+    /// The name and coefficient of each variable that appears in the constraint
+    pub terms: Vec<(String, f64)>,
+    pub constraint_type: Comparison,
+    pub rhs: f64,
+}
+
 /// Represents a complete linear programming model including variables, constraints, and objective function.
 ///
 /// # Example
@@ -131,6 +189,7 @@ pub struct LinearModel {
     optimization_type: OptimizationType,
     objective: Vec<f64>,
     constraints: Vec<LinearConstraint>,
+    constraint_prefix: String,
 }
 
 impl Default for LinearModel {
@@ -142,6 +201,7 @@ impl Default for LinearModel {
             optimization_type: OptimizationType::Min,
             objective: vec![],
             constraints: vec![],
+            constraint_prefix: "c".to_string(),
         }
     }
 }
@@ -178,6 +238,7 @@ impl LinearModel {
             variables,
             objective_offset,
             domain,
+            constraint_prefix: "c".to_string(),
         }
     }
 
@@ -308,6 +369,77 @@ impl LinearModel {
         &self.constraints
     }
 
+    /// Returns the name of the constraint at the given index, as used by the exporters and solvers.
+    /// Constraints without a name are named after the constraint prefix and their index
+    pub fn constraint_name(&self, index: usize) -> String {
+        match self.constraints.get(index).and_then(|c| c.name()) {
+            Some(name) => name.clone(),
+            None => self.generated_constraint_name(index, |name| {
+                self.constraints
+                    .iter()
+                    .any(|c| c.name().is_some_and(|n| n == name))
+            }),
+        }
+    }
+
+    /// Returns the names of all the constraints, in the same way as [`LinearModel::constraint_name`]
+    pub fn constraint_names(&self) -> Vec<String> {
+        let given = self
+            .constraints
+            .iter()
+            .filter_map(|c| c.name().map(|n| n.as_str()))
+            .collect::<HashSet<_>>();
+        self.constraints
+            .iter()
+            .enumerate()
+            .map(|(i, c)| match c.name() {
+                Some(name) => name.clone(),
+                None => self.generated_constraint_name(i, |name| given.contains(name)),
+            })
+            .collect()
+    }
+
+    /// Names a constraint after the prefix and its index, adding underscores until it differs
+    /// from the names given to the other constraints, like a constraint named `c1`
+    fn generated_constraint_name(&self, index: usize, is_given: impl Fn(&str) -> bool) -> String {
+        let mut name = format!("{}{}", self.constraint_prefix, index);
+        while is_given(&name) {
+            name.push('_');
+        }
+        name
+    }
+
+    /// Sets the prefix used to name the constraints that were not given a name, `c` by default
+    pub fn set_constraint_prefix(&mut self, prefix: String) {
+        self.constraint_prefix = prefix;
+    }
+
+    /// Returns the prefix used to name the constraints that were not given a name
+    pub fn constraint_prefix(&self) -> &str {
+        &self.constraint_prefix
+    }
+
+    /// Returns each constraint as the list of the variables it uses with their coefficients,
+    /// skipping the variables whose coefficient is zero.
+    pub fn terms(&self) -> Vec<SparseConstraint> {
+        self.constraints
+            .iter()
+            .zip(self.constraint_names())
+            .map(|(constraint, name)| SparseConstraint {
+                name,
+                terms: constraint
+                    .coefficients()
+                    .iter()
+                    .zip(self.variables.iter())
+                    .filter(|(c, _)| !c.is_zero())
+                    .map(|(c, name)| (name.clone(), *c))
+                    .collect(),
+                constraint_type: *constraint.constraint_type(),
+                rhs: constraint.rhs(),
+            })
+            .collect()
+    }
+
     /// Returns a reference to the variable names.
     pub fn variables(&self) -> &Vec<String> {
         &self.variables
@@ -322,6 +454,19 @@ impl LinearModel {
     pub fn domain(&self) -> &IndexMap<String, DomainVariable> {
         &self.domain
     }
+
+    /// Returns the initial value of every variable, if all of them were declared with one.
+    pub fn initial_assignment(&self) -> Option<IndexMap<String, f64>> {
+        self.variables
+            .iter()
+            .map(|name| {
+                self.domain
+                    .get(name)
+                    .and_then(|v| v.initial_value())
+                    .map(|value| (name.clone(), value))
+            })
+            .collect()
+    }
 }
 
 impl Display for LinearModel {
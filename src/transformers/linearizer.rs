@@ -1,19 +1,107 @@
 use crate::math::float_lt;
 use crate::math::{BinOp, UnOp};
-use crate::math::{Comparison, VariableType};
+use crate::math::{Comparison, OptimizationType, VariableType};
 use crate::parser::model_transformer::DomainVariable;
-use crate::parser::model_transformer::{Constraint, Exp, Model};
+use crate::parser::model_transformer::{Constraint, Exp, Model, TransformError};
+use crate::primitives::OperatorError;
+use crate::transformers::goal_program::expand_goals;
 use crate::transformers::linear_model::{LinearConstraint, LinearModel};
 use crate::utils::InputSpan;
 use indexmap::IndexMap;
 use std::collections::VecDeque;
 use std::fmt::Display;
 
+/// The direction in which the surrounding constraint or objective pushes an expression,
+/// convex terms like `|e|` can only be replaced by an auxiliary variable when they are pushed down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Polarity {
+    /// The expression is kept as small as possible, like in `e <= 0` or `min e`
+    Minimized,
+    /// The expression is kept as large as possible, like in `e >= 0` or `max e`
+    Maximized,
+    /// The expression has a fixed value, like in `e = 0`
+    Fixed,
+}
+
+impl Polarity {
+    fn from_comparison(comparison: Comparison) -> Self {
+        match comparison {
+            Comparison::LessOrEqual | Comparison::Less => Polarity::Minimized,
+            Comparison::GreaterOrEqual | Comparison::Greater => Polarity::Maximized,
+            Comparison::Equal => Polarity::Fixed,
+        }
+    }
+
+    fn from_optimization_type(optimization_type: &OptimizationType) -> Self {
+        match optimization_type {
+            OptimizationType::Min => Polarity::Minimized,
+            OptimizationType::Max => Polarity::Maximized,
+            OptimizationType::Satisfy => Polarity::Fixed,
+        }
+    }
+
+    fn flip(self) -> Self {
+        match self {
+            Polarity::Minimized => Polarity::Maximized,
+            Polarity::Maximized => Polarity::Minimized,
+            Polarity::Fixed => Polarity::Fixed,
+        }
+    }
+
+    /// Checks that the expression is pushed in the `required` direction, which is needed
+    /// to replace it with an auxiliary variable, `name` describes the expression in the error
+    fn require(self, required: Polarity, name: &str, exp: &Exp) -> Result<(), LinearizationError> {
+        if self == required {
+            return Ok(());
+        }
+        let reason = match self {
+            Polarity::Fixed => format!("the {} appears in an equality", name),
+            Polarity::Maximized => format!(
+                "the {} is maximized, either by a maximization objective \
+                 or by a constraint that requires it to be large",
+                name
+            ),
+            Polarity::Minimized => format!(
+                "the {} is minimized, either by a minimization objective \
+                 or by a constraint that requires it to be small",
+                name
+            ),
+        };
+        Err(LinearizationError::NonConvexExpression(
+            Box::new(exp.clone()),
+            reason,
+        ))
+    }
+
+    /// The polarity of an expression multiplied by `factor`
+    fn scaled_by(self, factor: f64) -> Self {
+        if float_lt(factor, 0.0) {
+            self.flip()
+        } else {
+            self
+        }
+    }
+}
+
 impl Exp {
+    /// Returns true if a variable appears anywhere in the expression.
+    pub(crate) fn has_variables(&self) -> bool {
+        match self {
+            Exp::Number(_) => false,
+            Exp::Variable(_) => true,
+            Exp::BinOp(_, lhs, rhs) => lhs.has_variables() || rhs.has_variables(),
+            Exp::UnOp(_, exp) | Exp::Abs(exp) => exp.has_variables(),
+            Exp::Min(exps) | Exp::Max(exps) => exps.iter().any(|e| e.has_variables()),
+            //the interpolation weights are variables even if the argument is not
+            Exp::Piecewise(_, _, _) => true,
+        }
+    }
+
     /// Converts an expression into a linear form.
     ///
     /// # Arguments
     /// * `linearizer_context` - The context containing variables and constraints
+    /// * `polarity` - The direction in which the expression is pushed by where it appears
     ///
     /// # Returns
     /// * `Ok(LinearizationContext)` - The linearized expression
@@ -21,59 +109,73 @@ impl Exp {
     fn linearize(
         &self,
         linearizer_context: &mut Linearizer,
+        polarity: Polarity,
     ) -> Result<LinearizationContext, LinearizationError> {
         match self {
-            Exp::BinOp(op, lhs, rhs) => {
-                let mut lhs = lhs.linearize(linearizer_context)?;
-                let mut rhs = rhs.linearize(linearizer_context)?;
-                let context = match op {
-                    BinOp::Add => {
-                        lhs.merge_add(rhs);
-                        lhs
-                    }
-                    BinOp::Sub => {
-                        lhs.merge_sub(rhs);
-                        lhs
-                    }
-                    BinOp::Mul => {
-                        if lhs.has_no_vars() {
-                            rhs.mul_by(lhs.rhs());
-                            rhs
-                        } else if rhs.has_no_vars() {
-                            lhs.mul_by(rhs.rhs());
-                            lhs
-                        } else {
-                            return Err(LinearizationError::NonLinearExpression(Box::new(
-                                self.clone(),
-                            )));
-                        }
-                    }
-                    BinOp::Div => {
-                        if rhs.has_no_vars() {
-                            lhs.div_by(rhs.rhs());
-                            lhs
-                        } else if lhs.has_no_vars() {
-                            rhs.div_by(lhs.rhs());
-                            rhs
-                        } else {
-                            return Err(LinearizationError::NonLinearExpression(Box::new(
-                                self.clone(),
-                            )));
-                        }
+            Exp::BinOp(op, lhs, rhs) => match op {
+                BinOp::Add => {
+                    let mut context = lhs.linearize(linearizer_context, polarity)?;
+                    context.merge_add(rhs.linearize(linearizer_context, polarity)?);
+                    Ok(context)
+                }
+                BinOp::Sub => {
+                    let mut context = lhs.linearize(linearizer_context, polarity)?;
+                    context.merge_sub(rhs.linearize(linearizer_context, polarity.flip())?);
+                    Ok(context)
+                }
+                BinOp::Mul => {
+                    let (mut lhs, mut rhs) =
+                        linearize_factors(lhs, rhs, linearizer_context, polarity)?;
+                    if lhs.has_no_vars() {
+                        rhs.mul_by(lhs.rhs());
+                        Ok(rhs)
+                    } else if rhs.has_no_vars() {
+                        lhs.mul_by(rhs.rhs());
+                        Ok(lhs)
+                    } else {
+                        linearizer_context.linearize_boolean_product(lhs, rhs, self)
                     }
-                };
-                Ok(context)
-            }
+                }
+                //check_linearity already rejected divisors and modulo operands with variables
+                BinOp::Div => {
+                    let (mut lhs, rhs) = linearize_factors(lhs, rhs, linearizer_context, polarity)?;
+                    lhs.div_by(non_zero_divisor(*op, rhs.rhs())?);
+                    Ok(lhs)
+                }
+                BinOp::Mod => {
+                    let lhs = lhs.linearize(linearizer_context, Polarity::Fixed)?;
+                    let rhs = rhs.linearize(linearizer_context, Polarity::Fixed)?;
+                    let divisor = non_zero_divisor(*op, rhs.rhs())?;
+                    Ok(LinearizationContext::from_rhs(
+                        lhs.rhs().rem_euclid(divisor),
+                    ))
+                }
+                BinOp::And
+                | BinOp::Or
+                | BinOp::Equal
+                | BinOp::NotEqual
+                | BinOp::Less
+                | BinOp::LessOrEqual
+                | BinOp::Greater
+                | BinOp::GreaterOrEqual => Err(LinearizationError::NonLinearExpression(Box::new(
+                    self.clone(),
+                ))),
+            },
             Exp::UnOp(op, exp) => match op {
                 UnOp::Neg => {
-                    let mut context = exp.linearize(linearizer_context)?;
+                    let mut context = exp.linearize(linearizer_context, polarity.flip())?;
                     context.mul_by(-1.0);
                     Ok(context)
                 }
+                UnOp::Not => Err(LinearizationError::NonLinearExpression(Box::new(
+                    self.clone(),
+                ))),
             },
             Exp::Number(num) => Ok(LinearizationContext::from_rhs(*num)),
             Exp::Variable(name) => Ok(LinearizationContext::from_var(name.clone(), 1.0)),
             Exp::Min(exps) => {
+                //min{e} is replaced by t with t <= e for each e, which is exact only if t is pushed up
+                polarity.require(Polarity::Maximized, "minimum", self)?;
                 let var_name = format!("$min_{}", linearizer_context.min_count);
                 linearizer_context.min_count += 1;
                 for exp in exps {
@@ -91,6 +193,8 @@ impl Exp {
                 Ok(LinearizationContext::from_var(var_name, 1.0))
             }
             Exp::Max(exps) => {
+                //max{e} is replaced by t with t >= e for each e, which is exact only if t is pushed down
+                polarity.require(Polarity::Minimized, "maximum", self)?;
                 let var_name = format!("$max_{}", linearizer_context.max_count);
                 linearizer_context.max_count += 1;
                 for exp in exps {
@@ -107,19 +211,148 @@ impl Exp {
                 )?;
                 Ok(LinearizationContext::from_var(var_name, 1.0))
             }
-            Exp::Abs(_) => Err(LinearizationError::UnimplementedExpression(Box::new(
-                self.clone(),
-            ))),
+            Exp::Abs(exp) => {
+                if !exp.has_variables() {
+                    let context = exp.linearize(linearizer_context, Polarity::Fixed)?;
+                    return Ok(LinearizationContext::from_rhs(context.rhs().abs()));
+                }
+                //|e| is replaced by t with t >= e and t >= -e, which is exact only if t is pushed down
+                polarity.require(Polarity::Minimized, "absolute value", self)?;
+                let var_name = format!("$abs_{}", linearizer_context.abs_count);
+                linearizer_context.abs_count += 1;
+                let exp = *exp.clone();
+                linearizer_context.add_constraint(Constraint::new(
+                    Exp::Variable(var_name.clone()),
+                    Comparison::GreaterOrEqual,
+                    Exp::UnOp(UnOp::Neg, exp.clone().to_box()),
+                ));
+                linearizer_context.add_constraint(Constraint::new(
+                    Exp::Variable(var_name.clone()),
+                    Comparison::GreaterOrEqual,
+                    exp,
+                ));
+                linearizer_context.declare_variable(
+                    var_name.clone(),
+                    VariableType::NonNegativeReal(0.0, f64::INFINITY),
+                )?;
+                Ok(LinearizationContext::from_var(var_name, 1.0))
+            }
+            Exp::Piecewise(exp, breakpoints, values) => {
+                //e = sum(b_i * l_i) and f(e) = sum(v_i * l_i) with the weights l_i summing to 1,
+                //at most two adjacent weights can be non zero, as picked by the segment booleans s_i
+                let index = linearizer_context.piecewise_count;
+                linearizer_context.piecewise_count += 1;
+                let weights = (0..breakpoints.len())
+                    .map(|i| format!("$pw_{}_l{}", index, i))
+                    .collect::<Vec<_>>();
+                let segments = (0..breakpoints.len() - 1)
+                    .map(|i| format!("$pw_{}_s{}", index, i))
+                    .collect::<Vec<_>>();
+                let sum = |terms: Vec<Exp>| {
+                    terms
+                        .into_iter()
+                        .reduce(|acc, term| Exp::BinOp(BinOp::Add, acc.to_box(), term.to_box()))
+                        .unwrap_or(Exp::Number(0.0))
+                };
+                let weighted = |coefficients: &Vec<f64>| {
+                    sum(coefficients
+                        .iter()
+                        .zip(weights.iter())
+                        .map(|(c, w)| {
+                            Exp::BinOp(
+                                BinOp::Mul,
+                                Exp::Number(*c).to_box(),
+                                Exp::Variable(w.clone()).to_box(),
+                            )
+                        })
+                        .collect())
+                };
+                let variables =
+                    |names: &[String]| names.iter().map(|n| Exp::Variable(n.clone())).collect();
+                let mut constraints = vec![
+                    Constraint::new(*exp.clone(), Comparison::Equal, weighted(breakpoints)),
+                    Constraint::new(
+                        sum(variables(&weights)),
+                        Comparison::Equal,
+                        Exp::Number(1.0),
+                    ),
+                    Constraint::new(
+                        sum(variables(&segments)),
+                        Comparison::Equal,
+                        Exp::Number(1.0),
+                    ),
+                ];
+                for (i, weight) in weights.iter().enumerate() {
+                    let adjacent = &segments[i.saturating_sub(1)..(i + 1).min(segments.len())];
+                    constraints.push(Constraint::new(
+                        Exp::Variable(weight.clone()),
+                        Comparison::LessOrEqual,
+                        sum(variables(adjacent)),
+                    ));
+                }
+                for constraint in constraints.into_iter().rev() {
+                    linearizer_context.add_constraint(constraint);
+                }
+                for weight in &weights {
+                    linearizer_context.declare_variable(
+                        weight.clone(),
+                        VariableType::NonNegativeReal(0.0, 1.0),
+                    )?;
+                }
+                for segment in &segments {
+                    linearizer_context.declare_variable(segment.clone(), VariableType::Boolean)?;
+                }
+                let mut context = LinearizationContext::new();
+                for (value, weight) in values.iter().zip(weights) {
+                    context.add_var(weight, *value);
+                }
+                Ok(context)
+            }
         }
     }
 }
 
+/// Linearizes the factors of a product or division, the sign of the constant factor
+/// decides the direction of the other one
+fn linearize_factors(
+    lhs: &Exp,
+    rhs: &Exp,
+    linearizer_context: &mut Linearizer,
+    polarity: Polarity,
+) -> Result<(LinearizationContext, LinearizationContext), LinearizationError> {
+    if !lhs.has_variables() {
+        let lhs = lhs.linearize(linearizer_context, Polarity::Fixed)?;
+        let rhs = rhs.linearize(linearizer_context, polarity.scaled_by(lhs.rhs()))?;
+        Ok((lhs, rhs))
+    } else if !rhs.has_variables() {
+        let rhs = rhs.linearize(linearizer_context, Polarity::Fixed)?;
+        let lhs = lhs.linearize(linearizer_context, polarity.scaled_by(rhs.rhs()))?;
+        Ok((lhs, rhs))
+    } else {
+        Ok((
+            lhs.linearize(linearizer_context, Polarity::Fixed)?,
+            rhs.linearize(linearizer_context, Polarity::Fixed)?,
+        ))
+    }
+}
+
+fn non_zero_divisor(op: BinOp, divisor: f64) -> Result<f64, LinearizationError> {
+    if divisor == 0.0 {
+        return Err(LinearizationError::OperatorError(
+            OperatorError::division_by_zero(op),
+        ));
+    }
+    Ok(divisor)
+}
+
 /// Represents an intermediate linear constraint during the linearization process.
 #[derive(Debug)]
 struct MidLinearConstraint {
     lhs: IndexMap<String, f64>,
     rhs: f64,
     comparison: Comparison,
+    name: Option<String>,
+    span: Option<InputSpan>,
 }
 
 impl MidLinearConstraint {
@@ -135,6 +368,8 @@ impl MidLinearConstraint {
             lhs,
             rhs,
             comparison,
+            name: None,
+            span: None,
         }
     }
 
@@ -151,9 +386,29 @@ impl MidLinearConstraint {
             lhs: context.current_vars,
             rhs: -context.current_rhs,
             comparison,
+            name: None,
+            span: None,
         }
     }
 
+    /// Gives the constraint a name that is kept in the final LinearConstraint.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the constraint
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the location in the source of the constraint this one was linearized from.
+    ///
+    /// # Arguments
+    /// * `span` - The span of the source constraint, if it has one
+    pub fn with_span(mut self, span: Option<InputSpan>) -> Self {
+        self.span = span;
+        self
+    }
+
     /// Converts the constraint's variables to a coefficient vector based on variable ordering.
     ///
     /// # Arguments
@@ -168,7 +423,14 @@ impl MidLinearConstraint {
     /// * `vars` - Mapping of variable names to their positions
     pub fn into_linear_constraint(self, vars: &IndexMap<String, usize>) -> LinearConstraint {
         let coeffs = self.to_coefficient_vector(vars);
-        LinearConstraint::new(coeffs, self.comparison, self.rhs)
+        let mut constraint = LinearConstraint::new(coeffs, self.comparison, self.rhs);
+        if let Some(name) = self.name {
+            constraint = constraint.with_name(name);
+        }
+        if let Some(span) = self.span {
+            constraint = constraint.with_span(span);
+        }
+        constraint
     }
 }
 
@@ -197,6 +459,10 @@ pub struct Linearizer {
     slack_count: u32,
     min_count: u32,
     max_count: u32,
+    abs_count: u32,
+    or_count: u32,
+    prod_count: u32,
+    piecewise_count: u32,
     domain: IndexMap<String, DomainVariable>,
 }
 
@@ -259,6 +525,158 @@ impl Linearizer {
         Ok(())
     }
 
+    /// Finds the largest value that a linearized expression can take, given the bounds of its variables.
+    ///
+    /// # Arguments
+    /// * `context` - The linearized expression
+    /// * `disjunction` - The disjunction the expression comes from, used in the error
+    fn max_value(
+        &self,
+        context: &LinearizationContext,
+        disjunction: &str,
+    ) -> Result<f64, LinearizationError> {
+        context
+            .vars()
+            .iter()
+            .filter(|(_, coeff)| **coeff != 0.0)
+            .try_fold(context.rhs(), |acc, (name, coeff)| {
+                let (min, max) = self
+                    .domain
+                    .get(name)
+                    .map(|v| v.get_type().bounds())
+                    .unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+                let bound = if *coeff > 0.0 { max } else { min };
+                if bound.is_infinite() {
+                    return Err(LinearizationError::UnboundedVariable(
+                        name.clone(),
+                        disjunction.to_string(),
+                    ));
+                }
+                Ok(acc + coeff * bound)
+            })
+    }
+
+    /// Converts a disjunction `a or b or ...` into Big-M constraints, each side of the disjunction
+    /// gets a boolean `$or_{n}_{i}` that enforces it when set to 1, and at least one of them must be 1.
+    /// The M of each constraint is the largest violation allowed by the variable bounds,
+    /// and is reported in the constraint name, like `$or_0_1_le_M10`.
+    ///
+    /// # Arguments
+    /// * `constraint` - The disjunction to convert
+    fn linearize_disjunction(
+        &mut self,
+        constraint: Constraint,
+    ) -> Result<Vec<MidLinearConstraint>, LinearizationError> {
+        let index = self.or_count;
+        self.or_count += 1;
+        let disjunction = constraint.to_string();
+        let span = constraint.span().cloned();
+        let mut constraints = Vec::new();
+        let mut selectors = LinearizationContext::new();
+        for (i, disjunct) in constraint.into_disjuncts().into_iter().enumerate() {
+            let selector = format!("$or_{}_{}", index, i);
+            self.declare_variable(selector.clone(), VariableType::Boolean)?;
+            selectors.add_var(selector.clone(), 1.0);
+            let (lhs, op, rhs) = disjunct.into_parts();
+            let exp = Exp::BinOp(BinOp::Sub, Box::new(lhs), Box::new(rhs))
+                .flatten()
+                .simplify();
+            let context = exp.linearize(self, Polarity::from_comparison(op))?;
+            //e <= 0 becomes e <= M(1 - y), with M the largest value of e
+            if let Comparison::LessOrEqual | Comparison::Less | Comparison::Equal = op {
+                let m = self.max_value(&context, &disjunction)?.max(0.0);
+                let mut upper = context.clone();
+                upper.add_var(selector.clone(), m);
+                upper.add_rhs(-m);
+                let comparison = match op {
+                    Comparison::Less => Comparison::Less,
+                    _ => Comparison::LessOrEqual,
+                };
+                constraints.push(
+                    MidLinearConstraint::new_from_linearized_context(upper, comparison)
+                        .with_name(format!("{}_le_M{}", selector, m))
+                        .with_span(span.clone()),
+                );
+            }
+            //e >= 0 becomes e >= -M(1 - y), with M the largest value of -e
+            if let Comparison::GreaterOrEqual | Comparison::Greater | Comparison::Equal = op {
+                let mut negated = context.clone();
+                negated.mul_by(-1.0);
+                let m = self.max_value(&negated, &disjunction)?.max(0.0);
+                let mut lower = context;
+                lower.add_var(selector.clone(), -m);
+                lower.add_rhs(m);
+                let comparison = match op {
+                    Comparison::Greater => Comparison::Greater,
+                    _ => Comparison::GreaterOrEqual,
+                };
+                constraints.push(
+                    MidLinearConstraint::new_from_linearized_context(lower, comparison)
+                        .with_name(format!("{}_ge_M{}", selector, m))
+                        .with_span(span.clone()),
+                );
+            }
+        }
+        selectors.add_rhs(-1.0);
+        constraints.push(
+            MidLinearConstraint::new_from_linearized_context(selectors, Comparison::GreaterOrEqual)
+                .with_name(format!("$or_{}", index))
+                .with_span(span),
+        );
+        Ok(constraints)
+    }
+
+    /// Replaces the product of two boolean variables with a new boolean `$prod_{n}`,
+    /// bound by `z <= x`, `z <= y` and `z >= x + y - 1` so that it is 1 only when both are 1.
+    ///
+    /// # Arguments
+    /// * `lhs` - The linearized left factor
+    /// * `rhs` - The linearized right factor
+    /// * `exp` - The product, used in the errors
+    fn linearize_boolean_product(
+        &mut self,
+        lhs: LinearizationContext,
+        rhs: LinearizationContext,
+        exp: &Exp,
+    ) -> Result<LinearizationContext, LinearizationError> {
+        let non_linear = || LinearizationError::NonLinearExpression(Box::new(exp.clone()));
+        let (x, x_coeff) = lhs.single_var().ok_or_else(non_linear)?;
+        let (y, y_coeff) = rhs.single_var().ok_or_else(non_linear)?;
+        //x * x = x for booleans
+        if x == y {
+            return Ok(LinearizationContext::from_var(x, x_coeff * y_coeff));
+        }
+        let var_name = format!("$prod_{}", self.prod_count);
+        self.prod_count += 1;
+        let z = Exp::Variable(var_name.clone());
+        self.add_constraint(Constraint::new(
+            z.clone(),
+            Comparison::GreaterOrEqual,
+            Exp::BinOp(
+                BinOp::Sub,
+                Exp::BinOp(
+                    BinOp::Add,
+                    Exp::Variable(x.clone()).to_box(),
+                    Exp::Variable(y.clone()).to_box(),
+                )
+                .to_box(),
+                Exp::Number(1.0).to_box(),
+            ),
+        ));
+        self.add_constraint(Constraint::new(
+            z.clone(),
+            Comparison::LessOrEqual,
+            Exp::Variable(y),
+        ));
+        self.add_constraint(Constraint::new(
+            z,
+            Comparison::LessOrEqual,
+            Exp::Variable(x),
+        ));
+        self.declare_variable(var_name.clone(), VariableType::Boolean)?;
+        Ok(LinearizationContext::from_var(var_name, x_coeff * y_coeff))
+    }
+
     /// Returns names of all variables that are used in constraints.
     pub fn used_variables(&self) -> Vec<String> {
         self.domain
@@ -268,7 +686,8 @@ impl Linearizer {
             .collect()
     }
 
-    /// Converts a model into linear form.
+    /// Converts a model into linear form, the goals are first turned into constraints with
+    /// deviation variables.
     ///
     /// # Arguments
     /// * `model` - The model to linearize
@@ -277,19 +696,37 @@ impl Linearizer {
     /// * `Ok(LinearModel)` - The linearized model
     /// * `Err(LinearizationError)` - If linearization fails
     pub fn linearize(model: Model) -> Result<LinearModel, LinearizationError> {
+        let model = expand_goals(model);
+        model
+            .check_linearity()
+            .map_err(LinearizationError::NonLinearTerm)?;
         let (objective, constraints, domain) = model.into_components();
         let mut context = Linearizer::new_from(constraints, domain);
         let mut linear_constraints: Vec<MidLinearConstraint> = Vec::new();
         let objective_type = objective.objective_type.clone();
         let objective_exp = objective.rhs.flatten().simplify();
-        let linearized_objective = objective_exp.linearize(&mut context)?;
+        let linearized_objective = objective_exp.linearize(
+            &mut context,
+            Polarity::from_optimization_type(&objective_type),
+        )?;
         while let Some(constraint) = context.pop_constraint() {
+            if constraint.is_disjunction() {
+                let big_m = context.linearize_disjunction(constraint)?;
+                linear_constraints.extend(big_m);
+                continue;
+            }
+            let name = constraint.name().cloned();
+            let span = constraint.span().cloned();
             let (lhs, op, rhs) = constraint.into_parts();
             let exp = Exp::BinOp(BinOp::Sub, Box::new(lhs), Box::new(rhs))
                 .flatten()
                 .simplify();
-            let res = exp.linearize(&mut context)?;
-            linear_constraints.push(MidLinearConstraint::new_from_linearized_context(res, op));
+            let res = exp.linearize(&mut context, Polarity::from_comparison(op))?;
+            let linear = MidLinearConstraint::new_from_linearized_context(res, op).with_span(span);
+            linear_constraints.push(match name {
+                Some(name) => linear.with_name(name),
+                None => linear,
+            });
         }
 
         let mut vars = context.used_variables();
@@ -309,7 +746,7 @@ impl Linearizer {
             .map(|c| c.into_linear_constraint(&vars_indexes))
             .collect();
         let objective_coeffs = extract_coeffs(&linearized_objective.current_vars, &vars_indexes);
-        let objective_offset = linearized_objective.current_rhs;
+        let objective_offset = linearized_objective.current_rhs + objective.offset;
         Ok(LinearModel::new_from_parts(
             objective_coeffs,
             objective_type,
@@ -335,6 +772,12 @@ pub enum LinearizationError {
     NonLinearExpression(Box<Exp>),
     VarAlreadyDeclared(String),
     UnimplementedExpression(Box<Exp>),
+    NonConvexExpression(Box<Exp>, String),
+    UnboundedVariable(String, String),
+    /// A term that can't be linearized, found before linearizing the model
+    NonLinearTerm(TransformError),
+    /// An operation between constants that has no result, like a division by zero
+    OperatorError(OperatorError),
 }
 impl Display for LinearizationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -348,12 +791,25 @@ impl Display for LinearizationError {
             LinearizationError::UnimplementedExpression(exp) => {
                 write!(f, "Unimplemented expression: \"{}\"", exp)
             }
+            LinearizationError::NonConvexExpression(exp, reason) => {
+                write!(f, "Cannot linearize \"{}\": {}", exp, reason)
+            }
+            LinearizationError::UnboundedVariable(name, disjunction) => {
+                write!(
+                    f,
+                    "Cannot compute the Big-M of \"{}\", variable \"{}\" is unbounded",
+                    disjunction, name
+                )
+            }
+            LinearizationError::NonLinearTerm(error) => write!(f, "{}", error),
+            LinearizationError::OperatorError(error) => write!(f, "{}", error),
         }
     }
 }
 
 /// Represents the intermediate state during expression linearization.
 /// Contains a map of variables to their coefficients and a constant term (RHS).
+#[derive(Clone)]
 struct LinearizationContext {
     current_vars: IndexMap<String, f64>,
     current_rhs: f64,
@@ -440,7 +896,6 @@ impl LinearizationContext {
         self.current_rhs += rhs;
     }
 
-    #[allow(unused)]
     /// Returns a reference to the map of variables and their coefficients.
     pub fn vars(&self) -> &IndexMap<String, f64> {
         &self.current_vars
@@ -482,6 +937,16 @@ impl LinearizationContext {
         self.current_rhs /= divisor;
     }
 
+    /// Returns the variable and its coefficient if the context is made of a single variable term.
+    pub fn single_var(&self) -> Option<(String, f64)> {
+        match self.current_vars.first() {
+            Some((name, coeff)) if self.current_vars.len() == 1 && self.current_rhs == 0.0 => {
+                Some((name.clone(), *coeff))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns true if the context has no variable terms.
     pub fn has_no_vars(&self) -> bool {
         self.current_vars.is_empty()
@@ -1,9 +1,15 @@
+pub mod exporters;
+pub mod goal_program;
 pub mod linear_model;
 pub mod linearizer;
+pub mod presolve;
 pub mod standard_linear_model;
 pub mod standardizer;
 
+pub use exporters::*;
+pub use goal_program::*;
 pub use linear_model::*;
 pub use linearizer::*;
+pub use presolve::*;
 pub use standard_linear_model::*;
 pub use standardizer::*;
@@ -0,0 +1,210 @@
+#[allow(unused_imports)]
+use crate::prelude::*;
+
+use crate::math::{float_eq, float_gt, float_lt, BinOp, Comparison, UnOp, VariableType};
+use crate::parser::model_transformer::{Constraint, Exp, Model};
+use indexmap::IndexMap;
+
+/// Returns the variables with their coefficients and the constant term of an expression,
+/// or None if the expression is not a linear combination of the variables
+fn affine_form(exp: &Exp) -> Option<(IndexMap<String, f64>, f64)> {
+    match exp {
+        Exp::Number(n) => Some((IndexMap::new(), *n)),
+        Exp::Variable(name) => Some((IndexMap::from([(name.clone(), 1.0)]), 0.0)),
+        Exp::UnOp(UnOp::Neg, exp) => {
+            let (vars, constant) = affine_form(exp)?;
+            Some((vars.into_iter().map(|(n, c)| (n, -c)).collect(), -constant))
+        }
+        Exp::BinOp(op, lhs, rhs) => {
+            let (mut lhs_vars, lhs_constant) = affine_form(lhs)?;
+            let (rhs_vars, rhs_constant) = affine_form(rhs)?;
+            match op {
+                BinOp::Add | BinOp::Sub => {
+                    let sign = if *op == BinOp::Add { 1.0 } else { -1.0 };
+                    for (name, coeff) in rhs_vars {
+                        *lhs_vars.entry(name).or_insert(0.0) += sign * coeff;
+                    }
+                    Some((lhs_vars, lhs_constant + sign * rhs_constant))
+                }
+                BinOp::Mul if rhs_vars.is_empty() => Some((
+                    lhs_vars
+                        .into_iter()
+                        .map(|(n, c)| (n, c * rhs_constant))
+                        .collect(),
+                    lhs_constant * rhs_constant,
+                )),
+                BinOp::Mul if lhs_vars.is_empty() => Some((
+                    rhs_vars
+                        .into_iter()
+                        .map(|(n, c)| (n, c * lhs_constant))
+                        .collect(),
+                    lhs_constant * rhs_constant,
+                )),
+                BinOp::Div if rhs_vars.is_empty() && !float_eq(rhs_constant, 0.0) => Some((
+                    lhs_vars
+                        .into_iter()
+                        .map(|(n, c)| (n, c / rhs_constant))
+                        .collect(),
+                    lhs_constant / rhs_constant,
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the variable of a constraint with a single variable, together with the bound
+/// it imposes and whether it is an upper bound, as in `a * x + c <= d`
+fn singleton_bound(constraint: &Constraint) -> Option<(String, f64, bool)> {
+    if constraint.is_disjunction() {
+        return None;
+    }
+    let (lhs, comparison, rhs) = constraint.clone().into_parts();
+    let is_upper = match comparison {
+        Comparison::LessOrEqual => true,
+        Comparison::GreaterOrEqual => false,
+        //equalities and strict inequalities can't be represented by a bound
+        Comparison::Equal | Comparison::Less | Comparison::Greater => return None,
+    };
+    let (mut vars, lhs_constant) = affine_form(&lhs)?;
+    let (rhs_vars, rhs_constant) = affine_form(&rhs)?;
+    for (name, coeff) in rhs_vars {
+        *vars.entry(name).or_insert(0.0) -= coeff;
+    }
+    vars.retain(|_, coeff| !float_eq(*coeff, 0.0));
+    if vars.len() != 1 {
+        return None;
+    }
+    let (name, coeff) = vars.pop()?;
+    let bound = (rhs_constant - lhs_constant) / coeff;
+    //dividing by a negative coefficient flips the comparison
+    let is_upper = is_upper != float_lt(coeff, 0.0);
+    Some((name, bound, is_upper))
+}
+
+/// Rounds a bound of an integer variable towards the inside of its domain
+fn integer_bound(bound: f64, is_upper: bool) -> f64 {
+    if float_eq(bound, bound.round()) {
+        bound.round()
+    } else if is_upper {
+        bound.floor()
+    } else {
+        bound.ceil()
+    }
+}
+
+/// Returns the domain tightened by a bound, or None if the bound would make it empty
+/// or can't be represented by the domain
+fn tighten(domain: &VariableType, bound: f64, is_upper: bool) -> Option<VariableType> {
+    let tighten_range = |min: f64, max: f64, bound: f64| {
+        let (min, max) = if is_upper {
+            (min, max.min(bound))
+        } else {
+            (min.max(bound), max)
+        };
+        if float_gt(min, max) {
+            None
+        } else {
+            Some((min, max))
+        }
+    };
+    match domain {
+        VariableType::Real(min, max) => {
+            tighten_range(*min, *max, bound).map(|(min, max)| VariableType::Real(min, max))
+        }
+        VariableType::NonNegativeReal(min, max) => tighten_range(*min, *max, bound)
+            .map(|(min, max)| VariableType::NonNegativeReal(min, max)),
+        VariableType::Integer(min, max) => {
+            tighten_range(*min, *max, integer_bound(bound, is_upper))
+                .map(|(min, max)| VariableType::Integer(min, max))
+        }
+        VariableType::IntegerRange(min, max) => {
+            tighten_range(*min as f64, *max as f64, integer_bound(bound, is_upper))
+                .map(|(min, max)| VariableType::IntegerRange(min as i32, max as i32))
+        }
+        //a boolean can only drop constraints that don't restrict it
+        VariableType::Boolean => {
+            let redundant = if is_upper {
+                !float_lt(bound, 1.0)
+            } else {
+                !float_gt(bound, 0.0)
+            };
+            redundant.then_some(VariableType::Boolean)
+        }
+    }
+}
+
+/// Folds the constraints made of a single variable, like `x <= 5` or `2x >= 1`, into the
+/// bounds of the variable's domain, removing them from the model.
+///
+/// The pass is conservative, equalities, strict inequalities, named constraints and
+/// constraints that would make a domain empty are kept as they are, so that the solver
+/// still reports them.
+///
+/// # Arguments
+/// * `model` - The model to presolve
+///
+/// # Returns
+/// The presolved model and the number of constraints that were removed
+pub fn presolve_bounds(model: &Model) -> (Model, usize) {
+    let mut domain = model.domain().clone();
+    let mut kept = Vec::with_capacity(model.constraints().len());
+    let mut removed = 0;
+    for constraint in model.constraints() {
+        let bound = match constraint.name() {
+            Some(_) => None,
+            None => singleton_bound(constraint),
+        };
+        let tightened = bound.and_then(|(name, bound, is_upper)| {
+            let variable = domain.get_mut(&name)?;
+            let as_type = tighten(variable.get_type(), bound, is_upper)?;
+            Some((variable, as_type))
+        });
+        match tightened {
+            Some((variable, as_type)) => {
+                variable.set_type(as_type);
+                removed += 1;
+            }
+            None => kept.push(constraint.clone()),
+        }
+    }
+    (Model::new(model.objective().clone(), kept, domain), removed)
+}
+
+/// A model after [`presolve_bounds`], together with the number of constraints that
+/// were folded into the domain of the variables
+#[derive(Debug, Clone)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub struct PresolvedModel {
+    model: Model,
+    removed_constraints: usize,
+}
+impl PresolvedModel {
+    pub fn new(model: Model, removed_constraints: usize) -> PresolvedModel {
+        PresolvedModel {
+            model,
+            removed_constraints,
+        }
+    }
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+    pub fn removed_constraints(&self) -> usize {
+        self.removed_constraints
+    }
+    pub fn into_model(self) -> Model {
+        self.model
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[cfg(target_arch = "wasm32")]
+impl PresolvedModel {
+    pub fn wasm_get_model(&self) -> Model {
+        self.model.clone()
+    }
+    pub fn wasm_get_removed_constraints(&self) -> usize {
+        self.removed_constraints
+    }
+}
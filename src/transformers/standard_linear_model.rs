@@ -1,13 +1,15 @@
 #[allow(unused_imports)]
 use crate::prelude::*;
+use indexmap::IndexMap;
 use num_traits::Zero;
+use std::collections::HashMap;
 use std::fmt::Display;
 
-use crate::math::{float_gt, float_lt, float_ne};
-use crate::solvers::SolverError;
-use crate::solvers::{divide_matrix_row_by, CanonicalTransformError, Tableau};
+use crate::math::{float_gt, float_lt, float_ne, Comparison, VariableType};
+use crate::solvers::{divide_matrix_row_by, CanonicalTransformError, SimplexOptions, Tableau};
+use crate::solvers::{irreducible_infeasible_rows, LpSolution, SolverError};
 use crate::transformers::linear_model::LinearModel;
-use crate::transformers::standardizer::to_standard_form;
+use crate::transformers::standardizer::{recombine_free_variables, to_standard_form};
 use crate::utils::remove_many;
 
 /// Represents a linear equality constraint in standard form: ax = b where a is a vector of coefficients and b is a constant.
@@ -16,6 +18,7 @@ use crate::utils::remove_many;
 pub struct EqualityConstraint {
     coefficients: Vec<f64>,
     rhs: f64,
+    negated: bool,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
@@ -41,16 +44,40 @@ impl StandardLinearModel {
     /// Converts the standard form linear model into a tableau representation suitable for the simplex method.
     ///
     /// This method implements two approaches:
-    /// 1. Direct conversion if enough independent variables are found
+    /// 1. Direct conversion if every constraint has an independent variable that can start in the basis
     /// 2. Two-phase simplex method using artificial variables if direct conversion is not possible
     ///
     /// # Returns
     /// * `Ok(Tableau)` - A valid tableau in canonical form
     /// * `Err(CanonicalTransformError)` - If the model cannot be converted to a tableau
     pub fn into_tableau(self) -> Result<Tableau, CanonicalTransformError> {
-        let mut usable_independent_vars: Vec<IndependentVariable> = Vec::new();
-        //find independent variables by checking if the column has a single value, and if so, add it to the independent list
-        for column in 0..self.variables.len() {
+        self.into_tableau_with_options(&SimplexOptions::new())
+    }
+
+    /// Converts the standard form linear model into a tableau, using the given options
+    /// for the simplex of the first phase of the two-phase method.
+    ///
+    /// The first phase adds an artificial variable to each constraint without an independent
+    /// variable and minimizes their sum, the problem is infeasible if it can't reach zero.
+    /// The artificial variables left in the basis are then pivoted out, dropping the constraints
+    /// that turn out to be redundant, before restoring the original objective for the second phase.
+    ///
+    /// # Arguments
+    /// * `options` - The pivoting rule of the simplex, the first phase is limited to
+    ///   [`SimplexOptions::phase_one_max_iterations`] iterations
+    ///
+    /// # Returns
+    /// * `Ok(Tableau)` - A valid tableau in canonical form
+    /// * `Err(CanonicalTransformError)` - If the model cannot be converted to a tableau
+    pub fn into_tableau_with_options(
+        self,
+        options: &SimplexOptions,
+    ) -> Result<Tableau, CanonicalTransformError> {
+        let number_of_variables = self.variables.len();
+        let mut independent_in_row: Vec<Option<IndependentVariable>> =
+            vec![None; self.constraints.len()];
+        //find independent variables by checking if the column has a single value, and if so, use it for its row
+        for column in 0..number_of_variables {
             let mut independent_count = 0;
             let mut independent_row = 0;
             let mut independent_value = 0.0;
@@ -63,39 +90,35 @@ impl StandardLinearModel {
                 }
             }
             //only positive values are allowed, as the B column must be all positive
-            if independent_count == 1 && float_gt(independent_value, 0.0) {
-                usable_independent_vars.push(IndependentVariable {
+            if independent_count == 1
+                && float_gt(independent_value, 0.0)
+                && independent_in_row[independent_row].is_none()
+            {
+                independent_in_row[independent_row] = Some(IndependentVariable {
                     row: independent_row,
                     column,
                     value: independent_value,
                 });
             }
         }
-        if usable_independent_vars.len() >= self.constraints.len() {
+        let mut a = self.a_matrix();
+        let mut b = self.b_vec();
+        //normalize the rows of the independent variables
+        for independent_variable in independent_in_row.iter().flatten() {
+            divide_matrix_row_by(&mut a, independent_variable.row, independent_variable.value);
+            b[independent_variable.row] /= independent_variable.value;
+        }
+        if independent_in_row.iter().all(|i| i.is_some()) {
             //can form a canonical tableau
-            let mut a = self.a_matrix();
-            let mut b = self.b_vec();
-            let mut c = self.c_vec();
-            let mut value = 0.0;
-            //normalize the rows of the independent variables
-            for independent_variable in usable_independent_vars.iter() {
-                divide_matrix_row_by(&mut a, independent_variable.row, independent_variable.value);
-                b[independent_variable.row] /= independent_variable.value;
-                let amount = c[independent_variable.column];
-                for (index, coefficient) in a[independent_variable.row].iter().enumerate() {
-                    c[index] -= amount * coefficient;
-                }
-                value += amount * b[independent_variable.row];
-            }
-
-            let mut basis = usable_independent_vars
+            let basis = independent_in_row
                 .iter()
+                .flatten()
                 .map(|i| i.column)
                 .collect::<Vec<_>>();
-            //we only need as many basis variables as there are constraints
-            basis.resize(self.constraints.len(), 0);
-            Ok(Tableau::new(
-                self.c_vec(),
+            let (c, value) = canonical_objective(self.c_vec(), &a, &b, &basis);
+            let (constraint_names, negated_rows, original_columns) = self.model_names();
+            return Ok(Tableau::new(
+                c,
                 a,
                 b,
                 basis,
@@ -103,105 +126,140 @@ impl StandardLinearModel {
                 self.objective_offset(),
                 self.variables(),
                 self.flip_objective,
-            ))
-        } else {
-            //use the 2 phase method to find a canonical tableau by adding artificial variables to the constraints and solving the tableau
-            let mut a = self.a_matrix();
-            //TODO can i simplify this by only adding necessary artificial variables? reusing the independent variables?
-            let number_of_artificial_variables = self.constraints.len();
-            let number_of_variables = self.variables.len();
-            let mut variables = self.variables();
-            let mut c = vec![0.0; number_of_variables + number_of_artificial_variables];
-            let mut basis = vec![0; number_of_artificial_variables];
-            for i in 0..number_of_artificial_variables {
-                c[number_of_variables + i] = 1.0;
-                basis[i] = number_of_variables + i;
-            }
-            let b = self.b_vec();
-
-            let mut value = 0.0;
-            //add the variables to the matrix and turn the objective function into
-            //canonical form by subtracting all rows from the objective function
-            for (i, constraint) in a.iter_mut().enumerate() {
-                constraint.resize(number_of_variables + number_of_artificial_variables, 0.0);
-                constraint[i + number_of_variables] = 1.0;
-                variables.push(format!("$a_{}", i));
-                for (j, coefficient) in constraint.iter().enumerate() {
+            )
+            .with_original_problem(self.a_matrix(), self.c_vec())
+            .with_model_names(constraint_names, negated_rows, original_columns)
+            .with_free_variables(self.free_variables.clone())
+            .with_feasibility_only(self.feasibility_only));
+        }
+        //use the 2 phase method to find a canonical tableau by adding artificial variables to the
+        //constraints without an independent variable, and minimizing their sum
+        let artificial_rows = independent_in_row
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.is_none())
+            .map(|(row, _)| row)
+            .collect::<Vec<_>>();
+        let total_variables = number_of_variables + artificial_rows.len();
+        let mut variables = self.variables();
+        let mut c = vec![0.0; total_variables];
+        let mut basis = independent_in_row
+            .iter()
+            .map(|i| i.as_ref().map(|i| i.column).unwrap_or(0))
+            .collect::<Vec<_>>();
+        for row in a.iter_mut() {
+            row.resize(total_variables, 0.0);
+        }
+        let mut value = 0.0;
+        //turn the objective function into canonical form by subtracting the rows of the artificial variables
+        for (i, row) in artificial_rows.iter().enumerate() {
+            let artificial_variable = number_of_variables + i;
+            a[*row][artificial_variable] = 1.0;
+            basis[*row] = artificial_variable;
+            variables.push(format!("$a_{}", row));
+            for (j, coefficient) in a[*row].iter().enumerate() {
+                if j != artificial_variable {
                     c[j] -= coefficient;
                 }
-                value -= b[i];
             }
+            value -= b[*row];
+        }
 
-            let mut tableau = Tableau::new(
-                c,
-                a,
-                b,
-                basis,
-                value,
-                self.objective_offset(),
-                variables,
-                self.flip_objective,
-            );
-            let artificial_variables = (number_of_variables
-                ..number_of_variables + number_of_artificial_variables)
-                .collect::<Vec<_>>();
-            match tableau.solve_avoiding(10000, &artificial_variables) {
-                Ok(optimal_tableau) => {
-                    let tableau = optimal_tableau.tableau();
-                    if float_ne(tableau.current_value(), 0.0) {
-                        return Err(CanonicalTransformError::Infesible(
-                            "Initial problem is infeasible".to_string(),
-                        ));
-                    }
-                    let new_basis = tableau.in_basis().clone();
-                    //check that the new basis is valid,
-                    if new_basis.iter().all(|&i| i < number_of_variables) {
-                        //restore the original objective function
-                        let mut new_a = tableau.a_matrix().clone();
-                        //remove the artificial variables from the tableau
-                        for row in new_a.iter_mut() {
-                            row.resize(number_of_variables, 0.0);
-                        }
-                        let mut value = 0.0;
-                        let mut new_c = self.c_vec();
-                        let new_b = tableau.b_vec().clone();
-                        //put in the original objective function in canonical form
-                        for (row_index, variable_index) in new_basis.iter().enumerate() {
-                            //values in base need to be 0, we know that the coefficient in basis is 0 or 1 so we can
-                            //simply multiply by the coefficient of the row
-                            let coefficient = new_c[*variable_index];
-                            for (index, c) in new_c.iter_mut().enumerate() {
-                                *c -= coefficient * new_a[row_index][index];
-                            }
-                            value -= coefficient * new_b[row_index];
-                        }
-
-                        Ok(Tableau::new(
-                            new_c,
-                            new_a,
-                            new_b,
-                            new_basis,
-                            value,
-                            self.objective_offset(),
-                            self.variables(),
-                            self.flip_objective,
-                        ))
-                    } else {
-                        Err(CanonicalTransformError::InvalidBasis(format!(
-                            "Invalid basis: {:?}",
-                            new_basis
-                        )))
-                    }
-                }
-                Err(e) => Err(CanonicalTransformError::SimplexError(format!(
-                    "Error solving initial tableau: {:?}",
+        let mut tableau = Tableau::new(
+            c,
+            a,
+            b,
+            basis,
+            value,
+            self.objective_offset(),
+            variables,
+            self.flip_objective,
+        );
+        let artificial_variables = (number_of_variables..total_variables).collect::<Vec<_>>();
+        let phase_one_options = options
+            .clone()
+            .with_max_iterations(options.phase_one_max_iterations);
+        let optimal_tableau = tableau
+            .solve_avoiding_with_options(&phase_one_options, &artificial_variables)
+            .map_err(|e| {
+                CanonicalTransformError::SimplexError(format!(
+                    "Error solving initial tableau: {}",
                     e
-                ))),
-            }
+                ))
+            })?;
+        let mut tableau = optimal_tableau.tableau().clone();
+        if float_ne(tableau.current_value(), 0.0) {
+            return Err(CanonicalTransformError::Infesible(
+                "Initial problem is infeasible".to_string(),
+            ));
+        }
+        //artificial variables can still be in the basis with value zero
+        let redundant_rows = tableau
+            .drive_out_of_basis(&artificial_variables)
+            .map_err(|e| {
+                CanonicalTransformError::SimplexError(format!(
+                    "Error driving the artificial variables out of the basis: {}",
+                    e
+                ))
+            })?;
+        let new_basis = tableau.in_basis().clone();
+        if new_basis.iter().any(|&i| i >= number_of_variables) {
+            return Err(CanonicalTransformError::InvalidBasis(format!(
+                "Invalid basis: {:?}",
+                new_basis
+            )));
         }
+        let mut new_a = tableau.a_matrix().clone();
+        //remove the artificial variables from the tableau
+        for row in new_a.iter_mut() {
+            row.resize(number_of_variables, 0.0);
+        }
+        let new_b = tableau.b_vec().clone();
+        //put in the original objective function in canonical form
+        let (new_c, value) = canonical_objective(self.c_vec(), &new_a, &new_b, &new_basis);
+        let mut original_a = self.a_matrix();
+        remove_many(&mut original_a, &redundant_rows);
+        let (mut constraint_names, mut negated_rows, original_columns) = self.model_names();
+        remove_many(&mut constraint_names, &redundant_rows);
+        remove_many(&mut negated_rows, &redundant_rows);
+        Ok(Tableau::new(
+            new_c,
+            new_a,
+            new_b,
+            new_basis,
+            value,
+            self.objective_offset(),
+            self.variables(),
+            self.flip_objective,
+        )
+        .with_original_problem(original_a, self.c_vec())
+        .with_model_names(constraint_names, negated_rows, original_columns)
+        .with_free_variables(self.free_variables.clone())
+        .with_feasibility_only(self.feasibility_only))
     }
 }
 
+/// Puts the objective function in canonical form for the given basis, where each row has a unit
+/// coefficient in the column of its basic variable, returning the new costs and the objective value.
+fn canonical_objective(
+    mut c: Vec<f64>,
+    a: &[Vec<f64>],
+    b: &[f64],
+    basis: &[usize],
+) -> (Vec<f64>, f64) {
+    let mut value = 0.0;
+    for (row_index, variable_index) in basis.iter().enumerate() {
+        //values in base need to be 0, we know that the coefficient in basis is 1 so we can
+        //simply multiply by the coefficient of the row
+        let coefficient = c[*variable_index];
+        for (index, c) in c.iter_mut().enumerate() {
+            *c -= coefficient * a[row_index][index];
+        }
+        value -= coefficient * b[row_index];
+    }
+    (c, value)
+}
+
 impl EqualityConstraint {
     /// Creates a new equality constraint, normalizing it so the right-hand side is non-negative.
     ///
@@ -220,11 +278,21 @@ impl EqualityConstraint {
             true => EqualityConstraint {
                 coefficients: coefficients.iter().map(|c| c * -1.0).collect(),
                 rhs: -rhs,
+                negated: true,
+            },
+            false => EqualityConstraint {
+                coefficients,
+                rhs,
+                negated: false,
             },
-            false => EqualityConstraint { coefficients, rhs },
         }
     }
 
+    /// Returns true if the constraint was multiplied by -1 to make its right-hand side non-negative.
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
     /// Returns a reference to the coefficient vector.
     pub fn coefficients(&self) -> &Vec<f64> {
         &self.coefficients
@@ -278,7 +346,11 @@ pub struct StandardLinearModel {
     objective_offset: f64,
     objective: Vec<f64>,
     flip_objective: bool,
+    feasibility_only: bool,
     constraints: Vec<EqualityConstraint>,
+    free_variables: IndexMap<String, (String, String)>,
+    original_variables: Vec<String>,
+    constraint_names: Vec<String>,
 }
 
 impl StandardLinearModel {
@@ -302,12 +374,179 @@ impl StandardLinearModel {
             .for_each(|c| c.ensure_size(variables.len()));
         objective.resize(variables.len(), 0.0);
         StandardLinearModel {
+            constraint_names: (0..constraints.len()).map(|i| format!("c{}", i)).collect(),
             objective,
             constraints,
+            original_variables: variables.clone(),
             variables,
             objective_offset,
             flip_objective,
+            feasibility_only: false,
+            free_variables: IndexMap::new(),
+        }
+    }
+
+    /// Sets the free variables that were split into the difference of two non negative variables,
+    /// as `x = $px - $mx`.
+    ///
+    /// # Arguments
+    /// * `free_variables` - Map of the name of each free variable to its positive and negative parts
+    pub fn with_free_variables(
+        mut self,
+        free_variables: IndexMap<String, (String, String)>,
+    ) -> StandardLinearModel {
+        self.free_variables = free_variables;
+        self
+    }
+
+    /// Returns the free variables of the original model, with the names of their positive and negative parts.
+    pub fn free_variables(&self) -> &IndexMap<String, (String, String)> {
+        &self.free_variables
+    }
+
+    /// Sets the variables of the model this standard model was created from, by default they
+    /// are the variables of the standard model.
+    ///
+    /// # Arguments
+    /// * `original_variables` - The names of the variables of the original model
+    pub fn with_original_variables(
+        mut self,
+        original_variables: Vec<String>,
+    ) -> StandardLinearModel {
+        self.original_variables = original_variables;
+        self
+    }
+
+    /// Returns the variables of the model this standard model was created from.
+    pub fn original_variables(&self) -> &Vec<String> {
+        &self.original_variables
+    }
+
+    /// Sets the names of the constraints of the model this standard model was created from,
+    /// in the order of the constraints. The constraints past the given names, like the ones
+    /// added for the bounds of the variables, are not part of the original model.
+    ///
+    /// # Arguments
+    /// * `constraint_names` - The names of the constraints of the original model
+    pub fn with_constraint_names(mut self, constraint_names: Vec<String>) -> StandardLinearModel {
+        self.constraint_names = constraint_names;
+        self
+    }
+
+    /// Returns the names of the constraints of the model this standard model was created from.
+    pub fn constraint_names(&self) -> &Vec<String> {
+        &self.constraint_names
+    }
+
+    /// Finds an irreducible infeasible subset of the constraints of the original model, a set of
+    /// constraints that can't be satisfied together but that is feasible once any of them is removed.
+    ///
+    /// The constraints added for the bounds of the variables are always kept, so they are never
+    /// part of the subset.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The names of the conflicting constraints, empty if the model is feasible
+    /// * `Err(SolverError)` - If the model could not be solved
+    pub fn find_irreducible_infeasible_subset(&self) -> Result<Vec<String>, SolverError> {
+        let mut lp = LinearModel::new();
+        for variable in &self.variables {
+            lp.add_variable(variable, VariableType::non_negative_real());
+        }
+        for constraint in &self.constraints {
+            lp.add_constraint(
+                constraint.coefficients.clone(),
+                Comparison::Equal,
+                constraint.rhs,
+            );
         }
+        let named = self.constraint_names.len().min(self.constraints.len());
+        let bounds = (named..self.constraints.len()).collect::<Vec<_>>();
+        let rows = irreducible_infeasible_rows(&lp, (0..named).collect(), &bounds)?;
+        Ok(rows
+            .into_iter()
+            .map(|i| self.constraint_names[i].clone())
+            .collect())
+    }
+
+    //the name of the original constraint of each row, whether the row was negated, and the column
+    //of each original variable, which is the positive part for the free variables
+    fn model_names(&self) -> (Vec<Option<String>>, Vec<bool>, IndexMap<String, usize>) {
+        let rows = (0..self.constraints.len())
+            .map(|i| self.constraint_names.get(i).cloned())
+            .collect();
+        let negated_rows = self.constraints.iter().map(|c| c.negated).collect();
+        let columns = self
+            .original_variables
+            .iter()
+            .filter_map(|name| {
+                let column_name = match self.free_variables.get(name) {
+                    Some((plus, _)) => plus,
+                    None => name,
+                };
+                let column = self.variables.iter().position(|v| v == column_name)?;
+                Some((name.clone(), column))
+            })
+            .collect();
+        (rows, negated_rows, columns)
+    }
+
+    /// Maps a solution of this model back to the variables of the original model, dropping the
+    /// slack and surplus variables and recombining the free variables that were split in two.
+    ///
+    /// # Arguments
+    /// * `solution` - A solution of this model, whose free variables may already be recombined
+    ///
+    /// # Returns
+    /// The value of each variable of the original model, variables missing from the solution are zero
+    pub fn reconstruct_solution(&self, solution: &LpSolution<f64>) -> HashMap<String, f64> {
+        let solution = recombine_free_variables(solution.clone(), &self.free_variables);
+        let values = solution
+            .assignment()
+            .iter()
+            .map(|a| (a.name.as_str(), a.value))
+            .collect::<HashMap<_, _>>();
+        self.original_variables
+            .iter()
+            .map(|name| (name.clone(), *values.get(name.as_str()).unwrap_or(&0.0)))
+            .collect()
+    }
+
+    /// Marks the model as coming from a `satisfy` problem, where any feasible point is a solution.
+    ///
+    /// # Arguments
+    /// * `feasibility_only` - Whether the model only needs a feasible point
+    pub fn with_feasibility_only(mut self, feasibility_only: bool) -> StandardLinearModel {
+        self.feasibility_only = feasibility_only;
+        self
+    }
+
+    /// Returns true if the model only needs a feasible point, as it has no objective.
+    pub fn is_feasibility_only(&self) -> bool {
+        self.feasibility_only
+    }
+
+    /// Returns the coefficient matrices of the model in the form `min c^T x` subject to `Ax = b`, `x >= 0`.
+    ///
+    /// # Returns
+    /// A tuple of (A, b, c, variables), where the columns of A and the entries of c follow
+    /// the order of the variables
+    pub fn to_matrices(&self) -> (Vec<Vec<f64>>, Vec<f64>, Vec<f64>, Vec<String>) {
+        (
+            self.a_matrix(),
+            self.b_vec(),
+            self.c_vec(),
+            self.variables(),
+        )
+    }
+
+    /// Returns the sense of the constraints, which is always an equality in standard form.
+    pub fn constraint_sense(&self) -> Comparison {
+        Comparison::Equal
+    }
+
+    /// Returns true if the objective was negated to turn a maximization into a minimization.
+    pub fn is_objective_flipped(&self) -> bool {
+        self.flip_objective
     }
 
     /// Converts a general linear model to standard form.
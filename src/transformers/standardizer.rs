@@ -1,7 +1,8 @@
-use crate::math::float_eq;
+use indexmap::IndexMap;
+
 use crate::math::{Comparison, OptimizationType, VariableType};
 use crate::parser::model_transformer::DomainVariable;
-use crate::solvers::{find_invalid_variables, SolverError};
+use crate::solvers::{find_invalid_variables, Assignment, LpSolution, SolverError};
 use crate::transformers::linear_model::{LinearConstraint, LinearModel};
 use crate::transformers::standard_linear_model::{EqualityConstraint, StandardLinearModel};
 use crate::utils::{remove_many, InputSpan};
@@ -41,6 +42,7 @@ use crate::utils::{remove_many, InputSpan};
 ///
 /// ```
 pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, SolverError> {
+    let constraint_names = problem.constraint_names();
     let (
         mut objective,
         optimization_type,
@@ -49,6 +51,7 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
         mut variables,
         mut domain,
     ) = problem.into_parts();
+    let original_variables = variables.clone();
     let mut context = NormalizationContext {
         surplus_index: 0,
         slack_index: 0,
@@ -136,11 +139,13 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
         .collect::<Vec<usize>>();
     //for each free variable we need to replace it with x = x' - x'' where x' and x'' are positive
     //we first add the new variables to the domain
+    let mut split_variables = IndexMap::new();
     for i in &free_variables {
         let var_name = variables[*i].clone();
         let (var_name1, var_name2) = (format!("$p{}", var_name), format!("$m{}", var_name));
         variables.push(var_name1.clone());
         variables.push(var_name2.clone());
+        split_variables.insert(var_name, (var_name1.clone(), var_name2.clone()));
         domain.insert(
             var_name1.clone(),
             DomainVariable::new(
@@ -155,20 +160,16 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
                 InputSpan::default(),
             ),
         );
-        context.total_variables += 1; //we add two variables, but one is removed, so only one is added
+        //we add two variables, but one is removed, so only one is added
+        context.total_variables += 1;
+        //the coefficients are pushed even when zero, to keep the columns aligned with the variables
         constraints.iter_mut().for_each(|c| {
             let original_coefficient = c.coefficients()[*i];
-            if float_eq(original_coefficient, 0.0) {
-                return;
-            }
             c.coefficients_mut().push(original_coefficient);
-            c.coefficients_mut().push(original_coefficient * -1.0);
+            c.coefficients_mut().push(-original_coefficient);
         });
-        if float_eq(objective[*i], 0.0) {
-            continue;
-        }
         objective.push(objective[*i]);
-        objective.push(objective[*i] * -1.0);
+        objective.push(-objective[*i]);
     }
 
     //we now remove the free variables from the constraints
@@ -216,12 +217,8 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
             true,
         ),
         OptimizationType::Min => (objective_offset, objective.clone(), false),
-        _ => {
-            return Err(SolverError::UnimplementedOptimizationType {
-                expected: vec![OptimizationType::Max, OptimizationType::Min],
-                got: optimization_type,
-            })
-        }
+        //any feasible point is a solution, so the objective is dropped and only phase I has work to do
+        OptimizationType::Satisfy => (0.0, vec![0.0; objective.len()], false),
     };
     Ok(StandardLinearModel::new(
         objective,
@@ -229,7 +226,51 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
         variables,
         objective_offset,
         flip_objective,
-    ))
+    )
+    .with_feasibility_only(optimization_type == OptimizationType::Satisfy)
+    .with_free_variables(split_variables)
+    .with_original_variables(original_variables)
+    .with_constraint_names(constraint_names))
+}
+
+/// Recombines the positive and negative parts of the free variables that were split by
+/// [`to_standard_form`], so that the solution is expressed in terms of the original variables.
+///
+/// Each free variable takes the place of its positive part, with value `$px - $mx`.
+///
+/// # Arguments
+/// * `solution` - A solution of the standard model
+/// * `free_variables` - The free variables of the standard model, as returned by [`StandardLinearModel::free_variables`]
+pub fn recombine_free_variables(
+    solution: LpSolution<f64>,
+    free_variables: &IndexMap<String, (String, String)>,
+) -> LpSolution<f64> {
+    if free_variables.is_empty() {
+        return solution;
+    }
+    let value_of = |name: &str| {
+        solution
+            .assignment()
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.value)
+            .unwrap_or(0.0)
+    };
+    let assignment = solution
+        .assignment()
+        .iter()
+        .filter(|a| !free_variables.values().any(|(_, minus)| *minus == a.name))
+        .map(
+            |a| match free_variables.iter().find(|(_, (plus, _))| *plus == a.name) {
+                Some((name, (plus, minus))) => Assignment {
+                    name: name.clone(),
+                    value: value_of(plus) - value_of(minus),
+                },
+                None => a.clone(),
+            },
+        )
+        .collect();
+    solution.with_assignment(assignment)
 }
 
 /// Context for tracking the normalization process of converting constraints to standard form.
@@ -8,6 +8,7 @@ use crate::parser::il::AddressableAccess;
 use crate::parser::il::PreExp;
 use crate::parser::model_transformer::Frame;
 use crate::parser::model_transformer::TransformError;
+use crate::parser::model_transformer::TransformerContext;
 use crate::runtime_builtin::RoocFunction;
 use crate::utils::Spanned;
 use crate::{
@@ -94,6 +95,7 @@ impl TypedToken {
 }
 
 /// Represents a variable type with associated source location information.
+#[derive(Clone)]
 pub struct StaticVariableType {
     pub value: PreVariableType,
     pub span: InputSpan,
@@ -161,6 +163,7 @@ pub struct TypeCheckerContext {
     frames: Vec<Frame<PrimitiveKind>>,
     static_domain: IndexMap<String, StaticVariableType>,
     token_map: IndexMap<u32, TypedToken>,
+    assignments: TransformerContext,
 }
 
 impl Default for TypeCheckerContext {
@@ -189,6 +192,7 @@ impl TypeCheckerContext {
             frames: vec![frame],
             token_map,
             static_domain,
+            assignments: TransformerContext::default(),
         }
     }
 
@@ -197,6 +201,17 @@ impl TypeCheckerContext {
         self.token_map
     }
 
+    /// Copies the scopes of the context, without the token map, so that variables can be
+    /// declared to find the type of an expression without changing this context.
+    pub(crate) fn scoped_copy(&self) -> Self {
+        Self {
+            frames: self.frames.clone(),
+            static_domain: self.static_domain.clone(),
+            token_map: IndexMap::new(),
+            assignments: self.assignments.clone(),
+        }
+    }
+
     /// Adds a new scope frame to the context.
     pub fn add_scope(&mut self) {
         let frame = Frame::new();
@@ -272,6 +287,48 @@ impl TypeCheckerContext {
         self.static_domain.get(name)
     }
 
+    /// Evaluates the value of a constant using the constants that were assigned before it,
+    /// adding it to the assignment table so that its type is known from its value.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the constant
+    /// * `value` - Expression of the value of the constant
+    /// * `fn_context` - Function context used to evaluate the value
+    ///
+    /// # Returns
+    /// * `Ok(Some(PrimitiveKind))` with the type of the value if it could be evaluated
+    /// * `Ok(None)` if it could not be evaluated, the error is left to the transformer
+    /// * `Err(TransformError)` if the value uses a variable of the model
+    pub fn assign_constant(
+        &mut self,
+        name: &Spanned<String>,
+        value: &PreExp,
+        fn_context: &FunctionContext,
+    ) -> Result<Option<PrimitiveKind>, TransformError> {
+        match value.as_primitive(&self.assignments, fn_context) {
+            Ok(primitive) => {
+                let kind = primitive.get_type();
+                self.assignments
+                    .declare_variable(name, primitive, true)
+                    .map_err(|e| e.add_span(name.span()))?;
+                Ok(Some(kind))
+            }
+            Err(e) => match e.base_error() {
+                TransformError::UndeclaredVariable(variable)
+                    if self.static_domain.contains_key(variable) =>
+                {
+                    Err(TransformError::Other(format!(
+                        "Constant \"{}\" cannot use the model variable \"{}\", constants can only depend on other constants",
+                        name.value(),
+                        variable
+                    ))
+                    .add_span(value.span()))
+                }
+                _ => Ok(None),
+            },
+        }
+    }
+
     /// Removes and returns the top scope frame.
     ///
     /// # Returns
@@ -330,6 +387,7 @@ impl TypeCheckerContext {
                 PrimitiveKind::Number
                 | PrimitiveKind::Integer
                 | PrimitiveKind::PositiveInteger
+                | PrimitiveKind::Rational
                 | PrimitiveKind::String
                 | PrimitiveKind::GraphNode => {}
                 _ => {
@@ -339,6 +397,7 @@ impl TypeCheckerContext {
                             PrimitiveKind::Number,
                             PrimitiveKind::Integer,
                             PrimitiveKind::PositiveInteger,
+                            PrimitiveKind::Rational,
                             PrimitiveKind::String,
                             PrimitiveKind::GraphNode,
                         ],
@@ -396,11 +455,26 @@ impl TypeCheckerContext {
             Some(v) => {
                 let mut last_value = v;
                 for access in addressable_access.accesses.iter() {
-                    if !access.get_type(self, fn_context).is_numeric() {
+                    let access_type = access.get_type(self, fn_context);
+                    if let PrimitiveKind::Map(v) = last_value {
+                        if !matches!(access_type, PrimitiveKind::String | PrimitiveKind::Any) {
+                            return Err(TransformError::Other(format!(
+                                "Expected value of type \"String\" to index map, got \"{}\", check the definition of \"{}\"",
+                                access_type,
+                                access
+                            )).add_span(access.span()));
+                        }
+                        last_value = v;
+                        continue;
+                    }
+                    //maps inside mixed arrays are only known at runtime
+                    let is_key = matches!(last_value, PrimitiveKind::Any)
+                        && access_type == PrimitiveKind::String;
+                    if !access_type.is_numeric() && !is_key {
                         //TODO this is a relaxed check, the runtime will check for the exact type
                         return Err(TransformError::Other(format!(
                             "Expected value of type \"Number\" to index array, got \"{}\", check the definition of \"{}\"",
-                            access.get_type(self, fn_context),
+                            access_type,
                             access
                         )));
                     }
@@ -408,6 +482,8 @@ impl TypeCheckerContext {
                         PrimitiveKind::Iterable(i) => {
                             last_value = i
                         }
+                        //mixed arrays are only known at runtime
+                        PrimitiveKind::Any => {}
                         _ => return Err(TransformError::Other(format!(
                             "Expected value of type \"Iterable\" to index, got \"{}\", check the definition of \"{}\"",
                             last_value,
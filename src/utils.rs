@@ -1,4 +1,5 @@
 use core::fmt;
+use std::cell::Cell;
 use std::{fmt::Debug, ops::Deref, ops::DerefMut};
 
 #[allow(unused_imports)]
@@ -228,6 +229,11 @@ impl CompilationError {
     pub fn to_error_string(&self) -> String {
         format!("{} {}", self.kind, self.text)
     }
+
+    /// Returns the location of the error in the source.
+    pub fn span(&self) -> &InputSpan {
+        &self.span
+    }
 }
 
 impl std::fmt::Debug for CompilationError {
@@ -306,6 +312,48 @@ impl fmt::Display for ParseError {
     }
 }
 
+thread_local! {
+    static DISPLAY_PRECISION: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Sets the number of significant digits used to display the numbers of a model and its LaTeX,
+/// on the current thread. The values stored in the model are not changed, and `None`
+/// (the default) prints the numbers with full precision.
+///
+/// # Arguments
+/// * `precision` - The number of significant digits, or `None` for full precision
+pub fn set_display_precision(precision: Option<usize>) {
+    DISPLAY_PRECISION.with(|p| p.set(precision));
+}
+
+/// Returns the number of significant digits used to display numbers on the current thread.
+pub fn display_precision() -> Option<usize> {
+    DISPLAY_PRECISION.with(|p| p.get())
+}
+
+/// Formats a number rounding it to the display precision of the current thread,
+/// removing the trailing zeros of the decimal part.
+///
+/// # Arguments
+/// * `value` - The number to format
+pub fn format_number(value: f64) -> String {
+    let precision = match display_precision() {
+        Some(precision) if value != 0.0 && value.is_finite() => precision.max(1),
+        _ => return value.to_string(),
+    };
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = precision as i32 - 1 - magnitude;
+    if decimals <= 0 {
+        let scale = 10f64.powi(-decimals);
+        return ((value / scale).round() * scale).to_string();
+    }
+    let rounded = format!("{:.*}", decimals as usize, value);
+    rounded
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
 /// Removes multiple elements from a vector by their indices.
 ///
 /// # Arguments
@@ -0,0 +1,19 @@
+from pulp import LpProblem, LpVariable, LpMinimize, LpMaximize, LpStatus, lpSum, value
+
+problem = LpProblem("rooc_model", LpMaximize)
+
+variables = {}
+variables["x"] = LpVariable("x", lowBound=0, upBound=None, cat="Continuous")
+variables["y"] = LpVariable("y", lowBound=0, upBound=10, cat="Integer")
+variables["z"] = LpVariable("z", cat="Binary")
+
+problem += 3 * variables["x"] + 2 * variables["y"] - variables["z"] + 5, "objective"
+problem += variables["x"] + variables["y"] <= 4, "c0"
+problem += variables["x"] + 3 * variables["y"] >= 2, "c1"
+problem += variables["x"] - variables["z"] == 1, "c2"
+
+problem.solve()
+print(LpStatus[problem.status])
+for name, variable in variables.items():
+    print(name, "=", variable.varValue)
+print("objective =", value(problem.objective))
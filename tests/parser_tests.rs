@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod parser_tests {
     use indexmap::IndexMap;
-    use rooc::RoocParser;
+    use rooc::model_transformer::{transform_parsed_problem, Exp, Model, TransformError};
+    use rooc::type_checker::type_checker_context::{FunctionContext, TypeCheckerContext};
+    use rooc::{
+        linear_model_to_lp_format, model_to_pulp, BinOp, Comparison, Constant, IterableKind,
+        Linearizer, Primitive, RoocParser, SparseConstraint, VariableType,
+    };
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -33,7 +38,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -49,7 +54,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
     #[test]
@@ -64,7 +69,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -74,7 +79,7 @@ define
         let input = "
         min 1
         s.t.
-            n >= sum(i in A) { i }
+            n <= sum(i in A) { i }
         where
             let B = false
             let n = 1
@@ -93,7 +98,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -103,7 +108,7 @@ define
         let input = "
             min 1
             s.t.
-                1 + sum(el in R, i in 0..(el + 1)) { i } <= 1 for R in M
+                1 + sum(el in R, i in 0..(el + 1)) { i } >= 1 for R in M
             where
                 let M = [[1, 2], [3, 4]]
             ";
@@ -111,7 +116,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -135,7 +140,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -145,7 +150,7 @@ define
         let input = "
             min 1
             s.t.
-               10 * -1 >= 1
+               10 * -1 <= 1
                x + -1 >= 1
                -1 * (x) >= 1
                2 * -1 * (x) >= 1
@@ -156,7 +161,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -188,7 +193,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -206,7 +211,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -261,11 +266,11 @@ define
         let input = "
         min 1
             s.t.
-            sum(row in C, el in row) { el } <= 0
-            sum(i in 0..len(C), el in C[i]) { el } <= 0
-            sum(i in 0..len(C), j in 0..len(C[i])) { C[i][j] } <= 0
+            sum(row in C, el in row) { el } >= 0
+            sum(i in 0..len(C), el in C[i]) { el } >= 0
+            sum(i in 0..len(C), j in 0..len(C[i])) { C[i][j] } >= 0
 
-            sum((row, i) in enumerate(C), el in row) { el + i } <= 0
+            sum((row, i) in enumerate(C), el in row) { el + i } >= 0
         where
             let C = [
                 [1,0,0],
@@ -277,7 +282,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -306,7 +311,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -338,7 +343,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -360,7 +365,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to type check problem");
     }
 
@@ -378,7 +383,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect_err("Failed to detect invalid identifier name");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect_err("Failed to detect invalid identifier name");
     }
 
@@ -396,7 +401,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect_err("Failed to detect invalid identifier name");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect_err("Failed to detect invalid identifier name");
     }
 
@@ -415,7 +420,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect_err("Failed to detect invalid identifier name");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect_err("Failed to detect invalid identifier name");
     }
 
@@ -478,10 +483,43 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to typecheck problem");
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_comments_before_sections() {
+        let input = "
+        min x
+        s.t.
+            x <= n
+        // the constants
+        where
+            let n = 5
+        // the domain
+        define
+            x as NonNegativeReal
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(model.constraints()[0].to_string(), "x <= 5");
+        let input = "
+        min 1
+        s.t.
+            x_i <= 5 for i in 0..2
+            /* the domain */
+
+            // of the variables
+        define
+            x_i as NonNegativeReal for i in 0..2
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_static_variable_check_1() {
@@ -494,7 +532,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect_err("Failed to detect undeclared static variable");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect_err("Failed to detect undeclared static variable");
     }
     #[test]
@@ -511,7 +549,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to typecheck problem");
     }
     #[test]
@@ -528,7 +566,7 @@ define
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse and transform problem");
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to typecheck problem");
     }
     #[test]
@@ -545,7 +583,7 @@ define
         ";
         //TODO should i add this to the compiler too?
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect_err("Failed to typecheck duplicate");
     }
 
@@ -562,7 +600,7 @@ define
             x as IntegerRange(0, a)
         ";
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect_err("Failed to find domain type error");
     }
 
@@ -583,7 +621,3148 @@ define
             z_3 as IntegerRange(0, len(y))
         ";
         RoocParser::new(input.to_string())
-            .type_check(&vec![], &IndexMap::new())
+            .type_check(&[], &IndexMap::new())
             .expect("Failed to typecheck");
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_pulp_export() {
+        let input = "
+        max 3x + 2y - z + 5
+        s.t.
+            x + y <= 4
+            x + 3y >= 2
+            x - z = 1
+        define
+            x as NonNegativeReal
+            y as IntegerRange(0, 10)
+            z as Boolean
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let python = model_to_pulp(&model).expect("Failed to export model");
+        assert_eq!(python, include_str!("golden/pulp_model.py"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_pulp_export_non_linear() {
+        let input = "
+        min x * y
+        s.t.
+            x + y <= 4
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        model_to_pulp(&model).expect_err("Failed to detect non linear model");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_shortest_path() {
+        let input = "
+        min 1
+        s.t.
+            sum((u, v, c) in shortest_path(G, \"A\", \"D\")) { c * x_u_v } <= 10
+            sum((u, v, c) in shortest_path(G, \"D\", \"A\")) { c * x_u_v } <= 10
+            sum((u, v, c) in shortest_path(G, n, \"D\")) { c * x_u_v } <= 10 for n in nodes(G)
+        where
+            let G = Graph {
+                A -> [B: 1, C: 4],
+                B -> [C: 2, D: 5],
+                C -> [D: 1],
+                D
+            }
+        define
+            x_u_v as Boolean for (u, v) in edges(G)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints[0], "1 * x_A_B + 2 * x_B_C + 1 * x_C_D <= 10");
+        //D has no path to A, so the empty sum is always satisfied and removed
+        assert_eq!(
+            model.warnings()[0].to_string(),
+            "[AlwaysSatisfiedConstraint] Constraint \"0 <= 10\" is always satisfied and was removed"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_shortest_path_missing_cost() {
+        let input = "
+        min 1
+        s.t.
+            sum((u, v, c) in shortest_path(G, \"A\", \"C\")) { c * x_u_v } <= 10
+        where
+            let G = Graph {
+                A -> [B: 1],
+                B -> [C],
+                C
+            }
+        define
+            x_u_v as Boolean for (u, v) in edges(G)
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect edge without cost");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_node_degrees() {
+        let input = "
+        min 1
+        s.t.
+            out_degree(G, n) * x_n <= in_degree(G, n) for n in nodes(G)
+            sum(n in nodes(G)) { degree(G, n) * x_n } <= degree(G, \"A\")
+        where
+            let G = Graph {
+                A -> [B, C],
+                B -> [C],
+                C -> [A],
+                D
+            }
+        define
+            x_n as Boolean for n in nodes(G)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "2 * x_A <= 1",
+                "1 * x_B <= 1",
+                "1 * x_C <= 2",
+                "0 * x_D <= 0",
+                "3 * x_A + 2 * x_B + 3 * x_C + 0 * x_D <= 3"
+            ]
+        );
+        //the names are the ones the functions are called with, shown in the errors
+        let functions = RoocParser::new(input.to_string())
+            .functions()
+            .make_std_with();
+        for name in ["degree", "in_degree", "out_degree"] {
+            assert_eq!(functions[name].function_name(), name);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_node_degree_missing_node() {
+        let input = "
+        min 1
+        s.t.
+            x <= degree(G, \"E\")
+        where
+            let G = Graph {
+                A -> [B],
+                B
+            }
+        define
+            x as Boolean
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect missing node");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_connected_components() {
+        let input = "
+        min 1
+        s.t.
+            sum(n in c) { x_n } <= len(c) for c in connected_components(G)
+            y <= if is_connected(G) { 1 } else { 0 }
+            y <= if is_connected(H) { 1 } else { 0 }
+        where
+            let G = Graph {
+                A -> [B],
+                C -> [B],
+                B,
+                D -> [E],
+                E
+            }
+            let H = Graph {
+                A -> [B],
+                B -> [C],
+                C
+            }
+        define
+            x_n as Boolean for n in nodes(G)
+            y as Boolean
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x_A + x_C + x_B <= 3", "x_D + x_E <= 2", "y <= 0", "y <= 1"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_adjacency_matrix() {
+        let input = "
+        min 1
+        s.t.
+            sum(j in 0..len(C)) { C[i][j] * x_j } <= 10 for i in 0..len(C)
+            sum(j in 0..len(B)) { B[0][j] * x_j } <= 1
+        where
+            let G = Graph {
+                C -> [A: 3],
+                A -> [B: 2, C: 1],
+                B
+            }
+            let C = adjacency_matrix(G, \"cost\")
+            let B = adjacency_matrix(G)
+        define
+            x_i as Boolean for i in 0..3
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        //rows and columns follow the declaration order C, A, B
+        assert_eq!(
+            constraints,
+            vec![
+                "0 * x_0 + 3 * x_1 + 0 * x_2 <= 10",
+                "1 * x_0 + 0 * x_1 + 2 * x_2 <= 10",
+                "0 * x_0 + 0 * x_1 + 0 * x_2 <= 10",
+                "0 * x_0 + 1 * x_1 + 0 * x_2 <= 1"
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_adjacency_matrix_errors() {
+        let missing_cost = "
+        min 1
+        s.t.
+            C[0][1] * x <= 10
+        where
+            let G = Graph {
+                A -> [B],
+                B
+            }
+            let C = adjacency_matrix(G, \"cost\")
+        define
+            x as Boolean
+        ";
+        RoocParser::new(missing_cost.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect edge without cost");
+        let unknown_mode = missing_cost.replace("\"cost\"", "\"weights\"");
+        RoocParser::new(unknown_mode)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect unknown mode");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_weighted_objective() {
+        let input = "
+        min w[0] * (x + y) + w[1] * (x - y)
+        s.t.
+            x + y <= 10
+        where
+            let w = [2, 3.5]
+        define
+            x, y as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.objective().to_string(),
+            "min 2 * (x + y) + 3.5 * (x - y)"
+        );
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        assert_eq!(linear.variables(), &vec!["x".to_string(), "y".to_string()]);
+        assert_eq!(linear.objective(), &vec![5.5, -1.5]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_transpose() {
+        let input = "
+        min 1
+        s.t.
+            sum(v in row) { v * y } <= 10 for row in transpose(M)
+            sum(row in transpose(M)) { sum(v in row) { v } } * y <= sum(row in M) { sum(v in row) { v } }
+        where
+            let M = [
+                [1, 2, 3],
+                [4, 5, 6]
+            ]
+        define
+            y as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints[0], "1 * y + 4 * y <= 10");
+        assert_eq!(constraints[1], "2 * y + 5 * y <= 10");
+        assert_eq!(constraints[2], "3 * y + 6 * y <= 10");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        let summed = &linear.constraints()[3];
+        assert_eq!(summed.coefficients(), &vec![21.0]);
+        assert_eq!(summed.rhs(), 21.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_transpose_ragged() {
+        let input = "
+        min 1
+        s.t.
+            sum(v in row) { v * y } <= 10 for row in transpose(M)
+        define
+            y as NonNegativeReal
+        ";
+        //the matrix is given as a constant, as ragged literals are rejected before transpose runs
+        let matrix = Constant::from_primitive(
+            "M",
+            Primitive::Iterable(IterableKind::Iterables(vec![
+                IterableKind::Integers(vec![1, 2, 3]),
+                IterableKind::Integers(vec![4, 5]),
+            ])),
+        );
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![matrix], &IndexMap::new())
+            .expect_err("Failed to detect ragged matrix");
+        assert!(err.contains("Cannot transpose a ragged matrix"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_3d_array_literal() {
+        let input = "
+        min 1
+        s.t.
+            T[i][j][k] * x <= 10 for i in 0..2, j in 0..2, k in 0..2
+        where
+            let T = [[[1, 2], [3, 4]], [[5, 6], [7, 8.5]]]
+        define
+            x as NonNegativeReal
+        ";
+        let parsed = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = parsed
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "1 * x <= 10",
+                "2 * x <= 10",
+                "3 * x <= 10",
+                "4 * x <= 10",
+                "5 * x <= 10",
+                "6 * x <= 10",
+                "7 * x <= 10",
+                "8.5 * x <= 10",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_3d_array_indexing() {
+        let input = "
+        min 1
+        s.t.
+            T[1][0][1] * x + T[0][1][0] * y <= 10
+        where
+            let T = [[[1, 2], [3, 4]], [[5, 6], [7, 8]]]
+        define
+            x, y as NonNegativeReal
+        ";
+        let parsed = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(parsed.constraints()[0].to_string(), "6 * x + 3 * y <= 10");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_ragged_3d_array_literal() {
+        let input = "
+        min 1
+        s.t.
+            T[0][0][0] * x <= 10
+        where
+            let T = [[[1, 2], [3, 4]], [[5, 6], [7]]]
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect ragged array");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_if_else() {
+        let input = "
+        min 1
+        s.t.
+            cost * x + if small { 1 } else { 2 } * y <= 10
+        where
+            let big = true
+            let small = false
+            let cost = if big { 100 } else { 50 }
+        define
+            x, y as NonNegativeReal
+        ";
+        let parsed = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(parsed.constraints()[0].to_string(), "100 * x + 2 * y <= 10");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_if_else_type_mismatch() {
+        let input = "
+        min 1
+        s.t.
+            cost * x <= 10
+        where
+            let big = true
+            let cost = if big { 100 } else { \"cheap\" }
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Failed to detect mismatched branch types");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_if_else_with_variable() {
+        let input = "
+        min 1
+        s.t.
+            if big { x } else { 1 } <= 10
+        where
+            let big = true
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect variable inside if/else");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_modulo_wrap_around_index() {
+        let input = "
+        min 1
+        s.t.
+            x_i - x_{(i + 1) % n} <= 1 for i in 0..n
+        where
+            let n = 3
+        define
+            x_i as NonNegativeReal for i in 0..n
+        ";
+        let parsed = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = parsed
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x_0 - x_1 <= 1", "x_1 - x_2 <= 1", "x_2 - x_0 <= 1"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_modulo_by_zero() {
+        let input = "
+        min 1
+        s.t.
+            x_{i % n} <= 1 for i in 0..3
+        where
+            let n = 0
+        define
+            x_i as NonNegativeReal for i in 0..3
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect modulo by zero");
+        assert!(err.contains("Division by zero"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_lp_format_export() {
+        let input = "
+        max 3x + 2y - z + 5
+        s.t.
+            x + y <= 4
+            x + 3y >= 2
+            x - z + w = 1
+        define
+            x as NonNegativeReal
+            y as IntegerRange(0, 10)
+            z as Boolean
+            w as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let lp = model.to_lp_format().expect("Failed to export model");
+        assert_eq!(lp, include_str!("golden/lp_model.lp"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_lp_format_export_non_linear() {
+        let input = "
+        min x * y
+        s.t.
+            x + y <= 4
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        model
+            .to_lp_format()
+            .expect_err("Failed to detect non linear model");
+    }
+
+    struct MpsData {
+        rows: IndexMap<String, String>,
+        columns: IndexMap<(String, String), f64>,
+        rhs: IndexMap<String, f64>,
+        bounds: Vec<(String, String)>,
+    }
+
+    fn read_mps(mps: &str) -> MpsData {
+        let mut data = MpsData {
+            rows: IndexMap::new(),
+            columns: IndexMap::new(),
+            rhs: IndexMap::new(),
+            bounds: Vec::new(),
+        };
+        let mut section = "";
+        for line in mps.lines() {
+            if !line.starts_with(' ') {
+                section = line.split_whitespace().next().unwrap_or("");
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            match section {
+                "ROWS" => {
+                    data.rows
+                        .insert(fields[1].to_string(), fields[0].to_string());
+                }
+                "COLUMNS" => {
+                    data.columns.insert(
+                        (fields[0].to_string(), fields[1].to_string()),
+                        fields[2].parse().unwrap(),
+                    );
+                }
+                "RHS" => {
+                    data.rhs
+                        .insert(fields[1].to_string(), fields[2].parse().unwrap());
+                }
+                "BOUNDS" => data
+                    .bounds
+                    .push((fields[0].to_string(), fields[2].to_string())),
+                _ => {}
+            }
+        }
+        data
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_mps_format_export() {
+        let input = "
+        max 3x + 2y - z
+        s.t.
+            x + y <= 4
+            x + 3y >= 2
+            x - z + w = 1
+        define
+            x as NonNegativeReal
+            y as IntegerRange(0, 10)
+            z as Boolean
+            w as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let mps = model.to_mps_format().expect("Failed to export model");
+        let data = read_mps(&mps);
+        let linear = Linearizer::linearize(model).expect("Failed to linearize model");
+        assert_eq!(data.rows.get("obj").unwrap(), "N");
+        assert_eq!(data.rows.get("c0").unwrap(), "L");
+        assert_eq!(data.rows.get("c1").unwrap(), "G");
+        assert_eq!(data.rows.get("c2").unwrap(), "E");
+        for (j, var) in linear.variables().iter().enumerate() {
+            let objective = data
+                .columns
+                .get(&(var.clone(), "obj".to_string()))
+                .copied()
+                .unwrap_or(0.0);
+            assert_eq!(objective, linear.objective()[j]);
+            for (i, constraint) in linear.constraints().iter().enumerate() {
+                let coefficient = data
+                    .columns
+                    .get(&(var.clone(), format!("c{}", i)))
+                    .copied()
+                    .unwrap_or(0.0);
+                assert_eq!(coefficient, constraint.coefficients()[j]);
+            }
+        }
+        for (i, constraint) in linear.constraints().iter().enumerate() {
+            let rhs = data.rhs.get(&format!("c{}", i)).copied().unwrap_or(0.0);
+            assert_eq!(rhs, constraint.rhs());
+        }
+        assert!(data.bounds.contains(&("BV".to_string(), "z".to_string())));
+        assert!(data.bounds.contains(&("LI".to_string(), "y".to_string())));
+        assert!(data.bounds.contains(&("UI".to_string(), "y".to_string())));
+        assert!(data.bounds.contains(&("FR".to_string(), "w".to_string())));
+        //the constraints have no ranges, so the optional section is not written
+        assert!(!mps.lines().any(|line| line == "RANGES"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_mps_format_export_non_linear() {
+        let input = "
+        min x * y
+        s.t.
+            x + y <= 4
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        model
+            .to_mps_format()
+            .expect_err("Failed to detect non linear model");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_disjunctive_constraints() {
+        let input = "
+        min sum(i in 0..2) { x_i }
+        s.t.
+            x_i <= 2 or x_i >= 8 for i in 0..2
+            x_0 + x_1 = 10
+                or x_0 = x_1
+        define
+            x_i as NonNegativeReal(0, 10) for i in 0..2
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "x_0 <= 2 or x_0 >= 8",
+                "x_1 <= 2 or x_1 >= 8",
+                "x_0 + x_1 = 10 or x_0 = x_1",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_type_annotations() {
+        let input = "
+        min 1
+        s.t.
+            sum(c in C) { c * x } <= n
+            sum(row in M, c in row) { c * x } <= n
+        where
+            let C = [] as Number[]
+            let M = [[1, 2], [3, 4]] as Number[][]
+            let n = 5 as Number
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["1 * x + 2 * x + 3 * x + 4 * x <= 5"]);
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("let C = [] as Number[]"));
+        assert!(formatted.contains("let n = 5 as Number"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_impossible_type_annotation() {
+        let input = "
+        min 1
+        s.t.
+            x <= 1
+        where
+            let G = Graph {
+                A -> [B],
+                B
+            }
+            let n = G as Number
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Failed to detect impossible cast");
+        let input = input.replace("G as Number", "[1.5] as Integer[]");
+        RoocParser::new(input)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect lossy cast");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_named_constraints() {
+        let input = "
+        min 1
+        s.t.
+            budget: x_0 + x_1 <= 10
+            cap_i: x_i <= c[i] for i in 0..2
+            x_0 >= 1
+        where
+            let c = [3, 4]
+        define
+            x_i as NonNegativeReal for i in 0..2
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let cap = model
+            .constraint_by_name("cap_1")
+            .expect("Failed to find named constraint");
+        assert_eq!(cap.to_string(), "cap_1: x_1 <= 4");
+        assert_eq!(
+            model.constraint_by_name("budget").map(|c| c.to_string()),
+            Some("budget: x_0 + x_1 <= 10".to_string())
+        );
+        assert!(model.constraint_by_name("cap_2").is_none());
+        assert!(model.constraints()[3].name().is_none());
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("cap_i: x_i <= c[i] for i in 0..2"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_duplicate_constraint_names() {
+        let input = "
+        min 1
+        s.t.
+            limit: x <= 10
+            limit: x >= 1
+        define
+            x as NonNegativeReal
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect duplicate constraint name");
+        assert!(err.contains("DuplicateConstraintName"));
+        let input = "
+        min 1
+        s.t.
+            c_i: x <= i for i in [1, 2, 1]
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect duplicate iterated constraint name");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_generated_constraint_names_dont_collide() {
+        let input = "
+        min x
+        s.t.
+            c1: x <= 10
+            x >= 1
+        define
+            x as NonNegativeReal
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        assert_eq!(linear.constraint_names(), vec!["c1", "c1_"]);
+        assert_eq!(linear.constraint_name(1), "c1_");
+        let lp = linear_model_to_lp_format(&linear).expect("Failed to export model");
+        assert!(lp.contains(" c1_: "), "{}", lp);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_sum_and_prod_functions() {
+        let input = "
+        min sum(A) * x + prod(A) * y
+        s.t.
+            sum(row in M) { sum(row) * x } <= 10
+            x + y >= sum(row in M) { prod(row) }
+        where
+            let A = [1, 2, 3.5]
+            let M = [[1, 2], [3, 4]]
+        define
+            x, y as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(model.objective().to_string(), "min 6.5 * x + 7 * y");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["3 * x + 7 * x <= 10", "x + y >= 2 + 12"]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_sum_of_non_numeric_iterable() {
+        let input = "
+        min sum(A) * x
+        s.t.
+            x <= 1
+        where
+            let A = [\"a\", \"b\"]
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected sum of strings to fail type checking");
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected sum of strings to fail");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_avg_min_max_functions() {
+        let input = "
+        max sum(row in M) { avg(row) * x }
+        s.t.
+            min(A) * x <= max(A)
+            sum(row in M) { max(row) - min(row) } * x >= 0
+        where
+            let A = [4, 2, 8]
+            let M = [[1, 2, 3], [4, 5, 6]]
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(model.objective().to_string(), "max 2 * x + 5 * x");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["2 * x <= 8", "(3 - 1 + 6 - 4) * x >= 0"]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_avg_of_empty_iterable() {
+        let input = "
+        min avg(A) * x
+        s.t.
+            x <= 1
+        where
+            let A = [] as Number[]
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected avg of an empty iterable to fail");
+        assert!(err.contains("Cannot compute the average of an empty iterable"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_reverse_and_negative_indexing() {
+        let input = "
+        min 1
+        s.t.
+            sum(c in reverse(A)) { c * x_c } <= A[-1]
+            x_1 >= M[-1][-2]
+        where
+            let A = [1, 2, 3]
+            let M = [[1, 2], [3, 4]]
+        define
+            x_i as Real for i in A
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["3 * x_3 + 2 * x_2 + 1 * x_1 <= 3", "x_1 >= 3"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_negative_index_out_of_bounds() {
+        let input = "
+        min 1
+        s.t.
+            x <= A[-4]
+        where
+            let A = [1, 2, 3]
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected out of bounds negative index to fail");
+        assert!(err.contains("OutOfBounds"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_concat_and_split_strings() {
+        let input = "
+        min 1
+        s.t.
+            sum(l in split(labels, \",\")) { x_{concat(\"n\", l)} } <= 1
+            x_{concat(\"n\", \"a\", \"\")} >= 0
+        where
+            let labels = \"a,b,c\"
+        define
+            x_{concat(\"n\", l)} as Real for l in split(labels, \",\")
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["x_na + x_nb + x_nc <= 1", "x_na >= 0"]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_split_with_empty_separator() {
+        let input = "
+        min 1
+        s.t.
+            sum(l in split(\"a,b\", \"\")) { x_l } <= 1
+        define
+            x_l as Real for l in [\"a\", \"b\"]
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected split with an empty separator to fail");
+        assert!(err.contains("Cannot split a string with an empty separator"));
+        let input = "
+        min 1
+        s.t.
+            x_{concat(\"a\", 1)} <= 1
+        define
+            x_a1 as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected concat of a number to fail type checking");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_rational_constants() {
+        let input = "
+        min x
+        s.t.
+            x <= 1/3 + 1/3 + 1/3
+            x >= c
+            y_{4/2} >= b
+        where
+            let c = (1/10 + 2/10) * 10 - 3
+            let b = 2/3 as Rational
+        define
+            x, y_2 as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x <= 1", "x >= 0", "y_2 >= 0.6666666666666666"]
+        );
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("x <= 1/3 + 1/3 + 1/3"));
+        assert!(formatted.contains("let c = (1/10 + 2/10) * 10 - 3"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_integer_domain() {
+        let input = "
+        min x + y
+        s.t.
+            x + y >= -3
+        define
+            x as Integer
+            y as Integer(0, 5)
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let domain = model.domain();
+        assert_eq!(
+            *domain.get("x").unwrap().get_type(),
+            VariableType::Integer(f64::NEG_INFINITY, f64::INFINITY)
+        );
+        assert_eq!(
+            *domain.get("y").unwrap().get_type(),
+            VariableType::Integer(0.0, 5.0)
+        );
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("x as Integer\n"));
+        assert!(formatted.contains("y as Integer(0, 5)"));
+        let model = model.to_string();
+        assert!(model.contains("x as Integer"));
+        assert!(model.contains("y as Integer(0, 5)"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_integer_domain_with_fractional_bound() {
+        let input = "
+        min x
+        s.t.
+            x >= 0
+        define
+            x as Integer(0.5, 10)
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected a fractional bound of an Integer to fail");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_domain_bounds_from_constants() {
+        let input = "
+        min sum(i in 0..len(C)) { x_i }
+        s.t.
+            x_0 >= 1
+        where
+            let C = [1, 2, 3]
+        define
+            x_i as IntegerRange(0, len(C)) for i in 0..len(C)
+            y as Real(-len(C), C[2] * 2)
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform");
+        let domain = model.domain();
+        for name in ["x_0", "x_1", "x_2"] {
+            assert_eq!(
+                domain.get(name).unwrap().get_type(),
+                &VariableType::IntegerRange(0, 3)
+            );
+        }
+        assert_eq!(
+            domain.get("y").unwrap().get_type(),
+            &VariableType::Real(-3.0, 6.0)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_domain_bounds_with_model_variable() {
+        let input = "
+        min x + y
+        s.t.
+            x >= 1
+        define
+            y as NonNegativeReal
+            x as IntegerRange(0, y)
+        ";
+        let parser = RoocParser::new(input.to_string());
+        let error = parser
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected a bound using a model variable to fail type checking");
+        assert!(error.to_string().contains("model variable \"y\""));
+        let error = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected a bound using a model variable to fail");
+        assert!(error.to_string().contains("model variable \"y\""));
+        let nested = input.replace("IntegerRange(0, y)", "IntegerRange(0, 2 * |y| + 1)");
+        let error = RoocParser::new(nested)
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected a bound using a nested model variable to fail type checking");
+        assert!(error.to_string().contains("model variable \"y\""));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_dot_expansion() {
+        let input = "
+        max dot(C, [\"x\", \"y\"])
+        s.t.
+            dot(A[i], [\"x\", \"y\"]) <= b[i] for i in 0..len(A)
+        where
+            let C = [3, 2]
+            let A = [[1, 1], [2, 1]]
+            let b = [4, 6]
+        define
+            x, y as NonNegativeReal
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform");
+        let product = |c: f64, name: &str| {
+            Exp::BinOp(
+                BinOp::Mul,
+                Exp::Number(c).to_box(),
+                Exp::Variable(name.to_string()).to_box(),
+            )
+        };
+        let expected = Exp::BinOp(
+            BinOp::Add,
+            product(3.0, "x").to_box(),
+            product(2.0, "y").to_box(),
+        );
+        assert_eq!(
+            format!("{:?}", model.objective().rhs),
+            format!("{:?}", expected)
+        );
+        assert_eq!(
+            model.to_string(),
+            "max 3 * x + 2 * y
+s.t.
+    1 * x + 1 * y <= 4
+    2 * x + 1 * y <= 6
+define
+    x, y as NonNegativeReal"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_dot_with_different_lengths() {
+        let input = "
+        max dot([1, 2, 3], [\"x\", \"y\"])
+        s.t.
+            x <= 1
+        define
+            x, y as NonNegativeReal
+        ";
+        let error = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected dot of iterables of different lengths to fail");
+        assert!(error.to_string().contains("same length"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_argmin_argmax() {
+        let input = "
+        min x_{argmin(i in 0..len(C)) { C[i] }}
+        s.t.
+            x_0 >= A[argmax(i in 0..len(C)) { C[i] }]
+            x_0 <= A[last]
+        where
+            let C = [4, 1, 7, 1, 7]
+            let A = [5, 6, 7, 8, 9]
+            let last = argmin(i in 0..len(C)) { -i }
+        define
+            x_i as NonNegativeReal for i in 0..len(C)
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform");
+        //ties keep the first index
+        assert_eq!(
+            model.to_string(),
+            "min x_1
+s.t.
+    x_0 >= 7
+    x_0 <= 9
+define
+    x_0, x_1, x_2, x_3, x_4 as NonNegativeReal"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_argmax_with_model_variable() {
+        let input = "
+        min x
+        s.t.
+            x >= argmax(i in 0..3) { x }
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected argmax over a model variable to fail");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_count() {
+        let input = "
+        min sum(i in 0..k) { x_i }
+        s.t.
+            x_0 >= count(p in P) { p }
+            x_1 >= count(p in P, q in P) { q }
+        where
+            let A = [3, 0, 1, 5, 2]
+            let P = [true, false, false, true, true]
+            let k = count(j in 0..len(A)) { (A[j] > 0) }
+        define
+            x_i as NonNegativeReal for i in 0..len(A)
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform");
+        assert_eq!(
+            model.to_string(),
+            "min x_0 + x_1 + x_2 + x_3
+s.t.
+    x_0 >= 3
+    x_1 >= 15
+define
+    x_0, x_1, x_2, x_3, x_4 as NonNegativeReal"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_count_with_numeric_body() {
+        let input = "
+        min x
+        s.t.
+            x >= count(a in A) { a }
+        where
+            let A = [3, 0, 1]
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected the body of count to be a boolean");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_type_from_value() {
+        let source = "
+        min x
+        s.t.
+            x >= U[0]
+            x <= sum((i, v) in D) { v }
+        where
+            let U = union([], {})
+            let D = enumerate([1, 2, 3])
+        define
+            x as NonNegativeReal
+        ";
+        RoocParser::new(source.replace("{}", "[1, 2]"))
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check");
+        let error = RoocParser::new(source.replace("{}", "[\"a\", \"b\"]"))
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected a string constant to fail in a numeric position");
+        assert!(error.contains("\"String\""));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_with_model_variable() {
+        let input = "
+        min x
+        s.t.
+            x >= D
+        where
+            let D = x
+        define
+            x as NonNegativeReal
+        ";
+        let error = RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected a constant using a model variable to fail");
+        assert!(error.contains("model variable \"x\""));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_model_json_round_trip() {
+        let input = "
+        max sum(i in 0..len(C)) { C[i] * x_i } + piecewise(y, [0, 1, 2], [0, 1, 4]) - |z|
+        s.t.
+            capacity: sum(i in 0..len(C)) { x_i } <= 10
+            y + z >= -(3)
+            b <= 1
+        where
+            let C = [3, 2, 5]
+        define
+            x_i as IntegerRange(0, 5) for i in 0..len(C)
+            y as NonNegativeReal
+            z as Real
+            w as Integer(0, 8)
+            b as Boolean
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform");
+        let json = model.to_json().expect("Failed to serialize the model");
+        let restored = Model::from_json(&json).expect("Failed to deserialize the model");
+        assert_eq!(restored.to_string(), model.to_string());
+        assert_eq!(restored.domain().len(), model.domain().len());
+        for (name, variable) in model.domain() {
+            let other = restored.domain().get(name).unwrap();
+            assert_eq!(other.get_type(), variable.get_type());
+            assert_eq!(other.span(), variable.span());
+            assert_eq!(other.usage_count(), variable.usage_count());
+        }
+        assert!(restored.constraint_by_name("capacity").is_some());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_implicit_multiplication_products() {
+        let input = "
+        min 2(x + y) + (a)(b) + |x|y
+        s.t.
+            2(x + y) <= len(C)
+        where
+            let C = [1, 2]
+        define
+            x, y, a, b as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let expected = Exp::BinOp(
+            BinOp::Add,
+            Exp::BinOp(
+                BinOp::Add,
+                Exp::BinOp(
+                    BinOp::Mul,
+                    Exp::Number(2.0).to_box(),
+                    Exp::BinOp(
+                        BinOp::Add,
+                        Exp::Variable("x".to_string()).to_box(),
+                        Exp::Variable("y".to_string()).to_box(),
+                    )
+                    .to_box(),
+                )
+                .to_box(),
+                Exp::BinOp(
+                    BinOp::Mul,
+                    Exp::Variable("a".to_string()).to_box(),
+                    Exp::Variable("b".to_string()).to_box(),
+                )
+                .to_box(),
+            )
+            .to_box(),
+            Exp::BinOp(
+                BinOp::Mul,
+                Exp::Abs(Exp::Variable("x".to_string()).to_box()).to_box(),
+                Exp::Variable("y".to_string()).to_box(),
+            )
+            .to_box(),
+        );
+        assert_eq!(
+            format!("{:?}", model.objective().rhs),
+            format!("{:?}", expected)
+        );
+        assert_eq!(model.constraints()[0].to_string(), "2 * (x + y) <= 2");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_domain_initial_value() {
+        let input = "
+        max sum(i in 0..len(C)) { x_i } + y
+        s.t.
+            sum(i in 0..len(C)) { x_i } + y <= 4
+        where
+            let C = [1, 0, 1]
+        define
+            x_i as Boolean = C[i] for i in 0..len(C)
+            y as Real = 1.5
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let formatted = parser.format().expect("Failed to format problem");
+        assert!(formatted.contains("x_i as Boolean = C[i] for i in 0..len(C)"));
+        assert!(formatted.contains("y as Real = 1.5"));
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let initial_values = model
+            .domain()
+            .iter()
+            .map(|(name, v)| (name.as_str(), v.initial_value()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            initial_values,
+            vec![
+                ("x_0", Some(1.0)),
+                ("x_1", Some(0.0)),
+                ("x_2", Some(1.0)),
+                ("y", Some(1.5))
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_domain_initial_value_outside_of_domain() {
+        let input = "
+        max x
+        s.t.
+            x <= 4
+        define
+            x as IntegerRange(0, 5) = 2.5
+        ";
+        let err = RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Failed to detect a fractional initial value");
+        assert!(err.contains("expected \"Integer\", got \"Number\""));
+        let input = "
+        max x
+        s.t.
+            x <= 4
+        define
+            x as IntegerRange(0, 5) = 7
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect an initial value outside of the domain");
+        assert!(err.contains("The initial value 7 of \"x\" is outside of its domain"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_neighbors_within() {
+        let input = "
+        min 1
+        s.t.
+            sum(n in neighbors_within(G, \"A\", 1)) { x_n } <= 1
+            sum(n in neighbors_within(G, \"A\", 2)) { x_n } <= 2
+            sum(n in neighbors_within(G, \"B\", 2)) { x_n } <= 2
+            sum(n in neighbors_within(G, \"A\", 0)) { x_n } <= 0
+        where
+            let G = Graph {
+                A -> [B],
+                B -> [C],
+                C -> [D],
+                D
+            }
+        define
+            x_n as Boolean for n in nodes(G)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x_B <= 1", "x_B + x_C <= 2", "x_C + x_D <= 2"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_neighbors_within_invalid_arguments() {
+        let source = "
+        min 1
+        s.t.
+            sum(n in neighbors_within(G, NODE, K)) { x_n } <= 1
+        where
+            let G = Graph {
+                A -> [B],
+                B
+            }
+        define
+            x_n as Boolean for n in nodes(G)
+        ";
+        for (node, k) in [("\"A\"", "-1"), ("\"Z\"", "1")] {
+            let input = source.replace("NODE", node).replace("K", k);
+            RoocParser::new(input)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .expect_err("Failed to detect invalid neighbors_within arguments");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_subgraph() {
+        let input = "
+        min 1
+        s.t.
+            sum((u, v) in edges(subgraph(G, S))) { x_u_v } <= len(nodes(subgraph(G, S)))
+            sum(n in nodes(subgraph(G, nodes(G)))) { y_n } <= 4
+            sum((u, v) in edges(subgraph(G, [\"D\"]))) { x_u_v } <= 0
+        where
+            let S = [\"A\", \"C\", \"D\"]
+            let G = Graph {
+                A -> [B, C],
+                B -> [C],
+                C -> [D, A],
+                D -> [B]
+            }
+        define
+            x_u_v as Boolean for (u, v) in edges(G)
+            y_n as Boolean for n in nodes(G)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x_A_C + x_C_D + x_C_A <= 3", "y_A + y_B + y_C + y_D <= 4"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_subgraph_missing_node() {
+        let input = "
+        min 1
+        s.t.
+            sum((u, v) in edges(subgraph(G, [\"A\", \"Z\"]))) { x_u_v } <= 1
+        where
+            let G = Graph {
+                A -> [B],
+                B
+            }
+        define
+            x_u_v as Boolean for (u, v) in edges(G)
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect missing node");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_range_constraints() {
+        let input = "
+        max x_0 + x_1
+        s.t.
+            0 <= x_0 + x_1 <= 10
+            C[i] >= x_i > -1 for i in 0..len(C)
+            y <= 2
+        where
+            let C = [3, 4]
+        define
+            x_i as Real for i in 0..len(C)
+            y as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "0 <= x_0 + x_1",
+                "x_0 + x_1 <= 10",
+                "3 >= x_0",
+                "4 >= x_1",
+                "x_0 > -1",
+                "x_1 > -1",
+                "y <= 2"
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_named_range_constraints() {
+        let input = "
+        max x_0 + x_1
+        s.t.
+            total: 0 <= x_0 + x_1 <= 10
+            cap_i: 0 <= x_i <= C[i] for i in 0..len(C)
+        where
+            let C = [3, 4]
+        define
+            x_i as Real for i in 0..len(C)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let names = model
+            .constraints()
+            .iter()
+            .map(|c| c.name().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                Some("total_lower".to_string()),
+                Some("total_upper".to_string()),
+                Some("cap_lower_0".to_string()),
+                Some("cap_lower_1".to_string()),
+                Some("cap_upper_0".to_string()),
+                Some("cap_upper_1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_range_constraint_opposite_directions() {
+        let input = "
+        max x
+        s.t.
+            0 <= x >= 10
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .parse()
+            .expect_err("Failed to detect range constraint with opposite comparisons");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_logical_operators() {
+        let input = "
+        max x
+        s.t.
+            x <= if a && !b { 1 } else { 0 }
+            x <= if a && b || !a { 1 } else { 2 }
+            x <= if !(a || b) { 1 } else { 3 }
+            x <= if is_connected(G) && a { 4 } else { 0 }
+        where
+            let a = true
+            let b = false
+            let G = Graph {
+                A -> [B],
+                B
+            }
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["x <= 1", "x <= 2", "x <= 3", "x <= 4"]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_logical_operators_errors() {
+        let source = "
+        max x
+        s.t.
+            x <= CONSTRAINT
+        where
+            let a = true
+            let n = 2
+        define
+            x as Real
+        ";
+        for constraint in ["if n && a { 1 } else { 0 }", "if !n { 1 } else { 0 }"] {
+            RoocParser::new(source.replace("CONSTRAINT", constraint))
+                .type_check(&[], &IndexMap::new())
+                .expect_err("Failed to detect logical operator on numbers");
+        }
+        //logical operators can't become part of the model
+        RoocParser::new(source.replace("CONSTRAINT", "x * (a || a)"))
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect logical operator in the model");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_comparison_operators() {
+        let input = "
+        max x
+        s.t.
+            x <= if 3 < 5 { 1 } else { 0 }
+            x <= if n >= 2 && n != 3 { 2 } else { 0 }
+            x <= if (n * 2 == 4) || n > 10 { 3 } else { 0 }
+            x <= if name == \"a\" && !(n <= 1) { 4 } else { 0 }
+        where
+            let n = 2
+            let name = \"a\"
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["x <= 1", "x <= 2", "x <= 3", "x <= 4"]);
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        RoocParser::new(formatted)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse formatted problem");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_comparison_operators_errors() {
+        let source = "
+        max x
+        s.t.
+            x <= CONSTRAINT
+        where
+            let a = true
+            let n = 2
+        define
+            x as Real
+        ";
+        //comparisons produce booleans, which can't be used as numbers
+        for constraint in ["2 * (3 < 5)", "(n == 2)", "|n > 1|", "-(n < 1)"] {
+            RoocParser::new(source.replace("CONSTRAINT", constraint))
+                .type_check(&[], &IndexMap::new())
+                .expect_err("Failed to detect comparison used as a number");
+        }
+        RoocParser::new(source.replace("CONSTRAINT", "x * (x < 3)"))
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect comparison in the model");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_len_of_graphs_and_strings() {
+        let input = "
+        max x
+        s.t.
+            x <= len(G)
+            x <= len(name)
+            x <= len(A)
+            x <= len(subgraph(G, [\"A\", \"B\"]))
+            x >= len(\"\")
+        where
+            let name = \"rooc\"
+            let A = [1, 2, 3, 4, 5]
+            let G = Graph {
+                A -> [B, C],
+                B -> [C],
+                C
+            }
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x <= 3", "x <= 4", "x <= 5", "x <= 2", "x >= 0"]
+        );
+        let source = "
+        max x
+        s.t.
+            x <= len(n)
+        where
+            let n = 2
+        define
+            x as Real
+        ";
+        RoocParser::new(source.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Failed to detect len of a number");
+        RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect len of a number");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_format_canonical_layout() {
+        let input = "
+  max   2x+3*y   +  sum(i in 0..2){ z_i }
+subject to
+
+      budget:x+y<=10
+  cap_i :   z_i<=c[i]   for i in 0..2
+
+
+  x>=1
+WHERE
+ let c=[3,4]
+     let G = Graph {
+   A -> [B],
+ B
+ }
+DEFINE
+  x,y as NonNegativeReal
+    z_i as Integer for i in 0..2
+";
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        let expected = "max 2 * x + 3 * y + sum[i in 0..2] { z_i }
+s.t.
+    budget: x + y <= 10
+    cap_i: z_i <= c[i] for i in 0..2
+    x >= 1
+where
+    let c = [3, 4]
+    let G = Graph {
+        A -> [ B ],
+        B
+    }
+define
+    x, y as NonNegativeReal
+    z_i as Integer for i in 0..2
+";
+        assert_eq!(formatted, expected);
+        let formatted_again = RoocParser::new(formatted)
+            .format()
+            .expect("Failed to format formatted problem");
+        assert_eq!(formatted_again, expected);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constraint_source_spans() {
+        let input = "
+        max x_0
+        s.t.
+            x_0 + x_1 <= 10
+            x_i <= c[i] for i in 0..2
+        where
+            let c = [3, 4]
+        define
+            x_i as NonNegativeReal for i in 0..2
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model.constraints();
+        assert_eq!(constraints.len(), 3);
+        assert_eq!(constraints[0].span().map(|s| s.start_line), Some(4));
+        assert_eq!(constraints[0].source_text(input), Some("x_0 + x_1 <= 10"));
+        for generated in &constraints[1..] {
+            assert_eq!(generated.span().map(|s| s.start_line), Some(5));
+            assert_eq!(
+                generated.source_text(input),
+                Some("x_i <= c[i] for i in 0..2")
+            );
+        }
+        assert_eq!(constraints[2].to_string(), "x_1 <= 4");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        let sources = linear
+            .constraints()
+            .iter()
+            .map(|c| c.source_text(input))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sources,
+            vec![
+                Some("x_0 + x_1 <= 10"),
+                Some("x_i <= c[i] for i in 0..2"),
+                Some("x_i <= c[i] for i in 0..2")
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_contains_function() {
+        let input = "
+        max x
+        s.t.
+            x <= if contains(A, 2) { 1 } else { 0 }
+            x <= if contains(A, 7) { 1 } else { 0 }
+            x <= if contains(names, \"b\") { 1 } else { 0 }
+            x <= if contains(names, \"z\") { 1 } else { 0 }
+            x <= if !contains(A, 3.0) { 1 } else { 0 }
+        where
+            let A = [1, 2, 3]
+            let names = [\"a\", \"b\"]
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x <= 1", "x <= 0", "x <= 1", "x <= 0", "x <= 0"]
+        );
+        let source = "
+        max x
+        s.t.
+            x <= if contains(A, \"a\") { 1 } else { 0 }
+        where
+            let A = [1, 2, 3]
+        define
+            x as Real
+        ";
+        RoocParser::new(source.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Should not allow strings in numeric arrays");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_maps() {
+        let input = "
+        max x
+        s.t.
+            x <= cost[\"a\"]
+            x <= sum(v in values(cost)) { v }
+            x >= len(keys(cost))
+            x <= sum(k in keys(cost)) { cost[k] }
+            y <= nested[\"b\"][1]
+        where
+            let cost = { \"a\": 1.5, \"b\": 2.5 }
+            let nested = {
+                \"a\": [1, 2],
+                \"b\": [3, 4]
+            }
+            let empty = {}
+        define
+            x, y as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "x <= 1.5",
+                "x <= 1.5 + 2.5",
+                "x >= 2",
+                "x <= 1.5 + 2.5",
+                "y <= 4"
+            ]
+        );
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("let cost = { \"a\": 1.5, \"b\": 2.5 }"));
+        assert!(formatted.contains("let empty = {}"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_maps_errors() {
+        let duplicate_key = "
+        max x
+        s.t.
+            x <= cost[\"a\"]
+        where
+            let cost = { \"a\": 1, \"a\": 2 }
+        define
+            x as Real
+        ";
+        RoocParser::new(duplicate_key.to_string())
+            .parse()
+            .expect_err("Should not allow duplicate keys");
+        let missing_key = "
+        max x
+        s.t.
+            x <= cost[\"c\"]
+        where
+            let cost = { \"a\": 1, \"b\": 2 }
+        define
+            x as Real
+        ";
+        RoocParser::new(missing_key.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Should not allow missing keys");
+        let numeric_key = "
+        max x
+        s.t.
+            x <= cost[0]
+        where
+            let cost = { \"a\": 1, \"b\": 2 }
+        define
+            x as Real
+        ";
+        RoocParser::new(numeric_key.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Should not allow indexing maps with numbers");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_node_weights() {
+        let input = "
+        min 1
+        s.t.
+            x_n <= node_weight(G, n) for n in nodes(G)
+            sum(n in nodes(G)) { x_n } <= node_weight(G, \"C\")
+        where
+            let G = Graph {
+                A: w=5 -> [B:2, C],
+                B: w=2.5,
+                C: w=-1 -> [A]
+            }
+        define
+            x_n as Real for n in nodes(G)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "x_A <= 5",
+                "x_B <= 2.5",
+                "x_C <= -1",
+                "x_A + x_B + x_C <= -1"
+            ]
+        );
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("A: w=5 -> [ B:2, C ]"));
+        assert!(formatted.contains("B: w=2.5,"));
+        let missing_weight = "
+        min 1
+        s.t.
+            x <= node_weight(G, \"B\")
+        where
+            let G = Graph {
+                A: w=5 -> [B],
+                B
+            }
+        define
+            x as Real
+        ";
+        RoocParser::new(missing_weight.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect node without weight");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_edge_accessors() {
+        let input = "
+        min sum(e in edges(G)) { cost(e) * x_{source(e)}_{target(e)} }
+        s.t.
+            x_{source(e)}_{target(e)} <= cost(e) for e in edges(G)
+        where
+            let G = Graph {
+                A -> [B:2, C:3.5],
+                B -> [C:1]
+            }
+            let cost = 10
+        define
+            x_{source(e)}_{target(e)} as Real for e in edges(G)
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        assert_eq!(
+            model.objective().to_string(),
+            "min 2 * x_A_B + 3.5 * x_A_C + 1 * x_B_C"
+        );
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x_A_B <= 2", "x_A_C <= 3.5", "x_B_C <= 1"]
+        );
+        let missing_cost = "
+        min 1
+        s.t.
+            x <= sum(e in edges(G)) { cost(e) }
+        where
+            let G = Graph {
+                A -> [B:2, C]
+            }
+        define
+            x as Real
+        ";
+        RoocParser::new(missing_cost.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect edge without cost");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_domain_variable_in_constant_context() {
+        let input = "
+        max x
+        s.t.
+            y <= A[x]
+        where
+            let A = [1, 2, 3]
+        define
+            x, y as IntegerRange(0, 2)
+        ";
+        let parsed = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let err = transform_parsed_problem(parsed, vec![], &IndexMap::new())
+            .expect_err("Should not allow domain variables as indexes");
+        match err.base_error() {
+            TransformError::DomainVariableInConstantContext(name) => assert_eq!(name, "x"),
+            e => panic!("Unexpected error: {}", e),
+        }
+        assert!(err.origin_span().is_some());
+    }
+
+    #[derive(Debug)]
+    struct Double {}
+
+    impl rooc::RoocFunction for Double {
+        fn call(
+            &self,
+            args: &[rooc::PreExp],
+            context: &rooc::model_transformer::TransformerContext,
+            fn_context: &FunctionContext,
+        ) -> Result<rooc::Primitive, TransformError> {
+            let value = args.first().unwrap().as_number_cast(context, fn_context)?;
+            Ok(rooc::Primitive::Number(value * 2.0))
+        }
+
+        fn type_signature(
+            &self,
+            _args: &[rooc::PreExp],
+            _context: &TypeCheckerContext,
+            _fn_context: &FunctionContext,
+        ) -> Vec<(String, rooc::PrimitiveKind)> {
+            vec![("x".to_string(), rooc::PrimitiveKind::Number)]
+        }
+
+        fn return_type(
+            &self,
+            _args: &[rooc::PreExp],
+            _context: &TypeCheckerContext,
+            _fn_context: &FunctionContext,
+        ) -> rooc::PrimitiveKind {
+            rooc::PrimitiveKind::Number
+        }
+
+        fn function_name(&self) -> String {
+            "double".to_string()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_registered_functions() {
+        let input = "
+    min double(3) * x + y
+    s.t.
+        x + double(c) * y >= 1
+    where
+        let c = 1.5
+    define
+        x, y as Real
+    ";
+        let parser = RoocParser::new(input.to_string())
+            .with_function("double", Double {})
+            .unwrap();
+        parser.type_check(&[], &IndexMap::new()).unwrap();
+        let parsed = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "min 6 * x + y\ns.t.\n    x + 3 * y >= 1\ndefine\n    x, y as Real"
+        );
+        let collision = RoocParser::new(input.to_string()).with_function("len", Double {});
+        assert!(matches!(
+            collision,
+            Err(TransformError::AlreadyDefined { name, .. }) if name == "len"
+        ));
+        let registry = rooc::FunctionRegistry::new()
+            .with_function("double", Double {})
+            .unwrap();
+        assert!(registry.clone().with_function("double", Double {}).is_err());
+        //functions that are not registered are still unknown
+        assert!(RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .is_err());
+        let with_registry = RoocParser::new(input.to_string()).with_functions(registry);
+        //the parser can be shared between threads together with its functions
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        assert_send_sync(&with_registry);
+        assert!(with_registry
+            .parse_and_transform(vec![], &IndexMap::new())
+            .is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_list_variables() {
+        let input = "
+    max sum(i in 0..3) { x_i } + y
+    s.t.
+        x_i <= 1 for i in 0..3
+        y <= 2
+    define
+        x_i as Boolean for i in 0..3
+        y as Real
+    ";
+        let parser = RoocParser::new(input.to_string());
+        let pre_model = parser.parse().unwrap();
+        let declared = pre_model
+            .declared_variables()
+            .into_iter()
+            .map(|(v, t)| format!("{} as {}", v, t))
+            .collect::<Vec<_>>();
+        assert_eq!(declared, vec!["x_i as Boolean", "y as Real"]);
+        let model = pre_model.transform(vec![], &IndexMap::new()).unwrap();
+        let variables = model
+            .variables()
+            .map(|(name, v)| format!("{} as {}", name, v.get_type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            variables,
+            vec![
+                "x_0 as Boolean",
+                "x_1 as Boolean",
+                "x_2 as Boolean",
+                "y as Real"
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_cyclic_constants() {
+        let input = "
+    min x
+    s.t.
+        x >= A
+    where
+        let A = B + 1
+        let B = A + 1
+    define
+        x as Real
+    ";
+        let parsed = RoocParser::new(input.to_string()).parse().unwrap();
+        let err = transform_parsed_problem(parsed, vec![], &IndexMap::new()).unwrap_err();
+        assert!(matches!(
+            err.base_error(),
+            TransformError::Other(message) if message == "cyclic constant definition: A -> B -> A"
+        ));
+        let err = RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .unwrap_err();
+        assert!(err.contains("cyclic constant definition: A -> B -> A"));
+        let input = "
+    min x
+    s.t.
+        x >= A
+    where
+        let A = A + 1
+    define
+        x as Real
+    ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap_err();
+        assert!(err.contains("cyclic constant definition: A -> A"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_dependency_chain() {
+        let input = "
+    min x
+    s.t.
+        x >= A
+        x <= sum(i in C) { i }
+    where
+        let D = 3
+        let B = D * 2
+        let A = B + 1
+        let C = range(D, B, false)
+    define
+        x as Real
+    ";
+        let parser = RoocParser::new(input.to_string());
+        parser.type_check(&[], &IndexMap::new()).unwrap();
+        let parsed = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "min x\ns.t.\n    x >= 7\n    x <= 3 + 4 + 5\ndefine\n    x as Real"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constants_from_expressions() {
+        let input = "
+    min x
+    s.t.
+        x >= C
+        x <= sum((v, i) in D) { v * i }
+    where
+        let C = 10 + 50
+        let D = enumerate([1, 2, 3])
+        let F = C / len(D)
+    define
+        x as Real
+    ";
+        let parser = RoocParser::new(input.to_string());
+        parser.type_check(&[], &IndexMap::new()).unwrap();
+        let parsed = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "min x\ns.t.\n    x >= 60\n    x <= 1 * 0 + 2 * 1 + 3 * 2\ndefine\n    x as Real"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_forward_reference() {
+        let input = "
+    min x
+    s.t.
+        x >= A
+    where
+        let A = B + 1
+        let B = 2
+    define
+        x as Real
+    ";
+        let parsed = RoocParser::new(input.to_string()).parse().unwrap();
+        let err = transform_parsed_problem(parsed, vec![], &IndexMap::new()).unwrap_err();
+        assert!(matches!(
+            err.base_error(),
+            TransformError::Other(message) if message == "constant \"A\" uses \"B\" before it is defined"
+        ));
+        let err = RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .unwrap_err();
+        assert!(err.contains("constant \"A\" uses \"B\" before it is defined"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_lint_unused_constants() {
+        let input = "
+    max sum(i in 0..N) { C[i] * x_i }
+    s.t.
+        sum(i in 0..N) { x_i } <= limit
+    where
+        let C = [1, 2, 3]
+        let N = len(C)
+        let L = 1
+        let limit = L * 2
+        let unused = 10
+    define
+        x_i as Boolean for i in 0..N
+    ";
+        let warnings = RoocParser::new(input.to_string()).lint();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            &warnings[0],
+            rooc::lint::Warning::UnusedConstant(name) if name.value() == "unused"
+        ));
+        assert_eq!(warnings[0].span().start_line, 10);
+        assert_eq!(
+            warnings[0].to_string(),
+            "[UnusedConstant] Constant \"unused\" is never used"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_constraints() {
+        let input = "
+        min x
+        s.t.
+            x >= 1
+            sum(i in A) { i } <= 10
+        where
+            let A = [1, 2, 3]
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["x >= 1"]);
+        let warnings = model.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            &warnings[0],
+            rooc::lint::Warning::AlwaysSatisfiedConstraint(c) if c.value() == "1 + 2 + 3 <= 10"
+        ));
+        assert_eq!(
+            warnings[0].span().span_text(input).unwrap(),
+            "sum(i in A) { i } <= 10"
+        );
+        let input = "
+        min x
+        s.t.
+            x >= 1
+            5 <= 3
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Contradictory constraint should fail");
+        assert!(err.contains("constraint \"5 <= 3\" is always violated"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_scoped_and_unscoped_min_max_avg() {
+        let input = "
+        max x
+        s.t.
+            x <= avg(row in M, el in row) { el }
+            x <= min(el in M[0]) { el }
+            x >= max { M[1] }
+            y >= min { x, M[0], 10 }
+            y <= avg { 1, 2, 3 }
+        where
+            let M = [[1, 2], [3, 4]]
+        define
+            x, y as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                "x <= (1 + 2 + 3 + 4) / 4",
+                "x <= min{ 1, 2 }",
+                "x >= max{ 3, 4 }",
+                "y >= min{ x, 1, 2, 10 }",
+                "y <= (1 + 2 + 3) / 3"
+            ]
+        );
+        let input = "
+        min x
+        s.t.
+            x >= min { A }
+        where
+            let A = [] as Number[]
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected min of an empty iterable to fail");
+        let input = "
+        min x
+        s.t.
+            x >= min { S }
+        where
+            let S = [\"a\", \"b\"]
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected min of strings to fail");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_bracketed_scoped_blocks() {
+        let bracketed = "
+        min sum[i in 0..3] { x_i }
+        s.t.
+            prod[
+                i in A,
+                j in A
+            ] { i * j } * x_0 <= max { 1, 2 }
+            avg[row in M, el in row] { el } * x_1 >= min[i in A] { i }
+        where
+            let A = [1, 2]
+            let M = [[1, 2], [3, 4]]
+        define
+            x_i as Real for i in 0..3
+        ";
+        let parenthesized = "
+        min sum(i in 0..3) { x_i }
+        s.t.
+            prod(
+                i in A,
+                j in A
+            ) { i * j } * x_0 <= max { 1, 2 }
+            avg(row in M, el in row) { el } * x_1 >= min(i in A) { i }
+        where
+            let A = [1, 2]
+            let M = [[1, 2], [3, 4]]
+        define
+            x_i as Real for i in 0..3
+        ";
+        let expected = "min sum[i in 0..3] { x_i }
+s.t.
+    prod[i in A, j in A] { i * j } * x_0 <= max { 1, 2 }
+    avg[row in M, el in row] { el } * x_1 >= min[i in A] { i }
+where
+    let A = [1, 2]
+    let M = [
+        [1, 2],
+        [3, 4]
+    ]
+define
+    x_i as Real for i in 0..3
+";
+        for input in [bracketed, parenthesized] {
+            let formatted = RoocParser::new(input.to_string())
+                .format()
+                .expect("Failed to format problem");
+            assert_eq!(formatted, expected);
+            RoocParser::new(input.to_string())
+                .type_check(&[], &IndexMap::new())
+                .expect("Failed to type check problem");
+        }
+        let bracketed = RoocParser::new(bracketed.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let parenthesized = RoocParser::new(parenthesized.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(bracketed.to_string(), parenthesized.to_string());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_optional_let_keyword() {
+        let with_let = "
+        min x
+        s.t.
+            x >= letter + footprint[0]
+        where
+            let letter = 1
+            let footprint = [2, 3] as Number[]
+        define
+            x as Real
+        ";
+        let without_let = "
+        min x
+        s.t.
+            x >= letter + footprint[0]
+        where
+            letter = 1
+            footprint = [2, 3] as Number[]
+        define
+            x as Real
+        ";
+        let with_let = RoocParser::new(with_let.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let without_let = RoocParser::new(without_let.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let constants = |model: &rooc::pre_model::PreModel| {
+            model
+                .constants()
+                .iter()
+                .map(|c| (c.name.value().clone(), c.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            constants(&with_let),
+            vec![
+                ("letter".to_string(), "let letter = 1".to_string()),
+                (
+                    "footprint".to_string(),
+                    "let footprint = [2, 3] as Number[]".to_string()
+                )
+            ]
+        );
+        assert_eq!(constants(&with_let), constants(&without_let));
+        assert_eq!(with_let.to_string(), without_let.to_string());
+        let model = RoocParser::new(without_let.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(model.constraints()[0].to_string(), "x >= 1 + 2");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_array_comprehension() {
+        let input = "
+        min 1
+        s.t.
+            sum(i in 0..len(C)) { C[i] * x_i } <= 10
+        where
+            let C = [i * 2 for i in 0..5]
+        define
+            x_i as Boolean for i in 0..5
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.constraints()[0].to_string(),
+            "0 * x_0 + 2 * x_1 + 4 * x_2 + 6 * x_3 + 8 * x_4 <= 10"
+        );
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("let C = [i * 2 for i in 0..5]"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_guarded_array_comprehension() {
+        let input = "
+        min 1
+        s.t.
+            sum(p in P) { x_p } <= len(P)
+            sum(k in [v * w for v in A, w in [1, 10] where v != w]) { k } * y >= 0
+        where
+            let A = [0, 1, 2, 3]
+            let P = [i for i in A where i > 1]
+        define
+            x_p as Boolean for p in P
+            y as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x_2 + x_3 <= 2", "(0 + 0 + 10 + 2 + 20 + 3 + 30) * y >= 0"]
+        );
+        let formatted = RoocParser::new(input.to_string())
+            .format()
+            .expect("Failed to format problem");
+        assert!(formatted.contains("let P = [i for i in A where (i > 1)]"));
+        RoocParser::new(formatted)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform formatted problem");
+        let input = "
+        min 1
+        s.t.
+            x >= 0
+        where
+            let P = [i for i in 0..3 where i + 1]
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected a non boolean guard to fail");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_flatten() {
+        let input = "
+        min 1
+        s.t.
+            sum(v in flatten(M)) { v * y } <= 30
+            len(flatten(M)) * y <= 10
+        where
+            let M = [
+                [1, 2, 3],
+                [4, 5, 6]
+            ]
+        define
+            y as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints[0],
+            "1 * y + 2 * y + 3 * y + 4 * y + 5 * y + 6 * y <= 30"
+        );
+        assert_eq!(constraints[1], "6 * y <= 10");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_flatten_wrong_type() {
+        let input = "
+        min 1
+        s.t.
+            sum(v in flatten(A)) { v * y } <= 30
+        where
+            let A = [1, 2, 3]
+        define
+            y as NonNegativeReal
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Failed to detect flatten of a non nested iterable");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_linear_model_terms() {
+        let input = "
+        min x_1 + x_2 + x_3
+        s.t.
+            2x_1 - 3x_2 <= 5
+            x_3 + x_1 - x_1 >= 1
+        define
+            x_1, x_2, x_3 as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        let terms = linear.terms();
+        assert_eq!(
+            terms[0],
+            SparseConstraint {
+                name: "c0".to_string(),
+                terms: vec![("x_1".to_string(), 2.0), ("x_2".to_string(), -3.0)],
+                constraint_type: Comparison::LessOrEqual,
+                rhs: 5.0,
+            }
+        );
+        assert_eq!(terms[1].terms, vec![("x_3".to_string(), 1.0)]);
+        assert_eq!(terms[1].constraint_type, Comparison::GreaterOrEqual);
+        assert_eq!(terms[1].rhs, 1.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_parse_and_type_check_diagnostics() {
+        use rooc::diagnostics::Severity;
+        let input = "
+    min x
+    s.t.
+        x >= len(total)
+    where
+        let total = 3
+        let unused = 10
+    define
+        x as NonNegativeReal
+    ";
+        let diagnostics =
+            RoocParser::new(input.to_string()).parse_and_type_check(&[], &IndexMap::new());
+        assert!(diagnostics.has_errors());
+        let errors = diagnostics.errors().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity(), Severity::Error);
+        assert!(
+            errors[0].message().starts_with("[WrongArgument]"),
+            "{}",
+            errors[0].message()
+        );
+        assert_eq!(errors[0].span().unwrap().start_line, 4);
+        let warnings = diagnostics.warnings().collect::<Vec<_>>();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message(),
+            "[UnusedConstant] Constant \"unused\" is never used"
+        );
+        assert_eq!(warnings[0].span().unwrap().start_line, 7);
+        let valid = RoocParser::new(
+            "min x\ns.t.\n    x >= 1\ndefine\n    x as NonNegativeReal".to_string(),
+        )
+        .parse_and_type_check(&[], &IndexMap::new());
+        assert!(valid.is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_prod_simplification() {
+        let input = "
+        min prod(i in 0..0) { x_i } * y
+        s.t.
+            prod(a in A) { a } * y <= 12
+            prod(a in A) { a * y } >= 1
+        where
+            let A = [2, 3]
+        define
+            x_i as Real for i in 0..0
+            y as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(model.objective().rhs.simplify().to_string(), "y");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| {
+                let (lhs, _, rhs) = c.clone().into_parts();
+                (lhs.simplify().to_string(), rhs.simplify().to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(constraints[0], ("6 * y".to_string(), "12".to_string()));
+        assert_eq!(constraints[1], ("6 * y * y".to_string(), "1".to_string()));
+        let mixed = Exp::BinOp(
+            BinOp::Mul,
+            Exp::BinOp(
+                BinOp::Mul,
+                Exp::Variable("x".to_string()).to_box(),
+                Exp::Number(2.0).to_box(),
+            )
+            .to_box(),
+            Exp::BinOp(
+                BinOp::Mul,
+                Exp::Number(0.5).to_box(),
+                Exp::Variable("y".to_string()).to_box(),
+            )
+            .to_box(),
+        );
+        assert_eq!(mixed.simplify().to_string(), "x * y");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_division_by_zero_constant() {
+        let input = "
+        min x
+        s.t.
+            (x + y) / (size - 2) <= 1
+        where
+            let size = 2
+        define
+            x, y as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap_err();
+        assert!(
+            err.contains("Division by zero in operator \"/\""),
+            "{}",
+            err
+        );
+        assert!(err.contains("4:"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_conversion_functions() {
+        let input = "
+        min 1
+        s.t.
+            to_number(\"3.5\") * x <= sum(v in values) { to_number(v) }
+            to_integer(3.9) * x >= to_integer(\"-2.7\") + to_integer(\" 12 \")
+            x_{to_string(42)} <= to_integer(false)
+        where
+            let values = split(\"1.5,2\", \",\")
+        define
+            x as Real
+            x_42 as Real
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["3.5 * x <= 1.5 + 2", "3 * x >= -2 + 12", "x_42 <= 0"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_conversion_functions_errors() {
+        let input = "
+        min 1
+        s.t.
+            x <= to_number(\"abc\")
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected a non numeric string to fail");
+        assert!(err.contains("Cannot convert \"abc\" to Number"), "{}", err);
+        let input = "
+        min 1
+        s.t.
+            x <= to_integer(\"1e40\")
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected an out of range integer to fail");
+        assert!(err.contains("to Integer"), "{}", err);
+        let input = "
+        min 1
+        s.t.
+            x <= to_number(A)
+        where
+            let A = [1, 2]
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect_err("Expected an array to fail type checking");
+        assert!(err.contains("expected one of"), "{}", err);
+        let input = "
+        min 1
+        s.t.
+            x <= to_number(\"inf\")
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Expected an infinite number to fail");
+        assert!(err.contains("Cannot convert \"inf\" to Number"), "{}", err);
+    }
 }
@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod primitive_tests {
     use indexmap::IndexMap;
-    use rooc::RoocParser;
+    use rooc::type_checker::type_checker_context::{
+        FunctionContext, TypeCheckable, TypeCheckerContext, WithType,
+    };
+    use rooc::{
+        ApplyOp, BinOp, Graph, GraphEdge, GraphNode, Primitive, PrimitiveKind, RoocParser, UnOp,
+    };
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -12,8 +17,8 @@ mod primitive_tests {
         min 1
         s.t.
             sum((u,v,c) in edges(G)){ (x_u + x_v)*c } <= 1
-            sum((first, second) in A){ first + second } <= 1
-            sum((el, j) in enumerate(A[i])){ el * j } <= 1 for i in 0..len(A)
+            sum((first, second) in A){ first + second } >= 1
+            sum((el, j) in enumerate(A[i])){ el * j } >= 1 for i in 0..len(A)
             where 
                 let G = Graph {
                     A -> [B],
@@ -30,4 +35,377 @@ mod primitive_tests {
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse");
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zip_spreading() {
+        let source = "
+        min 1
+        s.t.
+            sum((c, w) in zip(C, W)){ c * w * x } <= 10
+            where 
+                let C = [1, 2, 3]
+                let W = [4, 5, 6]
+            define
+                x as Boolean
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse");
+        assert_eq!(
+            model.constraints()[0].to_string(),
+            "1 * 4 * x + 2 * 5 * x + 3 * 6 * x <= 10"
+        );
+        RoocParser::new(source.to_string())
+            .type_check(&[], &IndexMap::new())
+            .expect("Failed to type check");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_zip_length_mismatch() {
+        let source = "
+        min 1
+        s.t.
+            sum((c, w) in zip(C, W)){ c * w * x } <= 10
+            where 
+                let C = [1, 2, 3]
+                let W = [4, 5]
+            define
+                x as Boolean
+        ";
+        RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect zip of different lengths");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_graph_to_dot() {
+        let graph = Graph::new(vec![
+            GraphNode::new(
+                "A".to_string(),
+                vec![GraphEdge::new("A".to_string(), "B".to_string(), Some(2.5))],
+            ),
+            GraphNode::new("B".to_string(), vec![]),
+        ]);
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    \"A\";\n    \"B\";\n    \"A\" -> \"B\" [label=\"2.5\"];\n}"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_rational_arithmetic() {
+        let third = Primitive::Rational(1, 3);
+        let sum = third
+            .apply_binary_op(BinOp::Add, &third)
+            .and_then(|s| s.apply_binary_op(BinOp::Add, &third))
+            .expect("Failed to add rationals");
+        assert_eq!(sum, Primitive::Rational(1, 1));
+        let half = Primitive::Rational(1, 2)
+            .apply_binary_op(BinOp::Mul, &Primitive::Integer(-3))
+            .expect("Failed to multiply rational");
+        assert_eq!(half, Primitive::Rational(-3, 2));
+        assert_eq!(half.as_number_cast().unwrap(), -1.5);
+        let mixed = Primitive::Rational(1, 4)
+            .apply_binary_op(BinOp::Add, &Primitive::Number(0.5))
+            .expect("Failed to add rational and number");
+        assert_eq!(mixed, Primitive::Number(0.75));
+        assert!(third
+            .apply_binary_op(BinOp::Div, &Primitive::Integer(0))
+            .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_modulo_is_non_negative() {
+        //the remainder is euclidean, so it is never negative, whatever the signs of the operands
+        let cases = [
+            (
+                Primitive::Integer(7),
+                Primitive::Integer(3),
+                Primitive::Integer(1),
+            ),
+            (
+                Primitive::Integer(-7),
+                Primitive::Integer(3),
+                Primitive::Integer(2),
+            ),
+            (
+                Primitive::Integer(7),
+                Primitive::Integer(-3),
+                Primitive::Integer(1),
+            ),
+            (
+                Primitive::Integer(-7),
+                Primitive::Integer(-3),
+                Primitive::Integer(2),
+            ),
+            (
+                Primitive::Number(7.5),
+                Primitive::Number(-2.0),
+                Primitive::Number(1.5),
+            ),
+            (
+                Primitive::Number(-7.5),
+                Primitive::Integer(2),
+                Primitive::Number(0.5),
+            ),
+        ];
+        for (lhs, rhs, expected) in cases {
+            let result = lhs
+                .apply_binary_op(BinOp::Mod, &rhs)
+                .expect("Failed to apply modulo");
+            assert_eq!(result, expected, "{} % {}", lhs, rhs);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_integer_overflow() {
+        let overflows = [
+            (
+                Primitive::Integer(i64::MAX),
+                BinOp::Add,
+                Primitive::Integer(1),
+            ),
+            (
+                Primitive::Integer(i64::MIN),
+                BinOp::Sub,
+                Primitive::Integer(1),
+            ),
+            (
+                Primitive::Integer(i64::MAX),
+                BinOp::Mul,
+                Primitive::Integer(2),
+            ),
+            (
+                Primitive::Integer(1),
+                BinOp::Add,
+                Primitive::PositiveInteger(u64::MAX),
+            ),
+            (
+                Primitive::PositiveInteger(u64::MAX),
+                BinOp::Add,
+                Primitive::PositiveInteger(1),
+            ),
+            (
+                Primitive::PositiveInteger(0),
+                BinOp::Sub,
+                Primitive::PositiveInteger(u64::MAX),
+            ),
+        ];
+        for (lhs, op, rhs) in overflows {
+            let err = lhs
+                .apply_binary_op(op, &rhs)
+                .expect_err("Failed to detect integer overflow");
+            assert!(err.to_string().contains("Integer overflow"));
+        }
+        for value in [
+            Primitive::Integer(i64::MIN),
+            Primitive::PositiveInteger(u64::MAX),
+        ] {
+            let err = value
+                .apply_unary_op(UnOp::Neg)
+                .expect_err("Failed to detect integer overflow");
+            assert!(err.to_string().contains("Integer overflow"));
+        }
+        assert_eq!(
+            Primitive::PositiveInteger(1 << 63)
+                .apply_unary_op(UnOp::Neg)
+                .unwrap(),
+            Primitive::Integer(i64::MIN)
+        );
+        //the remainder of i64::MIN and -1 is zero, even though the division overflows
+        assert_eq!(
+            Primitive::Integer(i64::MIN)
+                .apply_binary_op(BinOp::Mod, &Primitive::Integer(-1))
+                .unwrap(),
+            Primitive::Integer(0)
+        );
+        assert_eq!(
+            Primitive::Integer(-1)
+                .apply_binary_op(BinOp::Mod, &Primitive::PositiveInteger(u64::MAX))
+                .expect_err("Failed to detect integer overflow")
+                .to_string(),
+            format!(
+                "Integer overflow in operator \"%\" between -1 and {}",
+                u64::MAX
+            )
+        );
+        let err = Primitive::Integer(1)
+            .apply_binary_op(BinOp::Mod, &Primitive::Integer(0))
+            .expect_err("Failed to detect division by zero");
+        assert!(err.to_string().contains("Division by zero"));
+        assert_eq!(
+            Primitive::Integer(i64::MAX - 1)
+                .apply_binary_op(BinOp::Add, &Primitive::Integer(1))
+                .unwrap(),
+            Primitive::Integer(i64::MAX)
+        );
+        let source = "
+        min 1
+        s.t.
+            x <= A[n + 1]
+        where
+            let A = [1, 2, 3]
+            let n = 9223372036854775807
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect integer overflow");
+        assert!(err.to_string().contains("Integer overflow"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_numeric_promotion() {
+        let cases = [
+            (
+                Primitive::Integer(5),
+                BinOp::Add,
+                Primitive::Integer(2),
+                Primitive::Integer(7),
+            ),
+            (
+                Primitive::Integer(5),
+                BinOp::Div,
+                Primitive::Integer(2),
+                Primitive::Number(2.5),
+            ),
+            (
+                Primitive::Integer(5),
+                BinOp::Mul,
+                Primitive::Number(0.5),
+                Primitive::Number(2.5),
+            ),
+            (
+                Primitive::Number(0.5),
+                BinOp::Sub,
+                Primitive::Integer(2),
+                Primitive::Number(-1.5),
+            ),
+            (
+                Primitive::PositiveInteger(5),
+                BinOp::Add,
+                Primitive::PositiveInteger(2),
+                Primitive::PositiveInteger(7),
+            ),
+            (
+                Primitive::PositiveInteger(2),
+                BinOp::Sub,
+                Primitive::PositiveInteger(5),
+                Primitive::Integer(-3),
+            ),
+            (
+                Primitive::PositiveInteger(5),
+                BinOp::Div,
+                Primitive::PositiveInteger(2),
+                Primitive::Number(2.5),
+            ),
+            (
+                Primitive::Integer(-5),
+                BinOp::Mul,
+                Primitive::PositiveInteger(2),
+                Primitive::Integer(-10),
+            ),
+            (
+                Primitive::Integer(5),
+                BinOp::Mul,
+                Primitive::Boolean(false),
+                Primitive::Integer(0),
+            ),
+            (
+                Primitive::Integer(5),
+                BinOp::Div,
+                Primitive::Boolean(true),
+                Primitive::Number(5.0),
+            ),
+            (
+                Primitive::Integer(1),
+                BinOp::Add,
+                Primitive::Rational(1, 2),
+                Primitive::Rational(3, 2),
+            ),
+            (
+                Primitive::Rational(1, 2),
+                BinOp::Add,
+                Primitive::Number(0.25),
+                Primitive::Number(0.75),
+            ),
+            (
+                Primitive::Integer(5),
+                BinOp::Less,
+                Primitive::Number(2.5),
+                Primitive::Boolean(false),
+            ),
+        ];
+        for (lhs, op, rhs, expected) in cases {
+            let result = lhs
+                .apply_binary_op(op, &rhs)
+                .expect("Failed to apply operator");
+            assert_eq!(result, expected, "{} {} {}", lhs, op, rhs);
+            assert_eq!(
+                lhs.get_type().binary_op_result(op, &rhs.get_type()),
+                result.get_type(),
+                "{} {} {}",
+                lhs,
+                op,
+                rhs
+            );
+        }
+        assert_eq!(
+            PrimitiveKind::Any.binary_op_result(BinOp::Add, &PrimitiveKind::Integer),
+            PrimitiveKind::Any
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_literal_division_kind() {
+        let source = "
+        min x
+        s.t.
+            x >= a + b
+        where
+            let a = 5 / 2
+            let n = 5
+            let b = n / 2
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse()
+            .expect("Failed to parse");
+        let fns = IndexMap::new();
+        let fn_context = FunctionContext::new(&fns, &fns);
+        let mut context = TypeCheckerContext::default();
+        let kinds = model
+            .constants()
+            .iter()
+            .map(|constant| {
+                constant
+                    .type_check(&mut context, &fn_context)
+                    .expect("Failed to type check constant");
+                constant.get_type(&context, &fn_context)
+            })
+            .collect::<Vec<_>>();
+        //the division of two literals is folded into an exact rational, unlike the promoted n / 2
+        assert_eq!(
+            kinds,
+            vec![
+                PrimitiveKind::Rational,
+                PrimitiveKind::Integer,
+                PrimitiveKind::Number
+            ]
+        );
+        let transformed = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to transform");
+        assert_eq!(transformed.constraints()[0].to_string(), "x >= 2.5 + 2.5");
+    }
 }
@@ -7,15 +7,24 @@ pub mod solver_tests {
     use rooc::common::LpSolution;
     use rooc::linear_integer_binary_solver::IntOrBoolValue;
     use rooc::pipe::{
-        BinarySolverPipe, CompilerPipe, IntegerBinarySolverPipe, LinearModelPipe, MILPSolverPipe,
-        ModelPipe, PreModelPipe, RealSolver, StandardLinearModelPipe, TableauPipe,
+        BinarySolverPipe, CompilerPipe, IntegerBinarySolverPipe, LatexPipe, LinearModelPipe,
+        LpFormatPipe, MILPSolverPipe, ModelPipe, OptimalTableauPipe, PreModelPipe, PresolvePipe,
+        RealSolver, StandardLinearModelPipe, TableauPipe,
     };
     use rooc::pipe::{PipeContext, PipeRunner};
     use rooc::pipe::{PipeDataType, PipeError, PipeableData, StepByStepSimplexPipe};
     #[allow(unused_imports)]
     use rooc::simplex::{CanonicalTransformError, OptimalTableau, SimplexError};
-    use rooc::{float_eq, float_ne};
-    use rooc::{MILPValue, OptimalTableauWithSteps};
+    use rooc::{
+        find_irreducible_infeasible_subset, generate_gomory_cuts, presolve_bounds, rooc_pipe,
+        solve_all, solve_binary_lp_problem, solve_integer_binary_lp_problem, solve_milp_lp_problem,
+        solve_milp_lp_problem_with_options, solve_real_lp_problem_micro_lp,
+        solve_real_lp_problem_slow_simplex, solve_with, solve_with_warm_start, Comparison,
+        LinearConstraint, LinearModel, Linearizer, MILPOptions, MILPValue, OptimalTableauWithSteps,
+        OptimizationType, PivotRule, SimplexOptions, SolverError, SolverKind, StandardLinearModel,
+        VariableType,
+    };
+    use rooc::{float_eq, float_ne, set_display_precision, RoocParser};
 
     #[allow(unused)]
     #[allow(clippy::result_large_err)]
@@ -493,7 +502,11 @@ pub mod solver_tests {
         match solution {
             Ok(_) => panic!("Should not reach here"),
             Err(e) => match e {
-                PipeError::CanonicalizationError(CanonicalTransformError::Infesible(_)) => {}
+                PipeError::CanonicalizationError(CanonicalTransformError::InfeasibleSubset(
+                    constraints,
+                )) => {
+                    assert_eq!(constraints, vec!["c0", "c1", "c2"]);
+                }
                 _ => panic!("Should be infeasible"),
             },
         }
@@ -730,4 +743,1998 @@ define
             false,
         )
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reproduce_milp_search_with_seed() {
+        //stable set on a 5 cycle, the relaxation has every variable at 0.5
+        let mut model = LinearModel::new();
+        for i in 0..5 {
+            model.add_variable(&format!("x_{}", i), VariableType::Boolean);
+        }
+        for i in 0..5 {
+            let mut coeffs = vec![0.0; 5];
+            coeffs[i] = 1.0;
+            coeffs[(i + 1) % 5] = 1.0;
+            model.add_constraint(coeffs, Comparison::LessOrEqual, 1.0);
+        }
+        model.set_objective(vec![1.0; 5], OptimizationType::Max);
+        let options = MILPOptions::new().with_seed(42);
+        let (first, first_stats) = solve_milp_lp_problem_with_options(&model, &options).unwrap();
+        let (second, second_stats) = solve_milp_lp_problem_with_options(&model, &options).unwrap();
+        assert_precision(first.value(), 2.0);
+        assert_precision(second.value(), 2.0);
+        assert!(first_stats.nodes_explored > 1);
+        assert_eq!(first_stats, second_stats);
+        let first = first
+            .assignment_values()
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        let second = second
+            .assignment_values()
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_incumbent_earlier_with_rounding_heuristic() {
+        let mut model = LinearModel::new();
+        for i in 0..3 {
+            model.add_variable(&format!("x_{}", i), VariableType::Boolean);
+        }
+        model.add_constraint(vec![5.0, 4.0, 3.0], Comparison::LessOrEqual, 10.0);
+        model.set_objective(vec![10.0, 7.0, 5.0], OptimizationType::Max);
+        let (plain, plain_stats) =
+            solve_milp_lp_problem_with_options(&model, &MILPOptions::new()).unwrap();
+        let (rounded, rounded_stats) = solve_milp_lp_problem_with_options(
+            &model,
+            &MILPOptions::new().with_rounding_heuristic(true),
+        )
+        .unwrap();
+        assert_precision(plain.value(), 17.0);
+        assert_precision(rounded.value(), 17.0);
+        assert_eq!(rounded_stats.nodes_to_first_incumbent, Some(1));
+        assert!(
+            rounded_stats.nodes_to_first_incumbent.unwrap()
+                < plain_stats.nodes_to_first_incumbent.unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_irreducible_infeasible_subset() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 1.0);
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 10.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::GreaterOrEqual, 2.0);
+        model.add_constraint(vec![0.0, 1.0], Comparison::GreaterOrEqual, 1.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let iis = find_irreducible_infeasible_subset(&model).unwrap();
+        assert_eq!(iis, vec!["c0".to_string(), "c2".to_string()]);
+        let err = solve_real_lp_problem_micro_lp(&model)
+            .unwrap_err()
+            .with_infeasible_subset(&model);
+        assert_eq!(err.infeasible_subset(), Some(&iis));
+        let source = "
+        max x + y
+        s.t.
+            x <= 1
+            x + y <= 10
+            x >= 2
+            y >= 1
+        define
+            x, y as NonNegativeReal
+        ";
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(LinearModelPipe::new()),
+            Box::new(RealSolver::new()),
+        ]);
+        let fns = IndexMap::new();
+        let result = pipe_runner.run(
+            PipeableData::String(source.to_string()),
+            &PipeContext::new(vec![], &fns),
+        );
+        match result {
+            Err((PipeError::SolverError(e), _)) => assert_eq!(e.infeasible_subset(), Some(&iis)),
+            _ => panic!("Expected an infeasible subset error"),
+        }
+        //the simplex pipes find the infeasibility while building the tableau
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(LinearModelPipe::new()),
+            Box::new(StandardLinearModelPipe::new()),
+            Box::new(TableauPipe::new()),
+            Box::new(StepByStepSimplexPipe::new()),
+        ]);
+        let result = pipe_runner.run(
+            PipeableData::String(source.to_string()),
+            &PipeContext::new(vec![], &fns),
+        );
+        match result {
+            Err((PipeError::CanonicalizationError(e), _)) => {
+                assert_eq!(e.infeasible_subset(), Some(&iis))
+            }
+            _ => panic!("Expected an infeasible subset error"),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn solve_step_by_step_with_options(
+        source: &str,
+        options: SimplexOptions,
+    ) -> Result<OptimalTableauWithSteps, PipeError> {
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(LinearModelPipe::new()),
+            Box::new(StandardLinearModelPipe::new()),
+            Box::new(TableauPipe::new()),
+            Box::new(StepByStepSimplexPipe::new()),
+        ]);
+        let fns = IndexMap::new();
+        let result = pipe_runner.run(
+            PipeableData::String(source.to_string()),
+            &PipeContext::new(vec![], &fns).with_simplex_options(options),
+        );
+        match result {
+            Ok(data) => match data.last().unwrap() {
+                PipeableData::OptimalTableauWithSteps(data) => Ok(data.clone()),
+                last => Err(PipeError::InvalidData {
+                    expected: PipeDataType::OptimalTableauWithSteps,
+                    got: last.get_type(),
+                }),
+            },
+            Err((error, _context)) => Err(error),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_stop_cycling_simplex_with_iteration_limit() {
+        //Beale's degenerate problem, cycles when picking the most negative reduced cost
+        let source = "
+        min -0.75a + 20b - 0.5c + 6d
+        s.t.
+            0.25a - 8b - c + 9d <= 0
+            0.5a - 12b - 0.5c + 3d <= 0
+            c <= 1
+        define
+            a, b, c, d as NonNegativeReal
+        ";
+        let cycling = solve_step_by_step_with_options(
+            source,
+            SimplexOptions::new().with_max_iterations(Some(50)),
+        );
+        match cycling {
+            Err(PipeError::StepByStepSimplexError(SimplexError::IterationLimitReached, _)) => {}
+            Err(e) => panic!("Expected the iteration limit to be reached, got {}", e),
+            Ok(_) => panic!("Expected the iteration limit to be reached"),
+        }
+        let solution = solve_step_by_step_with_options(
+            source,
+            SimplexOptions::new()
+                .with_max_iterations(Some(50))
+                .with_pivot_rule(PivotRule::Bland),
+        )
+        .unwrap();
+        assert_precision(solution.result().optimal_value(), -1.25);
+        assert!(solution.steps().len() <= 50);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_converge_with_bland_rule_where_dantzig_cycles() {
+        //Beale's problem, the Dantzig rule returns to the starting basis after 6 degenerate pivots
+        let mut model = LinearModel::new();
+        for name in ["a", "b", "c", "d"] {
+            model.add_variable(name, VariableType::non_negative_real());
+        }
+        model.add_constraint(vec![0.25, -8.0, -1.0, 9.0], Comparison::LessOrEqual, 0.0);
+        model.add_constraint(vec![0.5, -12.0, -0.5, 3.0], Comparison::LessOrEqual, 0.0);
+        model.add_constraint(vec![0.0, 0.0, 1.0, 0.0], Comparison::LessOrEqual, 1.0);
+        model.set_objective(vec![-0.75, 20.0, -0.5, 6.0], OptimizationType::Min);
+        let tableau = model.into_standard_form().unwrap().into_tableau().unwrap();
+
+        let mut dantzig = tableau.clone();
+        let initial_basis = dantzig.in_basis().clone();
+        for _ in 0..6 {
+            dantzig.step_with_rule(&[], PivotRule::Dantzig).unwrap();
+        }
+        assert_eq!(dantzig.in_basis(), &initial_basis);
+        assert!(dantzig
+            .solve_with_options(&SimplexOptions::new().with_max_iterations(Some(100)))
+            .is_err());
+
+        let mut bland = tableau.clone();
+        let optimal = bland
+            .solve_with_options(
+                &SimplexOptions::new()
+                    .with_max_iterations(Some(100))
+                    .with_pivot_rule(PivotRule::Bland),
+            )
+            .unwrap();
+        assert_precision(optimal.optimal_value(), -1.25);
+    }
+
+    fn solve_optimal_tableau(source: &str) -> OptimalTableau {
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(LinearModelPipe::new()),
+            Box::new(StandardLinearModelPipe::new()),
+            Box::new(TableauPipe::new()),
+            Box::new(OptimalTableauPipe::new()),
+        ]);
+        let fns = IndexMap::new();
+        let result = pipe_runner
+            .run(
+                PipeableData::String(source.to_string()),
+                &PipeContext::new(vec![], &fns),
+            )
+            .unwrap();
+        result.last().unwrap().as_optimal_tableau().unwrap().clone()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_dual_values_and_reduced_costs() {
+        let source = "
+        max 3x + 5y
+        s.t.
+            x <= 4
+            2y <= 12
+            3x + 2y <= 18
+        define
+            x, y as NonNegativeReal
+        ";
+        let optimal = solve_optimal_tableau(source);
+        assert_precision(optimal.optimal_value(), 36.0);
+        let duals = optimal.dual_values();
+        assert_eq!(duals.len(), 3);
+        for (name, expected) in [("c0", 0.0), ("c1", 1.5), ("c2", 1.0)] {
+            assert_precision(duals[name], expected);
+        }
+        //only the variables of the model have a reduced cost, not the slacks
+        let reduced_costs = optimal.reduced_costs();
+        assert_eq!(reduced_costs.keys().collect::<Vec<_>>(), vec!["x", "y"]);
+        assert_precision(reduced_costs["x"], 0.0);
+        assert_precision(reduced_costs["y"], 0.0);
+        let display = optimal.to_string();
+        assert!(display.contains("Dual values:\n    c0: 0\n    c1: 1.5\n    c2: 1"));
+        assert!(display.contains("Reduced costs:\n    x: 0\n    y: 0"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_name_dual_values_after_the_model() {
+        //the bound of z adds a row that is not a constraint of the model
+        let source = "
+        max 3x + 5y - z
+        s.t.
+            wood: x <= 4
+            2y <= 12
+            labor: 3x + 2y + w <= 18
+        define
+            x, y as NonNegativeReal
+            w as Real
+            z as Real(1, 2)
+        ";
+        let optimal = solve_optimal_tableau(source);
+        let duals = optimal.dual_values();
+        assert_eq!(
+            duals.keys().collect::<Vec<_>>(),
+            vec!["wood", "c1", "labor"]
+        );
+        let mut variables = optimal.reduced_costs().into_keys().collect::<Vec<_>>();
+        variables.sort();
+        assert_eq!(variables, vec!["w", "x", "y", "z"]);
+        let analysis = optimal.sensitivity_analysis();
+        assert_eq!(
+            analysis
+                .rhs
+                .iter()
+                .map(|r| r.constraint.as_str())
+                .collect::<Vec<_>>(),
+            vec!["wood", "c1", "labor"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_dual_values_of_negative_right_hand_sides() {
+        //both rows are negated by the standardizer to make their right hand side non negative
+        let source = "
+        min x + y
+        s.t.
+            c: -x <= -2
+            d: y >= -3
+        define
+            x as NonNegativeReal
+            y as Real
+        ";
+        let optimal = solve_optimal_tableau(source);
+        assert_precision(optimal.optimal_value(), -1.0);
+        let duals = optimal.dual_values();
+        //raising the right hand side of c to -1 lowers the optimum to -2
+        assert_precision(duals["c"], -1.0);
+        //raising the right hand side of d to -2 raises the optimum to 0
+        assert_precision(duals["d"], 1.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_compute_sensitivity_ranges() {
+        let source = "
+        min 2x + 3y
+        s.t.
+            x + y >= 4
+            x + 3y >= 6
+        define
+            x, y as NonNegativeReal
+        ";
+        let optimal = solve_optimal_tableau(source);
+        assert_precision(optimal.optimal_value(), 9.0);
+        let analysis = optimal.sensitivity_analysis();
+        //the basis (x = 3, y = 1) stays optimal for 2 <= b0 <= 6 and 4 <= b1 <= 12
+        let expected_rhs = [("c0", 4.0, 2.0, 2.0), ("c1", 6.0, 6.0, 2.0)];
+        assert_eq!(analysis.rhs.len(), expected_rhs.len());
+        for (range, (name, value, increase, decrease)) in analysis.rhs.iter().zip(expected_rhs) {
+            assert_eq!(range.constraint, name);
+            assert_precision(range.value, value);
+            assert_precision(range.allowable_increase, increase);
+            assert_precision(range.allowable_decrease, decrease);
+        }
+        //the slope of the objective must stay between the slopes of the two constraints
+        let expected_objective = [("x", 2.0, 1.0, 1.0), ("y", 3.0, 3.0, 1.0)];
+        for (name, value, increase, decrease) in expected_objective {
+            let range = analysis
+                .objective
+                .iter()
+                .find(|r| r.variable == name)
+                .unwrap();
+            assert_precision(range.value, value);
+            assert_precision(range.allowable_increase, increase);
+            assert_precision(range.allowable_decrease, decrease);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_compute_sensitivity_ranges_of_negated_rows_and_free_variables() {
+        let source = "
+        min x + y
+        s.t.
+            c: -x <= -2
+            d: y >= -3
+        define
+            x as NonNegativeReal
+            y as Real
+        ";
+        let optimal = solve_optimal_tableau(source);
+        let analysis = optimal.sensitivity_analysis();
+        //the infinite allowances are compared exactly
+        let assert_limit = |a: f64, b: f64| a == b || assert_precision(a, b);
+        //c stays binding until x reaches its bound at 0, d until y turns positive
+        let expected_rhs = [
+            ("c", -2.0, 2.0, f64::INFINITY),
+            ("d", -3.0, 3.0, f64::INFINITY),
+        ];
+        assert_eq!(analysis.rhs.len(), expected_rhs.len());
+        for (range, (name, value, increase, decrease)) in analysis.rhs.iter().zip(expected_rhs) {
+            assert_eq!(range.constraint, name);
+            assert_precision(range.value, value);
+            assert_limit(range.allowable_increase, increase);
+            assert_limit(range.allowable_decrease, decrease);
+        }
+        //only the variables of the model are ranged, the free y instead of its two parts
+        let expected_objective = [
+            ("x", 1.0, f64::INFINITY, 1.0),
+            ("y", 1.0, f64::INFINITY, 1.0),
+        ];
+        assert_eq!(analysis.objective.len(), expected_objective.len());
+        for (range, (name, value, increase, decrease)) in
+            analysis.objective.iter().zip(expected_objective)
+        {
+            assert_eq!(range.variable, name);
+            assert_precision(range.value, value);
+            assert_limit(range.allowable_increase, increase);
+            assert_limit(range.allowable_decrease, decrease);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_linearize_absolute_values() {
+        let source = "
+        min |x - 3| + 2 * |x - 8|
+        s.t.
+            x <= 6
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(
+            linear.variables(),
+            &vec!["$abs_0".to_string(), "$abs_1".to_string(), "x".to_string()]
+        );
+        assert_eq!(linear.objective(), &vec![1.0, 2.0, 0.0]);
+        let constraints = linear
+            .constraints()
+            .iter()
+            .map(|c| (c.coefficients().clone(), *c.constraint_type(), c.rhs()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                (vec![0.0, 1.0, -1.0], Comparison::GreaterOrEqual, -8.0),
+                (vec![0.0, 1.0, 1.0], Comparison::GreaterOrEqual, 8.0),
+                (vec![1.0, 0.0, -1.0], Comparison::GreaterOrEqual, -3.0),
+                (vec![1.0, 0.0, 1.0], Comparison::GreaterOrEqual, 3.0),
+                (vec![0.0, 0.0, 1.0], Comparison::LessOrEqual, 6.0),
+            ]
+        );
+        let (simplex, real) = solve(source).unwrap();
+        //x = 6, |6 - 3| + 2 * |6 - 8| = 7
+        assert_precision(real.value(), 7.0);
+        assert_precision(simplex.result().optimal_value(), 7.0);
+        let value = |name: &str| {
+            real.assignment()
+                .iter()
+                .find(|a| a.name == name)
+                .unwrap()
+                .value
+        };
+        assert_precision(value("x"), 6.0);
+        assert_precision(value("$abs_0"), (value("x") - 3.0).abs());
+        assert_precision(value("$abs_1"), (value("x") - 8.0).abs());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_maximized_absolute_values() {
+        let sources = [
+            "
+            max |x|
+            s.t.
+                x <= 3
+            define
+                x as Real
+            ",
+            "
+            min x
+            s.t.
+                |x| >= 3
+            define
+                x as Real
+            ",
+        ];
+        for source in sources {
+            let model = RoocParser::new(source.to_string())
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let err = Linearizer::linearize(model).unwrap_err();
+            assert!(err.to_string().contains("Cannot linearize \"|x|\""));
+        }
+        //the absolute value is pushed down when it has a negative coefficient in a maximization
+        let (_, real) = solve(
+            "
+            max -|x| + y
+            s.t.
+                y <= x + 1
+                y <= 3
+            define
+                x as Real
+                y as NonNegativeReal
+            ",
+        )
+        .unwrap();
+        assert_precision(real.value(), 1.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_linearize_min_max_in_convex_positions() {
+        let source = "
+        max x + y
+        s.t.
+            max { x, y } <= 5
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(
+            linear.variables(),
+            &vec!["$max_0".to_string(), "x".to_string(), "y".to_string()]
+        );
+        let constraints = linear
+            .constraints()
+            .iter()
+            .map(|c| (c.coefficients().clone(), *c.constraint_type(), c.rhs()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                (vec![1.0, 0.0, 0.0], Comparison::LessOrEqual, 5.0),
+                (vec![1.0, 0.0, -1.0], Comparison::GreaterOrEqual, 0.0),
+                (vec![1.0, -1.0, 0.0], Comparison::GreaterOrEqual, 0.0),
+            ]
+        );
+        let (simplex, real) = solve(source).unwrap();
+        assert_precision(real.value(), 10.0);
+        assert_precision(simplex.result().optimal_value(), 10.0);
+        let (simplex, real) = solve(
+            "
+            max min { x, y }
+            s.t.
+                x + 2y <= 6
+            define
+                x, y as NonNegativeReal
+            ",
+        )
+        .unwrap();
+        assert_precision(real.value(), 2.0);
+        assert_precision(simplex.result().optimal_value(), 2.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_min_max_in_non_convex_positions() {
+        let sources = [
+            (
+                "
+                min x + y
+                s.t.
+                    max { x, y } >= 3
+                define
+                    x, y as NonNegativeReal
+                ",
+                "the maximum is maximized",
+            ),
+            (
+                "
+                min min { x, y }
+                s.t.
+                    x + y <= 3
+                define
+                    x, y as NonNegativeReal
+                ",
+                "the minimum is minimized",
+            ),
+            (
+                "
+                min x
+                s.t.
+                    max { x, y } = 3
+                define
+                    x, y as NonNegativeReal
+                ",
+                "the maximum appears in an equality",
+            ),
+        ];
+        for (source, reason) in sources {
+            let model = RoocParser::new(source.to_string())
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let err = Linearizer::linearize(model).unwrap_err();
+            assert!(err.to_string().contains(reason), "{}", err);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_linearize_disjunctions_with_big_m() {
+        let source = "
+        min x
+        s.t.
+            x <= 2 or x >= 8
+        define
+            x as NonNegativeReal(0, 10)
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        assert_eq!(model.constraints()[0].to_string(), "x <= 2 or x >= 8");
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(
+            linear.variables(),
+            &vec![
+                "$or_0_0".to_string(),
+                "$or_0_1".to_string(),
+                "x".to_string()
+            ]
+        );
+        let constraints = linear
+            .constraints()
+            .iter()
+            .map(|c| (c.coefficients().clone(), *c.constraint_type(), c.rhs()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                (vec![8.0, 0.0, 1.0], Comparison::LessOrEqual, 10.0),
+                (vec![0.0, -8.0, 1.0], Comparison::GreaterOrEqual, 0.0),
+                (vec![1.0, 1.0, 0.0], Comparison::GreaterOrEqual, 1.0),
+            ]
+        );
+        let names = (0..linear.constraints().len())
+            .map(|i| linear.constraint_name(i))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["$or_0_0_le_M8", "$or_0_1_ge_M8", "$or_0"]);
+        //the first side holds
+        let result = solve_milp(source).unwrap();
+        assert_precision(result.value(), 0.0);
+        //only the second side can hold
+        let result = solve_milp(
+            "
+            min x
+            s.t.
+                x <= 2 or x >= 8
+                x >= 3
+            define
+                x as NonNegativeReal(0, 10)
+            ",
+        )
+        .unwrap();
+        assert_precision(result.value(), 8.0);
+        let result = solve_milp(
+            "
+            min x
+            s.t.
+                x <= 2 or x >= 8
+                x >= 3 or x <= 1
+                x >= 1.5
+            define
+                x as NonNegativeReal(0, 10)
+            ",
+        )
+        .unwrap();
+        assert_precision(result.value(), 8.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_disjunctions_of_unbounded_variables() {
+        let model = RoocParser::new(
+            "
+            min x
+            s.t.
+                x <= 2 or x >= 8
+            define
+                x as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let err = Linearizer::linearize(model).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot compute the Big-M of \"x <= 2 or x >= 8\", variable \"x\" is unbounded"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_linearize_product_of_booleans() {
+        let source = "
+        max 2 x * y + y
+        s.t.
+            x + y <= 2
+        define
+            x, y as Boolean
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(
+            linear.variables(),
+            &vec!["$prod_0".to_string(), "x".to_string(), "y".to_string()]
+        );
+        assert_eq!(linear.objective(), &vec![2.0, 0.0, 1.0]);
+        let constraints = linear
+            .constraints()
+            .iter()
+            .map(|c| (c.coefficients().clone(), *c.constraint_type(), c.rhs()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec![
+                (vec![1.0, -1.0, 0.0], Comparison::LessOrEqual, 0.0),
+                (vec![1.0, 0.0, -1.0], Comparison::LessOrEqual, 0.0),
+                (vec![1.0, -1.0, -1.0], Comparison::GreaterOrEqual, -1.0),
+                (vec![0.0, 1.0, 1.0], Comparison::LessOrEqual, 2.0),
+            ]
+        );
+        let result = solve_milp(source).unwrap();
+        assert_precision(result.value(), 3.0);
+        let result = solve_milp(
+            "
+            max 2 x * y + y
+            s.t.
+                x + y <= 1
+            define
+                x, y as Boolean
+            ",
+        )
+        .unwrap();
+        assert_precision(result.value(), 1.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_product_with_continuous_variable() {
+        let model = RoocParser::new(
+            "
+            max x * y
+            s.t.
+                y <= 2
+            define
+                x as Boolean
+                y as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let err = Linearizer::linearize(model).unwrap_err();
+        assert!(err.to_string().contains("[NonLinearTerm]"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_linearize_piecewise_functions() {
+        //x^2 sampled at 0, 1, 2, 3
+        let source = "
+        min piecewise(x, [0, 1, 2, 3], [0, 1, 4, 9])
+        s.t.
+            x >= 1.5
+        define
+            x as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(
+            linear.variables(),
+            &vec![
+                "$pw_0_l0".to_string(),
+                "$pw_0_l1".to_string(),
+                "$pw_0_l2".to_string(),
+                "$pw_0_l3".to_string(),
+                "$pw_0_s0".to_string(),
+                "$pw_0_s1".to_string(),
+                "$pw_0_s2".to_string(),
+                "x".to_string(),
+            ]
+        );
+        assert_eq!(
+            linear.objective(),
+            &vec![0.0, 1.0, 4.0, 9.0, 0.0, 0.0, 0.0, 0.0]
+        );
+        //link, weights, segments, 4 adjacency constraints and x >= 1.5
+        assert_eq!(linear.constraints().len(), 8);
+        let result = solve_milp(source).unwrap();
+        assert_precision(result.value(), 2.5);
+        //the adjacency constraints stop the weights from skipping the middle breakpoint
+        let result = solve_milp(
+            "
+            min piecewise(x, [0, 1, 2], [0, 1, 0])
+            s.t.
+                x = 1
+            define
+                x as NonNegativeReal
+            ",
+        )
+        .unwrap();
+        assert_precision(result.value(), 1.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_validate_piecewise_breakpoints() {
+        let sources = [
+            (
+                "min piecewise(x, [0, 1, 2], [0, 1])",
+                "as many values as breakpoints",
+            ),
+            (
+                "min piecewise(x, [0, 2, 1], [0, 1, 4])",
+                "must be strictly increasing",
+            ),
+        ];
+        for (objective, error) in sources {
+            let source = format!(
+                "
+                {}
+                s.t.
+                    x >= 1
+                define
+                    x as NonNegativeReal
+                ",
+                objective
+            );
+            let err = RoocParser::new(source)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap_err();
+            assert!(err.contains(error), "{}", err);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_mark_satisfy_solutions_of_integer_solvers_as_feasible() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::Boolean);
+        model.add_variable("y", VariableType::Boolean);
+        model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 1.0);
+        model.set_objective(vec![0.0, 0.0], OptimizationType::Satisfy);
+        let milp = solve_milp_lp_problem(&model).unwrap();
+        assert!(milp.is_feasibility_only());
+        assert!(milp.to_string().starts_with("Feasible solution found"));
+        let pool = solve_all(&model, 2, None, MILPOptions::default().epsilon).unwrap();
+        assert!(pool.iter().all(|s| s.is_feasibility_only()));
+        let binary = solve_binary_lp_problem(&model).unwrap();
+        assert!(binary.is_feasibility_only());
+        assert!(binary.to_string().starts_with("Feasible solution found"));
+        let integer = solve_integer_binary_lp_problem(&model).unwrap();
+        assert!(integer.is_feasibility_only());
+        assert!(integer.to_string().starts_with("Feasible solution found"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_feasible_point_of_satisfy_problem() {
+        let (simplex, real) = solve(
+            "
+            solve
+            s.t.
+                x + y >= 2
+                x - y <= 1
+                y <= 3
+            define
+                x, y as NonNegativeReal
+            ",
+        )
+        .unwrap();
+        assert!(simplex.result().is_feasibility_only());
+        assert!(real.is_feasibility_only());
+        for solution in [simplex.result().as_lp_solution(), real] {
+            assert!(solution.is_feasibility_only());
+            assert!(solution.to_string().starts_with("Feasible solution found"));
+            let values = solution
+                .assignment()
+                .iter()
+                .filter(|a| a.name == "x" || a.name == "y")
+                .map(|a| a.value)
+                .collect::<Vec<_>>();
+            let (x, y) = (values[0], values[1]);
+            assert!(x + y >= 2.0 - 1e-9);
+            assert!(x - y <= 1.0 + 1e-9);
+            assert!(y <= 3.0 + 1e-9);
+        }
+        let result = solve(
+            "
+            solve
+            s.t.
+                x + y <= 1
+                x + y >= 3
+            define
+                x, y as NonNegativeReal
+            ",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_solve_with_each_kind() {
+        let mut real = LinearModel::new();
+        real.add_variable("x", VariableType::non_negative_real());
+        real.add_variable("y", VariableType::non_negative_real());
+        real.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        real.add_constraint(vec![1.0, 3.0], Comparison::LessOrEqual, 6.0);
+        real.set_objective(vec![3.0, 2.0], OptimizationType::Max);
+        for kind in [SolverKind::Simplex, SolverKind::Real, SolverKind::MILP] {
+            let solution = solve_with(&real, kind).expect("Failed to solve real model");
+            assert!(float_eq(solution.value(), 12.0), "{:?}", kind);
+        }
+
+        let mut integer = LinearModel::new();
+        integer.add_variable("x", VariableType::IntegerRange(0, 10));
+        integer.add_variable("b", VariableType::Boolean);
+        integer.add_constraint(vec![2.0, 1.0], Comparison::LessOrEqual, 7.0);
+        integer.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        for kind in [SolverKind::BinaryAndInteger, SolverKind::MILP] {
+            let solution = solve_with(&integer, kind).expect("Failed to solve integer model");
+            assert!(float_eq(solution.value(), 4.0), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_solve_with_mismatched_kind() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 10));
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.5);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        for kind in [
+            SolverKind::Simplex,
+            SolverKind::Real,
+            SolverKind::BinaryAndInteger,
+        ] {
+            match solve_with(&model, kind) {
+                Err(SolverError::InvalidDomain { got, .. }) => {
+                    assert_eq!(got.len(), 1, "{:?}", kind);
+                }
+                other => panic!("Expected invalid domain for {:?}, got {:?}", kind, other),
+            }
+        }
+        solve_with(&model, SolverKind::MILP).expect("Failed to solve mixed model");
+    }
+
+    fn knapsack_model() -> LinearModel {
+        let mut model = LinearModel::new();
+        for i in 0..5 {
+            model.add_variable(&format!("x{}", i), VariableType::Boolean);
+        }
+        model.add_constraint(
+            vec![12.0, 7.0, 11.0, 8.0, 9.0],
+            Comparison::LessOrEqual,
+            26.0,
+        );
+        model.set_objective(vec![24.0, 13.0, 23.0, 15.0, 16.0], OptimizationType::Max);
+        model
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_warm_start() {
+        let model = knapsack_model();
+        let (cold, cold_stats) =
+            solve_milp_lp_problem_with_options(&model, &MILPOptions::new()).unwrap();
+        let warm_start = IndexMap::from([
+            ("x0".to_string(), 0.0),
+            ("x1".to_string(), 1.0),
+            ("x2".to_string(), 1.0),
+            ("x3".to_string(), 1.0),
+            ("x4".to_string(), 0.0),
+        ]);
+        let (warm, warm_stats) = solve_with_warm_start(&model, warm_start).unwrap();
+        assert!(float_eq(cold.value(), 51.0));
+        assert!(float_eq(warm.value(), 51.0));
+        assert_eq!(warm_stats.nodes_to_first_incumbent, Some(0));
+        assert!(warm_stats.nodes_explored < cold_stats.nodes_explored);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_invalid_warm_start() {
+        let model = knapsack_model();
+        let valid = IndexMap::from([
+            ("x0".to_string(), 1.0),
+            ("x1".to_string(), 1.0),
+            ("x2".to_string(), 0.0),
+            ("x3".to_string(), 0.0),
+            ("x4".to_string(), 0.0),
+        ]);
+        let mut fractional = valid.clone();
+        fractional.insert("x2".to_string(), 0.5);
+        let mut out_of_bounds = valid.clone();
+        out_of_bounds.insert("x2".to_string(), 2.0);
+        let mut infeasible = valid.clone();
+        infeasible.insert("x2".to_string(), 1.0);
+        let mut missing = valid.clone();
+        missing.shift_remove("x4");
+        let mut unknown = valid.clone();
+        unknown.insert("y".to_string(), 0.0);
+        for warm_start in [fractional, out_of_bounds, infeasible, missing, unknown] {
+            match solve_with_warm_start(&model, warm_start) {
+                Err(SolverError::Other(message)) => {
+                    assert!(message.starts_with("Invalid warm start"))
+                }
+                other => panic!("Expected invalid warm start, got {:?}", other),
+            }
+        }
+        let (solution, _) = solve_with_warm_start(&model, valid).unwrap();
+        assert!(float_eq(solution.value(), 51.0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_search_stats() {
+        //odd capacities keep the relaxation fractional, so bigger problems need more branching
+        let mut previous_nodes = 0;
+        for n in [1, 3, 5, 7] {
+            let mut model = LinearModel::new();
+            for i in 0..n {
+                model.add_variable(&format!("x{}", i), VariableType::Boolean);
+            }
+            model.add_constraint(vec![2.0; n], Comparison::LessOrEqual, n as f64);
+            model.set_objective(vec![3.0; n], OptimizationType::Max);
+            let (solution, stats) =
+                solve_milp_lp_problem_with_options(&model, &MILPOptions::new()).unwrap();
+            assert!(stats.nodes_explored > previous_nodes);
+            assert!(stats.incumbents_found >= 1);
+            assert_eq!(solution.stats(), Some(&stats));
+            //the search runs to optimality, so the bound is the optimum itself
+            assert!(float_eq(stats.best_bound.unwrap(), solution.value()));
+            assert!(float_eq(solution.value(), 3.0 * (n / 2) as f64));
+            previous_nodes = stats.nodes_explored;
+        }
+        let source = "
+        max 3x + 3y + 3z
+        s.t.
+            2x + 2y + 2z <= 3
+        define
+            x, y, z as Boolean
+        ";
+        let solution = solve_milp(source).unwrap();
+        let stats = solution
+            .stats()
+            .expect("Expected the MILP pipe to return the statistics");
+        assert!(float_eq(solution.value(), 3.0));
+        assert!(float_eq(stats.best_bound.unwrap(), 3.0));
+        assert!(float_eq(stats.gap.unwrap(), 0.0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_relative_gap() {
+        let model = knapsack_model();
+        let (exact, exact_stats) =
+            solve_milp_lp_problem_with_options(&model, &MILPOptions::new()).unwrap();
+        assert!(float_eq(exact.value(), 51.0));
+        assert!(float_eq(exact_stats.gap.unwrap(), 0.0));
+        let options = MILPOptions::new().with_mip_gap_rel(0.1);
+        let (solution, stats) = solve_milp_lp_problem_with_options(&model, &options).unwrap();
+        //stops at a worse incumbent, without proving optimality
+        assert!(solution.value() < exact.value());
+        assert!(stats.nodes_explored < exact_stats.nodes_explored);
+        assert!(stats.gap.unwrap() <= 0.1);
+        assert!((exact.value() - solution.value()) / solution.value() <= 0.1);
+
+        let pipe_runner = PipeRunner::new(vec![Box::new(MILPSolverPipe::new())]);
+        let result = pipe_runner
+            .run(
+                PipeableData::LinearModel(model),
+                &PipeContext::new(vec![], &IndexMap::new()).with_milp_options(options),
+            )
+            .unwrap();
+        match result.last().unwrap() {
+            PipeableData::MILPSolution(piped) => {
+                assert!(float_eq(piped.value(), solution.value()));
+                assert_eq!(piped.stats(), Some(&stats));
+            }
+            _ => panic!("Expected a MILP solution"),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_solution_pool() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::Boolean);
+        model.add_variable("y", VariableType::Boolean);
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 1.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let optima = solve_all(&model, 5, Some(0.0), MILPOptions::default().epsilon).unwrap();
+        assert_eq!(optima.len(), 2);
+        let mut assignments = optima
+            .iter()
+            .map(|solution| {
+                assert!(float_eq(solution.value(), 1.0));
+                solution
+                    .assignment()
+                    .iter()
+                    .map(|a| a.value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        assignments.sort();
+        assert_eq!(assignments, vec!["false,true", "true,false"]);
+        let all = solve_all(&model, 5, None, MILPOptions::default().epsilon).unwrap();
+        let values = all.iter().map(|s| s.value()).collect::<Vec<_>>();
+        assert_eq!(values, vec![1.0, 1.0, 0.0]);
+        assert_eq!(
+            solve_all(&model, 1, None, MILPOptions::default().epsilon)
+                .unwrap()
+                .len(),
+            1
+        );
+        let best = solve_all(&knapsack_model(), 3, None, MILPOptions::default().epsilon).unwrap();
+        assert_eq!(best.len(), 3);
+        assert!(float_eq(best[0].value(), 51.0));
+        assert!(best.windows(2).all(|w| w[0].value() >= w[1].value()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_greater_or_equal_constraints_with_two_phases() {
+        let source = r#"
+    min 2x + 3y
+    s.t.
+        x + y >= 4
+        x + 3y >= 6
+    define
+        x, y as NonNegativeReal
+    "#;
+        let solution = solve(source).unwrap();
+        assert_correct_solution(solution, 9.0, vec![vec![3.0, 1.0, 0.0, 0.0]]);
+        let source = r#"
+    max 3x + y + 2z
+    s.t.
+        x + y + z = 10
+        x - y >= 2
+        y + z >= 3
+    define
+        x, y, z as NonNegativeReal
+    "#;
+        let solution = solve(source).unwrap();
+        assert_correct_solution(solution, 27.0, vec![vec![7.0, 0.0, 3.0, 5.0, 0.0]]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_drop_redundant_constraints_after_first_phase() {
+        let source = r#"
+    min x - y
+    s.t.
+        x + y = 4
+        2x + 2y = 8
+    define
+        x, y as NonNegativeReal
+    "#;
+        let solution = solve(source).unwrap();
+        assert_correct_solution(solution, -4.0, vec![vec![0.0, 4.0]]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_infeasibility_after_first_phase() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::GreaterOrEqual, 5.0);
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 3.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        match solve_real_lp_problem_slow_simplex(&model, 1000) {
+            Err(SolverError::Infisible) => {}
+            r => panic!("Expected the model to be infeasible, got {:?}", r),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_resolve_with_dual_simplex_after_adding_a_constraint() {
+        let source = "
+        max 3x + 5y
+        s.t.
+            x <= 4
+            2y <= 12
+            3x + 2y <= 18
+        define
+            x, y as NonNegativeReal
+        ";
+        let optimal = solve_optimal_tableau(source);
+        assert_precision(optimal.optimal_value(), 36.0);
+        let cut = LinearConstraint::new(vec![1.0, 1.0], Comparison::LessOrEqual, 7.0);
+        let resolved = optimal.add_constraint_and_resolve(&cut).unwrap();
+        let fresh = solve_optimal_tableau(
+            "
+        max 3x + 5y
+        s.t.
+            x <= 4
+            2y <= 12
+            3x + 2y <= 18
+            x + y <= 7
+        define
+            x, y as NonNegativeReal
+        ",
+        );
+        assert_precision(resolved.optimal_value(), 33.0);
+        assert_precision(resolved.optimal_value(), fresh.optimal_value());
+        assert_variables(
+            resolved.variables_values(),
+            &vec![fresh.variables_values().clone()],
+            false,
+        );
+        let lower = LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 3.0);
+        let resolved = optimal.add_constraint_and_resolve(&lower).unwrap();
+        assert_precision(resolved.optimal_value(), 31.5);
+        assert_variables(resolved.variables_values(), &vec![vec![3.0, 4.5]], true);
+        let infeasible = LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 5.0);
+        assert!(matches!(
+            optimal.add_constraint_and_resolve(&infeasible),
+            Err(SimplexError::Infeasible)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_generate_valid_gomory_cuts() {
+        let source = "
+        max y
+        s.t.
+            3x + 2y <= 6
+            -3x + 2y <= 0
+        define
+            x, y as NonNegativeReal
+        ";
+        let optimal = solve_optimal_tableau(source);
+        assert_precision(optimal.optimal_value(), 1.5);
+        let cuts = generate_gomory_cuts(&optimal);
+        assert!(!cuts.is_empty());
+        let evaluate = |cut: &LinearConstraint, values: &[f64]| {
+            cut.coefficients()
+                .iter()
+                .zip(values.iter())
+                .map(|(c, v)| c * v)
+                .sum::<f64>()
+        };
+        //the integer points (0, 0) and (1, 1) with their slack variables
+        let integer_points = [[0.0, 0.0, 6.0, 0.0], [1.0, 1.0, 1.0, 1.0]];
+        for cut in &cuts {
+            assert!(evaluate(cut, optimal.variables_values()) < cut.rhs());
+            for point in &integer_points {
+                assert!(evaluate(cut, point) >= cut.rhs() - 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reduce_branch_nodes_with_gomory_cuts() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 100));
+        model.add_variable("y", VariableType::IntegerRange(0, 100));
+        model.add_constraint(vec![2.0, 3.0], Comparison::LessOrEqual, 12.0);
+        model.add_constraint(vec![2.0, 1.0], Comparison::LessOrEqual, 6.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let (plain, plain_stats) =
+            solve_milp_lp_problem_with_options(&model, &MILPOptions::new()).unwrap();
+        let (cut, cut_stats) =
+            solve_milp_lp_problem_with_options(&model, &MILPOptions::new().with_cut_rounds(5))
+                .unwrap();
+        assert!(float_eq(plain.value(), 4.0));
+        assert!(float_eq(cut.value(), 4.0));
+        assert_eq!(plain_stats.cuts_added, 0);
+        assert!(cut_stats.cuts_added > 0);
+        assert!(cut_stats.nodes_explored < plain_stats.nodes_explored);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_unbounded_integer_variables() {
+        let source = "
+min x
+s.t.
+    2x >= -7
+define
+    x as Integer";
+        let result = solve_milp(source).unwrap();
+        assert_precision(result.value(), -3.0);
+        assert_variables_milp(&result.assignment_values(), &[MILPValue::Int(-3)], false);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_export_latex_through_pipe() {
+        let source = "
+        max sum(i in 0..len(C)) { C[i] * x_i }
+        s.t.
+            x_i <= 1 for i in 0..len(C)
+            x_0 + x_1 >= 1
+        where
+            let C = [3, 5]
+        define
+            x_i as Boolean for i in 0..len(C)
+        ";
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(LatexPipe::new()),
+        ]);
+        let result = pipe_runner
+            .run(
+                PipeableData::String(source.to_string()),
+                &PipeContext::new(vec![], &IndexMap::new()),
+            )
+            .unwrap_or_else(|(error, _)| panic!("Failed to export to LaTeX: {}", error));
+        let latex = result.last().unwrap().as_string_data().unwrap();
+        assert_eq!(latex, include_str!("golden/latex_model.tex"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_model_without_linearization_step() {
+        let source = "
+        max 3x + 2y
+        s.t.
+            x + y <= 4
+            x + 3y <= 6
+        define
+            x as IntegerRange(0, 3)
+            y as NonNegativeReal
+        ";
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(MILPSolverPipe::new()),
+        ]);
+        let result = pipe_runner
+            .run(
+                PipeableData::String(source.to_string()),
+                &PipeContext::new(vec![], &IndexMap::new()),
+            )
+            .unwrap_or_else(|(error, _)| panic!("Failed to solve model: {}", error));
+        let solution = match result.last().unwrap() {
+            PipeableData::MILPSolution(solution) => solution,
+            data => panic!("Expected a MILP solution, got {}", data.get_type()),
+        };
+        assert!(float_eq(solution.value(), 11.0));
+        let assignment = solution.assignment();
+        assert_eq!(assignment[0].name, "x");
+        assert!(matches!(assignment[0].value, MILPValue::Int(3)));
+        assert_eq!(assignment[1].name, "y");
+        assert!(matches!(assignment[1].value, MILPValue::Real(y) if float_eq(y, 1.0)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_name_unnamed_constraints_with_context_prefix() {
+        let source = "
+        max x + y
+        s.t.
+            capacity: x + y <= 4
+            x - y <= 1
+        define
+            x, y as NonNegativeReal
+        ";
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(LpFormatPipe::new()),
+        ]);
+        let result = pipe_runner
+            .run(
+                PipeableData::String(source.to_string()),
+                &PipeContext::new(vec![], &IndexMap::new())
+                    .with_constraint_prefix("auto_".to_string()),
+            )
+            .unwrap_or_else(|(error, _)| panic!("Failed to export model: {}", error));
+        let lp = result.last().unwrap().as_string_data().unwrap();
+        assert!(lp.contains(" capacity: x + y <= 4"));
+        assert!(lp.contains(" auto_1: x - y <= 1"));
+        let model = result.iter().find_map(|d| d.as_model().ok()).unwrap();
+        assert!(model.to_string().contains("capacity: x + y <= 4"));
+        assert!(!model.to_string().contains("auto_"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_unbounded_variable() {
+        let source = r#"
+        max x_1
+        s.t.
+            x_1 >= 0
+        define
+            x_1 as NonNegativeReal
+        "#;
+        match solve(source) {
+            Err(PipeError::StepByStepSimplexError(SimplexError::Unbounded, tableau)) => {
+                assert_eq!(tableau.unbounded_variable(), Some("x_1".to_string()));
+                let error = PipeError::StepByStepSimplexError(SimplexError::Unbounded, tableau);
+                assert_eq!(
+                    error.to_string(),
+                    "Unbounded Problem, the variable \"x_1\" can increase indefinitely"
+                );
+            }
+            Err(e) => panic!("Should be unbounded, got {}", e),
+            Ok(_) => panic!("Should not reach here"),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_round_displayed_numbers_without_changing_the_model() {
+        let source = "
+        max 1/3 * x
+        s.t.
+            x <= 1
+        define
+            x as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        set_display_precision(Some(3));
+        let displayed = model.to_string();
+        set_display_precision(None);
+        assert!(displayed.starts_with("max 0.333 * x\n"));
+        assert!(model
+            .to_string()
+            .starts_with("max 0.3333333333333333 * x\n"));
+        let linear = Linearizer::linearize(model).expect("Failed to linearize");
+        let solution = solve_real_lp_problem_slow_simplex(&linear, 1000).unwrap();
+        assert_eq!(solution.value(), 1.0 / 3.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_warm_start_from_initial_values() {
+        let source = "
+        max sum((v, i) in enumerate(P)) { v * x_i }
+        s.t.
+            sum((w, i) in enumerate(W)) { w * x_i } <= 26
+        where
+            let P = [24, 13, 23, 15, 16]
+            let W = [12, 7, 11, 8, 9]
+            let S = START
+        define
+            x_i as Boolean = S[i] for i in 0..len(P)
+        ";
+        let solve_source = |source: String| {
+            let model = RoocParser::new(source)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .expect("Failed to parse and transform problem");
+            let linear = Linearizer::linearize(model).expect("Failed to linearize");
+            solve_milp_lp_problem_with_options(&linear, &MILPOptions::new()).unwrap()
+        };
+        let (warm, warm_stats) = solve_source(source.replace("START", "[0, 1, 1, 1, 0]"));
+        assert!(float_eq(warm.value(), 51.0));
+        assert_eq!(warm_stats.nodes_to_first_incumbent, Some(0));
+        //an infeasible initial point is ignored instead of failing the solve
+        let (cold, cold_stats) = solve_source(source.replace("START", "[1, 1, 1, 1, 1]"));
+        assert!(float_eq(cold.value(), 51.0));
+        assert_ne!(cold_stats.nodes_to_first_incumbent, Some(0));
+        assert!(warm_stats.nodes_explored < cold_stats.nodes_explored);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_fold_singleton_constraints_into_bounds() {
+        let source = "
+        max x_1 + 2x_2 + y
+        s.t.
+            x_1 + x_2 + y <= 10
+            x_1 <= 3
+            -2x_2 >= -9
+            y = 1
+        define
+            x_1 as NonNegativeReal
+            x_2 as IntegerRange(0, 10)
+            y as Real
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let (presolved, removed) = presolve_bounds(&model);
+        assert_eq!(removed, 2);
+        let constraints = presolved
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(constraints, vec!["x_1 + x_2 + y <= 10", "y = 1"]);
+        let domain = presolved.domain();
+        assert!(matches!(
+            domain["x_1"].get_type(),
+            VariableType::NonNegativeReal(min, max) if *min == 0.0 && *max == 3.0
+        ));
+        assert!(matches!(
+            domain["x_2"].get_type(),
+            VariableType::IntegerRange(0, 4)
+        ));
+
+        let pipe_runner = PipeRunner::new(vec![
+            Box::new(CompilerPipe::new()),
+            Box::new(PreModelPipe::new()),
+            Box::new(ModelPipe::new()),
+            Box::new(PresolvePipe::new()),
+            Box::new(MILPSolverPipe::new()),
+        ]);
+        let result = pipe_runner
+            .run(
+                PipeableData::String(source.to_string()),
+                &PipeContext::new(vec![], &IndexMap::new()),
+            )
+            .unwrap_or_else(|(error, _)| panic!("Failed to solve presolved model: {}", error));
+        let presolved = result[4].as_presolved_model().unwrap();
+        assert_eq!(presolved.removed_constraints(), 2);
+        let solution = result.last().unwrap().clone().to_milp_solution().unwrap();
+        assert!(float_eq(solution.value(), 12.0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_objective_offset() {
+        let source = "
+        min x + 10 + 2y - 3
+        s.t.
+            x + y >= 2
+            x <= 5
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        assert!(float_eq(model.objective().offset, 7.0));
+        assert_eq!(model.objective().to_string(), "min x + 2 * y + 7");
+        let lp = model.to_lp_format().unwrap();
+        assert!(lp.contains("+ 7"));
+        let linear = Linearizer::linearize(model).unwrap();
+        assert!(float_eq(linear.objective_offset(), 7.0));
+        let (tableau, solution) = solve(source).unwrap();
+        assert!(float_eq(solution.value(), 9.0));
+        assert!(float_eq(tableau.result().optimal_value(), 9.0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_make_infeasible_model_feasible_as_goal_program() {
+        let hard = "
+        solve
+        s.t.
+            x + y = 10
+            x - y = 0
+            x <= 3
+            y <= 4
+        define
+            x, y as NonNegativeReal
+        ";
+        assert!(solve(hard).is_err());
+        let goals = "
+        solve
+        s.t.
+            x + y ~ 10
+            @weight(2) x - y ~ 0
+            x <= 3
+            y <= 4
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(goals.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            constraints,
+            vec!["x + y ~ 10", "@weight(2) x - y ~ 0", "x <= 3", "y <= 4"]
+        );
+        let (tableau, solution) = solve(goals).unwrap();
+        assert!(float_eq(solution.value(), 4.0));
+        assert!(float_eq(tableau.result().optimal_value(), 4.0));
+        let values = solution
+            .assignment()
+            .iter()
+            .filter(|a| a.name == "x" || a.name == "y")
+            .map(|a| a.value)
+            .collect::<Vec<_>>();
+        assert!(float_eq(values[0], 3.0) && float_eq(values[1], 3.0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_with_pipe_presets() {
+        let source = "
+        max 2x + 3y
+        s.t.
+            x + y <= 4
+            x + 3y <= 6
+        define
+            x, y as IntegerRange(0, 10)
+        ";
+        let fns = IndexMap::new();
+        let context = PipeContext::new(vec![], &fns);
+        let result = rooc_pipe!(solve_milp())
+            .run(PipeableData::String(source.to_string()), &context)
+            .unwrap();
+        let solution = result.last().unwrap().clone().to_milp_solution().unwrap();
+        assert_precision(solution.value(), 9.0);
+        let result = rooc_pipe!(to_linear_model())
+            .run(PipeableData::String(source.to_string()), &context)
+            .unwrap();
+        assert!(matches!(
+            result.last().unwrap(),
+            PipeableData::LinearModel(_)
+        ));
+        let result = rooc_pipe![CompilerPipe, PreModelPipe, ModelPipe]
+            .run(PipeableData::String(source.to_string()), &context)
+            .unwrap();
+        assert_eq!(result.len(), 4);
+        assert!(matches!(result.last().unwrap(), PipeableData::Model(_)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_export_standard_form_matrices() {
+        let source = "
+        max x + 2y
+        s.t.
+            x + y <= 4
+            x - y >= 1
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let standard = StandardLinearModel::from_linear_problem(linear).unwrap();
+        let (a, b, c, variables) = standard.to_matrices();
+        //x + y + s1 = 4, x - y - s2 = 1, the maximization is turned into min -x - 2y
+        assert_eq!(
+            a,
+            vec![vec![1.0, 1.0, 1.0, 0.0], vec![1.0, -1.0, 0.0, -1.0]]
+        );
+        assert_eq!(b, vec![4.0, 1.0]);
+        assert_eq!(c, vec![-1.0, -2.0, 0.0, 0.0]);
+        assert_eq!(variables, vec!["x", "y", "$su_1", "$sl_1"]);
+        assert_eq!(standard.constraint_sense(), Comparison::Equal);
+        assert!(standard.is_objective_flipped());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_recombine_free_variables() {
+        let source = "
+        min x + 2y
+        s.t.
+            x >= -3
+            y >= -5
+            x + y <= 10
+        define
+            x, y as Real
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let standard = linear.clone().into_standard_form().unwrap();
+        assert_eq!(standard.free_variables().len(), 2);
+        assert_eq!(
+            standard.free_variables().get("x"),
+            Some(&("$px".to_string(), "$mx".to_string()))
+        );
+        let solution = solve_real_lp_problem_slow_simplex(&linear, 1000).unwrap();
+        assert_precision(solution.value(), -13.0);
+        let values = solution
+            .assignment()
+            .iter()
+            .filter(|a| !a.name.starts_with('$'))
+            .map(|a| (a.name.clone(), a.value))
+            .collect::<Vec<_>>();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].0, "x");
+        assert_precision(values[0].1, -3.0);
+        assert_eq!(values[1].0, "y");
+        assert_precision(values[1].1, -5.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reconstruct_original_variables() {
+        let source = "
+        max -x - y
+        s.t.
+            x + y >= 2
+            x >= -4
+            y <= 3
+        define
+            x as Real
+            y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let standard = linear.into_standard_form().unwrap();
+        let optimal = standard
+            .clone()
+            .into_tableau()
+            .unwrap()
+            .solve(1000)
+            .unwrap();
+        let raw = optimal.as_lp_solution();
+        assert!(raw.assignment().iter().any(|a| a.name == "x"));
+        assert!(!raw
+            .assignment()
+            .iter()
+            .any(|a| a.name == "$px" || a.name == "$mx"));
+        let values = standard.reconstruct_solution(&raw);
+        assert_eq!(values.len(), 2);
+        assert_precision(values["x"] + values["y"], 2.0);
+        assert_precision(optimal.optimal_value(), -2.0);
+        let source = "
+        min x
+        s.t.
+            x + y >= 1
+            x >= -4
+            y <= 3
+        define
+            x as Real
+            y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let standard = linear.into_standard_form().unwrap();
+        let optimal = standard
+            .clone()
+            .into_tableau()
+            .unwrap()
+            .solve(1000)
+            .unwrap();
+        let values = standard.reconstruct_solution(&optimal.as_lp_solution());
+        assert_precision(values["x"], -2.0);
+        assert_precision(values["y"], 3.0);
+    }
+
+    #[test]
+    #[cfg(feature = "rational-simplex")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_exact_vertex_with_rational_arithmetic() {
+        use rooc::{solve_real_lp_problem_slow_simplex_with_options, SimplexArithmetic};
+        //the 5x5 Hilbert matrix is ill-conditioned, the only solution of H * x = H * 1 is x = 1
+        let n = 5;
+        let mut model = LinearModel::new();
+        for i in 0..n {
+            model.add_variable(&format!("x{}", i), VariableType::non_negative_real());
+        }
+        for i in 0..n {
+            let row = (0..n).map(|j| 1.0 / (i + j + 1) as f64).collect::<Vec<_>>();
+            let rhs = row.iter().sum();
+            model.add_constraint(row, Comparison::Equal, rhs);
+        }
+        model.set_objective(vec![1.0; n], OptimizationType::Min);
+
+        //the float simplex lands close to the vertex, but not exactly on it
+        let float = solve_real_lp_problem_slow_simplex_with_options(&model, &SimplexOptions::new())
+            .unwrap();
+        assert!(float.assignment().iter().all(|a| float_eq(a.value, 1.0)));
+        assert!(float.assignment().iter().any(|a| a.value != 1.0));
+
+        let rational = solve_real_lp_problem_slow_simplex_with_options(
+            &model,
+            &SimplexOptions::new().with_arithmetic(SimplexArithmetic::Rational),
+        )
+        .unwrap();
+        assert!(rational.assignment().iter().all(|a| a.value == 1.0));
+        assert_eq!(rational.value(), 5.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_first_feasible() {
+        let model = knapsack_model();
+        let (exact, exact_stats) =
+            solve_milp_lp_problem_with_options(&model, &MILPOptions::new()).unwrap();
+        let options = MILPOptions::new().with_first_feasible(true);
+        let (solution, stats) = solve_milp_lp_problem_with_options(&model, &options).unwrap();
+        assert_eq!(stats.incumbents_found, 1);
+        assert_eq!(stats.nodes_to_first_incumbent, Some(stats.nodes_explored));
+        //stops at the first incumbent, a worse solution than the optimum
+        assert_eq!(stats.nodes_explored, 4);
+        assert!(stats.nodes_explored < exact_stats.nodes_explored);
+        assert!(float_eq(solution.value(), 47.0));
+        assert!(float_eq(exact.value(), 51.0));
+        assert!(float_eq(
+            stats.gap.unwrap(),
+            (stats.best_bound.unwrap() - solution.value()) / solution.value()
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_enforce_equality_exactly_in_integer_binary_solver() {
+        //fixes both variables to each of the four assignments, only the ones where
+        //x_1 + x_2 = 1 must be feasible. Every variable is assigned by the propagation alone,
+        //which is where copper skips the last constraints without the guard variable of the solver
+        let mut feasible = vec![];
+        for (x1, x2) in [(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (1.0, 1.0)] {
+            let mut model = LinearModel::new();
+            model.add_variable("x_1", VariableType::Boolean);
+            model.add_variable("x_2", VariableType::Boolean);
+            model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 1.0);
+            model.add_constraint(vec![1.0, 0.0], Comparison::Equal, x1);
+            model.add_constraint(vec![0.0, 1.0], Comparison::Equal, x2);
+            model.set_objective(vec![0.0, 0.0], OptimizationType::Satisfy);
+            match solve_integer_binary_lp_problem(&model) {
+                Ok(_) => feasible.push((x1, x2)),
+                Err(e) => assert!(matches!(e, SolverError::DidNotSolve)),
+            }
+        }
+        assert_eq!(feasible, vec![(0.0, 1.0), (1.0, 0.0)]);
+
+        let mut model = LinearModel::new();
+        model.add_variable("x_1", VariableType::Boolean);
+        model.add_variable("x_2", VariableType::Boolean);
+        model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 1.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let solution = solve_integer_binary_lp_problem(&model).unwrap();
+        assert_eq!(solution.value(), 1.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_product_of_continuous_variables() {
+        use rooc::model_transformer::TransformError;
+        use rooc::LinearizationError;
+        let model = RoocParser::new(
+            "
+            max x
+            s.t.
+                x * y <= 2
+            define
+                x, y as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let err = Linearizer::linearize(model).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                LinearizationError::NonLinearTerm(TransformError::SpannedError { .. })
+            ),
+            "{}",
+            err
+        );
+        assert!(err.to_string().contains("[NonLinearTerm]"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_division_by_variable() {
+        let model = RoocParser::new(
+            "
+            min x / y
+            s.t.
+                y >= 1
+            define
+                x, y as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let err = Linearizer::linearize(model).unwrap_err();
+        assert!(err.to_string().contains("[NonLinearTerm]"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_nonlinear_goal() {
+        let model = RoocParser::new(
+            "
+            solve
+            s.t.
+                x * y ~ 10
+                x <= 3
+            define
+                x, y as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let err = Linearizer::linearize(model).unwrap_err();
+        assert!(err.to_string().contains("[NonLinearTerm]"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_constant_modulo_by_zero() {
+        use rooc::model_transformer::{Constraint, Exp, Model};
+        use rooc::{BinOp, LinearizationError, OperatorError};
+        let model = RoocParser::new(
+            "
+            max x
+            s.t.
+                x <= 5
+            define
+                x as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let (objective, _, domain) = model.into_components();
+        let modulo = Exp::BinOp(
+            BinOp::Mod,
+            Exp::Number(7.0).to_box(),
+            Exp::Number(0.0).to_box(),
+        );
+        let constraint = Constraint::new(
+            Exp::Variable("x".to_string()),
+            Comparison::LessOrEqual,
+            modulo,
+        );
+        let model = Model::new(objective, vec![constraint], domain);
+        let err = Linearizer::linearize(model).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                LinearizationError::OperatorError(OperatorError::DivisionByZero {
+                    operator: BinOp::Mod
+                })
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_accept_product_with_constant() {
+        let model = RoocParser::new(
+            "
+            max 3 * x + y / 2
+            s.t.
+                2 * (x + y) <= 4
+            define
+                x, y as NonNegativeReal
+            "
+            .to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let solution = solve_real_lp_problem_micro_lp(&linear).unwrap();
+        assert!(float_eq(solution.value(), 6.0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_milp_epsilon() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 10));
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 2.0);
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 3.5);
+        model.set_objective(vec![2.0, 1.0], OptimizationType::Max);
+        let warm_start = IndexMap::from([("x".to_string(), 2.0000000001), ("y".to_string(), 1.0)]);
+        //the float noise is within the default epsilon, both for integrality and the constraints
+        let options = MILPOptions::new().with_warm_start(warm_start.clone());
+        let (solution, stats) = solve_milp_lp_problem_with_options(&model, &options).unwrap();
+        assert_eq!(stats.nodes_to_first_incumbent, Some(0));
+        assert!(float_eq(solution.value(), 5.5));
+        let options = options.with_epsilon(1e-12);
+        match solve_milp_lp_problem_with_options(&model, &options) {
+            Err(SolverError::Other(e)) => assert!(e.contains("must be an integer"), "{}", e),
+            r => panic!("Expected the warm start to be rejected, got {:?}", r),
+        }
+    }
 }
@@ -1,6 +1,7 @@
 import {
     CompilationError as _CompilationError,
     EqualityConstraint as _EqualityConstraint,
+    graph_to_dot,
     InputSpan,
     JsFunction,
     LinearConstraint as _LinearConstraint,
@@ -12,15 +13,21 @@ import {
     PipeDataType,
     Pipes,
     PreModel as _PreModel,
+    PresolvedModel as _PresolvedModel,
     RoocParser as _RoocParser,
     SerializedCompilationError,
+    SerializedDiagnostics,
+    SerializedGraph,
     SerializedModel,
     SerializedPreModel,
     SerializedPrimitive,
     SerializedPrimitiveKind,
+    SerializedSensitivityAnalysis,
     SerializedTransformError,
     SerializedTypedToken,
+    SerializedWarning,
     SimplexStep as _SimplexStep,
+    SolveStats,
     StandardLinearModel as _StandardLinearModel,
     Tableau as _Tableau,
     TransformErrorWrapper as _TransformErrorWrapper,
@@ -125,6 +132,14 @@ export class RoocFunction<T extends [string, SerializedPrimitiveKind][] = [strin
     }
 }
 
+/**
+ * Renders a graph in the GraphViz DOT format, edges are labeled with their cost when present
+ * @param graph the graph to render
+ */
+export function graphToDot(graph: SerializedGraph): string {
+    return graph_to_dot(graph)
+}
+
 
 /**
  * The RoocParser is the main entry point to the Rooc library, it allows to parse, transform and compile rooc code
@@ -179,6 +194,22 @@ export class RoocParser {
         }
 
     }
+
+    /**
+     * Finds the problems of the source code that don't prevent it from compiling, like unused constants
+     */
+    lint(): SerializedWarning[] {
+        return this.instance.lint_wasm()
+    }
+
+    /**
+     * Parses and type checks the source code, returning the error, if any, followed by the lint warnings
+     * @param data additional constants given to the runtime
+     * @param fns additional functions that can be used in the type checking process
+     */
+    parseAndTypeCheck(data: ConstantEntry[] = [], fns: RoocFunction[] = []): SerializedDiagnostics {
+        return this.instance.parse_and_type_check_wasm(data, cloneJsFunction(fns))
+    }
 }
 
 function cloneJsFunction(fns: RoocFunction[]) {
@@ -307,6 +338,7 @@ export type RoocData =
     RoocType<PipeDataType.Parser, RoocParser> |
     RoocType<PipeDataType.PreModel, PreModel> |
     RoocType<PipeDataType.Model, Model> |
+    RoocType<PipeDataType.PresolvedModel, PresolvedModel> |
     RoocType<PipeDataType.LinearModel, LinearModel> |
     RoocType<PipeDataType.StandardLinearModel, StandardLinearModel> |
     RoocType<PipeDataType.Tableau, SimplexTableau> |
@@ -327,6 +359,8 @@ function toRoocData(data: WasmPipableData): RoocData {
             return {type: PipeDataType.PreModel, data: PreModel.fromPreModel(data.to_pre_model())}
         case PipeDataType.Model:
             return {type: PipeDataType.Model, data: new Model(data.to_model())}
+        case PipeDataType.PresolvedModel:
+            return {type: PipeDataType.PresolvedModel, data: new PresolvedModel(data.to_presolved_model())}
         case PipeDataType.LinearModel:
             return {type: PipeDataType.LinearModel, data: new LinearModel(data.to_linear_model())}
         case PipeDataType.StandardLinearModel:
@@ -543,6 +577,8 @@ export class OptimalTableau {
     private cache: {
         tableau?: SimplexTableau
         variablesValues?: number[]
+        dualValues?: Record<string, number>
+        reducedCosts?: Record<string, number>
     } = {}
 
     constructor(instance: _OptimalTableau) {
@@ -570,6 +606,41 @@ export class OptimalTableau {
     getOptimalValue() {
         return this.instance.wasm_get_optimal_value()
     }
+
+    /**
+     * Whether the tableau solves a `solve` (satisfy) problem, where the values are only a feasible point
+     */
+    isFeasibilityOnly(): boolean {
+        return this.instance.wasm_is_feasibility_only()
+    }
+
+    /**
+     * Get the dual values (shadow prices) of the constraints of the model, by constraint name
+     */
+    getDualValues(): Record<string, number> {
+        return this.cache.dualValues ??= this.instance.wasm_get_dual_values()
+    }
+
+    /**
+     * Get the reduced costs of the variables of the model, by variable name
+     */
+    getReducedCosts(): Record<string, number> {
+        return this.cache.reducedCosts ??= this.instance.wasm_get_reduced_costs()
+    }
+
+    /**
+     * Get the ranges of the right hand sides and objective coefficients for which the optimal basis stays the same
+     */
+    getSensitivityAnalysis(): SerializedSensitivityAnalysis {
+        return this.instance.wasm_get_sensitivity_analysis()
+    }
+
+    /**
+     * Get the solution of the original model, with the free variables recombined from their positive and negative parts
+     */
+    getLpSolution(): LpSolution<number> {
+        return this.instance.wasm_get_lp_solution()
+    }
 }
 
 export class SimplexStep {
@@ -871,6 +942,28 @@ export class Model {
     }
 }
 
+export class PresolvedModel {
+    instance: _PresolvedModel;
+
+    constructor(instance: _PresolvedModel) {
+        this.instance = instance;
+    }
+
+    /**
+     * Get the model with the single variable constraints folded into the domain
+     */
+    getModel(): Model {
+        return new Model(this.instance.wasm_get_model())
+    }
+
+    /**
+     * Get the number of constraints that were removed from the model
+     */
+    getRemovedConstraints(): number {
+        return this.instance.wasm_get_removed_constraints()
+    }
+}
+
 export type VarValue = {
     type: 'Int'
     value: number
@@ -898,6 +991,8 @@ export type LpAssignment<T> = {
 export type LpSolution<T> = {
     assignment: LpAssignment<T>[]
     value: number
+    feasibility_only: boolean
+    stats?: SolveStats | null
 }
 
 
@@ -936,6 +1031,7 @@ export type {
     ParseError,
     SerializedVariableToAssert,
     SerializedVariablesDomainDeclaration,
+    SolveStats,
 } from './pkg/rooc'
 
 export {
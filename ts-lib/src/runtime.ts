@@ -5,6 +5,7 @@ export const PrimitiveKind = {
     Number: {type: 'Number'},
     Integer: {type: 'Integer'},
     PositiveInteger: {type: 'PositiveInteger'},
+    Rational: {type: 'Rational'},
     String: {type: 'String'},
     Iterable: <T extends SerializedPrimitiveKind>(value: T) => ({type: 'Iterable', value} as const),
     Graph: {type: 'Graph'},
@@ -47,6 +48,7 @@ export enum PipeDataType {
     IntegerBinarySolution = 10,
     RealSolution = 11,
     MILPSolution = 12,
+    PresolvedModel = 13,
 }
 
 export enum Pipes {
@@ -62,6 +64,11 @@ export enum Pipes {
     IntegerBinarySolverPipe = 9,
     MILPSolverPipe = 10,
     AutoSolverPipe = 11,
+    LpFormatPipe = 12,
+    MpsFormatPipe = 13,
+    OptimalTableauPipe = 14,
+    LatexPipe = 15,
+    PresolvePipe = 16,
 }
 
 export type NamedParameter = {
@@ -144,6 +151,27 @@ export const FN_neigh_edges_of = makeRuntimeFunction("neigh_edges_of", [
     "Returns the neighbour edges of a node name in a graph"
 )
 
+export const FN_cost = makeRuntimeFunction("cost", [
+        {name: "of_edge", value: {type: "GraphEdge"}},
+    ],
+    {type: "Number"},
+    "Returns the cost of an edge, erroring if the edge has no cost"
+)
+
+export const FN_source = makeRuntimeFunction("source", [
+        {name: "of_edge", value: {type: "GraphEdge"}},
+    ],
+    {type: "String"},
+    "Returns the name of the node the edge starts from"
+)
+
+export const FN_target = makeRuntimeFunction("target", [
+        {name: "of_edge", value: {type: "GraphEdge"}},
+    ],
+    {type: "String"},
+    "Returns the name of the node the edge points to"
+)
+
 export const FN_zip = makeRuntimeFunction("zip", [
         {name: "arg1", value: {type: "Iterable", value: {type: "Any"}}},
         {name: "arg2", value: {type: "Iterable", value: {type: "Any"}}},
@@ -205,6 +233,207 @@ export const FN_intersection = makeRuntimeFunction("intersection", [
     "Returns the intersection of two iterables"
 )
 
+export const FN_contains = makeRuntimeFunction("contains", [
+        {name: "iterable", value: {type: "Iterable", value: {type: "Any"}}},
+        {name: "value", value: {type: "Any"}},
+    ],
+    {type: "Boolean"},
+    "Returns true if the iterable contains the value"
+)
+
+export const FN_keys = makeRuntimeFunction("keys", [
+        {name: "of_map", value: {type: "Map", value: {type: "Any"}}},
+    ],
+    {type: "Iterable", value: {type: "String"}},
+    "Returns the keys of the map, in the order they were declared"
+)
+
+export const FN_values = makeRuntimeFunction("values", [
+        {name: "of_map", value: {type: "Map", value: {type: "Any"}}},
+    ],
+    {type: "Iterable", value: {type: "Any"}},
+    "Returns the values of the map, in the order their keys were declared"
+)
+
+export const FN_shortest_path = makeRuntimeFunction("shortest_path", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "from", value: {type: "GraphNode"}},
+        {name: "to", value: {type: "GraphNode"}},
+    ],
+    {type: "Iterable", value: {type: "GraphEdge"}},
+    "Returns the edges of the shortest path between two nodes using the edge costs, empty if the nodes are not connected"
+)
+
+export const FN_degree = makeRuntimeFunction("degree", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "of_node", value: {type: "GraphNode"}},
+    ],
+    {type: "Integer"},
+    "Returns the number of edges entering and leaving a node of the graph"
+)
+
+export const FN_in_degree = makeRuntimeFunction("in_degree", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "of_node", value: {type: "GraphNode"}},
+    ],
+    {type: "Integer"},
+    "Returns the number of edges of the graph that point to the node"
+)
+
+export const FN_out_degree = makeRuntimeFunction("out_degree", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "of_node", value: {type: "GraphNode"}},
+    ],
+    {type: "Integer"},
+    "Returns the number of edges of the graph that start from the node"
+)
+
+export const FN_transpose = makeRuntimeFunction("transpose", [
+        {name: "matrix", value: {type: "Iterable", value: {type: "Iterable", value: {type: "Any"}}}},
+    ],
+    {type: "Iterable", value: {type: "Iterable", value: {type: "Any"}}},
+    "Returns the transpose of a 2D array, erroring if the rows have different lengths"
+)
+
+export const FN_to_dot = makeRuntimeFunction("to_dot", [
+        {name: "of_graph", value: {type: "Graph"}},
+    ],
+    {type: "String"},
+    "Returns the graph in the GraphViz DOT format"
+)
+
+export const FN_connected_components = makeRuntimeFunction("connected_components", [
+        {name: "of_graph", value: {type: "Graph"}},
+    ],
+    {type: "Iterable", value: {type: "Iterable", value: {type: "String"}}},
+    "Returns the names of the nodes of each connected component of the graph, ignoring the direction of the edges"
+)
+
+export const FN_is_connected = makeRuntimeFunction("is_connected", [
+        {name: "of_graph", value: {type: "Graph"}},
+    ],
+    {type: "Boolean"},
+    "Returns true if the graph is connected, ignoring the direction of the edges"
+)
+
+export const FN_adjacency_matrix = makeRuntimeFunction("adjacency_matrix", [
+        {name: "of_graph", value: {type: "Graph"}},
+    ],
+    {type: "Iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    "Returns the adjacency matrix of the graph in the order of nodes(G), an optional \"binary\" or \"cost\" mode selects the entries, binary by default"
+)
+
+export const FN_sum = makeRuntimeFunction("sum", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the sum of the elements of the iterable"
+)
+
+export const FN_prod = makeRuntimeFunction("prod", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the product of the elements of the iterable"
+)
+
+export const FN_avg = makeRuntimeFunction("avg", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the average of the elements of the iterable, erroring if it is empty"
+)
+
+export const FN_min = makeRuntimeFunction("min", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the minimum of the elements of the iterable, erroring if it is empty"
+)
+
+export const FN_max = makeRuntimeFunction("max", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the maximum of the elements of the iterable, erroring if it is empty"
+)
+
+export const FN_reverse = makeRuntimeFunction("reverse", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Any"}}},
+    ],
+    {type: "Iterable", value: {type: "Any"}},
+    "Returns the elements of the iterable in reverse order"
+)
+
+export const FN_concat = makeRuntimeFunction("concat", [
+        {name: "str_0", value: {type: "String"}},
+        {name: "str_1", value: {type: "String"}},
+    ],
+    {type: "String"},
+    "Concatenates two or more strings"
+)
+
+export const FN_split = makeRuntimeFunction("split", [
+        {name: "of_string", value: {type: "String"}},
+        {name: "separator", value: {type: "String"}},
+    ],
+    {type: "Iterable", value: {type: "String"}},
+    "Splits the string at every occurrence of the separator"
+)
+
+export const FN_neighbors_within = makeRuntimeFunction("neighbors_within", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "of_node", value: {type: "GraphNode"}},
+        {name: "k", value: {type: "PositiveInteger"}},
+    ],
+    {type: "Iterable", value: {type: "GraphNode"}},
+    "Returns the nodes that can be reached from the node within k edges"
+)
+
+export const FN_subgraph = makeRuntimeFunction("subgraph", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "of_nodes", value: {type: "Iterable", value: {type: "GraphNode"}}},
+    ],
+    {type: "Graph"},
+    "Returns the subgraph made of the nodes and the edges between them"
+)
+
+export const FN_node_weight = makeRuntimeFunction("node_weight", [
+        {name: "in_graph", value: {type: "Graph"}},
+        {name: "of_node", value: {type: "GraphNode"}},
+    ],
+    {type: "Number"},
+    "Returns the weight of a node, erroring if the node has no weight"
+)
+
+export const FN_flatten = makeRuntimeFunction("flatten", [
+        {name: "of_iterable", value: {type: "Iterable", value: {type: "Iterable", value: {type: "Any"}}}},
+    ],
+    {type: "Iterable", value: {type: "Any"}},
+    "Collapses one level of nesting of an iterable of iterables"
+)
+
+export const FN_to_number = makeRuntimeFunction("to_number", [
+        {name: "value", value: {type: "Any"}},
+    ],
+    {type: "Number"},
+    "Converts a number or a string to a number, erroring if the string is not a number"
+)
+
+export const FN_to_integer = makeRuntimeFunction("to_integer", [
+        {name: "value", value: {type: "Any"}},
+    ],
+    {type: "Integer"},
+    "Converts a number or a string to an integer, dropping the fractional part"
+)
+
+export const FN_to_string = makeRuntimeFunction("to_string", [
+        {name: "value", value: {type: "Any"}},
+    ],
+    {type: "String"},
+    "Converts a value to its string representation"
+)
+
 function variant(name: string, fun: RuntimeFunction<NamedParameter[], SerializedPrimitiveKind>){
     return {...fun, name}
 }
@@ -221,11 +450,41 @@ export const ROOC_RUNTIME_FUNCTIONS = new Map<string, RuntimeFunction<NamedParam
     ["N", variant("N", FN_neigh_edges)],
     [FN_neigh_edges_of.name, FN_neigh_edges_of],
     ["N_of", variant("N_of", FN_neigh_edges_of)],
+    [FN_cost.name, FN_cost],
+    [FN_source.name, FN_source],
+    [FN_target.name, FN_target],
     [FN_rangeArray.name, FN_rangeArray],
     [FN_zip.name, FN_zip],
     [FN_difference.name, FN_difference],
     [FN_union.name, FN_union],
     [FN_intersection.name, FN_intersection],
+    [FN_contains.name, FN_contains],
+    [FN_keys.name, FN_keys],
+    [FN_values.name, FN_values],
+    [FN_shortest_path.name, FN_shortest_path],
+    [FN_degree.name, FN_degree],
+    [FN_in_degree.name, FN_in_degree],
+    [FN_out_degree.name, FN_out_degree],
+    [FN_transpose.name, FN_transpose],
+    [FN_to_dot.name, FN_to_dot],
+    [FN_connected_components.name, FN_connected_components],
+    [FN_is_connected.name, FN_is_connected],
+    [FN_adjacency_matrix.name, FN_adjacency_matrix],
+    [FN_sum.name, FN_sum],
+    [FN_prod.name, FN_prod],
+    [FN_avg.name, FN_avg],
+    [FN_min.name, FN_min],
+    [FN_max.name, FN_max],
+    [FN_reverse.name, FN_reverse],
+    [FN_concat.name, FN_concat],
+    [FN_split.name, FN_split],
+    [FN_neighbors_within.name, FN_neighbors_within],
+    [FN_subgraph.name, FN_subgraph],
+    [FN_node_weight.name, FN_node_weight],
+    [FN_flatten.name, FN_flatten],
+    [FN_to_number.name, FN_to_number],
+    [FN_to_integer.name, FN_to_integer],
+    [FN_to_string.name, FN_to_string],
 ])
 
 export type RuntimeBlockScopedFunction = {
@@ -244,6 +503,9 @@ export const RUNTIME_BLOCK_SCOPED_FUNCTIONS = new Map([
     makeRuntimeBlockScopedFunctionEntry("min", "Expands the inner expression into the minimum of all elements"),
     makeRuntimeBlockScopedFunctionEntry("max", "Expands the inner expression into the maximum of all elements"),
     makeRuntimeBlockScopedFunctionEntry("avg", "Expands the inner expression into the average of all elements"),
+    makeRuntimeBlockScopedFunctionEntry("argmin", "Returns the binding for which the inner expression is minimum"),
+    makeRuntimeBlockScopedFunctionEntry("argmax", "Returns the binding for which the inner expression is maximum"),
+    makeRuntimeBlockScopedFunctionEntry("count", "Counts the bindings for which the inner condition is true"),
 ])
 
 export type RuntimeBlockFunction = {
@@ -306,6 +568,7 @@ export const BUILTIN_TYPES_MAP = new Map([
     makeBuiltinTypeEntry({type: "Integer"}, "A integer"),
     makeBuiltinTypeEntry({type: "PositiveInteger"}, "A positive integer"),
     makeBuiltinTypeEntry({type: "Number"}, "A floating point number"),
+    makeBuiltinTypeEntry({type: "Rational"}, "An exact fraction, defined by dividing two integers, e.g. `1/3`"),
     makeBuiltinTypeEntry({type: "String"}, "A string, can be defined by `\"...\"`"),
     makeBuiltinTypeEntry({type: "Any"}, "Any value"),
     makeBuiltinTypeEntry({
@@ -313,7 +576,11 @@ export const BUILTIN_TYPES_MAP = new Map([
         value: {type: "Any"}
     }, "An iterable of a value, usually an array, defined as a comma separated list of values in square brackets `[]`, e.g. `[1,2,3]`"),
     makeBuiltinTypeEntry({type: "Tuple", value: []}, "A tuple of values"),
-    makeBuiltinTypeEntry({type: "Graph"}, "A graph, can be defined as `Graph { ... }` where inside the brackets there are a list of nodes and it's adjacent edges, e.g. \n```rust\nGraph {\n    A -> [B:10, C],\n    B -> [C:2],\n    C\n}\n```"),
+    makeBuiltinTypeEntry({
+        type: "Map",
+        value: {type: "Any"}
+    }, "A map of values keyed by strings, defined as a comma separated list of entries in curly brackets `{}`, e.g. `{ \"a\": 1, \"b\": 2 }`, values are read with `M[\"a\"]`"),
+    makeBuiltinTypeEntry({type: "Graph"}, "A graph, can be defined as `Graph { ... }` where inside the brackets there are a list of nodes and it's adjacent edges, e.g. \n```rust\nGraph {\n    A -> [B:10, C],\n    B -> [C:2],\n    C\n}\n```\nnodes can also have a weight, e.g. `A: w=5 -> [B]`, which is read with `node_weight(G, \"A\")`"),
     makeBuiltinTypeEntry({type: "GraphNode"}, "A node of a graph"),
     makeBuiltinTypeEntry({type: "GraphEdge"}, "An edge of a graph"),
 ])
@@ -436,6 +703,41 @@ export const pipeDescriptions = {
         PipeDataType.LinearModel,
         PipeDataType.MILPSolution
     ),
+    [Pipes.LpFormatPipe]: makePipeDescriptionEntry(
+        Pipes.LpFormatPipe,
+        "LP format",
+        "Exports the model in the CPLEX LP format, the model must be linear",
+        PipeDataType.Model,
+        PipeDataType.String
+    ),
+    [Pipes.MpsFormatPipe]: makePipeDescriptionEntry(
+        Pipes.MpsFormatPipe,
+        "MPS format",
+        "Exports the model in the fixed-column MPS format, the model must be linear",
+        PipeDataType.Model,
+        PipeDataType.String
+    ),
+    [Pipes.OptimalTableauPipe]: makePipeDescriptionEntry(
+        Pipes.OptimalTableauPipe,
+        "Simplex solver",
+        "Runs the simplex algorithm to find the optimal tableau, together with the dual values and reduced costs",
+        PipeDataType.Tableau,
+        PipeDataType.OptimalTableau
+    ),
+    [Pipes.LatexPipe]: makePipeDescriptionEntry(
+        Pipes.LatexPipe,
+        "LaTeX",
+        "Converts the model into LaTeX",
+        PipeDataType.PreModel,
+        PipeDataType.String
+    ),
+    [Pipes.PresolvePipe]: makePipeDescriptionEntry(
+        Pipes.PresolvePipe,
+        "Presolve",
+        "Folds the constraints with a single variable into the bounds of its domain",
+        PipeDataType.Model,
+        PipeDataType.PresolvedModel
+    ),
 } satisfies Record<Pipes, PipeDescription>
 
 function makePipeDataEntry(type: PipeDataType, name: string, description: string) {
@@ -458,6 +760,7 @@ export const pipeDataDescriptions = {
     [PipeDataType.Parser]: makePipeDataEntry(PipeDataType.Parser, "Parser", "The ROOC parser"),
     [PipeDataType.PreModel]: makePipeDataEntry(PipeDataType.PreModel, "Pre Model", "The parsed model"),
     [PipeDataType.Model]: makePipeDataEntry(PipeDataType.Model, "Model", "The compiled model"),
+    [PipeDataType.PresolvedModel]: makePipeDataEntry(PipeDataType.PresolvedModel, "Presolved Model", "The compiled model with the single variable constraints folded into the domain"),
     [PipeDataType.LinearModel]: makePipeDataEntry(PipeDataType.LinearModel, "Linear Model", "The linear model"),
     [PipeDataType.StandardLinearModel]: makePipeDataEntry(PipeDataType.StandardLinearModel, "Standard Linear Model", "The linear model in standard form"),
     [PipeDataType.Tableau]: makePipeDataEntry(PipeDataType.Tableau, "Tableau", "The tableau for the simplex algorithm"),